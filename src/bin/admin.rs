@@ -14,8 +14,20 @@
 //   lcadmin peers                     Endpoint 전체 테이블
 //   lcadmin peers <ufrag>             Endpoint 상세
 //
-// 조작 명령
+// 조작 명령 (--api-key: manage-channels scope 키 또는 LIVECHAT_ADMIN_KEY)
 //   lcadmin floor-revoke <channel_id> Floor 강제 revoke
+//   lcadmin floor-grant <channel_id> <user_id> [--priority N] [--indicator I]  지정 사용자에게 Floor 부여
+//   lcadmin floor-policy <channel_id> [--max-taken-ms N ...]  Floor 정책 조회 / 변경 (지정 필드만)
+//   lcadmin floor-queue <channel_id> [--order a,b | --remove USER | --clear]  대기열 조회 / 조작
+//   lcadmin emergency-clear <channel_id> 채널 긴급 상태 해제 (Dispatcher)
+//   lcadmin floor-history <channel_id> [--user U] [--limit N] [--jsonl]  talk-burst 이력 (최신순)
+//   lcadmin say -c <channel_id> <text> 채널에 메시지 주입 (--author 생략 시 system)
+//   lcadmin say --all <text>          전체 채널 공지
+//
+// 서비스 계정
+//   lcadmin service-accounts                              목록
+//   lcadmin service-account-create <id> --scope a,b      생성 + API 키 발급
+//   lcadmin service-account-delete <id>                   삭제 (키 폐기)
 
 use clap::{Parser, Subcommand};
 use colored::Colorize;
@@ -41,6 +53,11 @@ struct Cli {
     #[arg(long, default_value_t = 8080)]
     port: u16,
 
+    /// 서비스 계정 API 키 (say / floor 조작 / service-account 요청에 Bearer로 첨부)
+    /// floor 조작 / service-account 명령은 생략 시 환경변수 LIVECHAT_ADMIN_KEY 사용
+    #[arg(long, global = true)]
+    api_key: Option<String>,
+
     #[command(subcommand)]
     command: Command,
 }
@@ -73,6 +90,177 @@ enum Command {
         /// 대상 channel_id
         channel_id: String,
     },
+
+    /// 지정 사용자에게 Floor 부여 (기존 holder는 revoke)
+    FloorGrant {
+        /// 대상 channel_id
+        channel_id: String,
+
+        /// 발언권을 받을 user_id (채널 멤버)
+        user_id: String,
+
+        /// priority (생략 시 사용자의 IDENTIFY priority)
+        #[arg(long)]
+        priority: Option<u8>,
+
+        /// normal | broadcast | imminent_peril | emergency
+        #[arg(long)]
+        indicator: Option<String>,
+    },
+
+    /// 채널 Floor 정책 조회 — 옵션 지정 시 해당 필드만 변경
+    FloorPolicy {
+        /// 대상 channel_id
+        channel_id: String,
+
+        /// 최대 발언 시간 ms (0 = 무제한)
+        #[arg(long)]
+        max_taken_ms: Option<u64>,
+
+        /// holder Ping 타임아웃 ms
+        #[arg(long)]
+        ping_timeout_ms: Option<u64>,
+
+        /// 점유 중 요청 대기열 사용 (false면 FLOOR_DENY)
+        #[arg(long)]
+        queue: Option<bool>,
+
+        /// 대기열 최대 길이 (0 = 무제한)
+        #[arg(long)]
+        max_queue_depth: Option<usize>,
+
+        /// priority 선점 허용 (Emergency는 항상 선점)
+        #[arg(long)]
+        preemption: Option<bool>,
+
+        /// 발언 종료 후 재요청 금지 ms
+        #[arg(long)]
+        cooldown_ms: Option<u64>,
+
+        /// 대기열 항목 만료 ms (0 = 무제한)
+        #[arg(long)]
+        queue_timeout_ms: Option<u64>,
+
+        /// max_duration Revoke 후 재요청 금지 ms (0 = 비활성)
+        #[arg(long)]
+        hog_cooldown_ms: Option<u64>,
+
+        /// 동일 priority 대기자 중 최근 발언이 오래된 사용자 우선
+        #[arg(long)]
+        round_robin: Option<bool>,
+
+        /// 대기 aging 주기 ms — 주기마다 유효 priority 가산 (0 = 비활성)
+        #[arg(long)]
+        aging_interval_ms: Option<u64>,
+
+        /// holder 연결 끊김 시 Floor 유지 유예 ms (0 = 즉시 revoke)
+        #[arg(long)]
+        suspend_grace_ms: Option<u64>,
+
+        /// holder RTP 무수신 시 revoke ms (0 = 비활성)
+        #[arg(long)]
+        media_timeout_ms: Option<u64>,
+
+        /// VOX — 음성 감지로 자동 Request / 무음 hang time 후 자동 Release
+        #[arg(long)]
+        vox: Option<bool>,
+
+        /// VOX 무음 hang time ms (ping_timeout 미만)
+        #[arg(long)]
+        vox_hang_ms: Option<u64>,
+
+        /// VOX 음성 판정 audio level 상한 (-dBov, 0~127)
+        #[arg(long)]
+        vox_threshold: Option<u8>,
+
+        /// 가상 talker 스트림 — 발언자와 무관한 채널 고정 SSRC로 릴레이
+        #[arg(long)]
+        virtual_talker: Option<bool>,
+
+        /// Broadcast 요청 최소 IDENTIFY priority (1~255, 1 = 전원 허용)
+        #[arg(long)]
+        broadcast_min_priority: Option<u8>,
+    },
+
+    /// 채널 Floor 대기열 조회 — 옵션 지정 시 순서 변경 / 제거 / 초기화
+    FloorQueue {
+        /// 대상 channel_id
+        channel_id: String,
+
+        /// 앞으로 옮길 user_id 순서 (쉼표 구분, 나머지는 기존 순서 유지)
+        #[arg(long, value_delimiter = ',', conflicts_with_all = ["remove", "clear"])]
+        order: Vec<String>,
+
+        /// 대기자 1명 제거
+        #[arg(long, conflicts_with = "clear")]
+        remove: Option<String>,
+
+        /// 대기열 전체 초기화
+        #[arg(long)]
+        clear: bool,
+    },
+
+    /// 채널 긴급 상태 해제 (Dispatcher)
+    EmergencyClear {
+        /// 대상 channel_id
+        channel_id: String,
+    },
+
+    /// 채널 talk-burst 이력 (누가 언제 얼마나 발언했는지, 최신순)
+    FloorHistory {
+        /// 대상 channel_id
+        channel_id: String,
+
+        /// 발언자 user_id 필터
+        #[arg(long)]
+        user: Option<String>,
+
+        /// 최대 건수
+        #[arg(long, default_value_t = 50)]
+        limit: usize,
+
+        /// 표 대신 JSON Lines 출력 (스크립트 / 감사 보관용)
+        #[arg(long)]
+        jsonl: bool,
+    },
+
+    /// 채널에 메시지 주입 (WS 없이 MESSAGE_EVENT 발생)
+    Say {
+        /// 대상 channel_id
+        #[arg(long, short = 'c', conflicts_with = "all", required_unless_present = "all")]
+        channel: Option<String>,
+
+        /// 전체 채널 공지
+        #[arg(long)]
+        all: bool,
+
+        /// 발신자 author_id (생략 시 서버 기본값 "system")
+        #[arg(long)]
+        author: Option<String>,
+
+        /// 메시지 본문 (여러 단어는 공백으로 연결)
+        #[arg(required = true, num_args = 1..)]
+        content: Vec<String>,
+    },
+
+    /// 서비스 계정 목록
+    ServiceAccounts,
+
+    /// 서비스 계정 생성 + API 키 발급 (키는 이때 한 번만 표시)
+    ServiceAccountCreate {
+        /// 계정 id (user_id / author_id 로 사용)
+        id: String,
+
+        /// 권한 범위 (쉼표 구분): post-message, read-trace, manage-channels, admin
+        #[arg(long = "scope", value_delimiter = ',', required = true)]
+        scopes: Vec<String>,
+    },
+
+    /// 서비스 계정 삭제 (키 폐기)
+    ServiceAccountDelete {
+        /// 계정 id
+        id: String,
+    },
 }
 
 // ----------------------------------------------------------------------------
@@ -94,17 +282,38 @@ struct AdminUserSummary {
     user_id:      String,
     #[tabled(rename = "PRI")]
     priority:     u8,
+    #[tabled(rename = "SVC")]
+    #[serde(default)]
+    service:      bool,
     #[tabled(rename = "IDLE(s)")]
     idle_secs:    u64,
 }
 
 #[derive(Deserialize)]
 struct AdminUserDetail {
-    user_id:      String,
-    priority:     u8,
-    last_seen_ms: u64,
-    idle_secs:    u64,
-    channels:     Vec<String>,
+    user_id:         String,
+    priority:        u8,
+    #[serde(default)]
+    service:         bool,
+    last_seen_ms:    u64,
+    idle_secs:       u64,
+    channels:        Vec<String>,
+    #[serde(default)]
+    rate_violations: u64,
+    #[serde(default)]
+    rate_limits:     Vec<AdminRateBucket>,
+}
+
+#[derive(Deserialize, Tabled)]
+struct AdminRateBucket {
+    #[tabled(rename = "CLASS")]
+    class:     String,
+    #[tabled(rename = "TOKENS")]
+    tokens:    u32,
+    #[tabled(rename = "ALLOWED")]
+    allowed:   u64,
+    #[tabled(rename = "THROTTLED")]
+    throttled: u64,
 }
 
 #[derive(Deserialize, Tabled)]
@@ -125,23 +334,58 @@ struct AdminChannelSummary {
     floor_holder: Option<String>,
     #[tabled(rename = "QUEUE")]
     queue_len:    usize,
+    #[tabled(rename = "EMERGENCY")]
+    emergency:    bool,
 }
 
 #[derive(Deserialize)]
 struct AdminChannelDetail {
-    channel_id:       String,
-    freq:             String,
-    name:             String,
-    capacity:         usize,
-    created_at:       u64,
-    members:          Vec<String>,
-    floor_state:      String,
-    floor_holder:     Option<String>,
-    floor_taken_secs: Option<u64>,
-    floor_priority:   u8,
-    queue_len:        usize,
-    queue:            Vec<AdminQueueEntry>,
-    peers:            Vec<AdminPeerSummary>,
+    channel_id:         String,
+    freq:               String,
+    name:               String,
+    capacity:           usize,
+    created_at:         u64,
+    members:            Vec<String>,
+    floor_state:        String,
+    floor_holder:       Option<String>,
+    floor_taken_secs:   Option<u64>,
+    floor_priority:     u8,
+    floor_indicator:    Option<String>,
+    floor_remaining_ms: Option<u64>,
+    floor_policy:       FloorPolicy,
+    queue_len:          usize,
+    queue:              Vec<AdminQueueEntry>,
+    emergency:          Option<EmergencyState>,
+    peers:              Vec<AdminPeerSummary>,
+}
+
+#[derive(Deserialize)]
+struct EmergencyState {
+    raised_by: String,
+    raised_at: u64,
+    source:    String,
+    acked_by:  Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct FloorPolicy {
+    max_taken_ms:       u64,
+    ping_timeout_ms:    u64,
+    queue_enabled:      bool,
+    max_queue_depth:    usize,
+    preemption_enabled: bool,
+    cooldown_ms:        u64,
+    queue_timeout_ms:   u64,
+    hog_cooldown_ms:    u64,
+    round_robin:        bool,
+    aging_interval_ms:  u64,
+    suspend_grace_ms:   u64,
+    media_timeout_ms:   u64,
+    vox:                bool,
+    vox_hang_ms:        u64,
+    vox_threshold:      u8,
+    virtual_talker:     bool,
+    broadcast_min_priority: u8,
 }
 
 #[derive(Deserialize, Tabled)]
@@ -152,35 +396,54 @@ struct AdminQueueEntry {
     priority:  u8,
     #[tabled(rename = "WAIT(s)")]
     wait_secs: u64,
+    #[tabled(rename = "PINNED")]
+    pinned:    bool,
 }
 
 #[derive(Deserialize, Tabled)]
 struct AdminPeerSummary {
     #[tabled(rename = "UFRAG")]
-    ufrag:      String,
+    ufrag:       String,
     #[tabled(rename = "USER ID")]
-    user_id:    String,
+    user_id:     String,
     #[tabled(rename = "CHANNEL")]
-    channel_id: String,
+    channel_id:  String,
     #[tabled(rename = "ADDRESS")]
     #[serde(deserialize_with = "deser_opt_string")]
-    address:    String,
+    address:     String,
     #[tabled(rename = "IDLE(s)")]
-    idle_secs:  u64,
+    idle_secs:   u64,
     #[tabled(rename = "SRTP")]
-    srtp_ready: bool,
+    srtp_ready:  bool,
+    #[tabled(rename = "NACK req/rtx/fwd", display_with = "display_nack")]
+    nack:        AdminNackStats,
+    #[tabled(rename = "LEVEL", display_with = "display_level")]
+    audio_level: Option<u8>,
 }
 
+#[derive(Deserialize)]
+struct AdminNackStats {
+    requested:   u64,
+    retransmits: u64,
+    forwarded:   u64,
+}
+
+fn display_nack(n: &AdminNackStats) -> String { format!("{}/{}/{}", n.requested, n.retransmits, n.forwarded) }
+
+fn display_level(l: &Option<u8>) -> String { l.map_or("-".to_string(), |l| format!("-{}dBov", l)) }
+
 #[derive(Deserialize)]
 struct AdminPeerDetail {
-    ufrag:      String,
-    user_id:    String,
-    channel_id: String,
-    address:    Option<String>,
-    last_seen:  u64,
-    idle_secs:  u64,
-    srtp_ready: bool,
-    tracks:     Vec<AdminTrack>,
+    ufrag:       String,
+    user_id:     String,
+    channel_id:  String,
+    address:     Option<String>,
+    last_seen:   u64,
+    idle_secs:   u64,
+    srtp_ready:  bool,
+    nack:        AdminNackStats,
+    audio_level: Option<u8>,
+    tracks:      Vec<AdminTrack>,
 }
 
 #[derive(Deserialize, Tabled)]
@@ -189,6 +452,41 @@ struct AdminTrack {
     ssrc: u32,
     #[tabled(rename = "KIND")]
     kind: String,
+    #[tabled(rename = "RID", display_with = "display_opt")]
+    rid:  Option<String>,
+}
+
+#[derive(Deserialize, Tabled)]
+struct AdminMessageResult {
+    #[tabled(rename = "CHANNEL")]
+    channel_id: String,
+    #[tabled(rename = "MESSAGE ID")]
+    message_id: String,
+    #[tabled(rename = "RECIPIENTS")]
+    recipients: usize,
+}
+
+#[derive(Deserialize)]
+struct AdminServiceAccount {
+    id:           String,
+    scopes:       Vec<String>,
+    key_prefix:   String,
+    last_used_ms: Option<u64>,
+    online:       bool,
+}
+
+#[derive(Tabled)]
+struct ServiceAccountRow {
+    #[tabled(rename = "ID")]
+    id:         String,
+    #[tabled(rename = "SCOPES")]
+    scopes:     String,
+    #[tabled(rename = "KEY")]
+    key_prefix: String,
+    #[tabled(rename = "LAST USED")]
+    last_used:  String,
+    #[tabled(rename = "ONLINE")]
+    online:     String,
 }
 
 // ----------------------------------------------------------------------------
@@ -198,6 +496,8 @@ struct AdminTrack {
 fn main() {
     let cli = Cli::parse();
     let base = format!("http://{}:{}", cli.host, cli.port);
+    let admin_key = cli.api_key.clone()
+        .or_else(|| std::env::var("LIVECHAT_ADMIN_KEY").ok().filter(|s| !s.is_empty()));
 
     let result = match &cli.command {
         Command::Status                        => cmd_status(&base),
@@ -207,7 +507,48 @@ fn main() {
         Command::Channels { channel_id: Some(cid) } => cmd_channel_detail(&base, cid),
         Command::Peers { ufrag: None }         => cmd_peers(&base),
         Command::Peers { ufrag: Some(uf) }     => cmd_peer_detail(&base, uf),
-        Command::FloorRevoke { channel_id }    => cmd_floor_revoke(&base, channel_id),
+        Command::FloorRevoke { channel_id }    => cmd_floor_revoke(&base, admin_key.as_deref(), channel_id),
+        Command::FloorGrant { channel_id, user_id, priority, indicator } =>
+            cmd_floor_grant(&base, admin_key.as_deref(), channel_id, user_id, *priority, indicator.as_deref()),
+        Command::FloorPolicy {
+            channel_id, max_taken_ms, ping_timeout_ms, queue, max_queue_depth, preemption, cooldown_ms, queue_timeout_ms,
+            hog_cooldown_ms, round_robin, aging_interval_ms, suspend_grace_ms, media_timeout_ms,
+            vox, vox_hang_ms, vox_threshold, virtual_talker, broadcast_min_priority,
+        } => {
+            let changes = [
+                ("max_taken_ms",       max_taken_ms.map(serde_json::Value::from)),
+                ("ping_timeout_ms",    ping_timeout_ms.map(serde_json::Value::from)),
+                ("queue_enabled",      queue.map(serde_json::Value::from)),
+                ("max_queue_depth",    max_queue_depth.map(serde_json::Value::from)),
+                ("preemption_enabled", preemption.map(serde_json::Value::from)),
+                ("cooldown_ms",        cooldown_ms.map(serde_json::Value::from)),
+                ("queue_timeout_ms",   queue_timeout_ms.map(serde_json::Value::from)),
+                ("hog_cooldown_ms",    hog_cooldown_ms.map(serde_json::Value::from)),
+                ("round_robin",        round_robin.map(serde_json::Value::from)),
+                ("aging_interval_ms",  aging_interval_ms.map(serde_json::Value::from)),
+                ("suspend_grace_ms",   suspend_grace_ms.map(serde_json::Value::from)),
+                ("media_timeout_ms",   media_timeout_ms.map(serde_json::Value::from)),
+                ("vox",                vox.map(serde_json::Value::from)),
+                ("vox_hang_ms",        vox_hang_ms.map(serde_json::Value::from)),
+                ("vox_threshold",      vox_threshold.map(serde_json::Value::from)),
+                ("virtual_talker",     virtual_talker.map(serde_json::Value::from)),
+                ("broadcast_min_priority", broadcast_min_priority.map(serde_json::Value::from)),
+            ];
+            cmd_floor_policy(&base, admin_key.as_deref(), channel_id, &changes)
+        }
+        Command::FloorQueue { channel_id, order, remove, clear } => {
+            cmd_floor_queue(&base, admin_key.as_deref(), channel_id, order, remove.as_deref(), *clear)
+        }
+        Command::EmergencyClear { channel_id } => cmd_emergency_clear(&base, admin_key.as_deref(), channel_id),
+        Command::FloorHistory { channel_id, user, limit, jsonl } => {
+            cmd_floor_history(&base, channel_id, user.as_deref(), *limit, *jsonl)
+        }
+        Command::Say { channel, author, content, .. } => {
+            cmd_say(&base, cli.api_key.as_deref(), channel.as_deref(), author.as_deref(), &content.join(" "))
+        }
+        Command::ServiceAccounts                     => cmd_service_accounts(&base, admin_key.as_deref()),
+        Command::ServiceAccountCreate { id, scopes } => cmd_service_account_create(&base, admin_key.as_deref(), id, scopes),
+        Command::ServiceAccountDelete { id }         => cmd_service_account_delete(&base, admin_key.as_deref(), id),
     };
 
     if let Err(e) = result {
@@ -278,6 +619,9 @@ fn cmd_user_detail(base: &str, user_id: &str) -> Result<(), Box<dyn std::error::
     println!("{}", format!("  User: {}", u.user_id).bold().cyan());
     println!("  {}", "─".repeat(36).dimmed());
     println!("  {:16} {}", "Priority:".bold(),  u.priority);
+    if u.service {
+        println!("  {:16} {}", "Service:".bold(), "서비스 계정 (API 키)".magenta());
+    }
     println!("  {:16} {}s", "Idle:".bold(),      u.idle_secs);
     println!("  {:16} {}", "Last Seen:".bold(),  format_ts(u.last_seen_ms));
     println!("  {:16} {}",
@@ -288,6 +632,24 @@ fn cmd_user_detail(base: &str, user_id: &str) -> Result<(), Box<dyn std::error::
             u.channels.join(", ").yellow().to_string()
         }
     );
+    println!("  {:16} {}",
+        "Rate Violations:".bold(),
+        if u.rate_violations > 0 {
+            u.rate_violations.to_string().red().bold().to_string()
+        } else {
+            u.rate_violations.to_string().dimmed().to_string()
+        }
+    );
+
+    if !u.rate_limits.is_empty() {
+        println!();
+        println!("{}", "  Rate Limits".bold());
+        println!("{}", Table::new(&u.rate_limits).to_string()
+            .lines()
+            .map(|l| format!("  {}", l))
+            .collect::<Vec<_>>()
+            .join("\n"));
+    }
     println!();
     Ok(())
 }
@@ -308,13 +670,18 @@ fn cmd_channels(base: &str) -> Result<(), Box<dyn std::error::Error>> {
             name:         ch.name.clone(),
             member_count: ch.member_count,
             capacity:     ch.capacity,
-            floor_state:  if ch.floor_state == "taken" {
-                "● TAKEN".red().bold().to_string()
-            } else {
-                "○ idle".dimmed().to_string()
+            floor_state:  match ch.floor_state.as_str() {
+                "taken"     => "● TAKEN".red().bold().to_string(),
+                "suspended" => "◐ SUSPENDED".yellow().bold().to_string(),
+                _           => "○ idle".dimmed().to_string(),
             },
             floor_holder: ch.floor_holder.clone().unwrap_or_else(|| "-".to_string()),
             queue_len:    ch.queue_len,
+            emergency:    if ch.emergency {
+                "▲ EMERGENCY".red().bold().to_string()
+            } else {
+                "-".dimmed().to_string()
+            },
         }
     }).collect();
 
@@ -344,6 +711,8 @@ struct AdminChannelSummaryDisplay {
     floor_holder: String,
     #[tabled(rename = "Q")]
     queue_len:    usize,
+    #[tabled(rename = "EMG")]
+    emergency:    String,
 }
 
 fn cmd_channel_detail(base: &str, channel_id: &str) -> Result<(), Box<dyn std::error::Error>> {
@@ -356,17 +725,30 @@ fn cmd_channel_detail(base: &str, channel_id: &str) -> Result<(), Box<dyn std::e
     println!("  {:18} {}", "Created:".bold(),      format_ts(ch.created_at));
 
     // Floor 상태
-    let floor_line = if ch.floor_state == "taken" {
-        format!("{} (holder: {}, {}s 경과, priority: {})",
-            "● TAKEN".red().bold(),
+    let floor_line = if ch.floor_state != "idle" {
+        format!("{} (holder: {}, {}, {}s 경과, 잔여: {}, priority: {})",
+            if ch.floor_state == "suspended" { "◐ SUSPENDED".yellow().bold() } else { "● TAKEN".red().bold() },
             ch.floor_holder.as_deref().unwrap_or("-").yellow(),
+            ch.floor_indicator.as_deref().unwrap_or("normal"),
             ch.floor_taken_secs.unwrap_or(0),
+            ch.floor_remaining_ms.map(|ms| format!("{}s", ms / 1000)).unwrap_or_else(|| "무제한".to_string()),
             ch.floor_priority,
         )
     } else {
         "○ idle".dimmed().to_string()
     };
     println!("  {:18} {}", "Floor:".bold(), floor_line);
+    println!("  {:18} {}", "Floor Policy:".bold(), format_policy(&ch.floor_policy).dimmed());
+    if let Some(em) = &ch.emergency {
+        println!("  {:18} {} (by: {}, {}, {}, ack: {})",
+            "Emergency:".bold(),
+            "▲ ACTIVE".red().bold(),
+            em.raised_by.yellow(),
+            em.source,
+            format_ts(em.raised_at),
+            if em.acked_by.is_empty() { "-".to_string() } else { em.acked_by.join(", ") },
+        );
+    }
 
     // 멤버 목록
     println!();
@@ -435,6 +817,12 @@ fn cmd_peer_detail(base: &str, ufrag: &str) -> Result<(), Box<dyn std::error::Er
         "SRTP:".bold(),
         if ep.srtp_ready { "✓ ready".green().to_string() } else { "✗ not ready".red().to_string() }
     );
+    println!("  {:16} requested {} / retransmitted {} / forwarded {}",
+        "NACK:".bold(), ep.nack.requested, ep.nack.retransmits.to_string().green(), ep.nack.forwarded);
+    println!("  {:16} {}", "Audio Level:".bold(), match ep.audio_level {
+        Some(l) => format!("-{}dBov", l),
+        None    => "- (audio level 확장 미협상)".dimmed().to_string(),
+    });
 
     if !ep.tracks.is_empty() {
         println!();
@@ -450,10 +838,10 @@ fn cmd_peer_detail(base: &str, ufrag: &str) -> Result<(), Box<dyn std::error::Er
     Ok(())
 }
 
-fn cmd_floor_revoke(base: &str, channel_id: &str) -> Result<(), Box<dyn std::error::Error>> {
+fn cmd_floor_revoke(base: &str, api_key: Option<&str>, channel_id: &str) -> Result<(), Box<dyn std::error::Error>> {
     let client = reqwest::blocking::Client::new();
     let url    = format!("{}/admin/floor-revoke/{}", base, channel_id);
-    let resp   = client.post(&url).send()?;
+    let resp   = with_key(client.post(&url), api_key).send()?;
 
     let status = resp.status();
     let body: serde_json::Value = resp.json()?;
@@ -475,6 +863,378 @@ fn cmd_floor_revoke(base: &str, channel_id: &str) -> Result<(), Box<dyn std::err
     Ok(())
 }
 
+fn cmd_floor_grant(
+    base:       &str,
+    api_key:    Option<&str>,
+    channel_id: &str,
+    user_id:    &str,
+    priority:   Option<u8>,
+    indicator:  Option<&str>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let client = reqwest::blocking::Client::new();
+    let url    = format!("{}/admin/floor-grant/{}", base, channel_id);
+    let resp   = with_key(client.post(&url), api_key).json(&serde_json::json!({
+        "user_id":   user_id,
+        "priority":  priority,
+        "indicator": indicator,
+    })).send()?;
+
+    let status = resp.status();
+    let body: serde_json::Value = resp.json()?;
+
+    if status.is_success() {
+        let revoked = body["revoked_from"].as_str().unwrap_or("-");
+        println!();
+        println!("  {} channel={} granted_to={} revoked_from={}",
+            "Floor Grant OK".green().bold(),
+            channel_id.yellow(),
+            user_id.cyan(),
+            revoked.cyan(),
+        );
+        println!();
+    } else {
+        let msg = body["error"].as_str().unwrap_or("unknown error");
+        return Err(format!("[{}] {}", status, msg).into());
+    }
+
+    Ok(())
+}
+
+fn cmd_emergency_clear(base: &str, api_key: Option<&str>, channel_id: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let client = reqwest::blocking::Client::new();
+    let url    = format!("{}/admin/channels/{}/emergency", base, channel_id);
+    let resp   = with_key(client.delete(&url), api_key).send()?;
+
+    let status = resp.status();
+    let body: serde_json::Value = resp.json()?;
+
+    if status.is_success() {
+        let raised_by = body["raised_by"].as_str().unwrap_or("-");
+        let acked     = body["acked_by"].as_array().map(|a| a.len()).unwrap_or(0);
+        println!();
+        println!("  {} channel={} raised_by={} acked={}",
+            "Emergency Cleared".green().bold(),
+            channel_id.yellow(),
+            raised_by.cyan(),
+            acked,
+        );
+        println!();
+    } else {
+        let msg = body["error"].as_str().unwrap_or("unknown error");
+        return Err(format!("[{}] {}", status, msg).into());
+    }
+
+    Ok(())
+}
+
+#[derive(Deserialize)]
+struct FloorHistory {
+    current: Option<CurrentBurst>,
+    bursts:  Vec<serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+struct CurrentBurst {
+    user_id:    String,
+    priority:   u8,
+    indicator:  String,
+    elapsed_ms: u64,
+}
+
+#[derive(Deserialize, Tabled)]
+struct TalkBurst {
+    #[tabled(rename = "GRANTED", display_with = "display_ts")]
+    granted_at:   u64,
+    #[tabled(rename = "USER ID")]
+    user_id:      String,
+    #[tabled(rename = "PRI")]
+    priority:     u8,
+    #[tabled(rename = "INDICATOR")]
+    indicator:    String,
+    #[tabled(rename = "DURATION", display_with = "display_secs")]
+    duration_ms:  u64,
+    #[tabled(rename = "END")]
+    end:          String,
+    #[tabled(rename = "CAUSE")]
+    cause:        String,
+    #[tabled(rename = "BY", display_with = "display_opt")]
+    preempted_by: Option<String>,
+}
+
+fn display_ts(ms: &u64) -> String { format_ts(*ms) }
+fn display_secs(ms: &u64) -> String { format!("{:.1}s", *ms as f64 / 1000.0) }
+fn display_opt(v: &Option<String>) -> String { v.clone().unwrap_or_else(|| "-".to_string()) }
+
+fn cmd_floor_history(
+    base:       &str,
+    channel_id: &str,
+    user:       Option<&str>,
+    limit:      usize,
+    jsonl:      bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut url = format!("{}/admin/channels/{}/floor-history?limit={}", base, channel_id, limit);
+    if let Some(u) = user {
+        url.push_str(&format!("&user={}", u));
+    }
+    let history: FloorHistory = get_json(&url)?;
+
+    if jsonl {
+        for b in &history.bursts {
+            println!("{}", serde_json::to_string(b)?);
+        }
+        return Ok(());
+    }
+
+    println!();
+    println!("{}", format!("  Floor History: {}", channel_id).bold().cyan());
+    if let Some(cur) = &history.current {
+        println!("  {:18} {} (priority: {}, {}, {:.1}s 경과)",
+            "Now Talking:".bold(),
+            cur.user_id.yellow(),
+            cur.priority,
+            cur.indicator,
+            cur.elapsed_ms as f64 / 1000.0,
+        );
+    }
+    if history.bursts.is_empty() {
+        println!("  {}", "(이력 없음)".dimmed());
+        println!();
+        return Ok(());
+    }
+    let bursts: Vec<TalkBurst> = history.bursts.into_iter()
+        .filter_map(|v| serde_json::from_value(v).ok())
+        .collect();
+    println!("{}", Table::new(&bursts).to_string()
+        .lines()
+        .map(|l| format!("  {}", l))
+        .collect::<Vec<_>>()
+        .join("\n"));
+    println!("  {} burst(s)", bursts.len());
+    println!();
+    Ok(())
+}
+
+fn cmd_floor_policy(
+    base:       &str,
+    api_key:    Option<&str>,
+    channel_id: &str,
+    changes:    &[(&str, Option<serde_json::Value>)],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let url = format!("{}/admin/channels/{}/floor-policy", base, channel_id);
+    let mut policy: serde_json::Value = get_json(&url)?;
+
+    // 지정한 필드만 현재 값 위에 덮어써서 PUT
+    let changed = changes.iter().any(|(_, v)| v.is_some());
+    if changed {
+        for (key, value) in changes {
+            if let Some(v) = value {
+                policy[*key] = v.clone();
+            }
+        }
+        let resp   = with_key(reqwest::blocking::Client::new().put(&url), api_key).json(&policy).send()?;
+        let status = resp.status();
+        policy     = resp.json()?;
+        if !status.is_success() {
+            let msg = policy["error"].as_str().unwrap_or("unknown error");
+            return Err(format!("[{}] {}", status, msg).into());
+        }
+    }
+
+    let p: FloorPolicy = serde_json::from_value(policy)?;
+    println!();
+    println!("{}", format!("  Floor Policy: {}{}", channel_id, if changed { " (updated)" } else { "" }).bold().cyan());
+    println!("  {}", "─".repeat(36).dimmed());
+    let ms = |v: u64| if v == 0 { "무제한".to_string() } else { format!("{}ms", v) };
+    println!("  {:20} {}", "Max Taken:".bold(),     ms(p.max_taken_ms));
+    println!("  {:20} {}ms", "Ping Timeout:".bold(), p.ping_timeout_ms);
+    println!("  {:20} {}", "Queue:".bold(),         if p.queue_enabled { "on".green().to_string() } else { "off (deny)".red().to_string() });
+    println!("  {:20} {}", "Max Queue Depth:".bold(), if p.max_queue_depth == 0 { "무제한".to_string() } else { p.max_queue_depth.to_string() });
+    println!("  {:20} {}", "Queue Timeout:".bold(), ms(p.queue_timeout_ms));
+    println!("  {:20} {}", "Preemption:".bold(),    if p.preemption_enabled { "on".green().to_string() } else { "off (emergency only)".yellow().to_string() });
+    println!("  {:20} {}", "Cooldown:".bold(),      if p.cooldown_ms == 0 { "-".to_string() } else { format!("{}ms", p.cooldown_ms) });
+    println!("  {:20} {}", "Hog Cooldown:".bold(),  if p.hog_cooldown_ms == 0 { "-".to_string() } else { format!("{}ms (after max_duration)", p.hog_cooldown_ms) });
+    println!("  {:20} {}", "Round Robin:".bold(),   if p.round_robin { "on".green().to_string() } else { "off (FIFO)".dimmed().to_string() });
+    println!("  {:20} {}", "Aging:".bold(),         if p.aging_interval_ms == 0 { "-".to_string() } else { format!("every {}ms", p.aging_interval_ms) });
+    println!("  {:20} {}", "Suspend Grace:".bold(), if p.suspend_grace_ms == 0 { "- (즉시 revoke)".to_string() } else { format!("{}ms", p.suspend_grace_ms) });
+    println!("  {:20} {}", "Media Timeout:".bold(), if p.media_timeout_ms == 0 { "-".to_string() } else { format!("{}ms (RTP 무수신 revoke)", p.media_timeout_ms) });
+    println!("  {:20} {}", "VOX:".bold(),           if p.vox { format!("{} (hang {}ms, level <= -{}dBov)", "on".green(), p.vox_hang_ms, p.vox_threshold) } else { "off".dimmed().to_string() });
+    println!("  {:20} {}", "Virtual Talker:".bold(), if p.virtual_talker { "on (고정 SSRC)".green().to_string() } else { "off".dimmed().to_string() });
+    println!("  {:20} priority >= {}", "Broadcast:".bold(), p.broadcast_min_priority);
+    println!();
+    Ok(())
+}
+
+fn cmd_floor_queue(
+    base:       &str,
+    api_key:    Option<&str>,
+    channel_id: &str,
+    order:      &[String],
+    remove:     Option<&str>,
+    clear:      bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let url    = format!("{}/admin/channels/{}/floor-queue", base, channel_id);
+    let client = reqwest::blocking::Client::new();
+
+    let resp = if clear {
+        Some(with_key(client.delete(&url), api_key).send()?)
+    } else if let Some(uid) = remove {
+        Some(with_key(client.delete(format!("{}/{}", url, uid)), api_key).send()?)
+    } else if !order.is_empty() {
+        Some(with_key(client.put(&url), api_key).json(&serde_json::json!({ "order": order })).send()?)
+    } else {
+        None
+    };
+
+    if let Some(resp) = resp {
+        let status = resp.status();
+        let body: serde_json::Value = resp.json()?;
+        if !status.is_success() {
+            let msg = body["error"].as_str().unwrap_or("unknown error");
+            return Err(format!("[{}] {}", status, msg).into());
+        }
+        if let Some(removed) = body["removed"].as_array() {
+            let names: Vec<&str> = removed.iter().filter_map(|v| v.as_str()).collect();
+            println!();
+            println!("  {} channel={} removed=[{}]",
+                "Floor Queue OK".green().bold(), channel_id.yellow(), names.join(", ").cyan());
+        }
+    }
+
+    let queue: Vec<AdminQueueEntry> = get_json(&url)?;
+    println!();
+    if queue.is_empty() {
+        println!("  {}", "대기열 비어 있음".dimmed());
+    } else {
+        println!("{}", Table::new(&queue));
+        println!("  {} waiting", queue.len());
+    }
+    println!();
+    Ok(())
+}
+
+fn cmd_say(
+    base:    &str,
+    api_key: Option<&str>,
+    channel: Option<&str>,
+    author:  Option<&str>,
+    content: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let client = reqwest::blocking::Client::new();
+    let url    = match channel {
+        Some(cid) => format!("{}/admin/channels/{}/messages", base, cid),
+        None      => format!("{}/admin/messages", base),
+    };
+    let req  = client.post(&url)
+        .json(&serde_json::json!({ "content": content, "author_id": author }));
+    let resp = with_key(req, api_key).send()?;
+
+    let status = resp.status();
+    let body: serde_json::Value = resp.json()?;
+    if !status.is_success() {
+        let msg = body["error"].as_str().unwrap_or("unknown error");
+        return Err(format!("[{}] {}", status, msg).into());
+    }
+
+    // 단일 채널은 객체, 전체 공지는 배열
+    let results: Vec<AdminMessageResult> = match body {
+        serde_json::Value::Array(_) => serde_json::from_value(body)?,
+        other                       => vec![serde_json::from_value(other)?],
+    };
+    println!();
+    println!("  {} {} channel(s)", "Message Sent".green().bold(), results.len());
+    println!("{}", Table::new(&results));
+    println!();
+    Ok(())
+}
+
+/// 요청에 Bearer 키 첨부 (None이면 그대로)
+fn with_key(req: reqwest::blocking::RequestBuilder, key: Option<&str>) -> reqwest::blocking::RequestBuilder {
+    match key {
+        Some(k) => req.bearer_auth(k),
+        None    => req,
+    }
+}
+
+fn cmd_service_accounts(base: &str, admin_key: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
+    let client = reqwest::blocking::Client::new();
+    let resp   = with_key(client.get(format!("{}/admin/service-accounts", base)), admin_key).send()?;
+    let status = resp.status();
+    if !status.is_success() {
+        let body: serde_json::Value = resp.json().unwrap_or_default();
+        let msg = body["error"].as_str().unwrap_or("unknown error");
+        return Err(format!("[{}] {}", status, msg).into());
+    }
+    let list: Vec<AdminServiceAccount> = resp.json()?;
+
+    if list.is_empty() {
+        println!("{}", "  등록된 서비스 계정 없음".dimmed());
+        return Ok(());
+    }
+
+    let rows: Vec<ServiceAccountRow> = list.into_iter()
+        .map(|sa| ServiceAccountRow {
+            id:         sa.id,
+            scopes:     sa.scopes.join(","),
+            key_prefix: format!("{}…", sa.key_prefix),
+            last_used:  sa.last_used_ms.map(format_ts).unwrap_or_else(|| "-".to_string()),
+            online:     if sa.online { "●".green().to_string() } else { "-".dimmed().to_string() },
+        })
+        .collect();
+
+    println!();
+    println!("{}", Table::new(&rows));
+    println!("  {} account(s)", rows.len());
+    println!();
+    Ok(())
+}
+
+fn cmd_service_account_create(
+    base:      &str,
+    admin_key: Option<&str>,
+    id:        &str,
+    scopes:    &[String],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let client = reqwest::blocking::Client::new();
+    let req    = client.post(format!("{}/admin/service-accounts", base))
+        .json(&serde_json::json!({ "id": id, "scopes": scopes }));
+    let resp   = with_key(req, admin_key).send()?;
+
+    let status = resp.status();
+    let body: serde_json::Value = resp.json()?;
+    if !status.is_success() {
+        let msg = body["error"].as_str().unwrap_or("unknown error");
+        return Err(format!("[{}] {}", status, msg).into());
+    }
+
+    println!();
+    println!("  {} id={} scopes={}",
+        "Service Account Created".green().bold(),
+        id.yellow(),
+        scopes.join(","),
+    );
+    println!("  {:10} {}", "API Key:".bold(), body["api_key"].as_str().unwrap_or("-").cyan());
+    println!("  {}", "이 키는 다시 조회할 수 없습니다 — 안전한 곳에 보관하세요".dimmed());
+    println!();
+    Ok(())
+}
+
+fn cmd_service_account_delete(base: &str, admin_key: Option<&str>, id: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let client = reqwest::blocking::Client::new();
+    let resp   = with_key(client.delete(format!("{}/admin/service-accounts/{}", base, id)), admin_key).send()?;
+
+    let status = resp.status();
+    if !status.is_success() {
+        let body: serde_json::Value = resp.json().unwrap_or_default();
+        let msg = body["error"].as_str().unwrap_or("unknown error");
+        return Err(format!("[{}] {}", status, msg).into());
+    }
+
+    println!();
+    println!("  {} id={}", "Service Account Deleted".green().bold(), id.yellow());
+    println!();
+    Ok(())
+}
+
 // ----------------------------------------------------------------------------
 // [공통 유틸]
 // ----------------------------------------------------------------------------
@@ -509,3 +1269,25 @@ fn format_ts(ms: u64) -> String {
     // 1970-01-01 기준 대략적 날짜 (운영 로그 참고용)
     format!("day+{} {:02}:{:02}:{:02} UTC", d, h, m, s)
 }
+
+/// 채널 상세용 한 줄 요약
+fn format_policy(p: &FloorPolicy) -> String {
+    format!(
+        "max={} ping={}ms queue={} depth={} preempt={} cooldown={}ms queue_timeout={} hog={}ms rr={} aging={} suspend={}ms media={} vox={} vtalker={} bcast>={}",
+        if p.max_taken_ms == 0 { "∞".to_string() } else { format!("{}ms", p.max_taken_ms) },
+        p.ping_timeout_ms,
+        if p.queue_enabled { "on" } else { "off" },
+        if p.max_queue_depth == 0 { "∞".to_string() } else { p.max_queue_depth.to_string() },
+        if p.preemption_enabled { "on" } else { "off" },
+        p.cooldown_ms,
+        if p.queue_timeout_ms == 0 { "∞".to_string() } else { format!("{}ms", p.queue_timeout_ms) },
+        p.hog_cooldown_ms,
+        if p.round_robin { "on" } else { "off" },
+        if p.aging_interval_ms == 0 { "off".to_string() } else { format!("{}ms", p.aging_interval_ms) },
+        p.suspend_grace_ms,
+        if p.media_timeout_ms == 0 { "off".to_string() } else { format!("{}ms", p.media_timeout_ms) },
+        if p.vox { format!("{}ms", p.vox_hang_ms) } else { "off".to_string() },
+        if p.virtual_talker { "on" } else { "off" },
+        p.broadcast_min_priority,
+    )
+}
@@ -11,6 +11,7 @@
 //   lctrace --filter floor           # Floor 이벤트만 (전체 채널)
 //   lctrace CH_0001 --filter floor   # CH_0001 + Floor 이벤트만
 //   lctrace --host 192.168.1.10 --port 8080 CH_0001
+//   lctrace --api-key lck_...        # --require-api-key 서버 (read-trace scope)
 
 use clap::Parser;
 use colored::Colorize;
@@ -43,6 +44,10 @@ struct Cli {
     #[arg(long, short = 'f')]
     filter: Option<String>,
 
+    /// 서비스 계정 API 키 (read-trace scope) — 서버가 --require-api-key 로 실행된 경우 필요
+    #[arg(long)]
+    api_key: Option<String>,
+
     /// 관찰할 채널 ID (생략 시 전체)
     channel_id: Option<String>,
 }
@@ -105,11 +110,14 @@ fn main() {
         .build()
         .expect("reqwest client 생성 실패");
 
-    let resp = match client
+    let mut req = client
         .get(&url)
-        .header("Accept", "text/event-stream")
-        .send()
-    {
+        .header("Accept", "text/event-stream");
+    if let Some(ref key) = cli.api_key {
+        req = req.bearer_auth(key);
+    }
+
+    let resp = match req.send() {
         Ok(r)  => r,
         Err(e) => {
             eprintln!("{} 서버 연결 실패: {}", "✗".red(), e);
@@ -29,14 +29,71 @@ pub const HEARTBEAT_INTERVAL_MS: u64 = 30_000;
 /// 채팅 메시지 최대 길이 (bytes)
 pub const MAX_MESSAGE_LENGTH: usize = 2_000;
 
+/// Admin REST로 주입하는 메시지의 기본 author_id (요청에 author_id 생략 시)
+pub const SYSTEM_AUTHOR_ID: &str = "system";
+
+/// 키 없이 주입하는 메시지에 허용되는 author_id 접두어 ("bot:cad" 등)
+/// SYSTEM_AUTHOR_ID와 함께 IDENTIFY user_id로는 사용 불가 — 실사용자 사칭 방지
+pub const BOT_AUTHOR_PREFIX: &str = "bot:";
+
 /// IDENTIFY 토큰 검증용 Secret Key
 /// 운영 환경에서는 환경변수 LIVECHAT_SECRET 으로 오버라이드 할 것
 pub const DEFAULT_SECRET_KEY: &str = "changeme-secret";
 
+// ----------------------------------------------------------------------------
+// Rate Limit (사용자별 opcode 계열 토큰 버킷, CLI --rate-* 로 오버라이드)
+// ----------------------------------------------------------------------------
+
+/// MESSAGE_CREATE — 초당 충전 토큰 / 버스트 허용량
+pub const RATE_MESSAGE_PER_SEC: u32 = 2;
+pub const RATE_MESSAGE_BURST:   u32 = 5;
+
+/// FLOOR_REQUEST / RELEASE / PING — holder Ping(2초 주기) 포함
+pub const RATE_FLOOR_PER_SEC: u32 = 5;
+pub const RATE_FLOOR_BURST:   u32 = 10;
+
+/// CHANNEL_LIST / CHANNEL_INFO 등 조회
+pub const RATE_QUERY_PER_SEC: u32 = 2;
+pub const RATE_QUERY_BURST:   u32 = 5;
+
+/// CHANNEL_CREATE / JOIN / LEAVE / UPDATE / DELETE, RENEGOTIATE
+/// Conference 다인원 입장 시 re-negotiation 연쇄를 감안해 버스트 여유 확보
+pub const RATE_CHANNEL_PER_SEC: u32 = 2;
+pub const RATE_CHANNEL_BURST:   u32 = 10;
+
+/// 위반 누적 허용치 — 윈도우 내 이 횟수에 도달하면 WS 강제 종료 (0이면 비활성화)
+pub const RATE_MAX_VIOLATIONS: u32 = 20;
+
+/// 위반 누적 윈도우 (10초)
+pub const RATE_VIOLATION_WINDOW_MS: u64 = 10_000;
+
+// ----------------------------------------------------------------------------
+// Webhook (외부 백엔드 이벤트 전달, CLI --webhook-url 로 대상 지정)
+// 서명 키는 환경변수 LIVECHAT_WEBHOOK_SECRET
+// ----------------------------------------------------------------------------
+
+/// 대상 URL별 전달 대기 큐 크기 — 꽉 차면 신규 이벤트 drop (시그널링 경로 보호)
+pub const WEBHOOK_QUEUE_SIZE: usize = 1_024;
+
+/// 전달 실패 시 최대 재시도 횟수 (최초 시도 제외)
+pub const WEBHOOK_MAX_RETRIES: u32 = 3;
+
+/// 재시도 백오프 기준 — 500ms, 1s, 2s ... 지수 증가
+pub const WEBHOOK_RETRY_BASE_MS: u64 = 500;
+
+/// 재시도 백오프 상한 (10초)
+pub const WEBHOOK_RETRY_MAX_MS: u64 = 10_000;
+
+/// HTTP POST 1회 타임아웃 (5초)
+pub const WEBHOOK_TIMEOUT_MS: u64 = 5_000;
+
 // ----------------------------------------------------------------------------
 // Floor Control (MBCP TS 24.380 기반)
 // ----------------------------------------------------------------------------
 
+// 아래 FLOOR_PING_TIMEOUT_MS / FLOOR_MAX_TAKEN_MS / FLOOR_MAX_QUEUE_DEPTH / FLOOR_QUEUE_TIMEOUT_MS는 FloorPolicy 기본값
+// 채널별로 CHANNEL_CREATE(floor_policy) 또는 PUT /admin/channels/{id}/floor-policy 로 변경
+
 /// 클라이언트 Floor Ping 수신 타임아웃 — 이 시간 안에 Ping 없으면 Floor Revoke (6초)
 /// 클라이언트 송신 주기 2초 기준 — 네트워크 지연마지 감안하여 3배 여유 확보
 pub const FLOOR_PING_TIMEOUT_MS: u64 = 6_000;
@@ -44,12 +101,66 @@ pub const FLOOR_PING_TIMEOUT_MS: u64 = 6_000;
 /// 최대 발언 점유 시간 — Emergency 포함 무조건 Revoke (30초)
 pub const FLOOR_MAX_TAKEN_MS: u64 = 30_000;
 
-/// Floor Request 응답 대기 타이머 T101 (3초)
+/// 대기열 항목 만료 기본값 (60초, 0이면 무제한) — 만료 시 FLOOR_DENY(queue_expired)
+pub const FLOOR_QUEUE_TIMEOUT_MS: u64 = 60_000;
+
+/// Ping 타임아웃 하한 — 클라이언트 Ping 주기(2초)보다 짧으면 정상 holder도 Revoke됨
+pub const FLOOR_PING_TIMEOUT_MIN_MS: u64 = 2_000;
+
+/// 대기열 최대 길이 기본값 (0이면 무제한) — 채널별 FloorPolicy로 오버라이드
+pub const FLOOR_MAX_QUEUE_DEPTH: usize = 0;
+
+/// max_duration Revoke 후 같은 사용자 재요청 금지 기본값 (10초, 0이면 비활성)
+/// 장시간 점유자가 대기열이 비었거나 priority가 높다는 이유로 즉시 재점유하는 것 방지
+pub const FLOOR_HOG_COOLDOWN_MS: u64 = 10_000;
+
+/// 대기열 aging — aging_interval_ms 대기마다 유효 priority 가산치
+/// 유효 priority는 Emergency 미만(254)까지만 올라감 (aging으로 Emergency 취급 안 함)
+pub const FLOOR_AGING_BOOST: u8 = 10;
+
+/// holder 연결 끊김 시 Floor 유지(suspended) 유예 기본값 (5초, 0이면 즉시 Revoke)
+/// 유예 중 같은 사용자가 재접속 + CHANNEL_JOIN 하면 발언 재개, 만료 시 Revoke(disconnect)
+pub const FLOOR_SUSPEND_GRACE_MS: u64 = 5_000;
+
+/// holder 미디어(RTP) 무수신 Revoke 기본값 (5초, 0이면 비활성) — Grant 후 / 마지막 RTP 이후
+/// 클라이언트 크래시로 FLOOR_PING / WS는 살아 있는데 마이크 경로가 죽은 경우 max_duration까지 채널이 잠기는 것 방지
+pub const FLOOR_MEDIA_TIMEOUT_MS: u64 = 5_000;
+
+/// 미디어 무수신 타임아웃 하한 — Opus DTX 무음 구간(400ms 주기 comfort noise)보다 충분히 길어야 함
+pub const FLOOR_MEDIA_TIMEOUT_MIN_MS: u64 = 1_000;
+
+/// VOX(음성 감지) Floor — 무음 지속 시 자동 Release까지의 hang time 기본값 (1.5초)
+pub const FLOOR_VOX_HANG_MS: u64 = 1_500;
+
+/// VOX 음성 판정 기준 — RFC 6464 audio level(-dBov, 0 = 최대 / 127 = 무음) 이 값 이하면 음성 (-50dBov)
+pub const FLOOR_VOX_THRESHOLD_DBOV: u8 = 50;
+
+/// VOX 자동 요청이 Deny / 오류로 끝난 뒤 같은 엔드포인트의 재요청 간격 (RTP마다 요청 폭주 방지)
+pub const VOX_REQUEST_RETRY_MS: u64 = 1_000;
+
+/// Floor Granted 확인 대기 타이머 T101 (3초)
+/// holder의 첫 FLOOR_PING이 확인 — 만료 시 FLOOR_GRANTED 재전송
 pub const FLOOR_T101_MS: u64 = 3_000;
 
-/// Floor Release 응답 대기 타이머 T100 (3초)
+/// Floor Revoke 확인 대기 타이머 T100 (3초)
+/// 대상의 FLOOR_RELEASE가 확인 — 만료 시 FLOOR_REVOKE 재전송
 pub const FLOOR_T100_MS: u64 = 3_000;
 
+/// T100 / T101 최대 재전송 횟수 (MBCP C100 / C101)
+pub const FLOOR_RETRANSMIT_MAX: u32 = 3;
+
+/// 서버가 보내는 MBCP(SRTCP APP "MCPT") 패킷의 SSRC — 미디어 SSRC와 겹치지 않는 고정값
+pub const MBCP_SERVER_SSRC: u32 = 0x4D43_5054; // "MCPT"
+
+/// 채널별 talk-burst 이력 보관 개수 (메모리, 초과 시 오래된 것부터 제거)
+pub const FLOOR_HISTORY_MAX: usize = 500;
+
+/// talk-burst 이력 파일 회전 기준 줄 수 — 초과 시 <path>.1로 이동 후 새 파일 (1세대 보관)
+pub const FLOOR_HISTORY_ROTATE_LINES: usize = 100_000;
+
+/// talk-burst 이력 writer 큐 크기 — 가득 차면 파일 기록만 생략 (메모리 이력은 유지)
+pub const FLOOR_HISTORY_QUEUE: usize = 1_024;
+
 /// 발언권 우선순위 — Emergency 고정값 (최고)
 pub const FLOOR_PRIORITY_EMERGENCY: u8 = 255;
 
@@ -59,6 +170,80 @@ pub const FLOOR_PRIORITY_IMMINENT_PERIL: u8 = 200;
 /// 발언권 우선순위 — 일반 기본값
 pub const FLOOR_PRIORITY_DEFAULT: u8 = 100;
 
+/// Broadcast 요청 최소 IDENTIFY priority 기본값 — 일반 사용자(100)는 불가, 관제 / 지령 계정에 부여
+pub const FLOOR_BROADCAST_MIN_PRIORITY: u8 = 200;
+
+// ----------------------------------------------------------------------------
+// 미디어 릴레이 — NACK 재전송 / keyframe 요청 / 가상 talker 스트림
+// ----------------------------------------------------------------------------
+
+/// 송신 SSRC별 재전송 버퍼 크기 (패킷 수, 65536의 약수) — 720p 영상 기준 약 1초
+pub const NACK_CACHE_PACKETS: usize = 512;
+
+/// 재전송 버퍼 보관 시간 — 이보다 오래된 패킷은 NACK에 응답하지 않음 (수신 jitter buffer가 이미 포기)
+pub const NACK_CACHE_MAX_AGE_MS: u64 = 1_000;
+
+/// 재전송 버퍼 최대 스트림 수 — 초과 시 새 SSRC는 버퍼 없이 릴레이만 (유휴 버퍼는 reaper가 정리)
+pub const NACK_CACHE_MAX_STREAMS: usize = 4_096;
+
+/// 서버가 직접 보내는 RTCP feedback(PLI)의 송신자 SSRC — 미디어 SSRC와 겹치지 않는 고정값
+pub const RTCP_SERVER_SSRC: u32 = 0x4C43_5356; // "LCSV"
+
+/// RTP clock rate — 가상 talker 스트림의 talker 전환 시 timestamp 간격 계산 (Opus 48kHz / video 90kHz)
+pub const RTP_CLOCK_RATE_AUDIO: u32 = 48_000;
+pub const RTP_CLOCK_RATE_VIDEO: u32 = 90_000;
+
+/// 서버 발 keyframe 요청(PLI) 최소 간격 — 입장 시 구성원 전원 re-nego 연쇄로 같은 송신자에 PLI가 몰리는 것 방지
+pub const KEYFRAME_REQUEST_MIN_MS: u64 = 500;
+
+// ----------------------------------------------------------------------------
+// 발언자 감지 (Conference active speaker — RFC 6464 audio level)
+// ----------------------------------------------------------------------------
+
+/// 발언자 판정 / 레벨 스냅샷 주기 기본값 (CLI --speaker-interval-ms 로 오버라이드, 0이면 비활성)
+pub const SPEAKER_INTERVAL_MS: u64 = 300;
+
+/// 발언 판정 기준 — 평활 audio level(-dBov)이 이 값 이하면 발언 중 (VOX 기본값과 같은 -50dBov)
+pub const SPEAKER_THRESHOLD_DBOV: u8 = 50;
+
+/// dominant speaker 교체 여유 — 현재 발언자보다 이만큼(dB) 넘게 커야 교체 (말이 겹칠 때 깜빡임 방지)
+pub const SPEAKER_SWITCH_MARGIN_DB: u8 = 6;
+
+/// 마지막 audio 패킷 이후 이 시간이 지나면 무음 — DTX로 송신이 멈춘 경우 (Opus DTX comfort noise 400ms 주기)
+pub const SPEAKER_SILENCE_MS: u64 = 500;
+
+/// audio level 평활 — 1/N 지수 이동 평균 (패킷 20ms 기준 N=4 → 약 80ms 시정수)
+pub const SPEAKER_SMOOTHING: u32 = 4;
+
+// ----------------------------------------------------------------------------
+// Simulcast 레이어 선택 (Conference video — 수신자별 레이어 전달)
+// ----------------------------------------------------------------------------
+
+/// 레이어 수신이 이 시간 끊기면 비활성 — 송신자가 대역폭 부족으로 상위 레이어를 멈춘 경우 아래 레이어로
+pub const SIMULCAST_LAYER_TIMEOUT_MS: u64 = 1_000;
+
+/// 수신자 RR fraction lost(0~255)가 이 값을 넘으면 한 단계 낮춤 (약 10%)
+pub const SIMULCAST_LOSS_DOWN: u8 = 26;
+
+/// fraction lost가 이 값 미만으로 SIMULCAST_UPGRADE_MS 유지되면 한 단계 올림 (약 2%)
+pub const SIMULCAST_LOSS_UP: u8 = 5;
+
+/// 손실 낮은 상태가 이만큼 이어져야 상위 레이어 시도 (RR 주기 약 1초 기준 5회)
+pub const SIMULCAST_UPGRADE_MS: u64 = 5_000;
+
+/// 레이어를 바꾼 직후 이 시간 동안은 손실로 다시 낮추지 않음 (전환 직후 RR은 이전 레이어 기준)
+pub const SIMULCAST_DOWNGRADE_HOLD_MS: u64 = 2_000;
+
+// ----------------------------------------------------------------------------
+// 1:1 개별 호출 (MCPTT private call)
+// ----------------------------------------------------------------------------
+
+/// call_id 접두어 — 같은 id로 숨은 2인 미디어 채널 생성 (CHANNEL_LIST 제외, 직접 JOIN 불가)
+pub const CALL_ID_PREFIX: &str = "CALL_";
+
+/// 피호출자 무응답 타임아웃 (30초) — 만료 시 CALL_ENDED(timeout)
+pub const CALL_RING_TIMEOUT_MS: u64 = 30_000;
+
 // ----------------------------------------------------------------------------
 // 사전 생성 채널 (서버 시작 시 자동 생성)
 // (channel_id, freq, name, mode, capacity)
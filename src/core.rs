@@ -1,13 +1,21 @@
 // author: kodeholic (powered by Claude)
 // 네트워크 로직과 철저히 분리된, 순수 비즈니스 상태 관리 모듈입니다.
 
+pub mod call;
 pub mod channel;
+pub mod emergency;
 pub mod floor;
 pub mod media_peer;
+pub mod rate_limit;
+pub mod service_account;
 pub mod user;
 
 // re-export: 기존 `use crate::core::*` 코드가 그대로 동작하도록
 pub use user::{BroadcastTx, User, UserHub};
+pub use call::{Call, CallHub, CallState, CallType};
 pub use channel::{Channel, ChannelHub, ChannelMode};
-pub use floor::{FloorControl, FloorControlState, FloorIndicator, FloorQueueEntry};
+pub use emergency::{EmergencySource, EmergencyState};
+pub use floor::{BurstEnd, FloorControl, FloorControlState, FloorDenyReason, FloorIndicator, FloorPolicy, FloorQueueEntry, TalkBurst};
 pub use media_peer::{ConsumerSsrcKey, Endpoint, MediaPeer, MediaPeerHub, Track, TrackKind};
+pub use rate_limit::{BucketSpec, RateClass, RateDecision, RateLimitConfig, RateLimiter};
+pub use service_account::{ApiScope, ServiceAccount, ServiceAccountHub};
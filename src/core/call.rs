@@ -0,0 +1,240 @@
+// author: kodeholic (powered by Claude)
+// CallHub — 1:1 개별 호출 (MCPTT private call) 상태 관리
+//
+// 상태 전이:
+//   INVITE  → Ringing (수동 응답) / Active (automatic commencement — 즉시 연결)
+//   Ringing → ACCEPT → Active
+//   Ringing → REJECT / CANCEL / 무응답 타임아웃 → 종료
+//   Active  → HANGUP / 연결 끊김 → 종료
+//
+// 미디어는 call_id와 같은 id의 숨은 2인 채널을 ChannelHub에 만들어 기존
+// MediaPeerHub 엔드포인트 / 릴레이 / FloorControl을 그대로 사용 (protocol::call)
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tracing::trace;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config;
+use crate::error::{LiveError, LiveResult};
+use crate::utils::current_timestamp;
+
+// ----------------------------------------------------------------------------
+// [호출 유형]
+//   FullDuplex — 양방향 동시 통화 (Conference 채널, Floor 미적용)
+//   Ptt        — 1:1 무전 (PTT 채널, Floor Control 적용)
+// ----------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CallType {
+    #[default]
+    FullDuplex,
+    Ptt,
+}
+
+impl CallType {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CallType::FullDuplex => "full_duplex",
+            CallType::Ptt        => "ptt",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CallState {
+    Ringing, // 피호출자 응답 대기
+    Active,  // 연결됨
+}
+
+impl CallState {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CallState::Ringing => "ringing",
+            CallState::Active  => "active",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Call {
+    pub call_id:     String,   // = 숨은 미디어 채널 id
+    pub caller:      String,
+    pub callee:      String,
+    pub call_type:   CallType,
+    pub automatic:   bool,     // automatic commencement — 피호출자 응답 없이 즉시 연결
+    pub state:       CallState,
+    pub created_at:  u64,
+    pub answered_at: Option<u64>,
+}
+
+impl Call {
+    pub fn is_party(&self, user_id: &str) -> bool {
+        self.caller == user_id || self.callee == user_id
+    }
+
+    /// 상대방 user_id (당사자가 아니면 None)
+    pub fn peer_of(&self, user_id: &str) -> Option<&str> {
+        if self.caller == user_id {
+            Some(&self.callee)
+        } else if self.callee == user_id {
+            Some(&self.caller)
+        } else {
+            None
+        }
+    }
+}
+
+pub struct CallHub {
+    calls:   RwLock<HashMap<String, Call>>,
+    next_id: AtomicU64,
+}
+
+impl CallHub {
+    pub fn new() -> Self {
+        Self {
+            calls:   RwLock::new(HashMap::new()),
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    /// 호출 생성 — 호출자 / 피호출자 중 하나라도 통화 중이면 CallBusy
+    /// automatic이면 바로 Active
+    pub fn invite(&self, caller: &str, callee: &str, call_type: CallType, automatic: bool) -> LiveResult<Call> {
+        if caller == callee {
+            return Err(LiveError::InvalidPayload("cannot call yourself".to_string()));
+        }
+
+        let mut calls = self.calls.write().unwrap();
+        if let Some(busy) = [caller, callee].into_iter().find(|u| calls.values().any(|c| c.is_party(u))) {
+            return Err(LiveError::CallBusy(busy.to_string()));
+        }
+
+        let now     = current_timestamp();
+        let call_id = format!("{}{:06}", config::CALL_ID_PREFIX, self.next_id.fetch_add(1, Ordering::Relaxed));
+        let call    = Call {
+            call_id:     call_id.clone(),
+            caller:      caller.to_string(),
+            callee:      callee.to_string(),
+            call_type,
+            automatic,
+            state:       if automatic { CallState::Active } else { CallState::Ringing },
+            created_at:  now,
+            answered_at: automatic.then_some(now),
+        };
+        trace!("Call {} created caller={} callee={} type={} automatic={}", call_id, caller, callee, call_type.as_str(), automatic);
+        calls.insert(call_id, call.clone());
+        Ok(call)
+    }
+
+    pub fn get(&self, call_id: &str) -> Option<Call> {
+        self.calls.read().unwrap().get(call_id).cloned()
+    }
+
+    /// 피호출자 응답 — Ringing → Active
+    pub fn accept(&self, call_id: &str, user_id: &str) -> LiveResult<Call> {
+        let mut calls = self.calls.write().unwrap();
+        let call = calls.get_mut(call_id)
+            .ok_or_else(|| LiveError::CallNotFound(call_id.to_string()))?;
+        if call.callee != user_id {
+            return Err(LiveError::CallNotFound(call_id.to_string()));
+        }
+        if call.state != CallState::Ringing {
+            return Err(LiveError::CallInvalidState(call_id.to_string()));
+        }
+        call.state       = CallState::Active;
+        call.answered_at = Some(current_timestamp());
+        trace!("Call {} accepted by {}", call_id, user_id);
+        Ok(call.clone())
+    }
+
+    /// 무응답 타임아웃 — 아직 Ringing일 때만 제거 (수락과 경합해도 한쪽만 성공)
+    pub fn remove_if_ringing(&self, call_id: &str) -> Option<Call> {
+        let mut calls = self.calls.write().unwrap();
+        if calls.get(call_id)?.state != CallState::Ringing {
+            return None;
+        }
+        trace!("Call {} ring timeout", call_id);
+        calls.remove(call_id)
+    }
+
+    pub fn remove(&self, call_id: &str) -> Option<Call> {
+        let removed = self.calls.write().unwrap().remove(call_id);
+        if removed.is_some() {
+            trace!("Call {} removed", call_id);
+        }
+        removed
+    }
+
+    /// 사용자가 당사자인 호출 (동시에 1개만 허용)
+    pub fn call_of(&self, user_id: &str) -> Option<Call> {
+        self.calls.read().unwrap().values().find(|c| c.is_party(user_id)).cloned()
+    }
+
+    pub fn all_calls(&self) -> Vec<Call> {
+        self.calls.read().unwrap().values().cloned().collect()
+    }
+
+    pub fn count(&self) -> usize {
+        self.calls.read().unwrap().len()
+    }
+}
+
+impl Default for CallHub {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invite_rings_then_accept_activates() {
+        let hub  = CallHub::new();
+        let call = hub.invite("alice", "bob", CallType::Ptt, false).unwrap();
+        assert_eq!(call.state, CallState::Ringing);
+        assert!(call.call_id.starts_with(config::CALL_ID_PREFIX));
+        assert_eq!(call.peer_of("alice"), Some("bob"));
+        assert_eq!(call.peer_of("carol"), None);
+
+        // 호출자는 수락 불가
+        assert!(matches!(hub.accept(&call.call_id, "alice"), Err(LiveError::CallNotFound(_))));
+        let active = hub.accept(&call.call_id, "bob").unwrap();
+        // 수락 후에는 타임아웃으로 제거되지 않음
+        assert!(hub.remove_if_ringing(&call.call_id).is_none());
+        assert_eq!(active.state, CallState::Active);
+        assert!(active.answered_at.is_some());
+        // 중복 수락
+        assert!(matches!(hub.accept(&call.call_id, "bob"), Err(LiveError::CallInvalidState(_))));
+    }
+
+    #[test]
+    fn automatic_commencement_is_active_immediately() {
+        let hub  = CallHub::new();
+        let call = hub.invite("alice", "bob", CallType::FullDuplex, true).unwrap();
+        assert_eq!(call.state, CallState::Active);
+        assert_eq!(call.answered_at, Some(call.created_at));
+    }
+
+    #[test]
+    fn busy_and_self_call_rejected() {
+        let hub = CallHub::new();
+        assert!(matches!(hub.invite("alice", "alice", CallType::Ptt, false), Err(LiveError::InvalidPayload(_))));
+
+        let call = hub.invite("alice", "bob", CallType::Ptt, false).unwrap();
+        assert!(matches!(hub.invite("carol", "bob", CallType::Ptt, false), Err(LiveError::CallBusy(u)) if u == "bob"));
+        assert!(matches!(hub.invite("alice", "carol", CallType::Ptt, false), Err(LiveError::CallBusy(u)) if u == "alice"));
+        assert_eq!(hub.call_of("bob").unwrap().call_id, call.call_id);
+
+        assert!(hub.remove(&call.call_id).is_some());
+        assert!(hub.remove(&call.call_id).is_none());
+        assert!(hub.call_of("bob").is_none());
+        assert!(hub.invite("carol", "bob", CallType::Ptt, false).is_ok());
+        assert_eq!(hub.count(), 1);
+    }
+}
@@ -1,16 +1,32 @@
 // author: kodeholic (powered by Claude)
 // ChannelHub — 채널 정의 + 멤버 목록 관리
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex, RwLock};
-use tracing::trace;
+use tokio::sync::{mpsc, Notify};
+use tracing::{trace, warn};
 
 use serde::{Deserialize, Serialize};
 
 use crate::error::{LiveError, LiveResult};
 use crate::utils::current_timestamp;
 
-use super::floor::{FloorControl, FloorControlState};
+use crate::config;
+use crate::media::rtp::TalkerStream;
+
+use super::emergency::{EmergencySource, EmergencyState};
+use super::floor::{FloorControl, FloorControlState, FloorPolicy, TalkBurst};
+use super::media_peer::TrackKind;
+
+fn new_talker_stream(kind: &TrackKind) -> TalkerStream {
+    let clock_rate = match kind {
+        TrackKind::Audio => config::RTP_CLOCK_RATE_AUDIO,
+        _                => config::RTP_CLOCK_RATE_VIDEO,
+    };
+    TalkerStream::new(rand::random::<u32>() | 1, clock_rate)
+}
 
 // ----------------------------------------------------------------------------
 // [채널 모드]
@@ -57,6 +73,8 @@ pub struct Channel {
     pub created_at: u64,
     pub members:    RwLock<HashSet<String>>,    // user_id
     pub floor:      Mutex<FloorControl>,        // MBCP Floor Control 상태 (Ptt 모드에서만 사용)
+    pub emergency:  Mutex<Option<EmergencyState>>, // 긴급 상태 (Dispatcher 해제 전까지 유지)
+    pub talker:     Mutex<HashMap<TrackKind, TalkerStream>>, // 가상 talker 스트림 (PTT FloorPolicy.virtual_talker)
 }
 
 impl Channel {
@@ -71,6 +89,8 @@ impl Channel {
             created_at: current_timestamp(),
             members:    RwLock::new(HashSet::new()),
             floor:      Mutex::new(FloorControl::new()),
+            emergency:  Mutex::new(None),
+            talker:     Mutex::new(HashMap::new()),
         }
     }
 
@@ -79,6 +99,96 @@ impl Channel {
         self.mode == ChannelMode::PTT
     }
 
+    /// 1:1 개별 호출용 숨은 채널 여부 (CallHub가 생성 / 제거)
+    pub fn is_call(&self) -> bool {
+        self.channel_id.starts_with(crate::config::CALL_ID_PREFIX)
+    }
+
+    /// 현재 Floor 정책 사본
+    pub fn floor_policy(&self) -> FloorPolicy {
+        self.floor.lock().unwrap().policy.clone()
+    }
+
+    /// Floor 정책 교체 — 진행 중인 발언에도 다음 타임아웃 체크부터 적용
+    /// virtual_talker는 멤버가 없을 때만 변경 가능 (입장 시 SDP answer의 가상 SSRC 광고 여부가 고정됨) — 위반 시 Err(사유)
+    pub fn set_floor_policy(&self, policy: FloorPolicy) -> Result<(), String> {
+        // members → floor 순서로 잡아 검사와 교체 사이에 입장(add_member)이 끼지 않도록
+        let members   = self.members.read().unwrap();
+        let mut floor = self.floor.lock().unwrap();
+        if floor.policy.virtual_talker != policy.virtual_talker && !members.is_empty() {
+            return Err(format!(
+                "virtual_talker cannot change while channel has members ({})", members.len()
+            ));
+        }
+        trace!("Channel {} floor policy: {:?}", self.channel_id, policy);
+        floor.policy = policy;
+        Ok(())
+    }
+
+    /// 가상 talker 스트림 SSRC (없으면 생성) — join SDP answer 광고용
+    pub fn talker_ssrc(&self, kind: TrackKind) -> u32 {
+        let mut talker = self.talker.lock().unwrap();
+        talker.entry(kind.clone()).or_insert_with(|| new_talker_stream(&kind)).ssrc
+    }
+
+    /// 생성된 가상 talker 스트림 SSRC 전체 — 채널 제거 시 재전송 버퍼 정리용
+    pub fn talker_ssrcs(&self) -> Vec<u32> {
+        self.talker.lock().unwrap().values().map(|s| s.ssrc).collect()
+    }
+
+    /// holder RTP → 가상 talker 스트림 RTP (relay 핫패스)
+    pub fn rewrite_talker(&self, kind: TrackKind, packet: &[u8], now: u64) -> Option<Vec<u8>> {
+        let mut talker = self.talker.lock().unwrap();
+        talker.entry(kind.clone()).or_insert_with(|| new_talker_stream(&kind)).rewrite(packet, now)
+    }
+
+    /// 가상 talker SSRC → 현재 talker 원본 SSRC (RTCP feedback 역매핑, 가상 SSRC가 아니면 None)
+    pub fn talker_source(&self, ssrc: u32) -> Option<u32> {
+        self.talker.lock().unwrap().values().find(|s| s.ssrc == ssrc)?.source()
+    }
+
+    /// 현재 talker SR → (가상 SSRC, 가상 RTP timestamp)
+    pub fn talker_sr(&self, source: u32, ts: u32) -> Option<(u32, u32)> {
+        self.talker.lock().unwrap().values()
+            .find_map(|s| s.map_timestamp(source, ts).map(|t| (s.ssrc, t)))
+    }
+
+    /// 현재 긴급 상태 사본
+    pub fn emergency(&self) -> Option<EmergencyState> {
+        self.emergency.lock().unwrap().clone()
+    }
+
+    /// 긴급 상태 발령 — 새로 발령된 경우에만 Some (이미 발령 중이면 None)
+    pub fn raise_emergency(&self, user_id: &str, source: EmergencySource) -> Option<EmergencyState> {
+        let mut emergency = self.emergency.lock().unwrap();
+        if emergency.is_some() {
+            return None;
+        }
+        let state = EmergencyState::new(user_id.to_string(), source);
+        tracing::warn!("Channel {} emergency raised by {} ({:?})", self.channel_id, user_id, source);
+        *emergency = Some(state.clone());
+        Some(state)
+    }
+
+    /// 긴급 상태 확인 — 새로 기록된 경우에만 갱신된 상태 반환
+    pub fn ack_emergency(&self, user_id: &str) -> Option<EmergencyState> {
+        let mut emergency = self.emergency.lock().unwrap();
+        let state = emergency.as_mut()?;
+        if !state.ack(user_id) {
+            return None;
+        }
+        Some(state.clone())
+    }
+
+    /// 긴급 상태 해제 — 해제된 상태 반환 (발령 중이 아니면 None)
+    pub fn clear_emergency(&self) -> Option<EmergencyState> {
+        let cleared = self.emergency.lock().unwrap().take();
+        if cleared.is_some() {
+            trace!("Channel {} emergency cleared", self.channel_id);
+        }
+        cleared
+    }
+
     pub fn add_member(&self, user_id: &str) -> LiveResult<()> {
         let mut members = self.members.write().unwrap();
         if members.len() >= self.capacity {
@@ -104,30 +214,186 @@ impl Channel {
     pub fn member_count(&self) -> usize {
         self.members.read().unwrap().len()
     }
+
+    /// 이력 파일에서 읽은 talk-burst 복원 (오래된 것부터)
+    fn restore_history(&self, bursts: VecDeque<TalkBurst>) {
+        let mut floor = self.floor.lock().unwrap();
+        for burst in bursts {
+            floor.push_history(burst);
+        }
+    }
 }
 
 pub struct ChannelHub {
-    pub channels: RwLock<HashMap<String, Arc<Channel>>>,
+    pub channels:    RwLock<HashMap<String, Arc<Channel>>>,
+    /// Floor 타이머 태스크 깨우기 — 만료 시각이 앞당겨지는 변경(Grant, 정책 변경, T100 시작) 후 호출
+    pub floor_timer: Notify,
+    /// talk-burst 이력 writer 큐 (JSONL 1줄) — None이면 메모리 전용
+    history_tx:      Mutex<Option<mpsc::Sender<String>>>,
+    history_writer:  Mutex<Option<std::thread::JoinHandle<()>>>,
+    /// 이력 파일에서 읽었으나 아직 없는 채널 몫 — 채널 생성 시 복원 (CHANNEL_CREATE 채널 포함)
+    pending_history: Mutex<HashMap<String, VecDeque<TalkBurst>>>,
+}
+
+/// talk-burst 이력 파일 1줄 — 채널 구분 포함
+#[derive(Serialize, Deserialize)]
+struct HistoryLine {
+    channel_id: String,
+    #[serde(flatten)]
+    burst:      TalkBurst,
+}
+
+/// 이력 파일 전용 writer 스레드 — blocking append, FLOOR_HISTORY_ROTATE_LINES 초과 시 <path>.1로 회전
+/// 회전본은 1세대만 보관 → 디스크 사용량 / 시작 시 읽는 양 모두 상한
+struct HistoryWriter {
+    path:  PathBuf,
+    file:  std::fs::File,
+    lines: usize,
+}
+
+impl HistoryWriter {
+    fn run(mut self, mut rx: mpsc::Receiver<String>) {
+        while let Some(line) = rx.blocking_recv() {
+            if self.lines >= config::FLOOR_HISTORY_ROTATE_LINES {
+                if let Err(e) = self.rotate() {
+                    warn!("[floor-history] Failed to rotate {}: {}", self.path.display(), e);
+                }
+            }
+            match writeln!(self.file, "{}", line) {
+                Ok(()) => self.lines += 1,
+                Err(e) => warn!("[floor-history] Failed to append: {}", e),
+            }
+        }
+    }
+
+    fn rotate(&mut self) -> std::io::Result<()> {
+        std::fs::rename(&self.path, rotated_path(&self.path))?;
+        self.file  = std::fs::OpenOptions::new().create(true).append(true).open(&self.path)?;
+        self.lines = 0;
+        Ok(())
+    }
+}
+
+fn rotated_path(path: &Path) -> PathBuf {
+    let mut rotated = path.as_os_str().to_owned();
+    rotated.push(".1");
+    PathBuf::from(rotated)
 }
 
 impl ChannelHub {
     pub fn new() -> Self {
         trace!("Initializing ChannelHub");
-        Self { channels: RwLock::new(HashMap::new()) }
+        Self {
+            channels:        RwLock::new(HashMap::new()),
+            floor_timer:     Notify::new(),
+            history_tx:      Mutex::new(None),
+            history_writer:  Mutex::new(None),
+            pending_history: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// talk-burst 이력 파일 연결 — 회전본(<path>.1) + 현재 파일을 읽어 채널별 최근 FLOOR_HISTORY_MAX건 복원,
+    /// 이후 기록은 writer 스레드가 append
+    ///   존재하는 채널은 즉시, 아직 없는 채널은 create 시점에 복원
+    /// 반환값: 읽은 건수 (채널별 상한 적용 후, 파싱 불가 줄은 건너뜀)
+    pub fn open_history_file(&self, path: &Path) -> std::io::Result<usize> {
+        let mut pending = self.pending_history.lock().unwrap();
+        let mut lines   = 0;
+        for (src, current) in [(rotated_path(path), false), (path.to_path_buf(), true)] {
+            if !src.exists() {
+                continue;
+            }
+            for line in std::io::BufReader::new(std::fs::File::open(&src)?).lines() {
+                let line = line?;
+                if current {
+                    lines += 1;
+                }
+                let Ok(record) = serde_json::from_str::<HistoryLine>(&line) else { continue };
+                let bursts = pending.entry(record.channel_id).or_default();
+                if bursts.len() >= config::FLOOR_HISTORY_MAX {
+                    bursts.pop_front();
+                }
+                bursts.push_back(record.burst);
+            }
+        }
+        let loaded = pending.values().map(VecDeque::len).sum();
+
+        for ch in self.channels.read().unwrap().values() {
+            if let Some(bursts) = pending.remove(&ch.channel_id) {
+                ch.restore_history(bursts);
+            }
+        }
+
+        let file     = std::fs::OpenOptions::new().create(true).append(true).open(path)?;
+        let writer   = HistoryWriter { path: path.to_path_buf(), file, lines };
+        let (tx, rx) = mpsc::channel(config::FLOOR_HISTORY_QUEUE);
+        let handle   = std::thread::Builder::new()
+            .name("floor-history".to_string())
+            .spawn(move || writer.run(rx))?;
+        *self.history_tx.lock().unwrap()     = Some(tx);
+        *self.history_writer.lock().unwrap() = Some(handle);
+        Ok(loaded)
+    }
+
+    /// writer 종료 — 큐에 남은 기록을 모두 쓴 뒤 반환
+    pub fn close_history_file(&self) {
+        self.history_tx.lock().unwrap().take();
+        if let Some(handle) = self.history_writer.lock().unwrap().take() {
+            let _ = handle.join();
+        }
+    }
+
+    /// 종료된 talk-burst를 이력 파일 writer 큐에 넣음 (파일 미연결 시 무시)
+    /// floor lock 보유 중 호출되므로 파일 I/O 없이 try_send만 — 큐가 가득 차면 파일 기록만 생략
+    /// 채널 메모리 이력은 FloorControl::end_burst()에서 이미 기록됨
+    pub fn record_burst(&self, channel_id: &str, burst: &TalkBurst) {
+        let tx = self.history_tx.lock().unwrap();
+        let Some(tx) = tx.as_ref() else { return };
+        let line = serde_json::to_string(&HistoryLine { channel_id: channel_id.to_string(), burst: burst.clone() })
+            .unwrap_or_default();
+        if tx.try_send(line).is_err() {
+            warn!("[floor-history] writer queue full, burst not persisted: channel={}", channel_id);
+        }
+    }
+
+    /// Floor 타이머 태스크에 만료 시각 재계산 요청
+    pub fn wake_floor_timer(&self) {
+        self.floor_timer.notify_one();
+    }
+
+    /// 전체 채널 중 가장 가까운 Floor 만료 시각
+    pub fn next_floor_deadline(&self) -> Option<u64> {
+        self.channels.read().unwrap()
+            .values()
+            .filter_map(|ch| ch.floor.lock().unwrap().next_deadline())
+            .min()
     }
 
     pub fn create(&self, channel_id: &str, freq: &str, name: &str, mode: ChannelMode, capacity: usize) -> Arc<Channel> {
+        self.create_if_absent(channel_id, freq, name, mode, capacity).0
+    }
+
+    /// get-or-insert — (채널, 새로 만들었는지). 기존 채널이면 인자는 무시
+    pub fn create_if_absent(&self, channel_id: &str, freq: &str, name: &str, mode: ChannelMode, capacity: usize) -> (Arc<Channel>, bool) {
         let mut channels = self.channels.write().unwrap();
-        let ch = channels.entry(channel_id.to_string()).or_insert_with(|| {
-            Arc::new(Channel::new(
-                channel_id.to_string(),
-                freq.to_string(),
-                name.to_string(),
-                mode,
-                capacity,
-            ))
-        });
-        Arc::clone(ch)
+        if let Some(ch) = channels.get(channel_id) {
+            return (Arc::clone(ch), false);
+        }
+        let ch = Arc::new(Channel::new(
+            channel_id.to_string(),
+            freq.to_string(),
+            name.to_string(),
+            mode,
+            capacity,
+        ));
+        channels.insert(channel_id.to_string(), Arc::clone(&ch));
+        drop(channels);
+
+        // 이력 파일에 남아 있던 같은 id 채널 기록 복원
+        if let Some(bursts) = self.pending_history.lock().unwrap().remove(channel_id) {
+            ch.restore_history(bursts);
+        }
+        (ch, true)
     }
 
     pub fn get(&self, channel_id: &str) -> Option<Arc<Channel>> {
@@ -170,8 +436,93 @@ mod tests {
         hub.create("CH_001", "0001", "first", ChannelMode::PTT, 10);
         hub.create("CH_001", "0001", "second", ChannelMode::PTT, 20);
         assert_eq!(hub.count(), 1);
-        // or_insert_with — 첫 번째 값 유지
+        // 첫 번째 값 유지
         assert_eq!(hub.get("CH_001").unwrap().capacity, 10);
+        assert!(!hub.create_if_absent("CH_001", "0001", "third", ChannelMode::PTT, 30).1);
+        assert!(hub.create_if_absent("CH_002", "0002", "new", ChannelMode::PTT, 30).1);
+    }
+
+    #[test]
+    fn emergency_persists_until_cleared() {
+        let hub = ChannelHub::new();
+        let ch  = hub.create("CH_001", "0001", "test", ChannelMode::PTT, 10);
+        assert!(ch.raise_emergency("alice", EmergencySource::Alert).is_some());
+        // 발령 중 재발령은 무시 — 최초 발령자 유지
+        assert!(ch.raise_emergency("bob", EmergencySource::FloorRequest).is_none());
+        assert_eq!(ch.ack_emergency("bob").unwrap().acked_by, vec!["bob"]);
+        assert!(ch.ack_emergency("bob").is_none());
+        assert_eq!(ch.emergency().unwrap().raised_by, "alice");
+
+        assert!(ch.clear_emergency().is_some());
+        assert!(ch.emergency().is_none());
+        assert!(ch.ack_emergency("carol").is_none());
+    }
+
+    #[test]
+    fn virtual_talker_locked_while_members_joined() {
+        let hub = ChannelHub::new();
+        let ch  = hub.create("CH_001", "0001", "test", ChannelMode::PTT, 10);
+        let talker = FloorPolicy { virtual_talker: true, ..Default::default() };
+
+        ch.add_member("alice").unwrap();
+        assert!(ch.set_floor_policy(talker.clone()).is_err());
+        assert!(!ch.floor_policy().virtual_talker);
+        // virtual_talker 외 필드는 멤버가 있어도 변경 가능
+        ch.set_floor_policy(FloorPolicy { queue_enabled: false, ..Default::default() }).unwrap();
+
+        ch.remove_member("alice");
+        ch.set_floor_policy(talker).unwrap();
+        assert!(ch.floor_policy().virtual_talker);
+    }
+
+    #[test]
+    fn history_file_roundtrip() {
+        use crate::core::{BurstEnd, FloorIndicator};
+
+        let path = std::env::temp_dir().join(format!("lc_history_{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let hub = ChannelHub::new();
+        let ch  = hub.create("CH_001", "0001", "test", ChannelMode::PTT, 10);
+        assert_eq!(hub.open_history_file(&path).unwrap(), 0);
+        let burst = {
+            let mut floor = ch.floor.lock().unwrap();
+            floor.grant("alice".into(), 100, FloorIndicator::Normal);
+            let b = floor.end_burst(BurstEnd::Release, "release", None).unwrap();
+            floor.clear_taken();
+            b
+        };
+        hub.record_burst("CH_001", &burst);
+        hub.record_burst("CH_LATER", &burst);
+        hub.close_history_file();
+
+        // 재시작 — 존재하는 채널은 즉시, 나중에 만든 채널(CHANNEL_CREATE)은 생성 시 복원
+        let hub2 = ChannelHub::new();
+        let ch2  = hub2.create("CH_001", "0001", "test", ChannelMode::PTT, 10);
+        assert_eq!(hub2.open_history_file(&path).unwrap(), 2);
+        assert_eq!(ch2.floor.lock().unwrap().history[0].user_id, "alice");
+        let later = hub2.create("CH_LATER", "0002", "later", ChannelMode::PTT, 10);
+        assert_eq!(later.floor.lock().unwrap().history.len(), 1);
+        hub2.close_history_file();
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn history_writer_rotates() {
+        let path = std::env::temp_dir().join(format!("lc_history_rot_{}.jsonl", std::process::id()));
+        std::fs::write(&path, "old\n").unwrap();
+        let file = std::fs::OpenOptions::new().append(true).open(&path).unwrap();
+
+        // 회전 기준 도달 상태에서 1줄 → 기존 파일은 .1로, 새 줄은 새 파일에
+        let (tx, rx) = mpsc::channel(4);
+        tx.try_send("new".to_string()).unwrap();
+        drop(tx);
+        HistoryWriter { path: path.clone(), file, lines: config::FLOOR_HISTORY_ROTATE_LINES }.run(rx);
+
+        assert_eq!(std::fs::read_to_string(rotated_path(&path)).unwrap(), "old\n");
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "new\n");
+        let _ = std::fs::remove_file(rotated_path(&path));
+        let _ = std::fs::remove_file(&path);
     }
 
     #[test]
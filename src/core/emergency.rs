@@ -0,0 +1,59 @@
+// author: kodeholic (powered by Claude)
+// EmergencyState — 채널 단위 긴급 상태
+// Channel당 0~1개, Channel.emergency(Mutex)로 보호
+//
+// 생애주기:
+//   Emergency Floor Request 또는 EMERGENCY_ALERT → 발령 (이미 발령 중이면 유지)
+//   멤버 EMERGENCY_ACK                            → acked_by 누적
+//   Dispatcher(Admin) 해제                        → 소멸
+// 발령자 퇴장 / Floor 종료와 무관하게 해제 전까지 유지
+
+use serde::Serialize;
+
+use crate::utils::current_timestamp;
+
+/// 긴급 상태 발령 경로
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmergencySource {
+    FloorRequest, // indicator=emergency Floor Request
+    Alert,        // EMERGENCY_ALERT opcode
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EmergencyState {
+    pub raised_by: String,
+    pub raised_at: u64,
+    pub source:    EmergencySource,
+    pub acked_by:  Vec<String>,   // 확인 순서 유지
+}
+
+impl EmergencyState {
+    pub fn new(raised_by: String, source: EmergencySource) -> Self {
+        Self { raised_by, raised_at: current_timestamp(), source, acked_by: Vec::new() }
+    }
+
+    /// 확인 기록 — 발령자 본인 / 중복 확인이면 false
+    pub fn ack(&mut self, user_id: &str) -> bool {
+        if user_id == self.raised_by || self.acked_by.iter().any(|u| u == user_id) {
+            return false;
+        }
+        self.acked_by.push(user_id.to_string());
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ack_ignores_raiser_and_duplicates() {
+        let mut s = EmergencyState::new("alice".into(), EmergencySource::Alert);
+        assert!(!s.ack("alice"));
+        assert!(s.ack("bob"));
+        assert!(!s.ack("bob"));
+        assert!(s.ack("carol"));
+        assert_eq!(s.acked_by, vec!["bob", "carol"]);
+    }
+}
@@ -2,13 +2,18 @@
 // FloorControl — MBCP TS 24.380 기반 Floor Control 상태 관리
 // Channel당 1개 인스턴스, Channel.floor(Mutex)로 보호
 
-use std::collections::VecDeque;
+use std::cmp::Reverse;
+use std::collections::{HashMap, VecDeque};
+
+use serde::{Deserialize, Serialize};
 
 use crate::config;
+use crate::error::LiveError;
 use crate::utils::current_timestamp;
 
 /// Floor 표시자 — 발언의 성격/우선순위를 나타냄 (MBCP Floor Indicator)
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum FloorIndicator {
     Normal,        // 일반 발언
     Broadcast,     // 단방향 방송 (청취자 응답 없음)
@@ -23,6 +28,170 @@ pub enum FloorControlState {
     Taken, // G: Floor Taken — 발언권 점유 중
 }
 
+/// 채널별 Floor 정책 — 관제 채널 / 일반 채널 등 채널 성격에 따라 다르게 설정
+/// 필드 생략 시 config.rs 기본값 (JSON 부분 지정 가능)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FloorPolicy {
+    /// 최대 발언 점유 시간 — 초과 시 Revoke (0이면 무제한)
+    pub max_taken_ms:       u64,
+    /// holder Ping 수신 타임아웃 — 초과 시 Revoke
+    pub ping_timeout_ms:    u64,
+    /// false면 점유 중 요청을 대기열 대신 FLOOR_DENY
+    pub queue_enabled:      bool,
+    /// 대기열 최대 길이 (0이면 무제한) — 초과 시 FLOOR_DENY
+    pub max_queue_depth:    usize,
+    /// false면 priority 선점 불가 (Emergency는 항상 선점)
+    pub preemption_enabled: bool,
+    /// 발언 종료 후 같은 사용자의 재요청 금지 시간 (Emergency 제외)
+    pub cooldown_ms:        u64,
+    /// 대기열 항목 만료 시간 — 초과 시 FLOOR_DENY(queue_expired) 후 제거 (0이면 무제한)
+    pub queue_timeout_ms:   u64,
+    /// max_duration Revoke 후 같은 사용자의 재요청 금지 시간 (Emergency 제외, 0이면 비활성)
+    pub hog_cooldown_ms:    u64,
+    /// 동일 priority 대기자 중 최근 발언 시점이 오래된 사용자 우선 (false면 FIFO)
+    pub round_robin:        bool,
+    /// 대기 aging 주기 — 주기마다 유효 priority +FLOOR_AGING_BOOST (0이면 비활성)
+    pub aging_interval_ms:  u64,
+    /// holder 연결 끊김 시 Floor 유지 유예 — 재접속 + 재입장 시 재개 (0이면 즉시 Revoke)
+    pub suspend_grace_ms:   u64,
+    /// holder RTP 무수신 허용 시간 — 초과 시 Revoke(media_inactive) (0이면 비활성)
+    pub media_timeout_ms:   u64,
+    /// VOX — 수신 RTP 음성 감지로 자동 Request / 무음 hang time 후 자동 Release
+    pub vox:                bool,
+    /// VOX 무음 지속 시간 — 초과 시 Release(vox_hang)
+    pub vox_hang_ms:        u64,
+    /// VOX 음성 판정 audio level 상한 (-dBov, 0~127 — 작을수록 큰 소리)
+    pub vox_threshold:      u8,
+    /// 가상 talker 스트림 — 모든 talker를 kind별 고정 서버 SSRC로 (seq / timestamp 연속), 이후 입장자부터 SDP 광고
+    pub virtual_talker:     bool,
+    /// Broadcast(일방 무제한) 요청 최소 IDENTIFY priority — 미만이면 FLOOR_DENY(broadcast_not_allowed)
+    pub broadcast_min_priority: u8,
+}
+
+impl Default for FloorPolicy {
+    fn default() -> Self {
+        Self {
+            max_taken_ms:       config::FLOOR_MAX_TAKEN_MS,
+            ping_timeout_ms:    config::FLOOR_PING_TIMEOUT_MS,
+            queue_enabled:      true,
+            max_queue_depth:    config::FLOOR_MAX_QUEUE_DEPTH,
+            preemption_enabled: true,
+            cooldown_ms:        0,
+            queue_timeout_ms:   config::FLOOR_QUEUE_TIMEOUT_MS,
+            hog_cooldown_ms:    config::FLOOR_HOG_COOLDOWN_MS,
+            round_robin:        false,
+            aging_interval_ms:  0,
+            suspend_grace_ms:   config::FLOOR_SUSPEND_GRACE_MS,
+            media_timeout_ms:   config::FLOOR_MEDIA_TIMEOUT_MS,
+            vox:                false,
+            vox_hang_ms:        config::FLOOR_VOX_HANG_MS,
+            vox_threshold:      config::FLOOR_VOX_THRESHOLD_DBOV,
+            virtual_talker:     false,
+            broadcast_min_priority: config::FLOOR_BROADCAST_MIN_PRIORITY,
+        }
+    }
+}
+
+impl FloorPolicy {
+    /// 설정값 검증 — Admin REST / CHANNEL_CREATE 입력 확인용
+    pub fn validate(&self) -> Result<(), String> {
+        if self.ping_timeout_ms < config::FLOOR_PING_TIMEOUT_MIN_MS {
+            return Err(format!("ping_timeout_ms must be >= {}", config::FLOOR_PING_TIMEOUT_MIN_MS));
+        }
+        if self.max_taken_ms != 0 && self.max_taken_ms < self.ping_timeout_ms {
+            return Err("max_taken_ms must be 0 (unlimited) or >= ping_timeout_ms".to_string());
+        }
+        if self.media_timeout_ms != 0 && self.media_timeout_ms < config::FLOOR_MEDIA_TIMEOUT_MIN_MS {
+            return Err(format!("media_timeout_ms must be 0 (disabled) or >= {}", config::FLOOR_MEDIA_TIMEOUT_MIN_MS));
+        }
+        if self.vox_threshold > 127 {
+            return Err("vox_threshold must be 0..=127 (-dBov)".to_string());
+        }
+        // 음성이 ping을 겸하므로 hang time이 ping_timeout 이상이면 무음 구간에 Revoke가 먼저 발생
+        if self.vox && (self.vox_hang_ms == 0 || self.vox_hang_ms >= self.ping_timeout_ms) {
+            return Err("vox_hang_ms must be > 0 and < ping_timeout_ms".to_string());
+        }
+        // 0이면 priority 0 사용자까지 전원 허용 — 게이트가 사라지므로 명시적으로 1 이상
+        if self.broadcast_min_priority == 0 {
+            return Err("broadcast_min_priority must be 1..=255 (1 allows every member)".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Floor Request 거부 사유 (FLOOR_DENY reason)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FloorDenyReason {
+    NotMember,          // 채널 미참여 사용자의 요청
+    WrongMode,          // PTT 모드가 아닌 채널
+    PriorityNotAllowed, // IDENTIFY priority를 초과한 요청
+    QueueDisabled,      // 정책상 대기열 비활성
+    QueueFull,          // max_queue_depth 도달
+    Cooldown,           // 발언 종료 직후 재요청
+    QueueExpired,       // 대기열 queue_timeout_ms 초과
+    QueueCleared,       // Admin 대기열 초기화 / 제거
+    BroadcastActive,    // Broadcast 발언 중 (대기열 비활성 시 청취자 요청)
+    BroadcastNotAllowed, // IDENTIFY priority가 broadcast_min_priority 미만인 Broadcast 요청
+}
+
+impl FloorDenyReason {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            FloorDenyReason::NotMember          => "not_member",
+            FloorDenyReason::WrongMode          => "wrong_mode",
+            FloorDenyReason::PriorityNotAllowed => "priority_not_allowed",
+            FloorDenyReason::QueueDisabled      => "queue_disabled",
+            FloorDenyReason::QueueFull          => "queue_full",
+            FloorDenyReason::Cooldown           => "cooldown",
+            FloorDenyReason::QueueExpired       => "queue_expired",
+            FloorDenyReason::QueueCleared       => "queue_cleared",
+            FloorDenyReason::BroadcastActive    => "broadcast_active",
+            FloorDenyReason::BroadcastNotAllowed => "broadcast_not_allowed",
+        }
+    }
+
+    /// 대응하는 4xxx LiveError — FLOOR_DENY code 필드로 전달
+    pub fn to_error(self, channel_id: &str) -> LiveError {
+        let id = channel_id.to_string();
+        match self {
+            FloorDenyReason::NotMember          => LiveError::FloorNotMember(id),
+            FloorDenyReason::WrongMode          => LiveError::FloorWrongMode(id),
+            FloorDenyReason::PriorityNotAllowed => LiveError::FloorPriorityNotAllowed(id),
+            FloorDenyReason::QueueDisabled      => LiveError::FloorQueueDisabled(id),
+            FloorDenyReason::QueueFull          => LiveError::FloorQueueFull(id),
+            FloorDenyReason::Cooldown           => LiveError::FloorCooldown(id),
+            FloorDenyReason::QueueExpired       => LiveError::FloorQueueExpired(id),
+            FloorDenyReason::QueueCleared       => LiveError::FloorQueueCleared(id),
+            FloorDenyReason::BroadcastActive    => LiveError::FloorBroadcastActive(id),
+            FloorDenyReason::BroadcastNotAllowed => LiveError::FloorBroadcastNotAllowed(id),
+        }
+    }
+}
+
+/// talk-burst 종료 형태
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BurstEnd {
+    Release, // holder 자발적 반납 (FLOOR_RELEASE / CHANNEL_LEAVE)
+    Revoke,  // 서버 회수 (preempted / ping_timeout / max_duration / admin_revoke / disconnect)
+}
+
+/// talk-burst 1건 — Grant부터 Release/Revoke까지 (사후 "누가, 언제" 추적용)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TalkBurst {
+    pub user_id:      String,
+    pub priority:     u8,
+    pub indicator:    FloorIndicator,
+    pub granted_at:   u64,
+    pub ended_at:     u64,
+    pub duration_ms:  u64,
+    pub end:          BurstEnd,
+    pub cause:        String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preempted_by: Option<String>,   // cause=preempted 일 때 선점자
+}
+
 /// 대기열 항목 — Floor Request가 Deny 대신 Queue에 들어올 때
 #[derive(Debug, Clone)]
 pub struct FloorQueueEntry {
@@ -30,6 +199,31 @@ pub struct FloorQueueEntry {
     pub priority:  u8,
     pub indicator: FloorIndicator,
     pub queued_at: u64,
+    pub pinned:    bool,   // Admin 재배치로 고정 — 공정성 재정렬 / 신규 삽입이 앞지르지 않음
+}
+
+/// MBCP 재전송 타이머 항목
+///   T101: FLOOR_GRANTED 후 holder의 첫 FLOOR_PING(확인) 대기 → 만료 시 GRANTED 재전송
+///   T100: FLOOR_REVOKE 후 대상의 FLOOR_RELEASE(확인) 대기 → 만료 시 REVOKE 재전송
+#[derive(Debug, Clone)]
+pub struct FloorTimer {
+    pub user_id:  String,
+    pub deadline: u64,
+    pub count:    u32,     // 재전송 횟수 (MBCP C100/C101)
+    pub cause:    String,  // T100: revoke cause (T101은 빈 값)
+}
+
+impl FloorTimer {
+    fn new(user_id: String, after_ms: u64, cause: &str) -> Self {
+        Self { user_id, deadline: current_timestamp() + after_ms, count: 0, cause: cause.to_string() }
+    }
+}
+
+/// expire_timers() 결과 — 재전송 대상
+#[derive(Debug, Default)]
+pub struct FloorTimerExpiry {
+    pub t101: Option<String>,          // FLOOR_GRANTED 재전송 대상 (holder)
+    pub t100: Vec<(String, String)>,   // FLOOR_REVOKE 재전송 대상 (user_id, cause)
 }
 
 /// 채널별 Floor Control 상태 (Mutex<FloorControl>로 보호)
@@ -38,7 +232,7 @@ pub struct FloorControl {
     pub state:           FloorControlState,
     /// 현재 발언 중인 user_id (MBCP: Granted Party's Identity)
     pub floor_taken_by:  Option<String>,
-    /// 발언권 획득 시각 — FLOOR_MAX_TAKEN_MS 초과 시 Revoke
+    /// 발언권 획득 시각 — policy.max_taken_ms 초과 시 Revoke
     pub floor_taken_at:  Option<u64>,
     /// 현재 holder의 우선순위 — Preemption 판단 기준
     pub floor_priority:  u8,
@@ -48,6 +242,30 @@ pub struct FloorControl {
     pub queue:           VecDeque<FloorQueueEntry>,
     /// 마지막 클라이언트 Ping 수신 시각 — 타임아웃 감지용
     pub last_ping_at:    u64,
+    /// 채널별 정책 (최대 발언 시간, Ping 타임아웃, 대기열/선점, cooldown)
+    pub policy:          FloorPolicy,
+    /// 발언 종료 시각 (user_id → ms) — cooldown 판단용
+    released_at:         HashMap<String, u64>,
+    /// max_duration Revoke된 사용자의 재요청 허용 시각 (user_id → ms) — hog_cooldown 판단용
+    hog_until:           HashMap<String, u64>,
+    /// T101 — 현재 holder의 Grant 확인 대기
+    pub t101:            Option<FloorTimer>,
+    /// T100 — Revoke된 사용자별 Release 확인 대기
+    pub t100:            Vec<FloorTimer>,
+    /// 종료된 talk-burst 이력 (오래된 순, 최대 FLOOR_HISTORY_MAX)
+    pub history:         VecDeque<TalkBurst>,
+    /// holder 연결 끊김 시각 — Some이면 suspended (Taken 유지, suspend_grace_ms 후 Revoke)
+    pub suspended_at:    Option<u64>,
+    /// holder 마지막 음성 감지 시각 (VOX) — Grant 시점이 초기값
+    pub last_voice_at:   u64,
+    /// holder 마지막 RTP 릴레이 시각 — Grant 시점이 초기값 (media_inactive 판단)
+    pub last_media_at:   u64,
+}
+
+impl Default for FloorControl {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl FloorControl {
@@ -60,47 +278,225 @@ impl FloorControl {
             floor_indicator: FloorIndicator::Normal,
             queue:           VecDeque::new(),
             last_ping_at:    0,
+            policy:          FloorPolicy::default(),
+            released_at:     HashMap::new(),
+            hog_until:       HashMap::new(),
+            t101:            None,
+            t100:            Vec::new(),
+            history:         VecDeque::new(),
+            suspended_at:    None,
+            last_voice_at:   0,
+            last_media_at:   0,
+        }
+    }
+
+    pub fn with_policy(policy: FloorPolicy) -> Self {
+        Self { policy, ..Self::new() }
+    }
+
+    /// 현재 holder의 발언 종료 기록 (cooldown 기준 시각)
+    fn note_release(&mut self) {
+        if let Some(prev) = self.floor_taken_by.take() {
+            let now      = current_timestamp();
+            let cooldown = self.policy.cooldown_ms;
+            self.released_at.retain(|_, t| now.saturating_sub(*t) < cooldown);
+            if cooldown > 0 {
+                self.released_at.insert(prev, now);
+            }
         }
     }
 
+    /// 현재 talk-burst 종료 — 이력에 기록하고 기록된 항목 반환 (Taken이 아니면 None)
+    /// 종료 직후 grant() / clear_taken()으로 상태 전이하는 경로에서 호출
+    pub fn end_burst(&mut self, end: BurstEnd, cause: &str, preempted_by: Option<&str>) -> Option<TalkBurst> {
+        if self.state != FloorControlState::Taken {
+            return None;
+        }
+        let user_id    = self.floor_taken_by.clone()?;
+        let granted_at = self.floor_taken_at.unwrap_or(0);
+        let ended_at   = current_timestamp();
+        let burst = TalkBurst {
+            user_id,
+            priority:     self.floor_priority,
+            indicator:    self.floor_indicator.clone(),
+            granted_at,
+            ended_at,
+            duration_ms:  ended_at.saturating_sub(granted_at),
+            end,
+            cause:        cause.to_string(),
+            preempted_by: preempted_by.map(str::to_string),
+        };
+        // 장시간 점유로 회수된 경우 재요청 제한 (hog_cooldown)
+        self.hog_until.retain(|_, until| *until > ended_at);
+        if end == BurstEnd::Revoke && cause == "max_duration" && self.policy.hog_cooldown_ms > 0 {
+            self.hog_until.insert(burst.user_id.clone(), ended_at + self.policy.hog_cooldown_ms);
+        }
+        self.push_history(burst.clone());
+        self.note_release();
+        Some(burst)
+    }
+
+    /// 이력 추가 — 보관 개수 초과 시 오래된 것부터 제거
+    pub fn push_history(&mut self, burst: TalkBurst) {
+        if self.history.len() >= config::FLOOR_HISTORY_MAX {
+            self.history.pop_front();
+        }
+        self.history.push_back(burst);
+    }
+
+    /// user_id가 cooldown 중인지 여부
+    pub fn in_cooldown(&self, user_id: &str) -> bool {
+        let now = current_timestamp();
+        self.released_at.get(user_id)
+            .is_some_and(|t| now.saturating_sub(*t) < self.policy.cooldown_ms)
+            || self.hog_until.get(user_id).is_some_and(|until| now < *until)
+    }
+
+    /// 대기 항목의 유효 priority — aging 적용 (Emergency 미만까지만 가산)
+    pub fn effective_priority(&self, entry: &FloorQueueEntry, now: u64) -> u8 {
+        let interval = self.policy.aging_interval_ms;
+        let cap      = config::FLOOR_PRIORITY_EMERGENCY - 1;
+        if interval == 0 || entry.priority >= cap {
+            return entry.priority;
+        }
+        let steps = now.saturating_sub(entry.queued_at) / interval;
+        let boost = steps.saturating_mul(config::FLOOR_AGING_BOOST as u64);
+        (entry.priority as u64 + boost).min(cap as u64) as u8
+    }
+
+    /// 사용자의 마지막 발언 종료 시각 (이력 기준, 없으면 0) — round-robin 판단용
+    fn last_talked(&self, user_id: &str) -> u64 {
+        self.history.iter().rev()
+            .find(|b| b.user_id == user_id)
+            .map(|b| b.ended_at)
+            .unwrap_or(0)
+    }
+
+    /// Admin 재배치로 고정된 선두 항목 수
+    fn pinned_len(&self) -> usize {
+        self.queue.iter().take_while(|e| e.pinned).count()
+    }
+
+    /// 공정성 정책(aging / round-robin) 적용 — 대기열 재정렬
+    /// 정렬 키: 유효 priority 내림차순 → (round_robin) 마지막 발언 오래된 순 → 기존 순서 (stable)
+    /// Admin 재배치로 고정된 선두 항목은 제외 — 그 뒤 항목만 재정렬
+    /// 둘 다 비활성이면 삽입 순서(priority 내림차순 + FIFO) 유지
+    fn rank_queue(&mut self, now: u64) {
+        if !self.policy.round_robin && self.policy.aging_interval_ms == 0 {
+            return;
+        }
+        let round_robin = self.policy.round_robin;
+        let mut entries: Vec<FloorQueueEntry> = self.queue.drain(..).collect();
+        let pinned = entries.iter().take_while(|e| e.pinned).count();
+        entries[pinned..].sort_by_cached_key(|e| (
+            Reverse(self.effective_priority(e, now)),
+            if round_robin { self.last_talked(&e.user_id) } else { 0 },
+        ));
+        self.queue = entries.into();
+    }
+
+    /// 대기열 진입 가능 여부 — 불가 시 FLOOR_DENY 사유
+    /// 이미 대기 중인 사용자의 재요청(priority 갱신)은 길이 제한 대상 아님
+    pub fn check_enqueue(&self, user_id: &str) -> Result<(), FloorDenyReason> {
+        if !self.policy.queue_enabled {
+            return Err(FloorDenyReason::QueueDisabled);
+        }
+        let depth = self.policy.max_queue_depth;
+        if depth > 0 && self.queue.len() >= depth && self.queue_position(user_id).is_none() {
+            return Err(FloorDenyReason::QueueFull);
+        }
+        Ok(())
+    }
+
     /// 발언권 상태 초기화 (Release/Revoke 후 공통 처리)
     pub fn clear_taken(&mut self) {
+        self.note_release();
         self.state           = FloorControlState::Idle;
         self.floor_taken_by  = None;
         self.floor_taken_at  = None;
         self.floor_priority  = 0;
         self.floor_indicator = FloorIndicator::Normal;
         self.last_ping_at    = 0;
+        self.t101            = None;
+        self.suspended_at    = None;
     }
 
     /// 발언권 부여 (Grant)
     pub fn grant(&mut self, user_id: String, priority: u8, indicator: FloorIndicator) {
+        self.note_release(); // Preempt / Queue Grant 시 이전 holder
         self.state           = FloorControlState::Taken;
         self.floor_taken_by  = Some(user_id);
         self.floor_taken_at  = Some(current_timestamp());
         self.floor_priority  = priority;
         self.floor_indicator = indicator;
         self.last_ping_at    = current_timestamp(); // Grant 시점을 초기값으로 설정
+        self.last_voice_at   = self.last_ping_at;
+        self.last_media_at   = self.last_ping_at;
+        self.suspended_at    = None;
+        self.t100.retain(|t| Some(&t.user_id) != self.floor_taken_by.as_ref());
+        self.t101 = self.floor_taken_by.clone()
+            .map(|uid| FloorTimer::new(uid, config::FLOOR_T101_MS, ""));
     }
 
-    /// 대기열에 요청 추가 — priority 내림차순 삽입 (높은 priority가 앞)
-    /// 같은 user_id가 이미 있으면 갱신
+    /// 대기열에 요청 추가 — priority 내림차순 삽입 (높은 priority가 앞, Admin 고정 항목 뒤)
+    /// 같은 user_id가 이미 있으면 갱신 (고정 해제)
     pub fn enqueue(&mut self, user_id: String, priority: u8, indicator: FloorIndicator) {
         self.queue.retain(|e| e.user_id != user_id);
-        let entry = FloorQueueEntry { user_id, priority, indicator, queued_at: current_timestamp() };
-        let pos = self.queue.iter().position(|e| e.priority < entry.priority)
-            .unwrap_or(self.queue.len());
+        let entry  = FloorQueueEntry { user_id, priority, indicator, queued_at: current_timestamp(), pinned: false };
+        let pinned = self.pinned_len();
+        let pos = self.queue.iter().skip(pinned).position(|e| e.priority < entry.priority)
+            .map_or(self.queue.len(), |i| pinned + i);
         self.queue.insert(pos, entry);
+        self.rank_queue(current_timestamp());
     }
 
-    /// 대기열에서 다음 후보 꺼내기
+    /// 대기열에서 다음 후보 꺼내기 (공정성 정책 적용 후 선두)
     pub fn dequeue_next(&mut self) -> Option<FloorQueueEntry> {
+        self.rank_queue(current_timestamp());
         self.queue.pop_front()
     }
 
-    /// 대기열에서 특정 user_id 제거 (CHANNEL_LEAVE 등)
-    pub fn remove_from_queue(&mut self, user_id: &str) {
+    /// 대기열에서 특정 user_id 제거 (CHANNEL_LEAVE, FLOOR_QUEUE_CANCEL 등) — 제거 여부 반환
+    pub fn remove_from_queue(&mut self, user_id: &str) -> bool {
+        let before = self.queue.len();
         self.queue.retain(|e| e.user_id != user_id);
+        self.queue.len() != before
+    }
+
+    /// queue_timeout_ms 초과 항목 제거 — 만료된 user_id 목록 반환
+    pub fn expire_queue(&mut self, now: u64) -> Vec<String> {
+        let timeout = self.policy.queue_timeout_ms;
+        if timeout == 0 { return Vec::new(); }
+        let mut expired = Vec::new();
+        self.queue.retain(|e| {
+            let alive = now.saturating_sub(e.queued_at) < timeout;
+            if !alive { expired.push(e.user_id.clone()); }
+            alive
+        });
+        expired
+    }
+
+    /// 대기열 순서 변경 (Admin) — order에 나열한 user를 앞으로 고정, 나머지는 기존 순서 유지
+    /// 고정 항목은 이후 enqueue / 공정성 재정렬에도 순서 유지 (직전 고정은 이번 order로 대체)
+    /// 대기열에 없는 user_id / 중복이 있으면 Err
+    pub fn reorder_queue(&mut self, order: &[String]) -> Result<(), String> {
+        for (i, uid) in order.iter().enumerate() {
+            if self.queue_position(uid).is_none() {
+                return Err(format!("Not in queue: {}", uid));
+            }
+            if order[..i].contains(uid) {
+                return Err(format!("Duplicate user: {}", uid));
+            }
+        }
+        let mut front: Vec<FloorQueueEntry> = order.iter()
+            .filter_map(|uid| self.queue.iter().find(|e| &e.user_id == uid).cloned())
+            .map(|e| FloorQueueEntry { pinned: true, ..e })
+            .collect();
+        self.queue.retain(|e| !order.contains(&e.user_id));
+        self.queue.iter_mut().for_each(|e| e.pinned = false);
+        front.extend(self.queue.drain(..));
+        self.queue = front.into();
+        Ok(())
     }
 
     /// 대기열 내 user_id의 순서 반환 (1-based, 없으면 None)
@@ -109,31 +505,194 @@ impl FloorControl {
     }
 
     /// Preemption 가능 여부 판단
-    /// Emergency는 priority 무관 항상 true
-    /// 그 외는 요청자 priority > 현재 holder priority 일 때만 true
+    /// Emergency는 priority / 정책 무관 항상 true
+    /// Broadcast 발언 중에는 Emergency만 true
+    /// 그 외는 preemption_enabled 이고 요청자 priority > 현재 holder priority 일 때만 true
     pub fn can_preempt(&self, req_priority: u8, req_indicator: &FloorIndicator) -> bool {
         if self.state != FloorControlState::Taken { return false; }
         match req_indicator {
             FloorIndicator::Emergency => true,
-            _ => req_priority > self.floor_priority,
+            _ if self.is_broadcast()  => false,
+            _ => self.policy.preemption_enabled && req_priority > self.floor_priority,
         }
     }
 
-    /// 클라이언트 Ping 수신 — last_ping_at 갱신
+    /// Broadcast 발언 중 여부 — 단방향 (청취자 선점 불가, max_duration 미적용)
+    pub fn is_broadcast(&self) -> bool {
+        self.state == FloorControlState::Taken && self.floor_indicator == FloorIndicator::Broadcast
+    }
+
+    /// 현재 발언에 적용되는 최대 발언 시간 (0 = 무제한) — Broadcast는 holder Release까지 무제한
+    pub fn max_taken_ms(&self) -> u64 {
+        if self.is_broadcast() { 0 } else { self.policy.max_taken_ms }
+    }
+
+    /// 미디어 릴레이 허용 여부 — holder만 (Broadcast 포함 PTT 단방향)
+    pub fn may_relay(&self, sender: &str) -> bool {
+        self.state == FloorControlState::Taken && self.floor_taken_by.as_deref() == Some(sender)
+    }
+
+    /// holder 연결 끊김 — 유예 정책이 있으면 suspended 전환 (Taken 유지, T101 해제)
+    /// 전환하지 않으면(Idle / 유예 0) false → 호출자가 즉시 Revoke
+    pub fn suspend(&mut self, now: u64) -> bool {
+        if self.state != FloorControlState::Taken || self.policy.suspend_grace_ms == 0 {
+            return false;
+        }
+        self.suspended_at = Some(now);
+        self.t101         = None;
+        true
+    }
+
+    pub fn is_suspended(&self) -> bool {
+        self.suspended_at.is_some()
+    }
+
+    /// suspended holder 재입장 — 발언 재개 (ping 기준 시각 갱신, GRANTED 재전송 확인용 T101 시작)
+    pub fn resume(&mut self, user_id: &str) -> bool {
+        if !self.is_suspended() || self.floor_taken_by.as_deref() != Some(user_id) {
+            return false;
+        }
+        self.suspended_at  = None;
+        self.last_ping_at  = current_timestamp();
+        self.last_voice_at = self.last_ping_at;
+        self.last_media_at = self.last_ping_at;
+        self.t101          = Some(FloorTimer::new(user_id.to_string(), config::FLOOR_T101_MS, ""));
+        true
+    }
+
+    /// suspended 유예 만료 여부
+    pub fn is_suspend_expired(&self, now: u64) -> bool {
+        self.suspended_at
+            .is_some_and(|t| now.saturating_sub(t) >= self.policy.suspend_grace_ms)
+    }
+
+    /// 클라이언트 Ping 수신 — last_ping_at 갱신, 첫 Ping은 Grant 확인 (T101 해제)
     pub fn on_ping(&mut self) {
         self.last_ping_at = current_timestamp();
+        self.t101         = None;
+    }
+
+    /// holder 음성 감지 (VOX) — hang time 재시작, 음성 수신이 Ping을 겸함
+    pub fn on_voice(&mut self) {
+        self.on_ping();
+        self.last_voice_at = self.last_ping_at;
+    }
+
+    /// holder RTP 수신 (relay_to_channel) — media_inactive 타이머 재시작
+    pub fn on_media(&mut self, now: u64) {
+        self.last_media_at = now;
+    }
+
+    /// holder 미디어 무수신 타임아웃 여부 (비활성 정책 / suspended 중 제외)
+    pub fn is_media_inactive(&self, now: u64) -> bool {
+        self.policy.media_timeout_ms > 0
+            && self.state == FloorControlState::Taken
+            && !self.is_suspended()
+            && now.saturating_sub(self.last_media_at) >= self.policy.media_timeout_ms
+    }
+
+    /// VOX hang time 만료 여부 (VOX 채널 holder가 vox_hang_ms 동안 무음, suspended 중 제외)
+    pub fn is_vox_hang_expired(&self, now: u64) -> bool {
+        self.policy.vox
+            && self.state == FloorControlState::Taken
+            && !self.is_suspended()
+            && now.saturating_sub(self.last_voice_at) >= self.policy.vox_hang_ms
+    }
+
+    /// Revoke 전송 후 T100 시작 (같은 사용자 기존 타이머는 교체)
+    pub fn arm_t100(&mut self, user_id: &str, cause: &str) {
+        self.t100.retain(|t| t.user_id != user_id);
+        self.t100.push(FloorTimer::new(user_id.to_string(), config::FLOOR_T100_MS, cause));
+    }
+
+    /// Revoke 대상의 FLOOR_RELEASE 수신 / 연결 종료 — T100 해제
+    pub fn ack_revoke(&mut self, user_id: &str) -> bool {
+        let before = self.t100.len();
+        self.t100.retain(|t| t.user_id != user_id);
+        self.t100.len() != before
+    }
+
+    /// 가장 가까운 만료 시각 (ping_timeout 또는 suspend 유예 / max_duration / T101 / T100 / 대기열 만료 / VOX hang / media_inactive), 없으면 None
+    pub fn next_deadline(&self) -> Option<u64> {
+        let mut deadlines: Vec<u64> = self.t100.iter().map(|t| t.deadline).collect();
+        if let Some(t) = self.suspended_at {
+            deadlines.push(t + self.policy.suspend_grace_ms); // suspended 중 ping_timeout 대신
+        } else if self.state == FloorControlState::Taken {
+            deadlines.push(self.last_ping_at + self.policy.ping_timeout_ms);
+            if self.policy.vox {
+                deadlines.push(self.last_voice_at + self.policy.vox_hang_ms);
+            }
+            if self.policy.media_timeout_ms > 0 {
+                deadlines.push(self.last_media_at + self.policy.media_timeout_ms);
+            }
+        }
+        if self.state == FloorControlState::Taken {
+            if let (Some(taken_at), true) = (self.floor_taken_at, self.max_taken_ms() > 0) {
+                deadlines.push(taken_at + self.max_taken_ms());
+            }
+            if let Some(ref t) = self.t101 {
+                deadlines.push(t.deadline);
+            }
+        }
+        if self.policy.queue_timeout_ms > 0 {
+            if let Some(oldest) = self.queue.iter().map(|e| e.queued_at).min() {
+                deadlines.push(oldest + self.policy.queue_timeout_ms);
+            }
+        }
+        deadlines.into_iter().min()
     }
 
-    /// Ping 타임아웃 여부 (last_ping_at 기준)
+    /// 만료된 T101 / T100 처리 — 재전송 대상 반환 후 재시작
+    /// FLOOR_RETRANSMIT_MAX 도달 시 타이머 폐기 (T101은 이후 ping_timeout이 Revoke)
+    pub fn expire_timers(&mut self, now: u64) -> FloorTimerExpiry {
+        let mut expiry = FloorTimerExpiry::default();
+
+        if let Some(t) = self.t101.as_mut().filter(|t| t.deadline <= now) {
+            if t.count < config::FLOOR_RETRANSMIT_MAX {
+                t.count   += 1;
+                t.deadline = now + config::FLOOR_T101_MS;
+                expiry.t101 = Some(t.user_id.clone());
+            } else {
+                self.t101 = None;
+            }
+        }
+
+        self.t100.retain_mut(|t| {
+            if t.deadline > now { return true; }
+            if t.count >= config::FLOOR_RETRANSMIT_MAX { return false; }
+            t.count   += 1;
+            t.deadline = now + config::FLOOR_T100_MS;
+            expiry.t100.push((t.user_id.clone(), t.cause.clone()));
+            true
+        });
+
+        expiry
+    }
+
+    /// Ping 타임아웃 여부 (last_ping_at 기준, suspended 중에는 유예 만료로 판단)
     pub fn is_ping_timeout(&self) -> bool {
-        if self.state != FloorControlState::Taken { return false; }
-        current_timestamp().saturating_sub(self.last_ping_at) >= config::FLOOR_PING_TIMEOUT_MS
+        if self.state != FloorControlState::Taken || self.is_suspended() { return false; }
+        current_timestamp().saturating_sub(self.last_ping_at) >= self.policy.ping_timeout_ms
+    }
+
+    /// 현재 발언 경과 시간 (Idle이면 None)
+    pub fn elapsed_ms(&self, now: u64) -> Option<u64> {
+        self.floor_taken_at.map(|t| now.saturating_sub(t))
     }
 
-    /// 최대 발언 시간 초과 여부
+    /// max_duration Revoke까지 남은 시간 (Idle / 무제한 / Broadcast면 None)
+    pub fn remaining_ms(&self, now: u64) -> Option<u64> {
+        let max = self.max_taken_ms();
+        if max == 0 { return None; }
+        self.elapsed_ms(now).map(|e| max.saturating_sub(e))
+    }
+
+    /// 최대 발언 시간 초과 여부 (max_taken_ms=0 또는 Broadcast면 무제한)
     pub fn is_max_taken_exceeded(&self) -> bool {
+        let max = self.max_taken_ms();
+        if max == 0 { return false; }
         if let Some(taken_at) = self.floor_taken_at {
-            current_timestamp().saturating_sub(taken_at) >= config::FLOOR_MAX_TAKEN_MS
+            current_timestamp().saturating_sub(taken_at) >= max
         } else {
             false
         }
@@ -203,11 +762,63 @@ mod tests {
         let mut f = FloorControl::new();
         f.enqueue("a".into(), 100, FloorIndicator::Normal);
         f.enqueue("b".into(), 100, FloorIndicator::Normal);
-        f.remove_from_queue("a");
+        assert!(f.remove_from_queue("a"));
+        assert!(!f.remove_from_queue("a"));
         assert_eq!(f.queue.len(), 1);
         assert_eq!(f.queue[0].user_id, "b");
     }
 
+    #[test]
+    fn expire_queue_removes_stale_entries() {
+        let mut f = FloorControl::with_policy(FloorPolicy { queue_timeout_ms: 1_000, ..Default::default() });
+        f.enqueue("old".into(), 100, FloorIndicator::Normal);
+        f.enqueue("new".into(), 100, FloorIndicator::Normal);
+        f.queue[0].queued_at -= 5_000;
+        assert_eq!(f.next_deadline(), Some(f.queue[0].queued_at + 1_000));
+
+        assert_eq!(f.expire_queue(current_timestamp()), vec!["old".to_string()]);
+        assert_eq!(f.queue_position("new"), Some(1));
+
+        f.policy.queue_timeout_ms = 0; // 무제한
+        f.queue[0].queued_at = 0;
+        assert!(f.expire_queue(current_timestamp()).is_empty());
+    }
+
+    #[test]
+    fn reorder_queue_moves_listed_to_front() {
+        let mut f = FloorControl::new();
+        for uid in ["a", "b", "c", "d"] {
+            f.enqueue(uid.into(), 100, FloorIndicator::Normal);
+        }
+        f.reorder_queue(&["c".to_string(), "a".to_string()]).unwrap();
+        let order: Vec<&str> = f.queue.iter().map(|e| e.user_id.as_str()).collect();
+        assert_eq!(order, vec!["c", "a", "b", "d"]);
+
+        assert!(f.reorder_queue(&["x".to_string()]).is_err());
+        assert!(f.reorder_queue(&["a".to_string(), "a".to_string()]).is_err());
+    }
+
+    #[test]
+    fn reorder_survives_fair_rerank() {
+        let mut f = FloorControl::with_policy(FloorPolicy { round_robin: true, aging_interval_ms: 1_000, ..Default::default() });
+        for uid in ["a", "b", "c"] {
+            f.enqueue(uid.into(), 100, FloorIndicator::Normal);
+        }
+        f.reorder_queue(&["c".to_string()]).unwrap();
+
+        // 높은 priority 신규 진입 / 재정렬 모두 고정 항목 뒤에서만
+        f.enqueue("vip".into(), 200, FloorIndicator::Normal);
+        let order: Vec<&str> = f.queue.iter().map(|e| e.user_id.as_str()).collect();
+        assert_eq!(order, vec!["c", "vip", "a", "b"]);
+        assert_eq!(f.dequeue_next().unwrap().user_id, "c");
+        assert_eq!(f.dequeue_next().unwrap().user_id, "vip");
+
+        // 재요청은 고정 해제
+        f.reorder_queue(&["b".to_string()]).unwrap();
+        f.enqueue("b".into(), 100, FloorIndicator::Normal);
+        assert!(f.queue.iter().all(|e| !e.pinned));
+    }
+
     #[test]
     fn queue_position_1based() {
         let mut f = FloorControl::new();
@@ -241,6 +852,54 @@ mod tests {
         assert!(!f.can_preempt(255, &FloorIndicator::Emergency));
     }
 
+    #[test]
+    fn broadcast_only_emergency_preempts_and_no_max_duration() {
+        let mut f = FloorControl::new();
+        f.grant("caller".into(), 100, FloorIndicator::Broadcast);
+        assert!(f.is_broadcast());
+        assert!(!f.can_preempt(200, &FloorIndicator::ImminentPeril));
+        assert!(f.can_preempt(0, &FloorIndicator::Emergency));
+
+        // holder Release까지 무제한 — max_duration deadline / 잔여 시간 없음
+        let taken_at = f.floor_taken_at.unwrap();
+        f.floor_taken_at = Some(taken_at - f.policy.max_taken_ms - 1);
+        assert!(!f.is_max_taken_exceeded());
+        assert_eq!(f.max_taken_ms(), 0);
+        assert_eq!(f.remaining_ms(current_timestamp()), None);
+        assert_eq!(f.next_deadline(), f.t101.as_ref().map(|t| t.deadline).into_iter()
+            .chain([f.last_ping_at + f.policy.ping_timeout_ms]).min());
+
+        assert!(f.may_relay("caller"));
+        assert!(!f.may_relay("listener"));
+    }
+
+    #[test]
+    fn suspend_holds_floor_until_resume_or_grace() {
+        let mut f = FloorControl::new();
+        assert!(!f.suspend(current_timestamp())); // Idle
+        f.grant("alice".into(), 100, FloorIndicator::Normal);
+        let now = current_timestamp();
+        assert!(f.suspend(now));
+        assert_eq!(f.state, FloorControlState::Taken);
+        assert!(f.t101.is_none());
+
+        // 유예 중 ping_timeout 미적용, deadline은 유예 만료
+        f.last_ping_at = 0;
+        assert!(!f.is_ping_timeout());
+        assert!(f.next_deadline().unwrap() <= now + f.policy.suspend_grace_ms);
+        assert!(!f.is_suspend_expired(now + f.policy.suspend_grace_ms - 1));
+        assert!(f.is_suspend_expired(now + f.policy.suspend_grace_ms));
+
+        assert!(!f.resume("bob"));
+        assert!(f.resume("alice"));
+        assert!(!f.is_suspended());
+        assert_eq!(f.t101.as_ref().map(|t| t.user_id.as_str()), Some("alice"));
+
+        // 유예 0 → suspend 안 함 (즉시 Revoke 경로)
+        f.policy.suspend_grace_ms = 0;
+        assert!(!f.suspend(current_timestamp()));
+    }
+
     #[test]
     fn on_ping_updates_last_ping_at() {
         let mut f = FloorControl::new();
@@ -269,4 +928,255 @@ mod tests {
         f.grant("alice".into(), 100, FloorIndicator::Normal);
         assert!(!f.is_max_taken_exceeded());
     }
+
+    #[test]
+    fn elapsed_and_remaining_ms() {
+        let mut f = FloorControl::new();
+        assert_eq!(f.elapsed_ms(current_timestamp()), None);
+        f.grant("alice".into(), 100, FloorIndicator::Normal);
+        let taken_at = f.floor_taken_at.unwrap();
+        assert_eq!(f.elapsed_ms(taken_at + 1_000), Some(1_000));
+        assert_eq!(f.remaining_ms(taken_at + 1_000), Some(f.policy.max_taken_ms - 1_000));
+        assert_eq!(f.remaining_ms(taken_at + f.policy.max_taken_ms + 5), Some(0));
+        f.policy.max_taken_ms = 0;
+        assert_eq!(f.remaining_ms(taken_at + 1_000), None);
+    }
+
+    #[test]
+    fn policy_timeouts_applied() {
+        let mut f = FloorControl::with_policy(FloorPolicy { max_taken_ms: 0, ..Default::default() });
+        f.grant("alice".into(), 100, FloorIndicator::Normal);
+        f.floor_taken_at = Some(0);
+        assert!(!f.is_max_taken_exceeded()); // 0 = 무제한
+
+        f.policy.max_taken_ms = 10_000;
+        assert!(f.is_max_taken_exceeded());
+
+        f.policy.ping_timeout_ms = 60_000;
+        f.last_ping_at = current_timestamp() - 10_000;
+        assert!(!f.is_ping_timeout());
+    }
+
+    #[test]
+    fn policy_preemption_disabled_except_emergency() {
+        let mut f = FloorControl::with_policy(FloorPolicy { preemption_enabled: false, ..Default::default() });
+        f.grant("alice".into(), 100, FloorIndicator::Normal);
+        assert!(!f.can_preempt(200, &FloorIndicator::Normal));
+        assert!(f.can_preempt(1, &FloorIndicator::Emergency));
+    }
+
+    #[test]
+    fn policy_queue_disabled_and_depth() {
+        let mut f = FloorControl::with_policy(FloorPolicy { queue_enabled: false, ..Default::default() });
+        assert_eq!(f.check_enqueue("a"), Err(FloorDenyReason::QueueDisabled));
+
+        f.policy = FloorPolicy { max_queue_depth: 1, ..Default::default() };
+        assert!(f.check_enqueue("a").is_ok());
+        f.enqueue("a".into(), 100, FloorIndicator::Normal);
+        assert_eq!(f.check_enqueue("b"), Err(FloorDenyReason::QueueFull));
+        assert!(f.check_enqueue("a").is_ok()); // 기존 대기자 갱신은 허용
+    }
+
+    #[test]
+    fn policy_cooldown_after_release() {
+        let mut f = FloorControl::with_policy(FloorPolicy { cooldown_ms: 60_000, ..Default::default() });
+        f.grant("alice".into(), 100, FloorIndicator::Normal);
+        assert!(!f.in_cooldown("alice"));
+        f.grant("bob".into(), 200, FloorIndicator::Normal); // preempt → alice 종료
+        assert!(f.in_cooldown("alice"));
+        f.clear_taken();
+        assert!(f.in_cooldown("bob"));
+        assert!(!f.in_cooldown("carol"));
+    }
+
+    #[test]
+    fn policy_validate_and_partial_json() {
+        assert!(FloorPolicy::default().validate().is_ok());
+        assert!(FloorPolicy { ping_timeout_ms: 500, ..Default::default() }.validate().is_err());
+        assert!(FloorPolicy { max_taken_ms: 3_000, ..Default::default() }.validate().is_err());
+        assert!(FloorPolicy { vox: true, ..Default::default() }.validate().is_ok());
+        assert!(FloorPolicy { vox: true, vox_hang_ms: 6_000, ..Default::default() }.validate().is_err());
+        assert!(FloorPolicy { vox_threshold: 128, ..Default::default() }.validate().is_err());
+        assert!(FloorPolicy { media_timeout_ms: 0, ..Default::default() }.validate().is_ok());
+        assert!(FloorPolicy { media_timeout_ms: 500, ..Default::default() }.validate().is_err());
+        assert!(FloorPolicy { broadcast_min_priority: 0, ..Default::default() }.validate().is_err());
+
+        let p: FloorPolicy = serde_json::from_str(r#"{"max_taken_ms":60000,"queue_enabled":false}"#).unwrap();
+        assert_eq!(p.max_taken_ms, 60_000);
+        assert!(!p.queue_enabled);
+        assert_eq!(p.ping_timeout_ms, config::FLOOR_PING_TIMEOUT_MS);
+    }
+
+    #[test]
+    fn media_inactive_after_grant_without_rtp() {
+        let mut f = FloorControl::new();
+        assert!(!f.is_media_inactive(u64::MAX)); // Idle
+        f.grant("alice".into(), 100, FloorIndicator::Normal);
+        let timeout = f.policy.media_timeout_ms;
+        let granted = f.last_media_at;
+        assert!(!f.is_media_inactive(granted + timeout - 1));
+        assert!(f.is_media_inactive(granted + timeout));
+
+        // RTP 수신 → 재시작
+        f.on_media(granted + timeout);
+        assert!(!f.is_media_inactive(granted + timeout * 2 - 1));
+
+        // suspended 중 / 비활성 정책은 제외
+        assert!(f.suspend(current_timestamp()));
+        assert!(!f.is_media_inactive(u64::MAX));
+        f.resume("alice");
+        f.policy.media_timeout_ms = 0;
+        assert!(!f.is_media_inactive(u64::MAX));
+    }
+
+    #[test]
+    fn vox_hang_expires_after_silence() {
+        let mut f = FloorControl::with_policy(FloorPolicy { vox: true, ..Default::default() });
+        f.grant("alice".into(), 100, FloorIndicator::Normal);
+        let hang = f.policy.vox_hang_ms;
+        let now  = f.last_voice_at;
+        assert!(!f.is_vox_hang_expired(now + hang - 1));
+        assert!(f.is_vox_hang_expired(now + hang));
+        assert_eq!(f.next_deadline(), Some(now + hang)); // hang(1.5s) < T101(3s)
+
+        // 음성 감지 → hang 재시작 + T101 해제
+        f.last_voice_at = 0;
+        f.on_voice();
+        assert!(f.t101.is_none());
+        assert!(!f.is_vox_hang_expired(f.last_voice_at + hang - 1));
+
+        // suspended 중 / VOX 비활성 채널은 만료 없음
+        assert!(f.suspend(current_timestamp()));
+        assert!(!f.is_vox_hang_expired(u64::MAX));
+        f.resume("alice");
+        f.policy.vox = false;
+        assert!(!f.is_vox_hang_expired(u64::MAX));
+    }
+
+    #[test]
+    fn t101_armed_on_grant_and_acked_by_ping() {
+        let mut f = FloorControl::new();
+        f.grant("alice".into(), 100, FloorIndicator::Normal);
+        let t = f.t101.as_ref().unwrap();
+        assert_eq!(t.user_id, "alice");
+        assert_eq!(f.next_deadline(), Some(t.deadline)); // T101(3s) < ping_timeout(6s)
+        f.on_ping();
+        assert!(f.t101.is_none());
+    }
+
+    #[test]
+    fn t101_retransmit_until_max() {
+        let mut f = FloorControl::new();
+        f.grant("alice".into(), 100, FloorIndicator::Normal);
+        let mut now = current_timestamp();
+        for _ in 0..config::FLOOR_RETRANSMIT_MAX {
+            now += config::FLOOR_T101_MS;
+            assert_eq!(f.expire_timers(now).t101.as_deref(), Some("alice"));
+        }
+        now += config::FLOOR_T101_MS;
+        assert!(f.expire_timers(now).t101.is_none());
+        assert!(f.t101.is_none());
+    }
+
+    #[test]
+    fn t100_retransmit_and_ack() {
+        let mut f = FloorControl::new();
+        f.arm_t100("alice", "preempted");
+        assert!(f.next_deadline().is_some()); // Idle이어도 T100은 유효
+        let now = current_timestamp() + config::FLOOR_T100_MS;
+        let exp = f.expire_timers(now);
+        assert_eq!(exp.t100, vec![("alice".to_string(), "preempted".to_string())]);
+        assert!(f.expire_timers(now).t100.is_empty()); // 재시작된 타이머는 아직 미만료
+        assert!(f.ack_revoke("alice"));
+        assert!(!f.ack_revoke("alice"));
+        assert!(f.next_deadline().is_none());
+    }
+
+    #[test]
+    fn grant_cancels_pending_t100_for_new_holder() {
+        let mut f = FloorControl::new();
+        f.arm_t100("alice", "preempted");
+        f.grant("alice".into(), 100, FloorIndicator::Normal);
+        assert!(f.t100.is_empty());
+    }
+
+    #[test]
+    fn end_burst_records_preemption() {
+        let mut f = FloorControl::new();
+        assert!(f.end_burst(BurstEnd::Release, "release", None).is_none()); // Idle
+        f.grant("alice".into(), 100, FloorIndicator::Normal);
+        let b = f.end_burst(BurstEnd::Revoke, "preempted", Some("bob")).unwrap();
+        f.grant("bob".into(), 255, FloorIndicator::Emergency);
+        assert_eq!(b.user_id, "alice");
+        assert_eq!(b.preempted_by.as_deref(), Some("bob"));
+        assert_eq!(f.history.len(), 1);
+        assert_eq!(f.floor_taken_by.as_deref(), Some("bob"));
+    }
+
+    #[test]
+    fn hog_cooldown_after_max_duration_only() {
+        let mut f = FloorControl::new();
+        f.grant("alice".into(), 100, FloorIndicator::Normal);
+        f.end_burst(BurstEnd::Revoke, "max_duration", None);
+        f.clear_taken();
+        assert!(f.in_cooldown("alice"));
+
+        f.grant("bob".into(), 100, FloorIndicator::Normal);
+        f.end_burst(BurstEnd::Release, "release", None);
+        f.clear_taken();
+        assert!(!f.in_cooldown("bob"));
+
+        let mut f = FloorControl::with_policy(FloorPolicy { hog_cooldown_ms: 0, ..Default::default() });
+        f.grant("alice".into(), 100, FloorIndicator::Normal);
+        f.end_burst(BurstEnd::Revoke, "max_duration", None);
+        assert!(!f.in_cooldown("alice"));
+    }
+
+    #[test]
+    fn round_robin_prefers_least_recent_talker() {
+        let mut f = FloorControl::with_policy(FloorPolicy { round_robin: true, ..Default::default() });
+        f.grant("alice".into(), 100, FloorIndicator::Normal);
+        f.end_burst(BurstEnd::Release, "release", None);
+        f.grant("holder".into(), 100, FloorIndicator::Normal);
+
+        // alice가 먼저 대기했지만 방금 발언 — 발언 이력 없는 bob이 앞
+        f.enqueue("alice".into(), 100, FloorIndicator::Normal);
+        f.enqueue("bob".into(), 100, FloorIndicator::Normal);
+        f.enqueue("vip".into(), 200, FloorIndicator::Normal);
+        let order: Vec<_> = f.queue.iter().map(|e| e.user_id.as_str()).collect();
+        assert_eq!(order, vec!["vip", "bob", "alice"]);
+
+        // round_robin off → FIFO
+        f.policy.round_robin = false;
+        f.queue.clear();
+        f.enqueue("alice".into(), 100, FloorIndicator::Normal);
+        f.enqueue("bob".into(), 100, FloorIndicator::Normal);
+        assert_eq!(f.dequeue_next().unwrap().user_id, "alice");
+    }
+
+    #[test]
+    fn aging_raises_long_waiters_below_emergency() {
+        let mut f = FloorControl::with_policy(FloorPolicy { aging_interval_ms: 1_000, ..Default::default() });
+        f.enqueue("old".into(), 100, FloorIndicator::Normal);
+        f.enqueue("new".into(), 120, FloorIndicator::Normal);
+        // 5초 대기 → 100 + 5*10 = 150 > 120
+        f.queue.iter_mut().find(|e| e.user_id == "old").unwrap().queued_at -= 5_000;
+        let now = current_timestamp();
+        assert_eq!(f.effective_priority(&f.queue[1], now), 150);
+        assert_eq!(f.dequeue_next().unwrap().user_id, "old");
+
+        let ancient = FloorQueueEntry { user_id: "x".into(), priority: 100, indicator: FloorIndicator::Normal, queued_at: 0, pinned: false };
+        assert_eq!(f.effective_priority(&ancient, now), config::FLOOR_PRIORITY_EMERGENCY - 1);
+    }
+
+    #[test]
+    fn history_is_bounded() {
+        let mut f = FloorControl::new();
+        for i in 0..config::FLOOR_HISTORY_MAX + 5 {
+            f.grant(format!("u{}", i), 100, FloorIndicator::Normal);
+            f.end_burst(BurstEnd::Release, "release", None);
+        }
+        assert_eq!(f.history.len(), config::FLOOR_HISTORY_MAX);
+        assert_eq!(f.history.front().unwrap().user_id, "u5");
+    }
 }
@@ -7,14 +7,18 @@
 //
 // ssrc는 라우팅 키가 아니라 Endpoint.tracks 내부 메타데이터.
 // BUNDLE 환경에서 하나의 Endpoint에 audio/video/data ssrc가 복수 달림.
+// simulcast video는 레이어(rid)마다 ssrc가 하나씩 — 수신자별 레이어 선택은 layer_selectors.
 
 use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::sync::{Arc, Mutex, RwLock};
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicU8, Ordering};
 use tracing::{debug, trace};
 use rand::Rng;
 
+use crate::config;
+use crate::media::rtp::{self, RtpRing};
+use crate::media::simulcast::{LayerForward, LayerSelector};
 use crate::media::srtp::SrtpContext;
 use crate::utils::current_timestamp;
 
@@ -30,15 +34,25 @@ pub enum TrackKind {
 pub struct Track {
     pub ssrc: u32,
     pub kind: TrackKind,
+    pub rid:  Option<String>,  // simulcast 레이어 rid (단일 트랙이면 None)
 }
 
 /// 피어당 엔드포인트 (Phase 2 확장 대비 필드 포함)
 pub struct Endpoint {
-    pub ufrag:      String,             // ICE ufrag — 주키, 불변
-    pub ice_pwd:    String,             // ICE pwd — STUN MESSAGE-INTEGRITY 검증용
-    pub user_id:    String,
-    pub channel_id: String,
-    pub last_seen:  AtomicU64,          // 좀비 피어 감지용
+    pub ufrag:           String,             // ICE ufrag — 주키, 불변
+    pub ice_pwd:         String,             // ICE pwd — STUN MESSAGE-INTEGRITY 검증용
+    pub user_id:         String,
+    pub channel_id:      String,
+    pub last_seen:       AtomicU64,          // 좀비 피어 감지용
+    pub mbcp:            AtomicBool,         // SRTCP APP "MCPT" 수신 이력 — floor 이벤트를 MBCP로도 전달
+    pub audio_level_ext: AtomicU8,           // RFC 6464 audio level 확장 id (0 = 미협상) — VOX 음성 감지
+    pub vox_request_at:  AtomicU64,          // 마지막 VOX 자동 Floor Request 시각 (재요청 간격 제한)
+    pub nack_requested:  AtomicU64,          // 이 엔드포인트가 NACK으로 요청한 seq 수
+    pub rtx_sent:        AtomicU64,          // 서버 재전송 버퍼에서 응답한 패킷 수
+    pub nack_forwarded:  AtomicU64,          // 버퍼 미스로 원 송신자에게 전달한 seq 수
+    pub keyframe_req_at: AtomicU64,          // 마지막 서버 발 keyframe 요청(PLI) 시각 (요청 간격 제한)
+    pub audio_level:     AtomicU32,          // 평활 audio level (-dBov × 16 고정소수점) — 발언자 감지
+    pub audio_level_at:  AtomicU64,          // 마지막 audio level 수신 시각 (0 = 미측정)
 
     // 핫패스 캐시: NAT 리바인딩 시 STUN에서 갱신
     pub address: Mutex<Option<SocketAddr>>,
@@ -46,6 +60,16 @@ pub struct Endpoint {
     // BUNDLE 트랙 목록 (ssrc 기준, 피어당 복수)
     pub tracks: RwLock<Vec<Track>>,
 
+    // SDP offer의 RTX 협상 (원본 PT, RTX PT) — a=fmtp:RTX apt=원본
+    pub rtx_pts: RwLock<Vec<(u8, u8)>>,
+
+    // SDP offer의 video 코덱 (PT, 인코딩 이름) — simulcast 전환용 keyframe 판별
+    pub video_codecs: RwLock<Vec<(u8, String)>>,
+
+    // SDP offer의 a=simulcast 송신 rid (저화질 → 고화질) + rtp-stream-id 확장 id (0 = 미협상)
+    pub simulcast: RwLock<Vec<String>>,
+    pub rid_ext:   AtomicU8,
+
     // DTLS/SRTP 컨텍스트 (피어당 1개, 모든 트랙 공유)
     pub inbound_srtp:  Mutex<SrtpContext>,
     pub outbound_srtp: Mutex<SrtpContext>,
@@ -59,11 +83,24 @@ impl Endpoint {
             ice_pwd,
             user_id,
             channel_id,
-            last_seen:     AtomicU64::new(current_timestamp()),
-            address:       Mutex::new(None),
-            tracks:        RwLock::new(Vec::new()),
-            inbound_srtp:  Mutex::new(SrtpContext::new()),
-            outbound_srtp: Mutex::new(SrtpContext::new()),
+            last_seen:       AtomicU64::new(current_timestamp()),
+            mbcp:            AtomicBool::new(false),
+            audio_level_ext: AtomicU8::new(0),
+            vox_request_at:  AtomicU64::new(0),
+            nack_requested:  AtomicU64::new(0),
+            rtx_sent:        AtomicU64::new(0),
+            nack_forwarded:  AtomicU64::new(0),
+            keyframe_req_at: AtomicU64::new(0),
+            audio_level:     AtomicU32::new(127 << 4),
+            audio_level_at:  AtomicU64::new(0),
+            address:         Mutex::new(None),
+            tracks:          RwLock::new(Vec::new()),
+            rtx_pts:         RwLock::new(Vec::new()),
+            video_codecs:    RwLock::new(Vec::new()),
+            simulcast:       RwLock::new(Vec::new()),
+            rid_ext:         AtomicU8::new(0),
+            inbound_srtp:    Mutex::new(SrtpContext::new()),
+            outbound_srtp:   Mutex::new(SrtpContext::new()),
         }
     }
 
@@ -81,11 +118,145 @@ impl Endpoint {
         *self.address.lock().unwrap()
     }
 
+    /// MBCP 사용 엔드포인트로 표시 — 이전 값 반환 (최초 전환 감지용)
+    pub fn mark_mbcp(&self) -> bool {
+        self.mbcp.swap(true, Ordering::Relaxed)
+    }
+
+    pub fn is_mbcp(&self) -> bool {
+        self.mbcp.load(Ordering::Relaxed)
+    }
+
+    /// SDP offer에서 협상된 audio level 확장 id 기록
+    pub fn set_audio_level_ext(&self, id: u8) {
+        self.audio_level_ext.store(id, Ordering::Relaxed);
+    }
+
+    pub fn audio_level_ext(&self) -> Option<u8> {
+        match self.audio_level_ext.load(Ordering::Relaxed) {
+            0  => None,
+            id => Some(id),
+        }
+    }
+
+    /// 수신 audio level(-dBov) 반영 — 지수 이동 평균 (SPEAKER_SMOOTHING), 무음 구간 뒤에는 무음부터 다시 평활
+    pub fn update_audio_level(&self, level: u8, now: u64) {
+        let prev = match self.audio_level(now) {
+            Some(127) | None => 127 << 4,
+            Some(_)          => self.audio_level.load(Ordering::Relaxed),
+        };
+        let sample = (level.min(127) as u32) << 4;
+        let next   = (prev * (config::SPEAKER_SMOOTHING - 1) + sample) / config::SPEAKER_SMOOTHING;
+        self.audio_level.store(next, Ordering::Relaxed);
+        self.audio_level_at.store(now, Ordering::Relaxed);
+    }
+
+    /// 평활 audio level(-dBov, 0 = 최대 / 127 = 무음) — 미측정이면 None, SPEAKER_SILENCE_MS 동안 수신 없으면 무음
+    pub fn audio_level(&self, now: u64) -> Option<u8> {
+        let at = self.audio_level_at.load(Ordering::Relaxed);
+        if at == 0 {
+            return None;
+        }
+        if now.saturating_sub(at) > config::SPEAKER_SILENCE_MS {
+            return Some(127);
+        }
+        Some((self.audio_level.load(Ordering::Relaxed) >> 4) as u8)
+    }
+
+    /// VOX 자동 요청 허용 여부 — 직전 요청 후 VOX_REQUEST_RETRY_MS 경과 시에만 true (시각 갱신)
+    pub fn try_vox_request(&self, now: u64) -> bool {
+        let last = self.vox_request_at.load(Ordering::Relaxed);
+        if last != 0 && now.saturating_sub(last) < config::VOX_REQUEST_RETRY_MS {
+            return false;
+        }
+        self.vox_request_at.store(now, Ordering::Relaxed);
+        true
+    }
+
+    /// 서버 발 keyframe 요청 허용 여부 — 직전 요청 후 KEYFRAME_REQUEST_MIN_MS 경과 시에만 true (시각 갱신)
+    pub fn try_keyframe_request(&self, now: u64) -> bool {
+        let last = self.keyframe_req_at.load(Ordering::Relaxed);
+        if last != 0 && now.saturating_sub(last) < config::KEYFRAME_REQUEST_MIN_MS {
+            return false;
+        }
+        self.keyframe_req_at.store(now, Ordering::Relaxed);
+        true
+    }
+
+    /// 등록된 video 트랙 SSRC 목록
+    pub fn video_ssrcs(&self) -> Vec<u32> {
+        self.tracks.read().unwrap().iter().filter(|t| t.kind == TrackKind::Video).map(|t| t.ssrc).collect()
+    }
+
+    /// SDP offer에서 협상된 RTX PT 기록 (re-nego 시 교체)
+    pub fn set_rtx_pts(&self, pts: Vec<(u8, u8)>) {
+        *self.rtx_pts.write().unwrap() = pts;
+    }
+
+    /// 원본 PT에 대응하는 RTX PT (미협상이면 None)
+    pub fn rtx_pt(&self, pt: u8) -> Option<u8> {
+        self.rtx_pts.read().unwrap().iter().find(|(apt, _)| *apt == pt).map(|(_, rtx)| *rtx)
+    }
+
+    /// SDP offer에서 협상된 video 코덱 기록 (re-nego 시 교체)
+    pub fn set_video_codecs(&self, codecs: Vec<(u8, String)>) {
+        *self.video_codecs.write().unwrap() = codecs;
+    }
+
+    /// video RTP가 keyframe 시작인지 — PT의 코덱을 모르거나 판별 불가 코덱이면 None
+    pub fn is_keyframe(&self, packet: &[u8]) -> Option<bool> {
+        let pt     = rtp::payload_type(packet)?;
+        let codecs = self.video_codecs.read().unwrap();
+        let codec  = codecs.iter().find(|(p, _)| *p == pt)?;
+        rtp::is_keyframe(packet, &codec.1)
+    }
+
+    /// SDP offer의 simulcast 송신 rid / rtp-stream-id 확장 id 기록 (re-nego 시 교체)
+    pub fn set_simulcast(&self, rids: Vec<String>, rid_ext: Option<u8>) {
+        *self.simulcast.write().unwrap() = rids;
+        self.rid_ext.store(rid_ext.unwrap_or(0), Ordering::Relaxed);
+    }
+
+    /// simulcast 레이어 SSRC → (레이어 index, 레이어 수) — 레이어가 아니면 None
+    pub fn layer_of(&self, ssrc: u32) -> Option<(usize, usize)> {
+        let tracks = self.tracks.read().unwrap();
+        let rid    = tracks.iter().find(|t| t.ssrc == ssrc)?.rid.as_ref()?;
+        let rids   = self.simulcast.read().unwrap();
+        Some((rids.iter().position(|r| r == rid)?, rids.len()))
+    }
+
+    /// simulcast 레이어 등록 — offer의 rid가 아니면 무시, 새로 등록(또는 rid 지정)했으면 true
+    pub fn add_layer(&self, ssrc: u32, rid: &str) -> bool {
+        if !self.simulcast.read().unwrap().iter().any(|r| r == rid) {
+            return false;
+        }
+        let mut tracks = self.tracks.write().unwrap();
+        match tracks.iter_mut().find(|t| t.ssrc == ssrc) {
+            Some(t) if t.rid.as_deref() == Some(rid) => return false,
+            Some(t) => t.rid = Some(rid.to_string()),
+            None    => tracks.push(Track { ssrc, kind: TrackKind::Video, rid: Some(rid.to_string()) }),
+        }
+        trace!("Layer added: ssrc={} rid={} ufrag={}", ssrc, rid, self.ufrag);
+        true
+    }
+
+    /// 미등록 SSRC의 RTP에 rtp-stream-id 확장이 있으면 레이어로 등록 (브라우저는 레이어 SSRC를 SDP에 싣지 않음)
+    pub fn learn_layer(&self, packet: &[u8]) -> bool {
+        let id = self.rid_ext.load(Ordering::Relaxed);
+        let Some(ssrc) = rtp::ssrc(packet) else { return false };
+        if id == 0 || self.tracks.read().unwrap().iter().any(|t| t.ssrc == ssrc) {
+            return false;
+        }
+        rtp::header_extension(packet, id)
+            .and_then(|rid| std::str::from_utf8(rid).ok())
+            .is_some_and(|rid| self.add_layer(ssrc, rid))
+    }
+
     /// 트랙 등록 (ssrc + 종류)
     pub fn add_track(&self, ssrc: u32, kind: TrackKind) {
         let mut tracks = self.tracks.write().unwrap();
         if !tracks.iter().any(|t| t.ssrc == ssrc) {
-            tracks.push(Track { ssrc, kind });
+            tracks.push(Track { ssrc, kind, rid: None });
             trace!("Track added: ssrc={} ufrag={}", ssrc, self.ufrag);
         }
     }
@@ -109,16 +280,27 @@ pub struct MediaPeerHub {
     /// 역방향 조회: sender_ssrc → Vec<(receiver_user_id, consumer_ssrc)>
     /// relay 핫패스에서 O(1) 조회용
     ssrc_relay_map: RwLock<HashMap<u32, Vec<(String, u32)>>>,
+    /// 송신 원본 SSRC → 최근 평문 RTP (NACK 재전송 버퍼)
+    rtp_cache: RwLock<HashMap<u32, Mutex<RtpRing>>>,
+    /// Conference RTX 스트림: (receiver, consumer SSRC) → (RTX SSRC, 다음 RTX seq)
+    /// re-nego answer에 a=ssrc-group:FID로 광고된 것만 존재
+    rtx_streams: RwLock<HashMap<(String, u32), (u32, u16)>>,
+    /// simulcast 레이어 선택: (receiver, consumer SSRC) → 선택 상태
+    /// 송신자가 simulcast인 video consumer만 존재 (첫 레이어 패킷 릴레이 또는 SIMULCAST_LAYER 시 생성)
+    layer_selectors: RwLock<HashMap<(String, u32), Mutex<LayerSelector>>>,
 }
 
 impl MediaPeerHub {
     pub fn new() -> Self {
         trace!("Initializing MediaPeerHub");
         Self {
-            by_addr:        RwLock::new(HashMap::new()),
+            by_addr:         RwLock::new(HashMap::new()),
             by_ufrag:        RwLock::new(HashMap::new()),
             consumer_ssrc:   RwLock::new(HashMap::new()),
             ssrc_relay_map:  RwLock::new(HashMap::new()),
+            rtp_cache:       RwLock::new(HashMap::new()),
+            rtx_streams:     RwLock::new(HashMap::new()),
+            layer_selectors: RwLock::new(HashMap::new()),
         }
     }
 
@@ -149,13 +331,17 @@ impl MediaPeerHub {
         self.by_addr.read().unwrap().get(addr).cloned()
     }
 
-    /// 엔드포인트 제거 (WS 종료 또는 CHANNEL_LEAVE)
+    /// 엔드포인트 제거 (WS 종료 또는 CHANNEL_LEAVE) — 등록 트랙의 재전송 버퍼 포함
     pub fn remove(&self, ufrag: &str) {
         let ep = self.by_ufrag.write().unwrap().remove(ufrag);
         if let Some(ep) = ep {
             if let Some(addr) = ep.get_address() {
                 self.by_addr.write().unwrap().remove(&addr);
             }
+            let mut cache = self.rtp_cache.write().unwrap();
+            for track in ep.tracks.read().unwrap().iter() {
+                cache.remove(&track.ssrc);
+            }
             debug!("Endpoint removed: ufrag={}", ufrag);
         }
     }
@@ -241,13 +427,171 @@ impl MediaPeerHub {
             .unwrap_or_default()
     }
 
-    /// 특정 user 관련 consumer SSRC 모두 제거 (퇴장 시)
+    /// RTCP feedback 역매핑: receiver가 보고한 consumer SSRC → (원 sender user_id, kind)
+    /// RTCP 빈도(초당 수 회)라 선형 탐색
+    pub fn resolve_consumer_ssrc(&self, receiver: &str, consumer_ssrc: u32) -> Option<(String, TrackKind)> {
+        self.consumer_ssrc.read().unwrap()
+            .iter()
+            .find(|(key, &ssrc)| ssrc == consumer_ssrc && key.receiver == receiver)
+            .map(|(key, _)| (key.sender.clone(), key.kind.clone()))
+    }
+
+    /// 특정 user 관련 consumer SSRC 모두 제거 (퇴장 시) — 해당 consumer의 RTX 스트림 / 레이어 선택 / 재전송 버퍼 포함
     pub fn remove_consumer_ssrc_for_user(&self, user_id: &str) {
         let mut map = self.consumer_ssrc.write().unwrap();
         map.retain(|key, _| key.receiver != user_id && key.sender != user_id);
+        let alive = |receiver: &str, ssrc: u32| map.iter().any(|(key, s)| *s == ssrc && key.receiver == receiver);
+        self.rtx_streams.write().unwrap().retain(|(receiver, ssrc), _| alive(receiver, *ssrc));
+        let mut removed = Vec::new();
+        self.layer_selectors.write().unwrap().retain(|(receiver, ssrc), _| {
+            let keep = alive(receiver, *ssrc);
+            if !keep {
+                removed.push(*ssrc);
+            }
+            keep
+        });
+        let mut cache = self.rtp_cache.write().unwrap();
+        for ssrc in removed {
+            cache.remove(&ssrc);
+        }
         trace!("consumer SSRCs removed for user={}", user_id);
     }
 
+    /// relay 핫패스: simulcast 레이어 패킷 → receiver의 consumer 스트림 (선택 상태 없으면 생성)
+    pub fn forward_layer(
+        &self,
+        receiver:      &str,
+        consumer_ssrc: u32,
+        layer:         (usize, usize),
+        packet:        &[u8],
+        keyframe:      Option<bool>,
+        now:           u64,
+    ) -> LayerForward {
+        let key = (receiver.to_string(), consumer_ssrc);
+        if let Some(sel) = self.layer_selectors.read().unwrap().get(&key) {
+            return sel.lock().unwrap().forward(layer.0, layer.1, packet, keyframe, now);
+        }
+        self.layer_selectors.write().unwrap()
+            .entry(key)
+            .or_insert_with(|| Mutex::new(LayerSelector::new(consumer_ssrc)))
+            .get_mut().unwrap()
+            .forward(layer.0, layer.1, packet, keyframe, now)
+    }
+
+    /// receiver가 sender video를 받을 레이어 지정 (None = auto) — 목표 레이어가 바뀌면 true
+    /// re-nego 전이라 consumer SSRC가 없으면 false
+    pub fn set_layer_preference(&self, receiver: &str, sender: &str, layer: Option<usize>, now: u64) -> bool {
+        let key = ConsumerSsrcKey { receiver: receiver.to_string(), sender: sender.to_string(), kind: TrackKind::Video };
+        let Some(&consumer_ssrc) = self.consumer_ssrc.read().unwrap().get(&key) else { return false };
+        self.layer_selectors.write().unwrap()
+            .entry((key.receiver, consumer_ssrc))
+            .or_insert_with(|| Mutex::new(LayerSelector::new(consumer_ssrc)))
+            .get_mut().unwrap()
+            .set_preferred(layer, now)
+    }
+
+    /// receiver RR의 fraction lost 반영 — simulcast consumer가 아니면 false
+    pub fn layer_feedback(&self, receiver: &str, consumer_ssrc: u32, fraction_lost: u8, now: u64) -> bool {
+        self.layer_selectors.read().unwrap()
+            .get(&(receiver.to_string(), consumer_ssrc))
+            .is_some_and(|sel| sel.lock().unwrap().on_loss(fraction_lost, now))
+    }
+
+    /// simulcast consumer 여부 (NACK 응답 / SR 전달 시 rewrite 스트림 기준 처리)
+    pub fn is_layered(&self, receiver: &str, consumer_ssrc: u32) -> bool {
+        self.layer_selectors.read().unwrap().contains_key(&(receiver.to_string(), consumer_ssrc))
+    }
+
+    /// simulcast consumer가 지금 받는 레이어의 원본 SSRC (RTCP feedback 역매핑용)
+    pub fn layer_source(&self, receiver: &str, consumer_ssrc: u32) -> Option<u32> {
+        self.layer_selectors.read().unwrap()
+            .get(&(receiver.to_string(), consumer_ssrc))?
+            .lock().unwrap()
+            .current_source()
+    }
+
+    /// 송신자 SR timestamp → simulcast consumer 스트림 timestamp (현재 레이어 SR만 Some)
+    pub fn layer_sr_timestamp(&self, receiver: &str, consumer_ssrc: u32, source: u32, ts: u32) -> Option<u32> {
+        self.layer_selectors.read().unwrap()
+            .get(&(receiver.to_string(), consumer_ssrc))?
+            .lock().unwrap()
+            .map_timestamp(source, ts)
+    }
+
+
+    /// relay 핫패스: 릴레이한 평문 RTP를 송신 SSRC별 재전송 버퍼에 저장
+    /// 송신 엔드포인트에 등록된 트랙 SSRC만 — 미등록 SSRC는 버퍼 없이 릴레이만
+    pub fn cache_rtp(&self, sender: &Endpoint, packet: &[u8], now: u64) {
+        let Some(ssrc) = rtp::ssrc(packet) else { return };
+        if !sender.tracks.read().unwrap().iter().any(|t| t.ssrc == ssrc) {
+            return;
+        }
+        self.store_rtp(ssrc, packet, now);
+    }
+
+    /// 서버가 rewrite한 RTP(consumer / 가상 talker SSRC) 저장 — SSRC를 서버가 할당하므로 소유 검사 없음
+    pub fn cache_rewritten_rtp(&self, packet: &[u8], now: u64) {
+        let Some(ssrc) = rtp::ssrc(packet) else { return };
+        self.store_rtp(ssrc, packet, now);
+    }
+
+    fn store_rtp(&self, ssrc: u32, packet: &[u8], now: u64) {
+        if let Some(ring) = self.rtp_cache.read().unwrap().get(&ssrc) {
+            ring.lock().unwrap().push(packet, now);
+            return;
+        }
+        let mut cache = self.rtp_cache.write().unwrap();
+        if cache.len() >= config::NACK_CACHE_MAX_STREAMS && !cache.contains_key(&ssrc) {
+            trace!("rtp cache full, not caching ssrc={}", ssrc);
+            return;
+        }
+        cache.entry(ssrc)
+            .or_insert_with(|| Mutex::new(RtpRing::new(config::NACK_CACHE_PACKETS)))
+            .get_mut().unwrap()
+            .push(packet, now);
+    }
+
+    /// 지정 SSRC 재전송 버퍼 제거 (채널 제거 시 가상 talker SSRC)
+    pub fn remove_rtp_cache(&self, ssrcs: &[u32]) {
+        let mut cache = self.rtp_cache.write().unwrap();
+        for ssrc in ssrcs {
+            cache.remove(ssrc);
+        }
+    }
+
+    /// NACK_CACHE_MAX_AGE_MS 동안 저장이 없던 재전송 버퍼 제거 — 응답 가능한 패킷이 남지 않은 버퍼 (reaper 주기)
+    pub fn prune_rtp_cache(&self, now: u64) -> usize {
+        let mut cache = self.rtp_cache.write().unwrap();
+        let before = cache.len();
+        cache.retain(|_, ring| now.saturating_sub(ring.get_mut().unwrap().last_at()) <= config::NACK_CACHE_MAX_AGE_MS);
+        before - cache.len()
+    }
+
+    /// NACK 응답용 조회 — 원본 SSRC / seq, NACK_CACHE_MAX_AGE_MS 이내만
+    pub fn cached_rtp(&self, ssrc: u32, seq: u16, now: u64) -> Option<Vec<u8>> {
+        let cache = self.rtp_cache.read().unwrap();
+        let ring  = cache.get(&ssrc)?.lock().unwrap();
+        ring.get(seq, now, config::NACK_CACHE_MAX_AGE_MS).map(<[u8]>::to_vec)
+    }
+
+    /// consumer SSRC에 짝지을 RTX SSRC 할당 또는 기존 값 반환 (re-nego answer의 FID 그룹용)
+    pub fn get_or_create_rtx_ssrc(&self, receiver: &str, consumer_ssrc: u32) -> u32 {
+        let mut streams = self.rtx_streams.write().unwrap();
+        let mut rng     = rand::thread_rng();
+        streams.entry((receiver.to_string(), consumer_ssrc))
+            .or_insert_with(|| (rng.gen::<u32>() | 1, rng.gen::<u16>()))
+            .0
+    }
+
+    /// RTX 전송: (RTX SSRC, 이번 seq) — seq는 호출마다 1 증가, 광고된 RTX 스트림이 없으면 None
+    pub fn next_rtx(&self, receiver: &str, consumer_ssrc: u32) -> Option<(u32, u16)> {
+        let mut streams = self.rtx_streams.write().unwrap();
+        let (ssrc, seq) = streams.get_mut(&(receiver.to_string(), consumer_ssrc))?;
+        let current = *seq;
+        *seq = seq.wrapping_add(1);
+        Some((*ssrc, current))
+    }
+
     /// 좀비 피어 목록 반환 (last_seen 기준)
     pub fn find_zombies(&self, timeout_ms: u64) -> Vec<String> {
         let now = current_timestamp();
@@ -345,6 +689,130 @@ mod tests {
         assert_eq!(ep.get_address(), Some(addr(9000)));
     }
 
+    #[test]
+    fn resolve_consumer_ssrc_maps_back_to_sender() {
+        let hub  = MediaPeerHub::new();
+        let ssrc = hub.get_or_create_consumer_ssrc("bob", "alice", TrackKind::Video);
+        assert_eq!(hub.resolve_consumer_ssrc("bob", ssrc), Some(("alice".to_string(), TrackKind::Video)));
+        // 다른 receiver의 SSRC 공간과 섞이지 않음
+        assert_eq!(hub.resolve_consumer_ssrc("carol", ssrc), None);
+    }
+
+    #[test]
+    fn rtp_cache_and_rtx_streams() {
+        let hub = MediaPeerHub::new();
+        let ep  = hub.insert("u1", "p", "alice", "CH_001");
+        ep.add_track(0x1111, TrackKind::Video);
+        let mut pkt = vec![0x80, 96, 0, 5, 0, 0, 0, 0, 0, 0, 0x11, 0x11];
+        pkt.extend_from_slice(&[0xAA; 10]);
+        hub.cache_rtp(&ep, &pkt, 1_000);
+        assert_eq!(hub.cached_rtp(0x1111, 5, 1_100), Some(pkt.clone()));
+        assert!(hub.cached_rtp(0x1111, 6, 1_100).is_none());
+
+        // 송신자에게 등록되지 않은 SSRC는 버퍼 없음
+        let mut foreign = pkt.clone();
+        foreign[8..12].copy_from_slice(&0x2222u32.to_be_bytes());
+        hub.cache_rtp(&ep, &foreign, 1_000);
+        assert!(hub.cached_rtp(0x2222, 5, 1_100).is_none());
+
+        // RTX는 광고된 consumer SSRC에만, seq 연속 증가
+        let consumer = hub.get_or_create_consumer_ssrc("bob", "alice", TrackKind::Video);
+        assert!(hub.next_rtx("bob", consumer).is_none());
+        let rtx_ssrc = hub.get_or_create_rtx_ssrc("bob", consumer);
+        assert_eq!(hub.get_or_create_rtx_ssrc("bob", consumer), rtx_ssrc);
+        let (s1, q1) = hub.next_rtx("bob", consumer).unwrap();
+        let (_, q2)  = hub.next_rtx("bob", consumer).unwrap();
+        assert_eq!((s1, q2), (rtx_ssrc, q1.wrapping_add(1)));
+
+        // 퇴장 시 RTX 스트림 / 재전송 버퍼 정리
+        hub.remove_consumer_ssrc_for_user("alice");
+        assert!(hub.next_rtx("bob", consumer).is_none());
+        hub.remove("u1");
+        assert!(hub.cached_rtp(0x1111, 5, 1_100).is_none());
+    }
+
+    #[test]
+    fn rewritten_rtp_cache_evicted_and_pruned() {
+        let hub = MediaPeerHub::new();
+        let pkt = |ssrc: u32| {
+            let mut p = vec![0x80, 96, 0, 5, 0, 0, 0, 0];
+            p.extend_from_slice(&ssrc.to_be_bytes());
+            p
+        };
+        hub.cache_rewritten_rtp(&pkt(0x3333), 1_000);
+        hub.cache_rewritten_rtp(&pkt(0x4444), 1_000);
+        hub.remove_rtp_cache(&[0x3333]);
+        assert!(hub.cached_rtp(0x3333, 5, 1_000).is_none());
+        assert!(hub.cached_rtp(0x4444, 5, 1_000).is_some());
+
+        // 유휴 버퍼는 NACK_CACHE_MAX_AGE_MS 경과 후 정리
+        assert_eq!(hub.prune_rtp_cache(1_000 + config::NACK_CACHE_MAX_AGE_MS), 0);
+        assert_eq!(hub.prune_rtp_cache(1_001 + config::NACK_CACHE_MAX_AGE_MS), 1);
+        assert!(hub.rtp_cache.read().unwrap().is_empty());
+
+        // 스트림 수 상한 — 기존 스트림만 갱신
+        for ssrc in 0..config::NACK_CACHE_MAX_STREAMS as u32 {
+            hub.cache_rewritten_rtp(&pkt(ssrc + 1), 2_000);
+        }
+        hub.cache_rewritten_rtp(&pkt(0xFFFF_FFFF), 2_000);
+        assert!(hub.cached_rtp(0xFFFF_FFFF, 5, 2_000).is_none());
+        assert_eq!(hub.rtp_cache.read().unwrap().len(), config::NACK_CACHE_MAX_STREAMS);
+    }
+
+    #[test]
+    fn keyframe_request_throttled_per_endpoint() {
+        let ep = Endpoint::new("u".into(), "p".into(), "alice".into(), "CH".into());
+        ep.add_track(1, TrackKind::Audio);
+        ep.add_track(2, TrackKind::Video);
+        assert_eq!(ep.video_ssrcs(), vec![2]);
+        assert!(ep.try_keyframe_request(10_000));
+        assert!(!ep.try_keyframe_request(10_000 + config::KEYFRAME_REQUEST_MIN_MS - 1));
+        assert!(ep.try_keyframe_request(10_000 + config::KEYFRAME_REQUEST_MIN_MS));
+    }
+
+    #[test]
+    fn audio_level_smoothed_and_silent_when_stale() {
+        let ep = Endpoint::new("u".into(), "p".into(), "alice".into(), "CH".into());
+        assert_eq!(ep.audio_level(10_000), None);
+
+        // 무음(127)에서 시작해 30dBov로 수렴
+        ep.update_audio_level(30, 10_000);
+        let first = ep.audio_level(10_000).unwrap();
+        assert!(first > 30 && first < 127);
+        for i in 1..20 {
+            ep.update_audio_level(30, 10_000 + i * 20);
+        }
+        assert!(ep.audio_level(10_400).unwrap() <= 31);
+
+        // DTX로 수신이 멈추면 무음
+        assert_eq!(ep.audio_level(10_400 + config::SPEAKER_SILENCE_MS + 1), Some(127));
+    }
+
+    #[test]
+    fn simulcast_layers_learned_from_rid_and_cleaned_up() {
+        let hub = MediaPeerHub::new();
+        let ep  = hub.insert("u1", "p", "alice", "CH_001");
+        ep.set_simulcast(vec!["q".into(), "f".into()], Some(10));
+
+        // one-byte 확장 id=10 rtp-stream-id "f"
+        let mut pkt = vec![0x90, 96, 0, 1, 0, 0, 0, 0, 0, 0, 0x22, 0x22];
+        pkt.extend_from_slice(&[0xBE, 0xDE, 0, 1, 0xA0, b'f', 0, 0, 0xAA]);
+        assert!(ep.learn_layer(&pkt));
+        assert!(!ep.learn_layer(&pkt)); // 이미 등록
+        assert_eq!(ep.layer_of(0x2222), Some((1, 2)));
+        assert!(!ep.add_layer(0x3333, "x")); // offer에 없는 rid
+
+        // 레이어 선택 상태 / rewrite 버퍼는 퇴장 시 정리
+        let consumer = hub.get_or_create_consumer_ssrc("bob", "alice", TrackKind::Video);
+        let LayerForward::Send(out) = hub.forward_layer("bob", consumer, (1, 2), &pkt, None, 1_000) else { panic!() };
+        hub.cache_rewritten_rtp(&out, 1_000);
+        assert!(hub.is_layered("bob", consumer));
+        assert_eq!(hub.layer_source("bob", consumer), Some(0x2222));
+        hub.remove_consumer_ssrc_for_user("alice");
+        assert!(!hub.is_layered("bob", consumer));
+        assert!(hub.cached_rtp(consumer, 1, 1_000).is_none());
+    }
+
     #[test]
     fn find_zombies_fresh_empty() {
         let hub = MediaPeerHub::new();
@@ -0,0 +1,316 @@
+// author: kodeholic (powered by Claude)
+// RateLimiter — 사용자별 opcode 계열 토큰 버킷
+//
+// 계열(RateClass)마다 독립 버킷을 둔다. 요청 1건 = 토큰 1개.
+//   - 토큰 있음 → Allow
+//   - 토큰 없음 → Throttle (요청 폐기 + 에러 응답)
+//   - 윈도우 내 위반 누적이 max_violations 에 도달 → Disconnect (WS 강제 종료)
+
+use std::str::FromStr;
+
+use crate::config;
+
+// ----------------------------------------------------------------------------
+// [RateClass] opcode 계열
+// ----------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateClass {
+    Message,  // MESSAGE_CREATE
+    Floor,    // FLOOR_REQUEST / RELEASE / PING / QUEUE_CANCEL
+    Query,    // CHANNEL_LIST / CHANNEL_INFO 등 조회
+    Channel,  // CHANNEL_CREATE / JOIN / LEAVE / UPDATE / DELETE, RENEGOTIATE
+}
+
+impl RateClass {
+    pub const ALL: [RateClass; 4] = [
+        RateClass::Message,
+        RateClass::Floor,
+        RateClass::Query,
+        RateClass::Channel,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RateClass::Message => "message",
+            RateClass::Floor   => "floor",
+            RateClass::Query   => "query",
+            RateClass::Channel => "channel",
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+// ----------------------------------------------------------------------------
+// [BucketSpec] 버킷 규격 — "초당 충전량/최대 보유량" (예: "2/5")
+// ----------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BucketSpec {
+    pub per_sec: u32,
+    pub burst:   u32,
+}
+
+impl FromStr for BucketSpec {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (rate, burst) = s.split_once('/')
+            .ok_or_else(|| format!("'{}' — 형식은 PER_SEC/BURST (예: 2/5)", s))?;
+        let per_sec: u32 = rate.trim().parse().map_err(|_| format!("잘못된 PER_SEC: {}", rate))?;
+        let burst:   u32 = burst.trim().parse().map_err(|_| format!("잘못된 BURST: {}", burst))?;
+        if burst == 0 {
+            return Err("BURST는 1 이상이어야 함".to_string());
+        }
+        Ok(Self { per_sec, burst })
+    }
+}
+
+// ----------------------------------------------------------------------------
+// [RateLimitConfig] 서버 전역 설정 (기본값은 config.rs 상수, CLI로 오버라이드)
+// ----------------------------------------------------------------------------
+
+#[derive(Debug, Clone)]
+pub struct RateLimitConfig {
+    pub message:             BucketSpec,
+    pub floor:               BucketSpec,
+    pub query:               BucketSpec,
+    pub channel:             BucketSpec,
+    pub max_violations:      u32,  // 0이면 강제 종료 비활성화
+    pub violation_window_ms: u64,
+}
+
+impl RateLimitConfig {
+    pub fn spec(&self, class: RateClass) -> BucketSpec {
+        match class {
+            RateClass::Message => self.message,
+            RateClass::Floor   => self.floor,
+            RateClass::Query   => self.query,
+            RateClass::Channel => self.channel,
+        }
+    }
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            message: BucketSpec { per_sec: config::RATE_MESSAGE_PER_SEC, burst: config::RATE_MESSAGE_BURST },
+            floor:   BucketSpec { per_sec: config::RATE_FLOOR_PER_SEC,   burst: config::RATE_FLOOR_BURST },
+            query:   BucketSpec { per_sec: config::RATE_QUERY_PER_SEC,   burst: config::RATE_QUERY_BURST },
+            channel: BucketSpec { per_sec: config::RATE_CHANNEL_PER_SEC, burst: config::RATE_CHANNEL_BURST },
+            max_violations:      config::RATE_MAX_VIOLATIONS,
+            violation_window_ms: config::RATE_VIOLATION_WINDOW_MS,
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// [TokenBucket]
+// ----------------------------------------------------------------------------
+
+struct TokenBucket {
+    spec:           BucketSpec,
+    tokens:         f64,
+    last_refill_ms: u64,
+    allowed:        u64,
+    throttled:      u64,
+}
+
+impl TokenBucket {
+    fn new(spec: BucketSpec) -> Self {
+        Self { spec, tokens: spec.burst as f64, last_refill_ms: 0, allowed: 0, throttled: 0 }
+    }
+
+    fn refill(&mut self, now_ms: u64) {
+        let elapsed_ms = now_ms.saturating_sub(self.last_refill_ms);
+        self.tokens = (self.tokens + elapsed_ms as f64 * self.spec.per_sec as f64 / 1000.0)
+            .min(self.spec.burst as f64);
+        self.last_refill_ms = now_ms;
+    }
+
+    fn try_take(&mut self, now_ms: u64) -> bool {
+        self.refill(now_ms);
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            self.allowed += 1;
+            true
+        } else {
+            self.throttled += 1;
+            false
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// [RateLimiter] User당 1개
+// ----------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateDecision {
+    Allow,
+    Throttle,
+    Disconnect,
+}
+
+/// admin 조회용 버킷 스냅샷
+#[derive(Debug, Clone)]
+pub struct RateBucketStats {
+    pub class:     &'static str,
+    pub tokens:    u32,
+    pub allowed:   u64,
+    pub throttled: u64,
+}
+
+pub struct RateLimiter {
+    buckets:           [TokenBucket; 4],
+    max_violations:    u32,
+    window_ms:         u64,
+    window_start_ms:   u64,
+    window_violations: u32,
+    total_violations:  u64,
+}
+
+impl RateLimiter {
+    pub fn new(cfg: &RateLimitConfig) -> Self {
+        Self {
+            buckets:           RateClass::ALL.map(|c| TokenBucket::new(cfg.spec(c))),
+            max_violations:    cfg.max_violations,
+            window_ms:         cfg.violation_window_ms,
+            window_start_ms:   0,
+            window_violations: 0,
+            total_violations:  0,
+        }
+    }
+
+    /// 요청 1건 판정 — now_ms는 호출자가 주입 (테스트 용이성)
+    pub fn check(&mut self, class: RateClass, now_ms: u64) -> RateDecision {
+        if self.buckets[class.index()].try_take(now_ms) {
+            return RateDecision::Allow;
+        }
+
+        if now_ms.saturating_sub(self.window_start_ms) >= self.window_ms {
+            self.window_start_ms   = now_ms;
+            self.window_violations = 0;
+        }
+        self.window_violations += 1;
+        self.total_violations  += 1;
+
+        if self.max_violations > 0 && self.window_violations >= self.max_violations {
+            RateDecision::Disconnect
+        } else {
+            RateDecision::Throttle
+        }
+    }
+
+    pub fn total_violations(&self) -> u64 {
+        self.total_violations
+    }
+
+    pub fn stats(&self) -> Vec<RateBucketStats> {
+        RateClass::ALL.iter()
+            .map(|c| {
+                let b = &self.buckets[c.index()];
+                RateBucketStats {
+                    class:     c.as_str(),
+                    tokens:    b.tokens.floor() as u32,
+                    allowed:   b.allowed,
+                    throttled: b.throttled,
+                }
+            })
+            .collect()
+    }
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        Self::new(&RateLimitConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cfg(per_sec: u32, burst: u32, max_violations: u32) -> RateLimitConfig {
+        let spec = BucketSpec { per_sec, burst };
+        RateLimitConfig {
+            message: spec, floor: spec, query: spec, channel: spec,
+            max_violations,
+            violation_window_ms: 10_000,
+        }
+    }
+
+    #[test]
+    fn burst_then_throttle() {
+        let mut rl = RateLimiter::new(&cfg(1, 3, 0));
+        let now = 1_000_000;
+        for _ in 0..3 {
+            assert_eq!(rl.check(RateClass::Message, now), RateDecision::Allow);
+        }
+        assert_eq!(rl.check(RateClass::Message, now), RateDecision::Throttle);
+        assert_eq!(rl.total_violations(), 1);
+    }
+
+    #[test]
+    fn tokens_refill_over_time() {
+        let mut rl = RateLimiter::new(&cfg(2, 2, 0));
+        let now = 1_000_000;
+        rl.check(RateClass::Floor, now);
+        rl.check(RateClass::Floor, now);
+        assert_eq!(rl.check(RateClass::Floor, now), RateDecision::Throttle);
+        // 500ms 후 토큰 1개 충전 (2/sec)
+        assert_eq!(rl.check(RateClass::Floor, now + 500), RateDecision::Allow);
+    }
+
+    #[test]
+    fn classes_are_independent() {
+        let mut rl = RateLimiter::new(&cfg(1, 1, 0));
+        let now = 1_000_000;
+        assert_eq!(rl.check(RateClass::Message, now), RateDecision::Allow);
+        assert_eq!(rl.check(RateClass::Message, now), RateDecision::Throttle);
+        assert_eq!(rl.check(RateClass::Query, now), RateDecision::Allow);
+    }
+
+    #[test]
+    fn repeated_violations_disconnect() {
+        let mut rl = RateLimiter::new(&cfg(0, 1, 3));
+        let now = 1_000_000;
+        assert_eq!(rl.check(RateClass::Channel, now), RateDecision::Allow);
+        assert_eq!(rl.check(RateClass::Channel, now), RateDecision::Throttle);
+        assert_eq!(rl.check(RateClass::Channel, now), RateDecision::Throttle);
+        assert_eq!(rl.check(RateClass::Channel, now), RateDecision::Disconnect);
+    }
+
+    #[test]
+    fn violation_window_resets() {
+        let mut rl = RateLimiter::new(&cfg(0, 1, 3));
+        let now = 1_000_000;
+        rl.check(RateClass::Channel, now);
+        rl.check(RateClass::Channel, now);
+        rl.check(RateClass::Channel, now);
+        // 윈도우(10초) 경과 후 누적 초기화
+        assert_eq!(rl.check(RateClass::Channel, now + 10_000), RateDecision::Throttle);
+    }
+
+    #[test]
+    fn stats_report_counters() {
+        let mut rl = RateLimiter::new(&cfg(0, 1, 0));
+        rl.check(RateClass::Message, 1_000);
+        rl.check(RateClass::Message, 1_000);
+        let s = rl.stats().into_iter().find(|s| s.class == "message").unwrap();
+        assert_eq!(s.allowed, 1);
+        assert_eq!(s.throttled, 1);
+        assert_eq!(s.tokens, 0);
+    }
+
+    #[test]
+    fn bucket_spec_parse() {
+        assert_eq!("2/5".parse::<BucketSpec>().unwrap(), BucketSpec { per_sec: 2, burst: 5 });
+        assert!("2".parse::<BucketSpec>().is_err());
+        assert!("2/0".parse::<BucketSpec>().is_err());
+        assert!("a/5".parse::<BucketSpec>().is_err());
+    }
+}
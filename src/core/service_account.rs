@@ -0,0 +1,323 @@
+// author: kodeholic (powered by Claude)
+// ServiceAccountHub — 봇/연동 시스템용 서비스 계정 + 범위 제한 API 키
+//
+// - 키 포맷: "lck_" + 64 hex (32 random bytes), 발급 시 1회만 평문 반환
+// - 저장: SHA-256 해시만 보관 (평문 키는 서버에 남지 않음)
+// - 사용처: IDENTIFY(api_key) / HTTP Authorization: Bearer <key>
+// - 파일 지정 시 변경마다 JSON 저장, 서버 시작 시 로드
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::{trace, warn};
+
+use crate::utils::current_timestamp;
+
+/// 발급 키 접두어 — 로그/설정 파일에서 식별 용이
+pub const API_KEY_PREFIX: &str = "lck_";
+
+// ----------------------------------------------------------------------------
+// [ApiScope] 키 권한 범위
+// ----------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ApiScope {
+    PostMessage,     // MESSAGE_CREATE, POST /admin/.../messages
+    ReadTrace,       // GET /trace
+    ManageChannels,  // CHANNEL_CREATE / UPDATE / DELETE
+    Admin,           // /admin/service-accounts (계정 생성 / 조회 / 삭제)
+}
+
+impl ApiScope {
+    pub const ALL: [ApiScope; 4] = [
+        ApiScope::PostMessage,
+        ApiScope::ReadTrace,
+        ApiScope::ManageChannels,
+        ApiScope::Admin,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ApiScope::PostMessage    => "post-message",
+            ApiScope::ReadTrace      => "read-trace",
+            ApiScope::ManageChannels => "manage-channels",
+            ApiScope::Admin          => "admin",
+        }
+    }
+}
+
+impl FromStr for ApiScope {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_lowercase();
+        ApiScope::ALL.iter()
+            .copied()
+            .find(|sc| sc.as_str() == s)
+            .ok_or_else(|| format!("'{}' — post-message | read-trace | manage-channels | admin 중 하나", s))
+    }
+}
+
+// ----------------------------------------------------------------------------
+// [ServiceAccount]
+// ----------------------------------------------------------------------------
+
+pub struct ServiceAccount {
+    pub id:           String,          // user_id / author_id 로 사용
+    pub scopes:       Vec<ApiScope>,
+    pub key_prefix:   String,          // 표시용 앞부분 (예: "lck_1a2b3c4d")
+    key_hash:         String,          // SHA-256 hex
+    pub created_at:   u64,
+    pub last_used_ms: AtomicU64,       // 0이면 미사용
+}
+
+// key_hash는 로그에 남기지 않음
+impl std::fmt::Debug for ServiceAccount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ServiceAccount")
+            .field("id", &self.id)
+            .field("scopes", &self.scopes)
+            .field("key_prefix", &self.key_prefix)
+            .finish()
+    }
+}
+
+impl ServiceAccount {
+    pub fn has_scope(&self, scope: ApiScope) -> bool {
+        self.scopes.contains(&scope)
+    }
+}
+
+/// 파일 저장 레코드
+#[derive(Serialize, Deserialize)]
+struct ServiceAccountRecord {
+    id:         String,
+    scopes:     Vec<ApiScope>,
+    key_prefix: String,
+    key_hash:   String,
+    created_at: u64,
+}
+
+fn hash_key(key: &str) -> String {
+    Sha256::digest(key.as_bytes()).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn generate_key() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    format!("{}{}", API_KEY_PREFIX, hex)
+}
+
+/// 서비스 계정 id 규칙 — 영숫자 / '-' / '_', 1~32자
+pub fn is_valid_account_id(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= 32
+        && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+// ----------------------------------------------------------------------------
+// [ServiceAccountHub]
+// ----------------------------------------------------------------------------
+
+#[derive(Default)]
+pub struct ServiceAccountHub {
+    accounts: RwLock<HashMap<String, Arc<ServiceAccount>>>,
+    path:     Option<PathBuf>,  // None이면 메모리 전용
+}
+
+impl ServiceAccountHub {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 파일 기반 — 파일이 없으면 빈 상태로 시작 (첫 발급 시 생성)
+    pub fn with_file(path: PathBuf) -> std::io::Result<Self> {
+        let mut accounts = HashMap::new();
+        if path.exists() {
+            let text = std::fs::read_to_string(&path)?;
+            let records: Vec<ServiceAccountRecord> = serde_json::from_str(&text)
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+            for r in records {
+                accounts.insert(r.id.clone(), Arc::new(ServiceAccount {
+                    id:           r.id,
+                    scopes:       r.scopes,
+                    key_prefix:   r.key_prefix,
+                    key_hash:     r.key_hash,
+                    created_at:   r.created_at,
+                    last_used_ms: AtomicU64::new(0),
+                }));
+            }
+        }
+        Ok(Self { accounts: RwLock::new(accounts), path: Some(path) })
+    }
+
+    /// 계정 생성 + 키 발급 — 반환되는 평문 키는 이후 조회 불가
+    pub fn create(&self, id: &str, scopes: Vec<ApiScope>) -> Result<(Arc<ServiceAccount>, String), String> {
+        if !is_valid_account_id(id) {
+            return Err(format!("Invalid service account id: {}", id));
+        }
+        let key     = generate_key();
+        let account = Arc::new(ServiceAccount {
+            id:           id.to_string(),
+            scopes,
+            key_prefix:   key[..API_KEY_PREFIX.len() + 8].to_string(),
+            key_hash:     hash_key(&key),
+            created_at:   current_timestamp(),
+            last_used_ms: AtomicU64::new(0),
+        });
+        {
+            let mut accounts = self.accounts.write().unwrap();
+            if accounts.contains_key(id) {
+                return Err(format!("Service account already exists: {}", id));
+            }
+            accounts.insert(id.to_string(), Arc::clone(&account));
+        }
+        self.persist();
+        trace!("Service account created: {}", id);
+        Ok((account, key))
+    }
+
+    pub fn remove(&self, id: &str) -> bool {
+        let removed = self.accounts.write().unwrap().remove(id).is_some();
+        if removed {
+            self.persist();
+            trace!("Service account removed: {}", id);
+        }
+        removed
+    }
+
+    pub fn get(&self, id: &str) -> Option<Arc<ServiceAccount>> {
+        self.accounts.read().unwrap().get(id).cloned()
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.accounts.read().unwrap().contains_key(id)
+    }
+
+    pub fn list(&self) -> Vec<Arc<ServiceAccount>> {
+        let mut list: Vec<_> = self.accounts.read().unwrap().values().cloned().collect();
+        list.sort_by(|a, b| a.id.cmp(&b.id));
+        list
+    }
+
+    pub fn count(&self) -> usize {
+        self.accounts.read().unwrap().len()
+    }
+
+    /// 평문 키 → 계정 (성공 시 last_used 갱신)
+    pub fn authenticate(&self, key: &str) -> Option<Arc<ServiceAccount>> {
+        if !key.starts_with(API_KEY_PREFIX) {
+            return None;
+        }
+        let hash    = hash_key(key);
+        let account = self.accounts.read().unwrap()
+            .values()
+            .find(|a| a.key_hash == hash)
+            .cloned()?;
+        account.last_used_ms.store(current_timestamp(), Ordering::Relaxed);
+        Some(account)
+    }
+
+    /// 파일 저장 (tmp 작성 후 rename) — 실패는 경고만
+    fn persist(&self) {
+        let Some(ref path) = self.path else { return };
+        let records: Vec<ServiceAccountRecord> = self.list().iter()
+            .map(|a| ServiceAccountRecord {
+                id:         a.id.clone(),
+                scopes:     a.scopes.clone(),
+                key_prefix: a.key_prefix.clone(),
+                key_hash:   a.key_hash.clone(),
+                created_at: a.created_at,
+            })
+            .collect();
+        let json = serde_json::to_string_pretty(&records).unwrap_or_default();
+        let tmp  = path.with_extension("tmp");
+        if let Err(e) = std::fs::write(&tmp, json).and_then(|_| std::fs::rename(&tmp, path)) {
+            warn!("[service-account] Failed to save {}: {}", path.display(), e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn create_and_authenticate() {
+        let hub = ServiceAccountHub::new();
+        let (acct, key) = hub.create("cad-bot", vec![ApiScope::PostMessage]).unwrap();
+        assert!(key.starts_with(API_KEY_PREFIX));
+        assert!(key.starts_with(&acct.key_prefix));
+
+        let found = hub.authenticate(&key).unwrap();
+        assert_eq!(found.id, "cad-bot");
+        assert!(found.has_scope(ApiScope::PostMessage));
+        assert!(!found.has_scope(ApiScope::ReadTrace));
+        assert!(found.last_used_ms.load(Ordering::Relaxed) > 0);
+    }
+
+    #[test]
+    fn wrong_key_rejected() {
+        let hub = ServiceAccountHub::new();
+        hub.create("bot", vec![]).unwrap();
+        assert!(hub.authenticate("lck_deadbeef").is_none());
+        assert!(hub.authenticate("changeme-secret").is_none());
+    }
+
+    #[test]
+    fn key_stored_hashed() {
+        let hub = ServiceAccountHub::new();
+        let (acct, key) = hub.create("bot", vec![]).unwrap();
+        assert_ne!(acct.key_hash, key);
+        assert_eq!(acct.key_hash, hash_key(&key));
+    }
+
+    #[test]
+    fn duplicate_and_invalid_id() {
+        let hub = ServiceAccountHub::new();
+        hub.create("bot", vec![]).unwrap();
+        assert!(hub.create("bot", vec![]).is_err());
+        assert!(hub.create("bad id!", vec![]).is_err());
+        assert!(hub.create("", vec![]).is_err());
+    }
+
+    #[test]
+    fn remove_revokes_key() {
+        let hub = ServiceAccountHub::new();
+        let (_, key) = hub.create("bot", vec![ApiScope::ReadTrace]).unwrap();
+        assert!(hub.remove("bot"));
+        assert!(!hub.remove("bot"));
+        assert!(hub.authenticate(&key).is_none());
+    }
+
+    #[test]
+    fn file_roundtrip() {
+        let path = std::env::temp_dir().join(format!("lc_sa_test_{}.json", current_timestamp()));
+        let key = {
+            let hub = ServiceAccountHub::with_file(path.clone()).unwrap();
+            hub.create("alarm", vec![ApiScope::PostMessage, ApiScope::ManageChannels]).unwrap().1
+        };
+        let hub  = ServiceAccountHub::with_file(path.clone()).unwrap();
+        let acct = hub.authenticate(&key).unwrap();
+        assert_eq!(acct.scopes, vec![ApiScope::PostMessage, ApiScope::ManageChannels]);
+        // 파일에 평문 키 없음
+        assert!(!std::fs::read_to_string(&path).unwrap().contains(&key));
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn scope_parse() {
+        assert_eq!("post-message".parse::<ApiScope>().unwrap(), ApiScope::PostMessage);
+        assert_eq!("READ-TRACE".parse::<ApiScope>().unwrap(), ApiScope::ReadTrace);
+        assert_eq!("admin".parse::<ApiScope>().unwrap(), ApiScope::Admin);
+        assert!("root".parse::<ApiScope>().is_err());
+    }
+}
@@ -2,11 +2,13 @@
 // UserHub — WS 세션 관리 + 라우팅 테이블
 
 use std::collections::{HashMap, HashSet};
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, Mutex, RwLock};
 use std::sync::atomic::{AtomicU64, Ordering};
 use tokio::sync::mpsc;
 use tracing::{trace, warn};
 
+use crate::core::rate_limit::{RateLimitConfig, RateLimiter};
+use crate::core::service_account::ServiceAccount;
 use crate::utils::current_timestamp;
 
 /// 브로드캐스트 송신자 타입 (직렬화된 GatewayPacket JSON)
@@ -17,20 +19,32 @@ pub type BroadcastTx = mpsc::Sender<String>;
 // ----------------------------------------------------------------------------
 
 pub struct User {
-    pub tx:        BroadcastTx,
-    pub last_seen: AtomicU64,   // 마지막 메시지 수신 시간 (좀비 세션 감지용)
-    pub priority:  u8,          // Floor Control 우선순위 (MBCP, 높을수록 우선)
+    pub tx:           BroadcastTx,
+    pub last_seen:    AtomicU64,   // 마지막 메시지 수신 시간 (좀비 세션 감지용)
+    pub priority:     u8,          // Floor Control 우선순위 (MBCP, 높을수록 우선)
+    pub rate_limiter: Mutex<RateLimiter>,  // opcode 계열별 토큰 버킷
+    pub service:      Option<Arc<ServiceAccount>>,  // API 키로 IDENTIFY한 서비스 계정이면 Some
 }
 
 impl User {
     pub fn new(tx: BroadcastTx, priority: u8) -> Self {
+        Self::with_rate_limit(tx, priority, &RateLimitConfig::default())
+    }
+
+    pub fn with_rate_limit(tx: BroadcastTx, priority: u8, rate_limit: &RateLimitConfig) -> Self {
         Self {
             tx,
-            last_seen: AtomicU64::new(current_timestamp()),
+            last_seen:    AtomicU64::new(current_timestamp()),
             priority,
+            rate_limiter: Mutex::new(RateLimiter::new(rate_limit)),
+            service:      None,
         }
     }
 
+    pub fn is_service(&self) -> bool {
+        self.service.is_some()
+    }
+
     pub fn touch(&self) {
         self.last_seen.store(current_timestamp(), Ordering::Relaxed);
     }
@@ -41,22 +55,38 @@ impl User {
 // ----------------------------------------------------------------------------
 
 pub struct UserHub {
-    users: RwLock<HashMap<String, Arc<User>>>,
+    users:      RwLock<HashMap<String, Arc<User>>>,
+    rate_limit: RateLimitConfig,  // 신규 User 버킷 생성 시 적용
 }
 
 impl UserHub {
     pub fn new() -> Self {
+        Self::with_rate_limit(RateLimitConfig::default())
+    }
+
+    pub fn with_rate_limit(rate_limit: RateLimitConfig) -> Self {
         trace!("Initializing UserHub");
-        Self { users: RwLock::new(HashMap::new()) }
+        Self { users: RwLock::new(HashMap::new()), rate_limit }
     }
 
     pub fn register(&self, user_id: &str, tx: BroadcastTx, priority: u8) -> Arc<User> {
-        let user = Arc::new(User::new(tx, priority));
+        let user = Arc::new(User::with_rate_limit(tx, priority, &self.rate_limit));
         self.users.write().unwrap().insert(user_id.to_string(), Arc::clone(&user));
         trace!("User registered: {}", user_id);
         user
     }
 
+    /// 서비스 계정 등록 — user_id는 계정 id
+    pub fn register_service(&self, account: Arc<ServiceAccount>, tx: BroadcastTx, priority: u8) -> Arc<User> {
+        let mut user = User::with_rate_limit(tx, priority, &self.rate_limit);
+        let user_id  = account.id.clone();
+        user.service = Some(account);
+        let user = Arc::new(user);
+        self.users.write().unwrap().insert(user_id.clone(), Arc::clone(&user));
+        trace!("Service user registered: {}", user_id);
+        user
+    }
+
     pub fn unregister(&self, user_id: &str) {
         self.users.write().unwrap().remove(user_id);
         trace!("User unregistered: {}", user_id);
@@ -84,6 +114,11 @@ impl UserHub {
         }
     }
 
+    /// 서비스 계정 접속 여부 (멤버 목록 표시용)
+    pub fn is_service(&self, user_id: &str) -> bool {
+        self.users.read().unwrap().get(user_id).is_some_and(|u| u.is_service())
+    }
+
     /// 전체 User 목록 반환 (admin 조회용)
     pub fn all_users(&self) -> Vec<(String, Arc<User>)> {
         self.users.read().unwrap()
@@ -174,6 +209,20 @@ mod tests {
         assert!(t2 >= t1);
     }
 
+    #[test]
+    fn register_applies_hub_rate_limit() {
+        use crate::core::rate_limit::{BucketSpec, RateClass, RateDecision};
+        let cfg = RateLimitConfig {
+            message: BucketSpec { per_sec: 0, burst: 1 },
+            ..RateLimitConfig::default()
+        };
+        let hub  = UserHub::with_rate_limit(cfg);
+        let user = hub.register("alice", make_tx(), 100);
+        let mut rl = user.rate_limiter.lock().unwrap();
+        assert_eq!(rl.check(RateClass::Message, 1_000), RateDecision::Allow);
+        assert_eq!(rl.check(RateClass::Message, 1_000), RateDecision::Throttle);
+    }
+
     #[test]
     fn find_zombies_fresh_users_empty() {
         let hub = UserHub::new();
@@ -181,4 +230,17 @@ mod tests {
         let zombies = hub.find_zombies(60_000);
         assert!(zombies.is_empty());
     }
+
+    #[test]
+    fn register_service_flags_user() {
+        use crate::core::service_account::{ApiScope, ServiceAccountHub};
+        let accounts = ServiceAccountHub::new();
+        let (acct, _) = accounts.create("cad-bot", vec![ApiScope::PostMessage]).unwrap();
+        let hub = UserHub::new();
+        hub.register("alice", make_tx(), 100);
+        hub.register_service(acct, make_tx(), 100);
+        assert!(hub.is_service("cad-bot"));
+        assert!(!hub.is_service("alice"));
+        assert!(!hub.is_service("nobody"));
+    }
 }
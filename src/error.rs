@@ -11,6 +11,8 @@ pub enum LiveError {
     InvalidToken,
     InvalidOpcode(u8),
     InvalidPayload(String),
+    RateLimited(String),
+    ScopeDenied(String),
 
     // 2xxx: 채널
     ChannelNotFound(String),
@@ -23,6 +25,25 @@ pub enum LiveError {
     EmptyMessage,
     MessageTooLong(usize),
     MessageNotInChannel(String),
+    MessageRejected(String),
+
+    // 4xxx: Floor Control (FLOOR_DENY code)
+    FloorNotMember(String),
+    FloorWrongMode(String),
+    FloorPriorityNotAllowed(String),
+    FloorQueueFull(String),
+    FloorQueueDisabled(String),
+    FloorCooldown(String),
+    FloorQueueExpired(String),
+    FloorQueueCleared(String),
+    FloorBroadcastActive(String),
+    FloorBroadcastNotAllowed(String),
+
+    // 5xxx: 1:1 개별 호출
+    CallNotFound(String),
+    CallBusy(String),
+    CallUserOffline(String),
+    CallInvalidState(String),
 
     // 9xxx: 서버 내부
     InternalError(String),
@@ -36,6 +57,8 @@ impl fmt::Display for LiveError {
             LiveError::InvalidToken             => write!(f, "Invalid or expired token"),
             LiveError::InvalidOpcode(op)        => write!(f, "Unknown opcode: {}", op),
             LiveError::InvalidPayload(msg)      => write!(f, "Invalid payload: {}", msg),
+            LiveError::RateLimited(class)       => write!(f, "Rate limit exceeded: {}", class),
+            LiveError::ScopeDenied(scope)       => write!(f, "Service account lacks scope: {}", scope),
 
             LiveError::ChannelNotFound(id)      => write!(f, "Channel not found: {}", id),
             LiveError::ChannelFull(id)          => write!(f, "Channel is full: {}", id),
@@ -46,6 +69,23 @@ impl fmt::Display for LiveError {
             LiveError::EmptyMessage             => write!(f, "Message content is empty"),
             LiveError::MessageTooLong(len)      => write!(f, "Message too long: {} chars", len),
             LiveError::MessageNotInChannel(id)  => write!(f, "Must join channel before messaging: {}", id),
+            LiveError::MessageRejected(reason)  => write!(f, "Message rejected: {}", reason),
+
+            LiveError::FloorNotMember(id)       => write!(f, "Must join channel before requesting floor: {}", id),
+            LiveError::FloorWrongMode(id)       => write!(f, "Floor control requires PTT channel: {}", id),
+            LiveError::FloorPriorityNotAllowed(id) => write!(f, "Requested floor priority not allowed: {}", id),
+            LiveError::FloorQueueFull(id)       => write!(f, "Floor queue is full: {}", id),
+            LiveError::FloorQueueDisabled(id)   => write!(f, "Floor queue is disabled: {}", id),
+            LiveError::FloorCooldown(id)        => write!(f, "Floor request in cooldown: {}", id),
+            LiveError::FloorQueueExpired(id)    => write!(f, "Floor queue entry expired: {}", id),
+            LiveError::FloorQueueCleared(id)    => write!(f, "Floor queue entry removed: {}", id),
+            LiveError::FloorBroadcastActive(id) => write!(f, "Broadcast call in progress: {}", id),
+            LiveError::FloorBroadcastNotAllowed(id) => write!(f, "Broadcast call not allowed for this priority: {}", id),
+
+            LiveError::CallNotFound(id)         => write!(f, "Call not found: {}", id),
+            LiveError::CallBusy(user)           => write!(f, "User is busy in another call: {}", user),
+            LiveError::CallUserOffline(user)    => write!(f, "User is not online: {}", user),
+            LiveError::CallInvalidState(id)     => write!(f, "Invalid call state for this operation: {}", id),
 
             LiveError::InternalError(msg)       => write!(f, "Internal server error: {}", msg),
             LiveError::IoError(err)             => write!(f, "I/O error: {}", err),
@@ -63,6 +103,8 @@ impl LiveError {
             LiveError::InvalidToken            => 1001,
             LiveError::InvalidOpcode(_)        => 1003,
             LiveError::InvalidPayload(_)       => 1004,
+            LiveError::RateLimited(_)          => 1005,
+            LiveError::ScopeDenied(_)          => 1006,
 
             // 2xxx: 채널
             LiveError::ChannelNotFound(_)      => 2000,
@@ -75,6 +117,25 @@ impl LiveError {
             LiveError::EmptyMessage            => 3000,
             LiveError::MessageTooLong(_)       => 3001,
             LiveError::MessageNotInChannel(_)  => 3002,
+            LiveError::MessageRejected(_)      => 3003,
+
+            // 4xxx: Floor Control
+            LiveError::FloorNotMember(_)       => 4000,
+            LiveError::FloorWrongMode(_)       => 4001,
+            LiveError::FloorPriorityNotAllowed(_) => 4002,
+            LiveError::FloorQueueFull(_)       => 4003,
+            LiveError::FloorQueueDisabled(_)   => 4004,
+            LiveError::FloorCooldown(_)        => 4005,
+            LiveError::FloorQueueExpired(_)    => 4006,
+            LiveError::FloorQueueCleared(_)    => 4007,
+            LiveError::FloorBroadcastActive(_) => 4008,
+            LiveError::FloorBroadcastNotAllowed(_) => 4009,
+
+            // 5xxx: 1:1 개별 호출
+            LiveError::CallNotFound(_)         => 5000,
+            LiveError::CallBusy(_)             => 5001,
+            LiveError::CallUserOffline(_)      => 5002,
+            LiveError::CallInvalidState(_)     => 5003,
 
             // 9xxx: 서버 내부
             LiveError::InternalError(_)
@@ -95,6 +156,8 @@ mod tests {
         assert_eq!(LiveError::InvalidToken.code(), 1001);
         assert_eq!(LiveError::InvalidOpcode(99).code(), 1003);
         assert_eq!(LiveError::InvalidPayload("x".into()).code(), 1004);
+        assert_eq!(LiveError::RateLimited("message".into()).code(), 1005);
+        assert_eq!(LiveError::ScopeDenied("post-message".into()).code(), 1006);
     }
 
     #[test]
@@ -111,6 +174,29 @@ mod tests {
         assert_eq!(LiveError::EmptyMessage.code(), 3000);
         assert_eq!(LiveError::MessageTooLong(9999).code(), 3001);
         assert_eq!(LiveError::MessageNotInChannel("c".into()).code(), 3002);
+        assert_eq!(LiveError::MessageRejected("r".into()).code(), 3003);
+    }
+
+    #[test]
+    fn error_codes_4xxx_floor() {
+        assert_eq!(LiveError::FloorNotMember("c".into()).code(), 4000);
+        assert_eq!(LiveError::FloorWrongMode("c".into()).code(), 4001);
+        assert_eq!(LiveError::FloorPriorityNotAllowed("c".into()).code(), 4002);
+        assert_eq!(LiveError::FloorQueueFull("c".into()).code(), 4003);
+        assert_eq!(LiveError::FloorQueueDisabled("c".into()).code(), 4004);
+        assert_eq!(LiveError::FloorCooldown("c".into()).code(), 4005);
+        assert_eq!(LiveError::FloorQueueExpired("c".into()).code(), 4006);
+        assert_eq!(LiveError::FloorQueueCleared("c".into()).code(), 4007);
+        assert_eq!(LiveError::FloorBroadcastActive("c".into()).code(), 4008);
+        assert_eq!(LiveError::FloorBroadcastNotAllowed("c".into()).code(), 4009);
+    }
+
+    #[test]
+    fn error_codes_5xxx_call() {
+        assert_eq!(LiveError::CallNotFound("c".into()).code(), 5000);
+        assert_eq!(LiveError::CallBusy("u".into()).code(), 5001);
+        assert_eq!(LiveError::CallUserOffline("u".into()).code(), 5002);
+        assert_eq!(LiveError::CallInvalidState("c".into()).code(), 5003);
     }
 
     #[test]
@@ -133,6 +219,7 @@ mod tests {
             LiveError::InvalidToken.code(),
             LiveError::InvalidOpcode(0).code(),
             LiveError::InvalidPayload(String::new()).code(),
+            LiveError::RateLimited(String::new()).code(),
             LiveError::ChannelNotFound(String::new()).code(),
             LiveError::ChannelFull(String::new()).code(),
             LiveError::ChannelAccessDenied(String::new()).code(),
@@ -141,12 +228,21 @@ mod tests {
             LiveError::EmptyMessage.code(),
             LiveError::MessageTooLong(0).code(),
             LiveError::MessageNotInChannel(String::new()).code(),
+            LiveError::MessageRejected(String::new()).code(),
+            LiveError::FloorNotMember(String::new()).code(),
+            LiveError::FloorQueueCleared(String::new()).code(),
+            LiveError::FloorBroadcastActive(String::new()).code(),
+            LiveError::FloorBroadcastNotAllowed(String::new()).code(),
+            LiveError::CallNotFound(String::new()).code(),
+            LiveError::CallInvalidState(String::new()).code(),
             LiveError::InternalError(String::new()).code(),
         ];
         for &c in &codes {
             let range_ok = (1000..2000).contains(&c)
                 || (2000..3000).contains(&c)
                 || (3000..4000).contains(&c)
+                || (4000..5000).contains(&c)
+                || (5000..6000).contains(&c)
                 || (9000..10000).contains(&c);
             assert!(range_ok, "code {} out of defined ranges", c);
         }
@@ -0,0 +1,297 @@
+// author: kodeholic (powered by Claude)
+//
+// MessageFilter — 채팅 메시지 브로드캐스트 전 콘텐츠 정책 훅
+//
+// 구조:
+//   handle_message_create()
+//       └── FilterChain::apply(ctx, content)
+//               ├── filter #1 → Pass | Rewrite(content') | Reject(reason)
+//               ├── filter #2 (Rewrite 결과를 이어 받음)
+//               └── ...        Reject가 나오면 즉시 중단
+//
+// 금칙어, 개인정보 마스킹, 역할별 길이 제한 등은 MessageFilter 구현체로 추가.
+// 내장 구현: WordListFilter (파일 기반 금칙어 목록, mask | reject)
+
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+
+// ----------------------------------------------------------------------------
+// [MessageFilter] 트레이트
+// ----------------------------------------------------------------------------
+
+/// 필터 판정에 필요한 메시지 문맥
+#[derive(Debug, Clone)]
+pub struct MessageContext<'a> {
+    pub channel_id: &'a str,
+    pub author_id:  &'a str,
+    pub priority:   u8,      // 발신자 Floor 우선순위 (역할 대용)
+}
+
+/// 필터 판정 결과
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterOutcome {
+    Pass,
+    Rewrite { content: String, reason: String },
+    Reject  { reason: String },
+}
+
+impl FilterOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FilterOutcome::Pass           => "pass",
+            FilterOutcome::Rewrite { .. } => "rewrite",
+            FilterOutcome::Reject { .. }  => "reject",
+        }
+    }
+}
+
+pub trait MessageFilter: Send + Sync {
+    /// trace 표기용 필터 이름
+    fn name(&self) -> &str;
+
+    fn check(&self, ctx: &MessageContext, content: &str) -> FilterOutcome;
+}
+
+// ----------------------------------------------------------------------------
+// [FilterChain] 등록 순서대로 적용
+// ----------------------------------------------------------------------------
+
+/// 체인 적용 결과
+///   outcome: Rewrite면 최종 content, reason은 적용된 필터 사유를 ", "로 연결
+///   filter:  판정에 관여한 필터 이름 (Pass면 None)
+#[derive(Debug, Clone)]
+pub struct FilterVerdict {
+    pub outcome: FilterOutcome,
+    pub filter:  Option<String>,
+}
+
+#[derive(Default)]
+pub struct FilterChain {
+    filters: Vec<Arc<dyn MessageFilter>>,
+}
+
+impl FilterChain {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, filter: Arc<dyn MessageFilter>) {
+        self.filters.push(filter);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.filters.is_empty()
+    }
+
+    pub fn apply(&self, ctx: &MessageContext, content: &str) -> FilterVerdict {
+        let mut current: Option<String> = None;
+        let mut reasons: Vec<String>    = Vec::new();
+        let mut names:   Vec<String>    = Vec::new();
+
+        for f in &self.filters {
+            let text = current.as_deref().unwrap_or(content);
+            match f.check(ctx, text) {
+                FilterOutcome::Pass => {}
+                FilterOutcome::Rewrite { content, reason } => {
+                    current = Some(content);
+                    reasons.push(reason);
+                    names.push(f.name().to_string());
+                }
+                FilterOutcome::Reject { reason } => {
+                    return FilterVerdict {
+                        outcome: FilterOutcome::Reject { reason },
+                        filter:  Some(f.name().to_string()),
+                    };
+                }
+            }
+        }
+
+        match current {
+            Some(content) => FilterVerdict {
+                outcome: FilterOutcome::Rewrite { content, reason: reasons.join(", ") },
+                filter:  Some(names.join(",")),
+            },
+            None => FilterVerdict { outcome: FilterOutcome::Pass, filter: None },
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// [WordListFilter] 내장 금칙어 필터
+// ----------------------------------------------------------------------------
+
+/// 금칙어 발견 시 처리 방식
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WordListAction {
+    Mask,    // 해당 부분을 '*'로 치환 후 전송
+    Reject,  // 메시지 전체 거부
+}
+
+impl FromStr for WordListAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "mask"   => Ok(WordListAction::Mask),
+            "reject" => Ok(WordListAction::Reject),
+            other    => Err(format!("'{}' — mask | reject 중 하나", other)),
+        }
+    }
+}
+
+/// 대소문자 무시 부분 문자열 매칭 (한글 등 띄어쓰기 없는 금칙어 대응)
+pub struct WordListFilter {
+    words:  Vec<Vec<char>>,  // 소문자 정규화된 금칙어
+    action: WordListAction,
+}
+
+impl WordListFilter {
+    pub fn new(words: impl IntoIterator<Item = String>, action: WordListAction) -> Self {
+        let words = words.into_iter()
+            .map(|w| w.trim().to_string())
+            .filter(|w| !w.is_empty())
+            .map(|w| w.chars().map(fold_char).collect())
+            .collect();
+        Self { words, action }
+    }
+
+    /// 파일 포맷: 한 줄에 금칙어 1개, 빈 줄 / '#' 주석 무시
+    pub fn from_file(path: &Path, action: WordListAction) -> std::io::Result<Self> {
+        let text  = std::fs::read_to_string(path)?;
+        let words = text.lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty() && !l.starts_with('#'))
+            .map(str::to_string);
+        Ok(Self::new(words, action))
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// 매칭된 문자 위치 마스크 반환 (매칭 없으면 None)
+    fn find_matches(&self, chars: &[char]) -> Option<Vec<bool>> {
+        let folded: Vec<char> = chars.iter().map(|&c| fold_char(c)).collect();
+        let mut hit  = vec![false; chars.len()];
+        let mut any  = false;
+
+        for word in &self.words {
+            if word.len() > folded.len() { continue; }
+            for start in 0..=folded.len() - word.len() {
+                if folded[start..start + word.len()] == word[..] {
+                    hit[start..start + word.len()].iter_mut().for_each(|h| *h = true);
+                    any = true;
+                }
+            }
+        }
+
+        any.then_some(hit)
+    }
+}
+
+impl MessageFilter for WordListFilter {
+    fn name(&self) -> &str {
+        "word_list"
+    }
+
+    fn check(&self, _ctx: &MessageContext, content: &str) -> FilterOutcome {
+        let chars: Vec<char> = content.chars().collect();
+        let hit = match self.find_matches(&chars) {
+            Some(h) => h,
+            None    => return FilterOutcome::Pass,
+        };
+
+        match self.action {
+            WordListAction::Reject => FilterOutcome::Reject {
+                reason: "banned word".to_string(),
+            },
+            WordListAction::Mask => FilterOutcome::Rewrite {
+                content: chars.iter().zip(&hit)
+                    .map(|(&c, &h)| if h { '*' } else { c })
+                    .collect(),
+                reason: "banned word masked".to_string(),
+            },
+        }
+    }
+}
+
+fn fold_char(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx() -> MessageContext<'static> {
+        MessageContext { channel_id: "CH_0001", author_id: "alice", priority: 100 }
+    }
+
+    fn words(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn word_list_pass_when_clean() {
+        let f = WordListFilter::new(words(&["badword"]), WordListAction::Mask);
+        assert_eq!(f.check(&ctx(), "hello world"), FilterOutcome::Pass);
+    }
+
+    #[test]
+    fn word_list_masks_case_insensitive() {
+        let f = WordListFilter::new(words(&["bad"]), WordListAction::Mask);
+        match f.check(&ctx(), "so BAD news") {
+            FilterOutcome::Rewrite { content, .. } => assert_eq!(content, "so *** news"),
+            other => panic!("expected rewrite, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn word_list_masks_korean_substring() {
+        let f = WordListFilter::new(words(&["바보"]), WordListAction::Mask);
+        match f.check(&ctx(), "너는바보야") {
+            FilterOutcome::Rewrite { content, .. } => assert_eq!(content, "너는**야"),
+            other => panic!("expected rewrite, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn word_list_reject_mode() {
+        let f = WordListFilter::new(words(&["bad"]), WordListAction::Reject);
+        assert!(matches!(f.check(&ctx(), "bad"), FilterOutcome::Reject { .. }));
+    }
+
+    #[test]
+    fn chain_feeds_rewrite_and_stops_on_reject() {
+        let mut chain = FilterChain::new();
+        chain.add(Arc::new(WordListFilter::new(words(&["foo"]), WordListAction::Mask)));
+        chain.add(Arc::new(WordListFilter::new(words(&["bar"]), WordListAction::Reject)));
+
+        let v = chain.apply(&ctx(), "foo baz");
+        assert_eq!(v.outcome.as_str(), "rewrite");
+        assert!(matches!(v.outcome, FilterOutcome::Rewrite { ref content, .. } if content == "*** baz"));
+
+        let v = chain.apply(&ctx(), "foo bar");
+        assert_eq!(v.outcome.as_str(), "reject");
+        assert_eq!(v.filter.as_deref(), Some("word_list"));
+    }
+
+    #[test]
+    fn empty_chain_passes() {
+        let chain = FilterChain::new();
+        assert!(chain.is_empty());
+        assert_eq!(chain.apply(&ctx(), "anything").outcome, FilterOutcome::Pass);
+    }
+
+    #[test]
+    fn word_list_action_parse() {
+        assert_eq!("mask".parse::<WordListAction>().unwrap(), WordListAction::Mask);
+        assert_eq!("REJECT".parse::<WordListAction>().unwrap(), WordListAction::Reject);
+        assert!("drop".parse::<WordListAction>().is_err());
+    }
+}
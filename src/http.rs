@@ -2,6 +2,7 @@
 // HTTP REST API 모듈
 
 pub mod admin;
+pub mod auth;
 pub mod channel;
 pub mod dto;
 pub mod state;
@@ -16,6 +17,10 @@ pub use admin::{
     admin_status, admin_list_users, admin_get_user,
     admin_list_channels, admin_get_channel,
     admin_list_peers, admin_get_peer,
-    admin_floor_revoke,
+    admin_floor_revoke, admin_floor_grant, admin_get_floor_policy, admin_put_floor_policy,
+    admin_get_floor_queue, admin_reorder_floor_queue, admin_clear_floor_queue, admin_remove_floor_queue_entry,
+    admin_clear_emergency, admin_get_floor_history,
+    admin_post_channel_message, admin_post_broadcast_message,
+    admin_list_service_accounts, admin_create_service_account, admin_delete_service_account,
 };
 pub use trace::trace_stream;
@@ -10,22 +10,48 @@
 //   GET /admin/peers                   → Endpoint 전체 목록
 //   GET /admin/peers/{ufrag}           → Endpoint 상세
 //
-// 조작
-//   POST /admin/floor-revoke/{channel_id} → Floor 강제 revoke
+// 조작 (manage-channels scope 키 또는 admin 자격 필요 — auth::authorize_manage)
+//   POST /admin/floor-revoke/{channel_id}      → Floor 강제 revoke
+//   POST /admin/floor-grant/{channel_id}       → 지정 사용자에게 Floor 부여 (body: user_id, priority?, indicator?)
+//   GET  /admin/channels/{channel_id}/floor-policy → 채널 Floor 정책 조회
+//   PUT  /admin/channels/{channel_id}/floor-policy → 채널 Floor 정책 교체 (생략 필드는 기본값, virtual_talker 변경은 빈 채널만)
+//   GET    /admin/channels/{channel_id}/floor-queue           → 대기열 조회
+//   PUT    /admin/channels/{channel_id}/floor-queue           → 대기열 순서 변경
+//   DELETE /admin/channels/{channel_id}/floor-queue           → 대기열 초기화 (대기자 FLOOR_DENY)
+//   DELETE /admin/channels/{channel_id}/floor-queue/{user_id} → 대기자 1명 제거
+//   DELETE /admin/channels/{channel_id}/emergency → 긴급 상태 해제 (Dispatcher)
+//   GET  /admin/channels/{channel_id}/floor-history?user=&since=&limit= → talk-burst 이력 (최신순)
+//   POST /admin/channels/{channel_id}/messages → 채널에 메시지 주입 (system/bot author)
+//   POST /admin/messages                       → 전체 채널 공지
+//
+// 서비스 계정 (admin key 또는 admin scope 키 필요 — auth::authorize_admin)
+//   GET    /admin/service-accounts      → 목록 (키 해시/평문 미노출)
+//   POST   /admin/service-accounts      → 생성 + API 키 발급 (평문 키는 응답에서 1회만)
+//   DELETE /admin/service-accounts/{id} → 삭제 (키 폐기, 접속 중이면 다음 요청 시 종료)
 
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
     response::IntoResponse,
     Json,
 };
 use std::sync::atomic::Ordering;
 
-use crate::core::FloorControlState;
+use crate::config;
+use crate::core::{
+    ApiScope, BurstEnd, Channel, EmergencySource, Endpoint, FloorControl, FloorControlState, FloorDenyReason,
+    FloorIndicator, FloorPolicy,
+};
+use crate::protocol::{broadcast_message, is_reserved_author};
+use crate::protocol::emergency;
+use crate::protocol::floor::{dispatch_packets, end_burst, grant_packets, queue_deny_packets, queue_pos_packets, request_holder_keyframe};
 use crate::protocol::message::{FloorIdlePayload, FloorRevokePayload, GatewayPacket};
 use crate::protocol::opcode::server;
+use crate::trace::{TraceDir, TraceEvent};
 use crate::utils::current_timestamp;
+use crate::webhook::WebhookEvent;
 
+use super::auth::{authorize, authorize_admin, authorize_manage};
 use super::dto::*;
 use super::state::HttpState;
 
@@ -33,10 +59,33 @@ use super::state::HttpState;
 // [유틸]
 // ----------------------------------------------------------------------------
 
-fn floor_state_str(state: &FloorControlState) -> String {
-    match state {
-        FloorControlState::Idle  => "idle".to_string(),
-        FloorControlState::Taken => "taken".to_string(),
+fn queue_entries(floor: &FloorControl, now: u64) -> Vec<AdminQueueEntry> {
+    floor.queue.iter()
+        .map(|e| AdminQueueEntry {
+            user_id:   e.user_id.clone(),
+            priority:  e.priority,
+            queued_at: e.queued_at,
+            wait_secs: now.saturating_sub(e.queued_at) / 1000,
+            pinned:    e.pinned,
+        })
+        .collect()
+}
+
+fn admin_denied((code, msg): (StatusCode, String)) -> axum::response::Response {
+    (code, Json(serde_json::json!({ "error": msg }))).into_response()
+}
+
+fn channel_not_found(channel_id: &str) -> axum::response::Response {
+    (StatusCode::NOT_FOUND, Json(serde_json::json!({
+        "error": format!("Channel not found: {}", channel_id)
+    }))).into_response()
+}
+
+fn floor_state_str(floor: &FloorControl) -> String {
+    match floor.state {
+        FloorControlState::Idle                         => "idle".to_string(),
+        FloorControlState::Taken if floor.is_suspended() => "suspended".to_string(),
+        FloorControlState::Taken                        => "taken".to_string(),
     }
 }
 
@@ -68,6 +117,7 @@ pub async fn admin_list_users(State(state): State<HttpState>) -> impl IntoRespon
             AdminUserSummary {
                 user_id:      uid,
                 priority:     user.priority,
+                service:      user.is_service(),
                 last_seen_ms,
                 idle_secs:    now.saturating_sub(last_seen_ms) / 1000,
             }
@@ -100,12 +150,29 @@ pub async fn admin_get_user(
         .map(|ch| ch.channel_id.clone())
         .collect();
 
+    let (rate_violations, rate_limits) = {
+        let rl = user.rate_limiter.lock().unwrap();
+        let buckets: Vec<AdminRateBucket> = rl.stats()
+            .into_iter()
+            .map(|b| AdminRateBucket {
+                class:     b.class.to_string(),
+                tokens:    b.tokens,
+                allowed:   b.allowed,
+                throttled: b.throttled,
+            })
+            .collect();
+        (rl.total_violations(), buckets)
+    };
+
     Json(AdminUserDetail {
         user_id,
         priority: user.priority,
+        service:  user.is_service(),
         last_seen_ms,
         idle_secs: now.saturating_sub(last_seen_ms) / 1000,
         channels,
+        rate_violations,
+        rate_limits,
     }).into_response()
 }
 
@@ -122,9 +189,10 @@ pub async fn admin_list_channels(State(state): State<HttpState>) -> impl IntoRes
                 mode:         ch.mode.to_string(),
                 member_count: ch.member_count(),
                 capacity:     ch.capacity,
-                floor_state:  floor_state_str(&floor.state),
+                floor_state:  floor_state_str(&floor),
                 floor_holder: floor.floor_taken_by.clone(),
                 queue_len:    floor.queue.len(),
+                emergency:    ch.emergency.lock().unwrap().is_some(),
             }
         })
         .collect();
@@ -145,23 +213,22 @@ pub async fn admin_get_channel(
     };
 
     let now = current_timestamp();
-    let (floor_state, floor_holder, floor_taken_secs, floor_priority, queue_len, queue_entries) = {
+    let (floor_state, floor_holder, floor_taken_secs, floor_priority, floor_indicator,
+         floor_elapsed_ms, floor_remaining_ms, floor_policy, queue_len, queue_entries) = {
         let floor = channel.floor.lock().unwrap();
+        let taken = floor.state == FloorControlState::Taken;
         let taken_secs = floor.floor_taken_at
             .map(|t| now.saturating_sub(t) / 1000);
-        let entries: Vec<AdminQueueEntry> = floor.queue.iter()
-            .map(|e| AdminQueueEntry {
-                user_id:   e.user_id.clone(),
-                priority:  e.priority,
-                queued_at: e.queued_at,
-                wait_secs: now.saturating_sub(e.queued_at) / 1000,
-            })
-            .collect();
+        let entries = queue_entries(&floor, now);
         (
-            floor_state_str(&floor.state),
+            floor_state_str(&floor),
             floor.floor_taken_by.clone(),
             taken_secs,
             floor.floor_priority,
+            taken.then(|| floor.floor_indicator.clone()),
+            floor.elapsed_ms(now),
+            floor.remaining_ms(now),
+            floor.policy.clone(),
             floor.queue.len(),
             entries,
         )
@@ -175,12 +242,14 @@ pub async fn admin_get_channel(
         .map(|ep| {
             let last = ep.last_seen.load(Ordering::Relaxed);
             AdminPeerSummary {
-                ufrag:      ep.ufrag.clone(),
-                user_id:    ep.user_id.clone(),
-                channel_id: ep.channel_id.clone(),
-                address:    ep.get_address().map(|a| a.to_string()),
-                idle_secs:  now.saturating_sub(last) / 1000,
-                srtp_ready: ep.inbound_srtp.lock().unwrap().is_ready(),
+                ufrag:       ep.ufrag.clone(),
+                user_id:     ep.user_id.clone(),
+                channel_id:  ep.channel_id.clone(),
+                address:     ep.get_address().map(|a| a.to_string()),
+                idle_secs:   now.saturating_sub(last) / 1000,
+                srtp_ready:  ep.inbound_srtp.lock().unwrap().is_ready(),
+                nack:        nack_stats(&ep),
+                audio_level: ep.audio_level(now),
             }
         })
         .collect();
@@ -197,8 +266,13 @@ pub async fn admin_get_channel(
         floor_holder,
         floor_taken_secs,
         floor_priority,
+        floor_indicator,
+        floor_elapsed_ms,
+        floor_remaining_ms,
+        floor_policy,
         queue_len,
         queue: queue_entries,
+        emergency: channel.emergency(),
         peers,
     }).into_response()
 }
@@ -212,12 +286,14 @@ pub async fn admin_list_peers(State(state): State<HttpState>) -> impl IntoRespon
         .map(|ep| {
             let last = ep.last_seen.load(Ordering::Relaxed);
             AdminPeerSummary {
-                ufrag:      ep.ufrag.clone(),
-                user_id:    ep.user_id.clone(),
-                channel_id: ep.channel_id.clone(),
-                address:    ep.get_address().map(|a| a.to_string()),
-                idle_secs:  now.saturating_sub(last) / 1000,
-                srtp_ready: ep.inbound_srtp.lock().unwrap().is_ready(),
+                ufrag:       ep.ufrag.clone(),
+                user_id:     ep.user_id.clone(),
+                channel_id:  ep.channel_id.clone(),
+                address:     ep.get_address().map(|a| a.to_string()),
+                idle_secs:   now.saturating_sub(last) / 1000,
+                srtp_ready:  ep.inbound_srtp.lock().unwrap().is_ready(),
+                nack:        nack_stats(&ep),
+                audio_level: ep.audio_level(now),
             }
         })
         .collect();
@@ -245,26 +321,41 @@ pub async fn admin_get_peer(
         .map(|t| AdminTrack {
             ssrc: t.ssrc,
             kind: format!("{:?}", t.kind).to_lowercase(),
+            rid:  t.rid.clone(),
         })
         .collect();
 
     Json(AdminPeerDetail {
-        ufrag:      ep.ufrag.clone(),
-        user_id:    ep.user_id.clone(),
-        channel_id: ep.channel_id.clone(),
-        address:    ep.get_address().map(|a| a.to_string()),
-        last_seen:  last,
-        idle_secs:  now.saturating_sub(last) / 1000,
+        ufrag:       ep.ufrag.clone(),
+        user_id:     ep.user_id.clone(),
+        channel_id:  ep.channel_id.clone(),
+        address:     ep.get_address().map(|a| a.to_string()),
+        last_seen:   last,
+        idle_secs:   now.saturating_sub(last) / 1000,
         srtp_ready,
+        nack:        nack_stats(&ep),
+        audio_level: ep.audio_level(now),
         tracks,
     }).into_response()
 }
 
+fn nack_stats(ep: &Endpoint) -> AdminNackStats {
+    AdminNackStats {
+        requested:   ep.nack_requested.load(Ordering::Relaxed),
+        retransmits: ep.rtx_sent.load(Ordering::Relaxed),
+        forwarded:   ep.nack_forwarded.load(Ordering::Relaxed),
+    }
+}
+
 /// POST /admin/floor-revoke/{channel_id}
 pub async fn admin_floor_revoke(
     State(state): State<HttpState>,
     Path(channel_id): Path<String>,
+    headers: HeaderMap,
 ) -> impl IntoResponse {
+    if let Err(e) = authorize_manage(&state, &headers) {
+        return admin_denied(e);
+    }
     let channel = match state.channel_hub.get(&channel_id) {
         Some(ch) => ch,
         None => return (StatusCode::NOT_FOUND, Json(serde_json::json!({
@@ -287,16 +378,22 @@ pub async fn admin_floor_revoke(
         }))).into_response();
     }
 
-    // Floor 강제 초기화
+    // Floor 강제 초기화 (holder에게는 T100 — Release 확인까지 REVOKE 재전송)
     {
         let mut floor = channel.floor.lock().unwrap();
         floor.queue.clear();
+        end_burst(&state.channel_hub, &channel_id, &mut floor, BurstEnd::Revoke, "admin_revoke", None);
         floor.clear_taken();
+        if let Some(ref holder_id) = holder {
+            floor.arm_t100(holder_id, "admin_revoke");
+        }
     }
+    state.channel_hub.wake_floor_timer();
 
     tracing::warn!("[admin] floor-revoke channel={} was_held_by={:?}", channel_id, holder);
 
-    // holder에게 FLOOR_REVOKE 전송
+    // holder에게 FLOOR_REVOKE, 전체 멤버에게 FLOOR_IDLE 전송
+    let mut packets = Vec::new();
     if let Some(ref holder_id) = holder {
         let revoke_json = serde_json::to_string(&GatewayPacket::new(
             server::FLOOR_REVOKE,
@@ -305,20 +402,22 @@ pub async fn admin_floor_revoke(
                 cause: "admin_revoke".to_string(),
             },
         )).unwrap_or_default();
-        if let Some(user) = state.user_hub.get(holder_id) {
-            let _ = user.tx.send(revoke_json).await;
-        }
+        packets.push((Some(holder_id.clone()), None, revoke_json));
     }
-
-    // 전체 멤버에게 FLOOR_IDLE 전송
     let idle_json = serde_json::to_string(&GatewayPacket::new(
         server::FLOOR_IDLE,
         FloorIdlePayload {
             channel_id: channel_id.clone(),
         },
     )).unwrap_or_default();
-    let members = channel.get_members();
-    state.user_hub.broadcast_to(&members, &idle_json, None).await;
+    packets.push((None, None, idle_json));
+    dispatch_packets(packets, &channel_id, &channel.get_members(), &state.user_hub, &state.media_peer_hub).await;
+
+    state.trace_hub.publish(TraceEvent::new(
+        TraceDir::Sys, Some(&channel_id), holder.as_deref(),
+        server::FLOOR_REVOKE, "FLOOR_REVOKE",
+        format!("cause=admin_revoke user={}", holder.as_deref().unwrap_or("-")),
+    ).with_webhook(WebhookEvent::FloorRevoked));
 
     Json(serde_json::json!({
         "ok": true,
@@ -326,3 +425,599 @@ pub async fn admin_floor_revoke(
         "revoked_from": holder
     })).into_response()
 }
+
+/// POST /admin/floor-grant/{channel_id}
+/// 지정 사용자에게 발언권 부여 — 기존 holder는 FLOOR_REVOKE(admin_grant), 대상이 대기 중이면 대기열에서 제거
+pub async fn admin_floor_grant(
+    State(state): State<HttpState>,
+    Path(channel_id): Path<String>,
+    headers: HeaderMap,
+    Json(req): Json<AdminFloorGrant>,
+) -> impl IntoResponse {
+    if let Err(e) = authorize_manage(&state, &headers) {
+        return admin_denied(e);
+    }
+    let Some(channel) = state.channel_hub.get(&channel_id) else {
+        return channel_not_found(&channel_id);
+    };
+    if !channel.is_ptt() {
+        return (StatusCode::CONFLICT, Json(serde_json::json!({
+            "error": "Channel is not in PTT mode",
+            "channel_id": channel_id
+        }))).into_response();
+    }
+    let members = channel.get_members();
+    if !members.contains(&req.user_id) {
+        return (StatusCode::NOT_FOUND, Json(serde_json::json!({
+            "error": format!("User not in channel: {}", req.user_id)
+        }))).into_response();
+    }
+
+    let priority = req.priority.unwrap_or_else(|| state.user_hub.get(&req.user_id)
+        .map(|u| u.priority)
+        .unwrap_or(config::FLOOR_PRIORITY_DEFAULT));
+    let indicator = req.indicator.unwrap_or(FloorIndicator::Normal);
+
+    let (old_holder, packets) = {
+        let mut floor = channel.floor.lock().unwrap();
+        let old_holder = floor.floor_taken_by.clone();
+        if old_holder.as_deref() == Some(req.user_id.as_str()) {
+            return (StatusCode::CONFLICT, Json(serde_json::json!({
+                "error": "User already holds the floor",
+                "channel_id": channel_id
+            }))).into_response();
+        }
+        if let Some(ref holder_id) = old_holder {
+            end_burst(&state.channel_hub, &channel_id, &mut floor, BurstEnd::Revoke, "admin_grant", Some(&req.user_id));
+            floor.arm_t100(holder_id, "admin_grant");
+        }
+        floor.remove_from_queue(&req.user_id);
+        floor.grant(req.user_id.clone(), priority, indicator.clone());
+        (old_holder, grant_packets(&channel_id, &floor))
+        // MutexGuard drop
+    };
+    state.channel_hub.wake_floor_timer();
+
+    tracing::warn!("[admin] floor-grant channel={} user={} was_held_by={:?}", channel_id, req.user_id, old_holder);
+
+    if let Some(ref holder_id) = old_holder {
+        let revoke_json = serde_json::to_string(&GatewayPacket::new(
+            server::FLOOR_REVOKE,
+            FloorRevokePayload {
+                channel_id: channel_id.clone(),
+                cause: "admin_grant".to_string(),
+            },
+        )).unwrap_or_default();
+        dispatch_packets(vec![(Some(holder_id.clone()), None, revoke_json)], &channel_id, &members, &state.user_hub, &state.media_peer_hub).await;
+        state.trace_hub.publish(TraceEvent::new(
+            TraceDir::Sys, Some(&channel_id), Some(holder_id),
+            server::FLOOR_REVOKE, "FLOOR_REVOKE",
+            format!("cause=admin_grant user={} by={}", holder_id, req.user_id),
+        ).with_webhook(WebhookEvent::FloorRevoked));
+    }
+    dispatch_packets(packets, &channel_id, &members, &state.user_hub, &state.media_peer_hub).await;
+    request_holder_keyframe(&state.media_peer_hub, &channel_id, &req.user_id).await;
+
+    state.trace_hub.publish(TraceEvent::new(
+        TraceDir::Sys, Some(&channel_id), Some(&req.user_id),
+        server::FLOOR_GRANTED, "FLOOR_GRANTED(ADMIN)",
+        format!("user={} priority={} indicator={:?}", req.user_id, priority, indicator),
+    ).with_webhook(WebhookEvent::FloorGranted));
+
+    // Emergency 부여 → 채널 긴급 상태 발령 (FLOOR_REQUEST와 동일)
+    if indicator == FloorIndicator::Emergency {
+        emergency::raise(&channel, &req.user_id, EmergencySource::FloorRequest, &state.user_hub, &state.trace_hub).await;
+    }
+
+    Json(serde_json::json!({
+        "ok": true,
+        "channel_id": channel_id,
+        "granted_to": req.user_id,
+        "revoked_from": old_holder
+    })).into_response()
+}
+
+/// GET /admin/channels/{channel_id}/floor-policy
+pub async fn admin_get_floor_policy(
+    State(state): State<HttpState>,
+    Path(channel_id): Path<String>,
+) -> impl IntoResponse {
+    match state.channel_hub.get(&channel_id) {
+        Some(ch) => Json(ch.floor_policy()).into_response(),
+        None => (StatusCode::NOT_FOUND, Json(serde_json::json!({
+            "error": format!("Channel not found: {}", channel_id)
+        }))).into_response(),
+    }
+}
+
+/// PUT /admin/channels/{channel_id}/floor-policy
+/// 진행 중인 발언에는 다음 타임아웃 체크부터 적용, 기존 대기열은 유지
+/// virtual_talker 변경은 멤버가 없는 채널만 (409)
+pub async fn admin_put_floor_policy(
+    State(state): State<HttpState>,
+    Path(channel_id): Path<String>,
+    headers: HeaderMap,
+    Json(policy): Json<FloorPolicy>,
+) -> impl IntoResponse {
+    if let Err(e) = authorize_manage(&state, &headers) {
+        return admin_denied(e);
+    }
+    let channel = match state.channel_hub.get(&channel_id) {
+        Some(ch) => ch,
+        None => return (StatusCode::NOT_FOUND, Json(serde_json::json!({
+            "error": format!("Channel not found: {}", channel_id)
+        }))).into_response(),
+    };
+    if let Err(e) = policy.validate() {
+        return (StatusCode::BAD_REQUEST, Json(serde_json::json!({
+            "error": format!("Invalid floor policy: {}", e)
+        }))).into_response();
+    }
+
+    if let Err(e) = channel.set_floor_policy(policy.clone()) {
+        return (StatusCode::CONFLICT, Json(serde_json::json!({
+            "error": e,
+            "channel_id": channel_id
+        }))).into_response();
+    }
+    tracing::info!("[admin] floor-policy channel={} {:?}", channel_id, policy);
+    state.channel_hub.wake_floor_timer();
+    Json(policy).into_response()
+}
+
+/// GET /admin/channels/{channel_id}/floor-queue
+pub async fn admin_get_floor_queue(
+    State(state): State<HttpState>,
+    Path(channel_id): Path<String>,
+) -> impl IntoResponse {
+    match state.channel_hub.get(&channel_id) {
+        Some(ch) => Json(queue_entries(&ch.floor.lock().unwrap(), current_timestamp())).into_response(),
+        None     => channel_not_found(&channel_id),
+    }
+}
+
+/// GET /admin/channels/{channel_id}/floor-history — 진행 중 발언 + 종료된 talk-burst (최신순)
+pub async fn admin_get_floor_history(
+    State(state): State<HttpState>,
+    Path(channel_id): Path<String>,
+    Query(query): Query<AdminFloorHistoryQuery>,
+) -> impl IntoResponse {
+    let Some(channel) = state.channel_hub.get(&channel_id) else {
+        return channel_not_found(&channel_id);
+    };
+
+    let now   = current_timestamp();
+    let floor = channel.floor.lock().unwrap();
+    let current = match (&floor.state, &floor.floor_taken_by) {
+        (FloorControlState::Taken, Some(holder)) => Some(AdminCurrentBurst {
+            user_id:    holder.clone(),
+            priority:   floor.floor_priority,
+            indicator:  floor.floor_indicator.clone(),
+            granted_at: floor.floor_taken_at.unwrap_or(0),
+            elapsed_ms: floor.floor_taken_at.map(|t| now.saturating_sub(t)).unwrap_or(0),
+        }),
+        _ => None,
+    };
+    let bursts = floor.history.iter().rev()
+        .filter(|b| query.user.as_deref().is_none_or(|u| b.user_id == u))
+        .filter(|b| query.since.is_none_or(|s| b.granted_at >= s))
+        .take(query.limit.unwrap_or(usize::MAX))
+        .cloned()
+        .collect();
+
+    Json(AdminFloorHistory { channel_id, current, bursts }).into_response()
+}
+
+/// PUT /admin/channels/{channel_id}/floor-queue — 순서 변경 후 대기자 전원 순서 갱신
+pub async fn admin_reorder_floor_queue(
+    State(state): State<HttpState>,
+    Path(channel_id): Path<String>,
+    headers: HeaderMap,
+    Json(req): Json<AdminQueueReorder>,
+) -> impl IntoResponse {
+    if let Err(e) = authorize_manage(&state, &headers) {
+        return admin_denied(e);
+    }
+    let Some(channel) = state.channel_hub.get(&channel_id) else {
+        return channel_not_found(&channel_id);
+    };
+
+    let result = {
+        let mut floor = channel.floor.lock().unwrap();
+        floor.reorder_queue(&req.order)
+            .map(|_| (queue_entries(&floor, current_timestamp()), queue_pos_packets(&channel_id, &floor)))
+    };
+    let (entries, packets) = match result {
+        Ok(r)  => r,
+        Err(e) => return (StatusCode::BAD_REQUEST, Json(serde_json::json!({ "error": e }))).into_response(),
+    };
+
+    tracing::info!("[admin] floor-queue reorder channel={} order={:?}", channel_id, req.order);
+    dispatch_packets(packets, &channel_id, &channel.get_members(), &state.user_hub, &state.media_peer_hub).await;
+    Json(entries).into_response()
+}
+
+/// DELETE /admin/channels/{channel_id}/floor-queue — 전원 FLOOR_DENY(queue_cleared)
+pub async fn admin_clear_floor_queue(
+    State(state): State<HttpState>,
+    Path(channel_id): Path<String>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    if let Err(e) = authorize_manage(&state, &headers) {
+        return admin_denied(e);
+    }
+    let Some(channel) = state.channel_hub.get(&channel_id) else {
+        return channel_not_found(&channel_id);
+    };
+
+    let removed: Vec<String> = {
+        let mut floor = channel.floor.lock().unwrap();
+        floor.queue.drain(..).map(|e| e.user_id).collect()
+    };
+
+    tracing::warn!("[admin] floor-queue clear channel={} removed={:?}", channel_id, removed);
+    let packets = queue_deny_packets(&channel_id, &removed, FloorDenyReason::QueueCleared);
+    dispatch_packets(packets, &channel_id, &channel.get_members(), &state.user_hub, &state.media_peer_hub).await;
+    Json(serde_json::json!({ "ok": true, "channel_id": channel_id, "removed": removed })).into_response()
+}
+
+/// DELETE /admin/channels/{channel_id}/floor-queue/{user_id}
+/// 대상에게 FLOOR_DENY(queue_cleared), 남은 대기자 순서 갱신
+pub async fn admin_remove_floor_queue_entry(
+    State(state): State<HttpState>,
+    Path((channel_id, user_id)): Path<(String, String)>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    if let Err(e) = authorize_manage(&state, &headers) {
+        return admin_denied(e);
+    }
+    let Some(channel) = state.channel_hub.get(&channel_id) else {
+        return channel_not_found(&channel_id);
+    };
+
+    let packets = {
+        let mut floor = channel.floor.lock().unwrap();
+        if !floor.remove_from_queue(&user_id) {
+            None
+        } else {
+            let mut packets = queue_deny_packets(&channel_id, std::slice::from_ref(&user_id), FloorDenyReason::QueueCleared);
+            packets.extend(queue_pos_packets(&channel_id, &floor));
+            Some(packets)
+        }
+    };
+    let Some(packets) = packets else {
+        return (StatusCode::NOT_FOUND, Json(serde_json::json!({
+            "error": format!("Not in queue: {}", user_id)
+        }))).into_response();
+    };
+
+    tracing::warn!("[admin] floor-queue remove channel={} user={}", channel_id, user_id);
+    dispatch_packets(packets, &channel_id, &channel.get_members(), &state.user_hub, &state.media_peer_hub).await;
+    Json(serde_json::json!({ "ok": true, "channel_id": channel_id, "removed": [user_id] })).into_response()
+}
+
+/// DELETE /admin/channels/{channel_id}/emergency — 긴급 상태 해제, 멤버 전원 EMERGENCY_CLEARED
+pub async fn admin_clear_emergency(
+    State(state): State<HttpState>,
+    Path(channel_id): Path<String>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    if let Err(e) = authorize_manage(&state, &headers) {
+        return admin_denied(e);
+    }
+    let Some(channel) = state.channel_hub.get(&channel_id) else {
+        return channel_not_found(&channel_id);
+    };
+
+    match emergency::clear(&channel, "admin", &state.user_hub, &state.trace_hub).await {
+        Some(cleared) => Json(serde_json::json!({
+            "ok": true,
+            "channel_id": channel_id,
+            "raised_by": cleared.raised_by,
+            "acked_by": cleared.acked_by,
+        })).into_response(),
+        None => (StatusCode::CONFLICT, Json(serde_json::json!({
+            "error": "No active emergency",
+            "channel_id": channel_id
+        }))).into_response(),
+    }
+}
+
+// ----------------------------------------------------------------------------
+// [메시지 주입] — CAD / 경보 패널 등 WS 없이 채널에 텍스트 전달
+// 필터 체인은 적용하지 않음 (Admin 경로 = 신뢰 구간)
+// ----------------------------------------------------------------------------
+
+/// 요청 검증 → (author_id, content), 실패 시 400 에러 메시지
+fn validate_message(req: AdminMessageRequest) -> Result<(String, String), String> {
+    if req.content.trim().is_empty() {
+        return Err("Message content is empty".to_string());
+    }
+    if req.content.len() > config::MAX_MESSAGE_LENGTH {
+        return Err(format!(
+            "Message too long: {} bytes (max {})", req.content.len(), config::MAX_MESSAGE_LENGTH
+        ));
+    }
+    let author_id = req.author_id
+        .map(|a| a.trim().to_string())
+        .filter(|a| !a.is_empty())
+        .unwrap_or_else(|| config::SYSTEM_AUTHOR_ID.to_string());
+    Ok((author_id, req.content))
+}
+
+/// 키 인증(post-message) + 요청 검증
+/// 서비스 계정 키로 호출하면 author_id는 계정 id로 고정 (다른 id 지정 시 403)
+/// 키 없이(익명) 호출하면 author_id는 system / bot:* 만 허용 — 실사용자 사칭 방지 (403)
+fn authorize_message(
+    state:   &HttpState,
+    headers: &HeaderMap,
+    req:     AdminMessageRequest,
+) -> Result<(String, String), (StatusCode, String)> {
+    let account   = authorize(state, headers, ApiScope::PostMessage)?;
+    let requested = req.author_id.clone();
+    let (author_id, content) = validate_message(req)
+        .map_err(|msg| (StatusCode::BAD_REQUEST, msg))?;
+
+    match account {
+        None if !is_reserved_author(&author_id) => Err((StatusCode::FORBIDDEN, format!(
+            "Anonymous caller can post only as {} or {}<name>: {}",
+            config::SYSTEM_AUTHOR_ID, config::BOT_AUTHOR_PREFIX, author_id
+        ))),
+        None => Ok((author_id, content)),
+        Some(sa) => match requested.as_deref().map(str::trim).filter(|a| !a.is_empty()) {
+            Some(a) if a != sa.id => Err((StatusCode::FORBIDDEN, format!(
+                "Service account {} cannot post as {}", sa.id, a
+            ))),
+            _ => Ok((sa.id.clone(), content)),
+        },
+    }
+}
+
+async fn post_to_channel(
+    state:     &HttpState,
+    channel:   &Channel,
+    author_id: &str,
+    content:   String,
+) -> AdminMessageResult {
+    let members    = channel.get_members();
+    let message_id = broadcast_message(
+        &state.user_hub, &state.trace_hub, &channel.channel_id, &members, author_id, content, "admin",
+    ).await;
+    AdminMessageResult {
+        channel_id: channel.channel_id.clone(),
+        message_id,
+        recipients: members.len(),
+    }
+}
+
+/// POST /admin/channels/{channel_id}/messages
+pub async fn admin_post_channel_message(
+    State(state): State<HttpState>,
+    Path(channel_id): Path<String>,
+    headers: HeaderMap,
+    Json(req): Json<AdminMessageRequest>,
+) -> impl IntoResponse {
+    let (author_id, content) = match authorize_message(&state, &headers, req) {
+        Ok(v)    => v,
+        Err((code, msg)) => return (code, Json(serde_json::json!({
+            "error": msg
+        }))).into_response(),
+    };
+    let channel = match state.channel_hub.get(&channel_id) {
+        Some(ch) => ch,
+        None => return (StatusCode::NOT_FOUND, Json(serde_json::json!({
+            "error": format!("Channel not found: {}", channel_id)
+        }))).into_response(),
+    };
+
+    let result = post_to_channel(&state, &channel, &author_id, content).await;
+    tracing::info!("[admin] message channel={} author={} recipients={}",
+        channel_id, author_id, result.recipients);
+    Json(result).into_response()
+}
+
+/// POST /admin/messages — 전체 채널 공지
+pub async fn admin_post_broadcast_message(
+    State(state): State<HttpState>,
+    headers: HeaderMap,
+    Json(req): Json<AdminMessageRequest>,
+) -> impl IntoResponse {
+    let (author_id, content) = match authorize_message(&state, &headers, req) {
+        Ok(v)    => v,
+        Err((code, msg)) => return (code, Json(serde_json::json!({
+            "error": msg
+        }))).into_response(),
+    };
+
+    // 1:1 호출 채널은 공지 대상 아님
+    let mut channels: Vec<_> = {
+        state.channel_hub.channels.read().unwrap().values()
+            .filter(|ch| !ch.is_call())
+            .cloned()
+            .collect()
+    };
+    channels.sort_by(|a, b| a.freq.cmp(&b.freq));
+
+    let mut results = Vec::with_capacity(channels.len());
+    for channel in &channels {
+        results.push(post_to_channel(&state, channel, &author_id, content.clone()).await);
+    }
+
+    tracing::info!("[admin] broadcast message author={} channels={}", author_id, results.len());
+    Json(results).into_response()
+}
+
+// ----------------------------------------------------------------------------
+// [서비스 계정 관리]
+// ----------------------------------------------------------------------------
+
+/// GET /admin/service-accounts
+pub async fn admin_list_service_accounts(
+    State(state): State<HttpState>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    if let Err(e) = authorize_admin(&state, &headers) {
+        return admin_denied(e);
+    }
+    let list: Vec<AdminServiceAccount> = state.service_accounts.list()
+        .into_iter()
+        .map(|sa| {
+            let last_used = sa.last_used_ms.load(Ordering::Relaxed);
+            AdminServiceAccount {
+                id:           sa.id.clone(),
+                scopes:       sa.scopes.iter().map(|s| s.as_str().to_string()).collect(),
+                key_prefix:   sa.key_prefix.clone(),
+                created_at:   sa.created_at,
+                last_used_ms: (last_used > 0).then_some(last_used),
+                online:       state.user_hub.is_service(&sa.id),
+            }
+        })
+        .collect();
+    Json(list).into_response()
+}
+
+/// POST /admin/service-accounts
+pub async fn admin_create_service_account(
+    State(state): State<HttpState>,
+    headers: HeaderMap,
+    Json(req): Json<AdminServiceAccountCreate>,
+) -> impl IntoResponse {
+    if let Err(e) = authorize_admin(&state, &headers) {
+        return admin_denied(e);
+    }
+    let scopes: Result<Vec<ApiScope>, String> = req.scopes.iter().map(|s| s.parse()).collect();
+    let scopes = match scopes {
+        Ok(s)  => s,
+        Err(e) => return (StatusCode::BAD_REQUEST, Json(serde_json::json!({
+            "error": format!("Invalid scope: {}", e)
+        }))).into_response(),
+    };
+
+    // 접속 중인 일반 사용자 id와 충돌 방지
+    if state.user_hub.get(&req.id).is_some() {
+        return (StatusCode::CONFLICT, Json(serde_json::json!({
+            "error": format!("User id in use: {}", req.id)
+        }))).into_response();
+    }
+
+    match state.service_accounts.create(&req.id, scopes) {
+        Ok((sa, api_key)) => {
+            tracing::info!("[admin] service account created id={} scopes={:?}", sa.id, sa.scopes);
+            (StatusCode::CREATED, Json(AdminServiceAccountCreated {
+                id:         sa.id.clone(),
+                scopes:     sa.scopes.iter().map(|s| s.as_str().to_string()).collect(),
+                key_prefix: sa.key_prefix.clone(),
+                api_key,
+            })).into_response()
+        }
+        Err(e) => (StatusCode::CONFLICT, Json(serde_json::json!({
+            "error": e
+        }))).into_response(),
+    }
+}
+
+/// DELETE /admin/service-accounts/{id}
+pub async fn admin_delete_service_account(
+    State(state): State<HttpState>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    if let Err(e) = authorize_admin(&state, &headers) {
+        return admin_denied(e);
+    }
+    if !state.service_accounts.remove(&id) {
+        return (StatusCode::NOT_FOUND, Json(serde_json::json!({
+            "error": format!("Service account not found: {}", id)
+        }))).into_response();
+    }
+    tracing::warn!("[admin] service account deleted id={}", id);
+    Json(serde_json::json!({ "ok": true, "id": id })).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn req(content: &str, author_id: Option<&str>) -> AdminMessageRequest {
+        AdminMessageRequest { content: content.to_string(), author_id: author_id.map(str::to_string) }
+    }
+
+    #[test]
+    fn validate_message_defaults_author() {
+        let (author, content) = validate_message(req("fire alarm", None)).unwrap();
+        assert_eq!(author, config::SYSTEM_AUTHOR_ID);
+        assert_eq!(content, "fire alarm");
+
+        let (author, _) = validate_message(req("x", Some("  bot:cad "))).unwrap();
+        assert_eq!(author, "bot:cad");
+        let (author, _) = validate_message(req("x", Some(""))).unwrap();
+        assert_eq!(author, config::SYSTEM_AUTHOR_ID);
+    }
+
+    #[test]
+    fn validate_message_rejects_empty_and_long() {
+        assert!(validate_message(req("   ", None)).is_err());
+        assert!(validate_message(req(&"a".repeat(config::MAX_MESSAGE_LENGTH + 1), None)).is_err());
+    }
+
+    fn http_state(require_api_key: bool, admin_key: Option<&str>) -> HttpState {
+        use std::sync::Arc;
+        use crate::core::{ChannelHub, MediaPeerHub, ServiceAccountHub, UserHub};
+        use crate::trace::TraceHub;
+
+        HttpState::new(
+            Arc::new(UserHub::new()),
+            Arc::new(ChannelHub::new()),
+            Arc::new(MediaPeerHub::new()),
+            TraceHub::new(),
+            Arc::new(ServiceAccountHub::new()),
+            require_api_key,
+            admin_key.map(str::to_string),
+        )
+    }
+
+    #[test]
+    fn anonymous_message_author_restricted() {
+        let state = http_state(false, None);
+        let anon  = HeaderMap::new();
+
+        assert_eq!(authorize_message(&state, &anon, req("x", None)).unwrap().0, config::SYSTEM_AUTHOR_ID);
+        assert_eq!(authorize_message(&state, &anon, req("x", Some("bot:cad"))).unwrap().0, "bot:cad");
+        let (code, _) = authorize_message(&state, &anon, req("x", Some("alice"))).unwrap_err();
+        assert_eq!(code, StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn service_account_create_requires_admin_credential() {
+        let state = http_state(true, Some("admin-secret"));
+        let create = |headers: HeaderMap| admin_create_service_account(
+            State(state.clone()),
+            headers,
+            Json(AdminServiceAccountCreate { id: "cad-bot".into(), scopes: vec!["post-message".into()] }),
+        );
+
+        let resp = create(HeaderMap::new()).await.into_response();
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(state.service_accounts.count(), 0);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(axum::http::header::AUTHORIZATION, "Bearer admin-secret".parse().unwrap());
+        let resp = create(headers).await.into_response();
+        assert_eq!(resp.status(), StatusCode::CREATED);
+        assert!(state.service_accounts.contains("cad-bot"));
+    }
+
+    #[tokio::test]
+    async fn broadcast_skips_call_channels() {
+        let state = http_state(false, None);
+        state.channel_hub.create("CH_0001", "0001", "ops", crate::core::ChannelMode::PTT, 10);
+        state.channel_hub.create(&format!("{}000001", config::CALL_ID_PREFIX), "0000", "a ↔ b", crate::core::ChannelMode::PTT, 2);
+
+        let resp = admin_post_broadcast_message(State(state), HeaderMap::new(), Json(req("notice", None))).await.into_response();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+        let results: Vec<serde_json::Value> = serde_json::from_slice(&body).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0]["channel_id"], "CH_0001");
+    }
+}
@@ -0,0 +1,164 @@
+// author: kodeholic (powered by Claude)
+// 서비스 계정 API 키 인증 — Authorization: Bearer <lck_...>
+//
+//   헤더 있음 → 유효한 키 + 요구 scope 보유 시 통과 (401 / 403)
+//   헤더 없음 → --require-api-key 미지정 시 통과 (기존 동작 유지), 지정 시 401
+//
+// Admin Floor / 대기열 / 정책 / 긴급 해제 조작은 authorize_manage
+//   manage-channels scope 키, 또는 authorize_admin을 통과하는 admin 자격
+//
+// 서비스 계정 관리(/admin/service-accounts)는 별도 — authorize_admin
+//   LIVECHAT_ADMIN_KEY(환경변수) 또는 admin scope 키만 통과
+//   둘 다 없이 열린 서버(admin key 미설정 + --require-api-key 미지정)에서만 익명 허용
+
+use std::sync::Arc;
+
+use axum::http::{header, HeaderMap, StatusCode};
+
+use crate::core::{ApiScope, ServiceAccount};
+
+use super::state::HttpState;
+
+/// 인증 결과 — Ok(Some)=서비스 계정, Ok(None)=익명 허용, Err=(상태코드, 사유)
+pub fn authorize(
+    state:   &HttpState,
+    headers: &HeaderMap,
+    scope:   ApiScope,
+) -> Result<Option<Arc<ServiceAccount>>, (StatusCode, String)> {
+    let key = match headers.get(header::AUTHORIZATION) {
+        Some(v) => v.to_str().ok()
+            .and_then(|s| s.strip_prefix("Bearer "))
+            .map(str::trim)
+            .ok_or((StatusCode::UNAUTHORIZED, "Malformed Authorization header".to_string()))?,
+        None if state.require_api_key => {
+            return Err((StatusCode::UNAUTHORIZED, "API key required".to_string()));
+        }
+        None => return Ok(None),
+    };
+
+    let account = state.service_accounts.authenticate(key)
+        .ok_or((StatusCode::UNAUTHORIZED, "Invalid API key".to_string()))?;
+    if !account.has_scope(scope) {
+        return Err((StatusCode::FORBIDDEN, format!("Service account lacks scope: {}", scope.as_str())));
+    }
+    Ok(Some(account))
+}
+
+/// 서비스 계정 관리 인증 — admin key 또는 admin scope 서비스 계정 키
+pub fn authorize_admin(state: &HttpState, headers: &HeaderMap) -> Result<(), (StatusCode, String)> {
+    let locked = state.require_api_key || state.admin_key.is_some();
+    if !locked && !headers.contains_key(header::AUTHORIZATION) {
+        return Ok(());
+    }
+    let key = headers.get(header::AUTHORIZATION)
+        .ok_or((StatusCode::UNAUTHORIZED, "Admin credential required".to_string()))?
+        .to_str().ok()
+        .and_then(|s| s.strip_prefix("Bearer "))
+        .map(str::trim)
+        .ok_or((StatusCode::UNAUTHORIZED, "Malformed Authorization header".to_string()))?;
+
+    if state.admin_key.as_deref().is_some_and(|admin| constant_time_eq(admin, key)) {
+        return Ok(());
+    }
+    let account = state.service_accounts.authenticate(key)
+        .ok_or((StatusCode::UNAUTHORIZED, "Invalid API key".to_string()))?;
+    if !account.has_scope(ApiScope::Admin) {
+        return Err((StatusCode::FORBIDDEN, format!("Service account lacks scope: {}", ApiScope::Admin.as_str())));
+    }
+    Ok(())
+}
+
+/// 채널 / Floor 조작 인증 — manage-channels scope 키 또는 admin 자격(admin key / admin scope 키)
+pub fn authorize_manage(state: &HttpState, headers: &HeaderMap) -> Result<(), (StatusCode, String)> {
+    match authorize(state, headers, ApiScope::ManageChannels) {
+        Ok(_) => Ok(()),
+        Err(e) if headers.contains_key(header::AUTHORIZATION) => authorize_admin(state, headers).map_err(|_| e),
+        Err(e) => Err(e),
+    }
+}
+
+/// 길이 외 정보가 비교 시간으로 새지 않도록 전체 바이트 비교
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len() && a.bytes().zip(b.bytes()).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ChannelHub, MediaPeerHub, ServiceAccountHub, UserHub};
+    use crate::trace::TraceHub;
+
+    fn state(require_api_key: bool) -> HttpState {
+        state_with_admin(require_api_key, None)
+    }
+
+    fn state_with_admin(require_api_key: bool, admin_key: Option<&str>) -> HttpState {
+        HttpState::new(
+            Arc::new(UserHub::new()),
+            Arc::new(ChannelHub::new()),
+            Arc::new(MediaPeerHub::new()),
+            TraceHub::new(),
+            Arc::new(ServiceAccountHub::new()),
+            require_api_key,
+            admin_key.map(str::to_string),
+        )
+    }
+
+    fn bearer(key: &str) -> HeaderMap {
+        let mut h = HeaderMap::new();
+        h.insert(header::AUTHORIZATION, format!("Bearer {}", key).parse().unwrap());
+        h
+    }
+
+    #[test]
+    fn anonymous_depends_on_require_flag() {
+        assert!(matches!(authorize(&state(false), &HeaderMap::new(), ApiScope::ReadTrace), Ok(None)));
+        assert_eq!(
+            authorize(&state(true), &HeaderMap::new(), ApiScope::ReadTrace).unwrap_err().0,
+            StatusCode::UNAUTHORIZED,
+        );
+    }
+
+    #[test]
+    fn key_scope_checked() {
+        let st = state(true);
+        let (_, key) = st.service_accounts.create("bot", vec![ApiScope::PostMessage]).unwrap();
+        assert_eq!(authorize(&st, &bearer(&key), ApiScope::PostMessage).unwrap().unwrap().id, "bot");
+        assert_eq!(authorize(&st, &bearer(&key), ApiScope::ReadTrace).unwrap_err().0, StatusCode::FORBIDDEN);
+        assert_eq!(authorize(&st, &bearer("lck_nope"), ApiScope::PostMessage).unwrap_err().0, StatusCode::UNAUTHORIZED);
+    }
+
+    #[test]
+    fn admin_requires_admin_key_or_scope() {
+        // 열린 서버만 익명 허용
+        assert!(authorize_admin(&state(false), &HeaderMap::new()).is_ok());
+        assert_eq!(authorize_admin(&state(true), &HeaderMap::new()).unwrap_err().0, StatusCode::UNAUTHORIZED);
+
+        let st = state_with_admin(false, Some("s3cret-admin"));
+        assert_eq!(authorize_admin(&st, &HeaderMap::new()).unwrap_err().0, StatusCode::UNAUTHORIZED);
+        assert!(authorize_admin(&st, &bearer("s3cret-admin")).is_ok());
+        assert_eq!(authorize_admin(&st, &bearer("s3cret-admiN")).unwrap_err().0, StatusCode::UNAUTHORIZED);
+
+        // 일반 scope 키로는 관리 불가, admin scope 키는 허용
+        let (_, bot)   = st.service_accounts.create("bot", ApiScope::ALL.iter().copied().filter(|s| *s != ApiScope::Admin).collect()).unwrap();
+        let (_, admin) = st.service_accounts.create("ops", vec![ApiScope::Admin]).unwrap();
+        assert_eq!(authorize_admin(&st, &bearer(&bot)).unwrap_err().0, StatusCode::FORBIDDEN);
+        assert!(authorize_admin(&st, &bearer(&admin)).is_ok());
+    }
+
+    #[test]
+    fn manage_accepts_scope_or_admin_credential() {
+        assert!(authorize_manage(&state(false), &HeaderMap::new()).is_ok());
+        assert_eq!(authorize_manage(&state(true), &HeaderMap::new()).unwrap_err().0, StatusCode::UNAUTHORIZED);
+
+        let st = state_with_admin(true, Some("s3cret-admin"));
+        let (_, ops)   = st.service_accounts.create("ops", vec![ApiScope::ManageChannels]).unwrap();
+        let (_, root)  = st.service_accounts.create("root", vec![ApiScope::Admin]).unwrap();
+        let (_, bot)   = st.service_accounts.create("bot", vec![ApiScope::PostMessage]).unwrap();
+        assert!(authorize_manage(&st, &bearer(&ops)).is_ok());
+        assert!(authorize_manage(&st, &bearer(&root)).is_ok());
+        assert!(authorize_manage(&st, &bearer("s3cret-admin")).is_ok());
+        assert_eq!(authorize_manage(&st, &bearer(&bot)).unwrap_err().0, StatusCode::FORBIDDEN);
+        assert_eq!(authorize_manage(&st, &bearer("lck_nope")).unwrap_err().0, StatusCode::UNAUTHORIZED);
+    }
+}
@@ -18,6 +18,7 @@ pub async fn list_channels(State(state): State<HttpState>) -> impl IntoResponse
     let channels = state.channel_hub.channels.read().unwrap();
 
     let mut list: Vec<ChannelSummary> = channels.values()
+        .filter(|ch| !ch.is_call())
         .map(|ch| ChannelSummary {
             channel_id:   ch.channel_id.clone(),
             freq:         ch.freq.clone(),
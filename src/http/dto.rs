@@ -1,7 +1,9 @@
 // author: kodeholic (powered by Claude)
 // HTTP 응답 DTO — Admin / 일반 조회 공용
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+
+use crate::core::{EmergencyState, FloorIndicator, FloorPolicy, TalkBurst};
 
 // ----------------------------------------------------------------------------
 // [일반 조회]
@@ -58,6 +60,7 @@ pub struct ServerStatus {
 pub struct AdminUserSummary {
     pub user_id:      String,
     pub priority:     u8,
+    pub service:      bool,  // 서비스 계정 여부
     pub last_seen_ms: u64,   // Unix millis
     pub idle_secs:    u64,   // 마지막 heartbeat 이후 경과 초
 }
@@ -65,11 +68,23 @@ pub struct AdminUserSummary {
 /// GET /admin/users/{user_id}
 #[derive(Serialize)]
 pub struct AdminUserDetail {
-    pub user_id:      String,
-    pub priority:     u8,
-    pub last_seen_ms: u64,
-    pub idle_secs:    u64,
-    pub channels:     Vec<String>, // 소속 채널 id 목록
+    pub user_id:         String,
+    pub priority:        u8,
+    pub service:         bool,
+    pub last_seen_ms:    u64,
+    pub idle_secs:       u64,
+    pub channels:        Vec<String>, // 소속 채널 id 목록
+    pub rate_violations: u64,         // rate limit 위반 누적
+    pub rate_limits:     Vec<AdminRateBucket>,
+}
+
+/// rate limit 버킷 상태 (User 상세에 포함)
+#[derive(Serialize)]
+pub struct AdminRateBucket {
+    pub class:     String,   // "message" | "floor" | "query" | "channel"
+    pub tokens:    u32,      // 현재 잔여 토큰
+    pub allowed:   u64,
+    pub throttled: u64,
 }
 
 /// GET /admin/channels 아이템 (Floor 상태 포함)
@@ -81,28 +96,34 @@ pub struct AdminChannelSummary {
     pub mode:          String,
     pub member_count:  usize,
     pub capacity:      usize,
-    pub floor_state:   String,          // "idle" | "taken"
+    pub floor_state:   String,          // "idle" | "taken" | "suspended"
     pub floor_holder:  Option<String>,  // Taken 시 holder user_id
     pub queue_len:     usize,
+    pub emergency:     bool,            // 긴급 상태 발령 중
 }
 
 /// GET /admin/channels/{id}
 #[derive(Serialize)]
 pub struct AdminChannelDetail {
-    pub channel_id:       String,
-    pub freq:             String,
-    pub name:             String,
-    pub mode:             String,
-    pub capacity:         usize,
-    pub created_at:       u64,
-    pub members:          Vec<String>,
-    pub floor_state:      String,
-    pub floor_holder:     Option<String>,
-    pub floor_taken_secs: Option<u64>,  // Taken된 후 경과 초
-    pub floor_priority:   u8,
-    pub queue_len:        usize,
-    pub queue:            Vec<AdminQueueEntry>,
-    pub peers:            Vec<AdminPeerSummary>,
+    pub channel_id:         String,
+    pub freq:               String,
+    pub name:               String,
+    pub mode:               String,
+    pub capacity:           usize,
+    pub created_at:         u64,
+    pub members:            Vec<String>,
+    pub floor_state:        String,
+    pub floor_holder:       Option<String>,
+    pub floor_taken_secs:   Option<u64>,  // Taken된 후 경과 초
+    pub floor_priority:     u8,
+    pub floor_indicator:    Option<FloorIndicator>,  // Taken 시 현재 발언의 성격
+    pub floor_elapsed_ms:   Option<u64>,
+    pub floor_remaining_ms: Option<u64>,  // max_duration Revoke까지 (무제한이면 null)
+    pub floor_policy:       FloorPolicy,
+    pub queue_len:          usize,
+    pub queue:              Vec<AdminQueueEntry>,
+    pub emergency:          Option<EmergencyState>,
+    pub peers:              Vec<AdminPeerSummary>,
 }
 
 #[derive(Serialize)]
@@ -111,34 +132,133 @@ pub struct AdminQueueEntry {
     pub priority:   u8,
     pub queued_at:  u64,
     pub wait_secs:  u64,
+    pub pinned:     bool,   // Admin 재배치로 고정 (공정성 재정렬 제외)
 }
 
 /// GET /admin/peers 아이템
 #[derive(Serialize)]
 pub struct AdminPeerSummary {
-    pub ufrag:      String,
-    pub user_id:    String,
-    pub channel_id: String,
-    pub address:    Option<String>,
-    pub idle_secs:  u64,
-    pub srtp_ready: bool,
+    pub ufrag:       String,
+    pub user_id:     String,
+    pub channel_id:  String,
+    pub address:     Option<String>,
+    pub idle_secs:   u64,
+    pub srtp_ready:  bool,
+    pub nack:        AdminNackStats,
+    pub audio_level: Option<u8>,  // 평활 audio level(-dBov, 127 = 무음) — audio level 확장 미협상이면 None
+}
+
+/// NACK 재전송 카운터 (seq 단위, 엔드포인트가 수신자로서 보낸 NACK 기준)
+#[derive(Serialize)]
+pub struct AdminNackStats {
+    pub requested:   u64,  // NACK으로 요청한 seq 수
+    pub retransmits: u64,  // 서버 재전송 버퍼에서 응답한 패킷 수
+    pub forwarded:   u64,  // 버퍼 미스로 원 송신자에게 전달한 seq 수
 }
 
 /// GET /admin/peers/{ufrag}
 #[derive(Serialize)]
 pub struct AdminPeerDetail {
-    pub ufrag:      String,
-    pub user_id:    String,
-    pub channel_id: String,
-    pub address:    Option<String>,
-    pub last_seen:  u64,
-    pub idle_secs:  u64,
-    pub srtp_ready: bool,
-    pub tracks:     Vec<AdminTrack>,
+    pub ufrag:       String,
+    pub user_id:     String,
+    pub channel_id:  String,
+    pub address:     Option<String>,
+    pub last_seen:   u64,
+    pub idle_secs:   u64,
+    pub srtp_ready:  bool,
+    pub nack:        AdminNackStats,
+    pub audio_level: Option<u8>,
+    pub tracks:      Vec<AdminTrack>,
 }
 
 #[derive(Serialize)]
 pub struct AdminTrack {
     pub ssrc: u32,
     pub kind: String,
+    pub rid:  Option<String>,  // simulcast 레이어 rid
+}
+
+// ----------------------------------------------------------------------------
+// [Admin 요청]
+// ----------------------------------------------------------------------------
+
+/// POST /admin/channels/{id}/messages, POST /admin/messages 요청 body
+#[derive(Deserialize)]
+pub struct AdminMessageRequest {
+    pub content:   String,
+    pub author_id: Option<String>,  // 생략 시 config::SYSTEM_AUTHOR_ID
+}
+
+/// 메시지 주입 결과 (채널 단위)
+#[derive(Serialize)]
+pub struct AdminMessageResult {
+    pub channel_id: String,
+    pub message_id: String,
+    pub recipients: usize,          // MESSAGE_EVENT 수신 멤버 수
+}
+
+/// POST /admin/floor-grant/{channel_id} 요청 body
+#[derive(Deserialize)]
+pub struct AdminFloorGrant {
+    pub user_id:   String,
+    pub priority:  Option<u8>,              // 생략 시 대상 사용자의 IDENTIFY priority
+    pub indicator: Option<FloorIndicator>,  // 생략 시 normal
+}
+
+/// PUT /admin/channels/{id}/floor-queue 요청 body
+#[derive(Deserialize)]
+pub struct AdminQueueReorder {
+    pub order: Vec<String>,  // 앞으로 옮길 user_id 순서 (나머지는 기존 순서 유지)
+}
+
+/// GET /admin/channels/{id}/floor-history 쿼리 (모두 선택)
+#[derive(Deserialize, Default)]
+pub struct AdminFloorHistoryQuery {
+    pub user:  Option<String>,  // 발언자 user_id
+    pub since: Option<u64>,     // granted_at 하한 (ms)
+    pub limit: Option<usize>,   // 최신순 최대 건수
+}
+
+/// GET /admin/channels/{id}/floor-history
+#[derive(Serialize)]
+pub struct AdminFloorHistory {
+    pub channel_id: String,
+    pub current:    Option<AdminCurrentBurst>,  // 진행 중인 발언 (Taken)
+    pub bursts:     Vec<TalkBurst>,             // 종료된 발언, 최신순
+}
+
+#[derive(Serialize)]
+pub struct AdminCurrentBurst {
+    pub user_id:    String,
+    pub priority:   u8,
+    pub indicator:  FloorIndicator,
+    pub granted_at: u64,
+    pub elapsed_ms: u64,
+}
+
+/// POST /admin/service-accounts 요청 body
+#[derive(Deserialize)]
+pub struct AdminServiceAccountCreate {
+    pub id:     String,
+    pub scopes: Vec<String>,  // "post-message" | "read-trace" | "manage-channels" | "admin"
+}
+
+/// GET /admin/service-accounts 아이템
+#[derive(Serialize)]
+pub struct AdminServiceAccount {
+    pub id:           String,
+    pub scopes:       Vec<String>,
+    pub key_prefix:   String,          // 키 앞부분 (식별용)
+    pub created_at:   u64,
+    pub last_used_ms: Option<u64>,     // 미사용이면 None
+    pub online:       bool,            // 현재 WS 접속 여부
+}
+
+/// POST /admin/service-accounts 응답 — api_key는 이 응답에서만 노출
+#[derive(Serialize)]
+pub struct AdminServiceAccountCreated {
+    pub id:         String,
+    pub scopes:     Vec<String>,
+    pub key_prefix: String,
+    pub api_key:    String,
 }
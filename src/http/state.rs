@@ -4,30 +4,41 @@
 use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 
-use crate::core::{ChannelHub, MediaPeerHub, UserHub};
+use crate::core::{ChannelHub, MediaPeerHub, ServiceAccountHub, UserHub};
 use crate::trace::TraceHub;
 
 #[derive(Clone)]
 pub struct HttpState {
-    pub user_hub:       Arc<UserHub>,
-    pub channel_hub:    Arc<ChannelHub>,
-    pub media_peer_hub: Arc<MediaPeerHub>,
-    pub trace_hub:      Arc<TraceHub>,
+    pub user_hub:         Arc<UserHub>,
+    pub channel_hub:      Arc<ChannelHub>,
+    pub media_peer_hub:   Arc<MediaPeerHub>,
+    pub trace_hub:        Arc<TraceHub>,
+    pub service_accounts: Arc<ServiceAccountHub>,
+    /// true면 연동 경로(메시지 주입, /trace)에 API 키 필수
+    pub require_api_key:  bool,
+    /// 서비스 계정 관리용 admin 키 (LIVECHAT_ADMIN_KEY) — None이면 admin scope 키만
+    pub admin_key:        Option<String>,
     /// 서버 프로세스 시작 시각 (Unix millis) — uptime 계산용
-    pub start_time_ms:  u64,
+    pub start_time_ms:    u64,
 }
 
 impl HttpState {
     pub fn new(
-        user_hub:       Arc<UserHub>,
-        channel_hub:    Arc<ChannelHub>,
-        media_peer_hub: Arc<MediaPeerHub>,
-        trace_hub:      Arc<TraceHub>,
+        user_hub:         Arc<UserHub>,
+        channel_hub:      Arc<ChannelHub>,
+        media_peer_hub:   Arc<MediaPeerHub>,
+        trace_hub:        Arc<TraceHub>,
+        service_accounts: Arc<ServiceAccountHub>,
+        require_api_key:  bool,
+        admin_key:        Option<String>,
     ) -> Self {
         let start_time_ms = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap_or_default()
             .as_millis() as u64;
-        Self { user_hub, channel_hub, media_peer_hub, trace_hub, start_time_ms }
+        Self {
+            user_hub, channel_hub, media_peer_hub, trace_hub,
+            service_accounts, require_api_key, admin_key, start_time_ms,
+        }
     }
 }
@@ -6,18 +6,29 @@
 
 use axum::{
     extract::{Path, State},
-    response::{IntoResponse, Sse},
+    http::HeaderMap,
+    response::{IntoResponse, Response, Sse},
+    Json,
 };
 use axum::response::sse::Event;
 use tokio_stream::wrappers::BroadcastStream;
 use tokio_stream::StreamExt as _;
 
+use crate::core::ApiScope;
+
+use super::auth::authorize;
 use super::state::HttpState;
 
 pub async fn trace_stream(
     State(state): State<HttpState>,
+    headers: HeaderMap,
     channel_filter: Option<Path<String>>,
-) -> impl IntoResponse {
+) -> Response {
+    // 서비스 계정 키는 read-trace scope 필요
+    if let Err((code, msg)) = authorize(&state, &headers, ApiScope::ReadTrace) {
+        return (code, Json(serde_json::json!({ "error": msg }))).into_response();
+    }
+
     let rx   = state.trace_hub.subscribe();
     let filter = channel_filter.map(|Path(id)| id);
 
@@ -47,5 +58,5 @@ pub async fn trace_stream(
         axum::response::sse::KeepAlive::new()
             .interval(std::time::Duration::from_secs(15))
             .text("keep-alive"),
-    )
+    ).into_response()
 }
@@ -3,36 +3,49 @@
 pub mod config;
 pub mod core;
 pub mod error;
+pub mod filter;
 pub mod http;
 pub mod media;
 pub mod protocol;
 pub mod reaper;
 pub mod trace;
 pub mod utils;
+pub mod webhook;
 
-use axum::{routing::{get, post}, Router};
+use axum::{routing::{delete, get, post}, Router};
 use std::sync::Arc;
 use tokio::net::TcpListener;
 use tower_http::cors::{Any, CorsLayer};
 use tracing::{error, info};
 
-use crate::core::{ChannelHub, MediaPeerHub, UserHub};
+use crate::core::{CallHub, ChannelHub, MediaPeerHub, RateLimitConfig, ServiceAccountHub, UserHub};
+use crate::filter::{FilterChain, WordListAction, WordListFilter};
 use crate::media::{DtlsSessionMap, ServerCert};
 use crate::protocol::{ws_handler, AppState};
 use crate::http::HttpState;
 use crate::trace::TraceHub;
+use crate::webhook::WebhookConfig;
 
 /// CLI에서 주입되는 런타임 설정
 /// - 기본값은 config.rs 상수
 /// - 비밀값(SECRET)은 환경변수로 별도 관리
 pub struct ServerArgs {
-    pub port:         u16,
-    pub udp_port:     u16,
-    pub advertise_ip: Option<String>, // None이면 detect_local_ip() 자동 감지
+    pub port:                u16,
+    pub udp_port:            u16,
+    pub advertise_ip:        Option<String>, // None이면 detect_local_ip() 자동 감지
+    pub rate_limit:          RateLimitConfig,
+    pub word_list:           Option<std::path::PathBuf>, // 금칙어 파일 (None이면 필터 없음)
+    pub word_action:         WordListAction,
+    pub webhook:             WebhookConfig,  // urls 비어 있으면 비활성화
+    pub service_accounts:    Option<std::path::PathBuf>, // 서비스 계정 저장 파일 (None이면 메모리 전용)
+    pub require_api_key:     bool,           // 연동 경로(메시지 주입, /trace) API 키 필수
+    pub admin_key:           Option<String>, // 서비스 계정 관리 admin 키 (LIVECHAT_ADMIN_KEY)
+    pub floor_history:       Option<std::path::PathBuf>, // talk-burst 이력 파일 (None이면 메모리 전용)
+    pub speaker_interval_ms: u64,            // 발언자 감지 주기 (0이면 비활성)
 }
 
 pub async fn run_server(args: ServerArgs) {
-    let user_hub       = Arc::new(UserHub::new());
+    let user_hub       = Arc::new(UserHub::with_rate_limit(args.rate_limit.clone()));
     let channel_hub    = Arc::new(ChannelHub::new());
     let media_peer_hub = Arc::new(MediaPeerHub::new());
 
@@ -43,6 +56,17 @@ pub async fn run_server(args: ServerArgs) {
         info!("[channel] preset created: {} freq={} name={} mode={} cap={}", channel_id, freq, name, mode, capacity);
     }
 
+    // talk-burst 이력 파일 — 사전 생성 채널은 즉시, 이후 생성 채널은 생성 시 복원 + writer 스레드 append
+    if let Some(ref path) = args.floor_history {
+        match channel_hub.open_history_file(path) {
+            Ok(n)  => info!("[floor-history] {} ({} bursts loaded)", path.display(), n),
+            Err(e) => {
+                error!("[floor-history] Failed to open {}: {}", path.display(), e);
+                return;
+            }
+        }
+    }
+
     // DTLS 자체서명 인증서 — 프로세스 시작 시 1회 생성, 전체 공유
     let server_cert = match ServerCert::generate() {
         Ok(c)  => Arc::new(c),
@@ -57,40 +81,100 @@ pub async fn run_server(args: ServerArgs) {
 
     let trace_hub = TraceHub::new();
 
+    // 메시지 필터 체인 — 금칙어 파일 지정 시 WordListFilter 등록
+    let mut message_filter = FilterChain::new();
+    if let Some(ref path) = args.word_list {
+        match WordListFilter::from_file(path, args.word_action) {
+            Ok(f) => {
+                info!("[filter] word list loaded: {} ({} words, action={:?})", path.display(), f.len(), args.word_action);
+                message_filter.add(Arc::new(f));
+            }
+            Err(e) => {
+                error!("[filter] Failed to load word list {}: {}", path.display(), e);
+                return;
+            }
+        }
+    }
+    let message_filter = Arc::new(message_filter);
+
+    // 서비스 계정 (봇/연동 API 키)
+    let service_accounts = match args.service_accounts {
+        Some(ref path) => match ServiceAccountHub::with_file(path.clone()) {
+            Ok(hub) => {
+                info!("[service-account] loaded: {} ({} accounts)", path.display(), hub.count());
+                Arc::new(hub)
+            }
+            Err(e) => {
+                error!("[service-account] Failed to load {}: {}", path.display(), e);
+                return;
+            }
+        },
+        None => Arc::new(ServiceAccountHub::new()),
+    };
+
     let app_state = AppState {
-        user_hub:       Arc::clone(&user_hub),
-        channel_hub:    Arc::clone(&channel_hub),
-        media_peer_hub: Arc::clone(&media_peer_hub),
-        server_cert:    Arc::clone(&server_cert),
-        trace_hub:      Arc::clone(&trace_hub),
-        udp_port:       args.udp_port,
+        user_hub:         Arc::clone(&user_hub),
+        channel_hub:      Arc::clone(&channel_hub),
+        media_peer_hub:   Arc::clone(&media_peer_hub),
+        call_hub:         Arc::new(CallHub::new()),
+        server_cert:      Arc::clone(&server_cert),
+        trace_hub:        Arc::clone(&trace_hub),
+        message_filter:   Arc::clone(&message_filter),
+        service_accounts: Arc::clone(&service_accounts),
+        udp_port:         args.udp_port,
     };
 
     // UDP 미디어 릴레이 태스크
     tokio::spawn(media::run_udp_relay(
-        Arc::clone(&media_peer_hub),
-        Arc::clone(&channel_hub),
-        Arc::clone(&server_cert),
+        app_state.clone(),
         Arc::clone(&dtls_session_map),
-        args.udp_port,
         args.advertise_ip.clone(),
     ));
 
-    // 좀비 세션 자동 종료 태스크 (Floor 타임아웃 체크 포함)
+    // Webhook 전달 태스크 (TraceHub 구독)
+    if args.webhook.is_enabled() {
+        if args.webhook.secret.is_none() {
+            tracing::warn!("[webhook] LIVECHAT_WEBHOOK_SECRET not set — deliveries are unsigned");
+        }
+        tokio::spawn(webhook::run_webhook_dispatcher(Arc::clone(&trace_hub), args.webhook.clone()));
+    }
+
+    // 좀비 세션 자동 종료 태스크
     tokio::spawn(reaper::run_zombie_reaper(
         Arc::clone(&user_hub),
         Arc::clone(&channel_hub),
         Arc::clone(&media_peer_hub),
         Arc::clone(&dtls_session_map),
+    ));
+
+    // Floor 타이머 (ping_timeout / max_duration Revoke, T101 / T100 재전송)
+    tokio::spawn(protocol::floor::run_floor_timer(
+        Arc::clone(&user_hub),
+        Arc::clone(&channel_hub),
+        Arc::clone(&media_peer_hub),
         Arc::clone(&trace_hub),
     ));
 
+    // 발언자 감지 (Conference speaker_changed / audio_levels)
+    tokio::spawn(protocol::speaker::run_speaker_detector(
+        Arc::clone(&user_hub),
+        Arc::clone(&channel_hub),
+        Arc::clone(&media_peer_hub),
+        args.speaker_interval_ms,
+    ));
+
     let http_state = HttpState::new(
         Arc::clone(&user_hub),
         Arc::clone(&channel_hub),
         Arc::clone(&media_peer_hub),
         Arc::clone(&trace_hub),
+        Arc::clone(&service_accounts),
+        args.require_api_key,
+        args.admin_key.clone(),
     );
+    if args.admin_key.is_none() && !args.require_api_key {
+        tracing::warn!("[service-account] LIVECHAT_ADMIN_KEY not set and --require-api-key off — /admin control and service-account routes are open");
+    }
 
     let admin_router = Router::new()
         .route("/admin/status",                 get(http::admin_status))
@@ -101,6 +185,17 @@ pub async fn run_server(args: ServerArgs) {
         .route("/admin/peers",                  get(http::admin_list_peers))
         .route("/admin/peers/{ufrag}",          get(http::admin_get_peer))
         .route("/admin/floor-revoke/{channel_id}", post(http::admin_floor_revoke))
+        .route("/admin/floor-grant/{channel_id}",  post(http::admin_floor_grant))
+        .route("/admin/channels/{channel_id}/messages", post(http::admin_post_channel_message))
+        .route("/admin/channels/{channel_id}/floor-policy", get(http::admin_get_floor_policy).put(http::admin_put_floor_policy))
+        .route("/admin/channels/{channel_id}/floor-queue",
+            get(http::admin_get_floor_queue).put(http::admin_reorder_floor_queue).delete(http::admin_clear_floor_queue))
+        .route("/admin/channels/{channel_id}/floor-queue/{user_id}", delete(http::admin_remove_floor_queue_entry))
+        .route("/admin/channels/{channel_id}/floor-history", get(http::admin_get_floor_history))
+        .route("/admin/channels/{channel_id}/emergency", delete(http::admin_clear_emergency))
+        .route("/admin/messages",               post(http::admin_post_broadcast_message))
+        .route("/admin/service-accounts",       get(http::admin_list_service_accounts).post(http::admin_create_service_account))
+        .route("/admin/service-accounts/{id}",  delete(http::admin_delete_service_account))
         .route("/trace",             get(http::trace_stream))
         .route("/trace/{channel_id}", get(http::trace_stream))
         .route("/channels",      get(http::list_channels))
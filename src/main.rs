@@ -1,6 +1,9 @@
 // author: kodeholic (powered by Claude)
 
 use clap::Parser;
+use mini_livechat::core::{BucketSpec, RateLimitConfig};
+use mini_livechat::filter::WordListAction;
+use mini_livechat::webhook::{WebhookConfig, WebhookEvent};
 use mini_livechat::{run_server, ServerArgs};
 
 /// mini-livechat 미디어 릴레이 서버
@@ -18,6 +21,59 @@ pub struct Args {
     /// SDP candidate에 광고할 IP (생략 시 라우팅 테이블 기반 자동 감지)
     #[arg(long)]
     pub advertise_ip: Option<String>,
+
+    /// MESSAGE_CREATE rate limit "초당/버스트" (예: 2/5)
+    #[arg(long, value_name = "PER_SEC/BURST")]
+    pub rate_message: Option<BucketSpec>,
+
+    /// FLOOR_REQUEST/RELEASE/PING rate limit "초당/버스트"
+    #[arg(long, value_name = "PER_SEC/BURST")]
+    pub rate_floor: Option<BucketSpec>,
+
+    /// CHANNEL_LIST/INFO 조회 rate limit "초당/버스트"
+    #[arg(long, value_name = "PER_SEC/BURST")]
+    pub rate_query: Option<BucketSpec>,
+
+    /// CHANNEL_CREATE/JOIN/LEAVE/UPDATE/DELETE, RENEGOTIATE rate limit "초당/버스트"
+    #[arg(long, value_name = "PER_SEC/BURST")]
+    pub rate_channel: Option<BucketSpec>,
+
+    /// 위반 누적 허용치 — 윈도우 내 도달 시 연결 종료 (0이면 비활성화)
+    #[arg(long, default_value_t = mini_livechat::config::RATE_MAX_VIOLATIONS)]
+    pub rate_max_violations: u32,
+
+    /// 채팅 금칙어 파일 (한 줄 1단어, '#' 주석)
+    #[arg(long, value_name = "PATH")]
+    pub word_list: Option<std::path::PathBuf>,
+
+    /// 금칙어 처리 방식: mask | reject
+    #[arg(long, default_value = "mask")]
+    pub word_action: WordListAction,
+
+    /// 서버 이벤트 webhook 대상 URL (반복 지정 가능, 서명 키는 LIVECHAT_WEBHOOK_SECRET)
+    #[arg(long, value_name = "URL")]
+    pub webhook_url: Vec<String>,
+
+    /// webhook 전달 이벤트 (쉼표 구분, 생략 시 전체)
+    /// join | leave | floor_granted | floor_revoked | message | emergency
+    #[arg(long, value_name = "EVENTS", value_delimiter = ',')]
+    pub webhook_events: Vec<WebhookEvent>,
+
+    /// 서비스 계정 저장 파일 (JSON, 키는 SHA-256 해시로 저장). 생략 시 메모리 전용
+    #[arg(long, value_name = "PATH")]
+    pub service_accounts: Option<std::path::PathBuf>,
+
+    /// 연동 경로(메시지 주입 REST, /trace SSE)에 서비스 계정 API 키 필수
+    #[arg(long)]
+    pub require_api_key: bool,
+
+    /// talk-burst 이력 파일 (JSONL append, 시작 시 복원). 생략 시 메모리 전용
+    #[arg(long, value_name = "PATH")]
+    pub floor_history: Option<std::path::PathBuf>,
+
+    /// Conference 발언자 감지 / audio level 스냅샷 주기 ms (0이면 비활성)
+    #[arg(long, default_value_t = mini_livechat::config::SPEAKER_INTERVAL_MS)]
+    pub speaker_interval_ms: u64,
 }
 
 #[tokio::main]
@@ -41,10 +97,38 @@ async fn main() {
     let advertise_ip = args.advertise_ip
         .or_else(|| std::env::var("ADVERTISE_IP").ok().filter(|s| !s.is_empty()));
 
+    // rate limit: CLI 인자 > config.rs 기본값
+    let defaults   = RateLimitConfig::default();
+    let rate_limit = RateLimitConfig {
+        message:        args.rate_message.unwrap_or(defaults.message),
+        floor:          args.rate_floor.unwrap_or(defaults.floor),
+        query:          args.rate_query.unwrap_or(defaults.query),
+        channel:        args.rate_channel.unwrap_or(defaults.channel),
+        max_violations: args.rate_max_violations,
+        ..defaults
+    };
+
+    // webhook: 대상은 CLI, 서명 키는 환경변수
+    let webhook = WebhookConfig {
+        urls:   args.webhook_url,
+        secret: std::env::var("LIVECHAT_WEBHOOK_SECRET").ok().filter(|s| !s.is_empty()),
+        events: args.webhook_events,
+        ..WebhookConfig::default()
+    };
+
     run_server(ServerArgs {
-        port:         args.port,
-        udp_port:     args.udp_port,
+        port:                args.port,
+        udp_port:            args.udp_port,
         advertise_ip,
+        rate_limit,
+        word_list:           args.word_list,
+        word_action:         args.word_action,
+        webhook,
+        service_accounts:    args.service_accounts,
+        require_api_key:     args.require_api_key,
+        admin_key:           std::env::var("LIVECHAT_ADMIN_KEY").ok().filter(|s| !s.is_empty()),
+        floor_history:       args.floor_history,
+        speaker_interval_ms: args.speaker_interval_ms,
     })
     .await;
 }
@@ -2,6 +2,9 @@
 
 pub mod dtls;
 pub mod net;
+pub mod rtcp;
+pub mod rtp;
+pub mod simulcast;
 pub mod srtp;
 
 pub use dtls::{DtlsSessionMap, ServerCert};
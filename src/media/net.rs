@@ -7,13 +7,26 @@
 //     → STUN : ufrag 파싱 → latch → Binding Response
 //     → DTLS : DtlsSessionMap 조회 → 기존 세션에 주입 or 신규 핸드셰이크 시작
 //     → SRTP : by_addr O(1) 조회 → 복호화 → 채널 내 다른 피어 재암호화 → 릴레이
+//              simulcast 레이어는 수신자별로 1개만 (media::simulcast — keyframe 전환, consumer 스트림 rewrite)
+//     → SRTCP APP "MCPT" : MBCP 디코딩 → floor 핸들러 (protocol::mbcp)
+//     → SRTCP NACK       : 재전송 버퍼에 있으면 서버가 직접 재전송 (answer_nacks), 없는 seq만 원 송신자로
+//     → SRTCP 그 외      : SR → 수신자 / RR · NACK · PLI · FIR → 원 송신자 (route_rtcp)
 
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 use tokio::net::UdpSocket;
 use tracing::{debug, info, trace, warn};
 
-use crate::core::{ChannelHub, FloorControlState, MediaPeerHub};
+use crate::config;
+use crate::core::{Channel, ChannelHub, Endpoint, MediaPeerHub, TrackKind, UserHub};
 use crate::media::dtls::{DtlsSessionMap, ServerCert, start_dtls_handshake};
+use crate::media::{rtcp, rtp};
+use crate::media::simulcast::LayerForward;
+use crate::protocol::mbcp::{self, MbcpMessage};
+use crate::protocol::{speaker, vox};
+use crate::protocol::AppState;
+use crate::trace::TraceHub;
+use crate::utils::current_timestamp;
+use std::sync::atomic::Ordering;
 
 const UDP_RECV_BUF_SIZE: usize = 65535;
 
@@ -52,18 +65,85 @@ fn classify(buf: &[u8]) -> PacketKind {
     }
 }
 
+// ----------------------------------------------------------------------------
+// UDP 소켓 전역 저장소
+// run_udp_relay() bind 직후 1회 세팅, 이후 send_mbcp() / request_keyframe()에서 사용
+// (floor 핸들러 / HTTP admin 송신 경로는 소켓을 직접 들고 있지 않음)
+// ----------------------------------------------------------------------------
+
+static UDP_SOCKET: OnceLock<Arc<UdpSocket>> = OnceLock::new();
+
+/// MBCP 메시지를 엔드포인트로 전송 (APP 인코딩 → SRTCP 암호화 → send_to)
+/// 주소 미확정 / 키 미설치 / 소켓 미기동이면 조용히 drop
+pub async fn send_mbcp(ep: &Endpoint, msg: &MbcpMessage) {
+    let socket = match UDP_SOCKET.get() {
+        Some(s) => s,
+        None    => { debug!("[mbcp] udp socket not ready, dropping {}", msg.kind.as_str()); return; }
+    };
+    let addr = match ep.get_address() {
+        Some(a) => a,
+        None    => { debug!("[mbcp] user={} no addr yet", ep.user_id); return; }
+    };
+
+    let encrypted = {
+        let mut ctx = ep.outbound_srtp.lock().unwrap();
+        match ctx.encrypt_rtcp(&msg.encode()) {
+            Ok(p)  => p,
+            Err(e) => { warn!("[mbcp] encrypt failed user={}: {}", ep.user_id, e); return; }
+        }
+    };
+
+    if let Err(e) = socket.send_to(&encrypted, addr).await {
+        warn!("[mbcp] send failed user={} addr={}: {}", ep.user_id, addr, e);
+    } else {
+        trace!("[mbcp] {} → user={} addr={}", msg.kind.as_str(), ep.user_id, addr);
+    }
+}
+
+/// 서버 발 keyframe 요청 — 엔드포인트의 video 트랙마다 PLI (compound 1개, SRTCP)
+/// video 트랙이 없거나 KEYFRAME_REQUEST_MIN_MS 안에 이미 요청했으면 생략
+pub async fn request_keyframe(ep: &Endpoint, reason: &str) {
+    let ssrcs = ep.video_ssrcs();
+    if ssrcs.is_empty() || !ep.try_keyframe_request(current_timestamp()) {
+        return;
+    }
+    let (socket, addr) = match (UDP_SOCKET.get(), ep.get_address()) {
+        (Some(s), Some(a)) => (s, a),
+        _                  => { debug!("[pli] user={} not reachable yet ({})", ep.user_id, reason); return; }
+    };
+
+    let mut compound = Vec::new();
+    for ssrc in &ssrcs {
+        compound.extend(rtcp::build_pli(config::RTCP_SERVER_SSRC, *ssrc));
+    }
+    let encrypted = {
+        let mut ctx = ep.outbound_srtp.lock().unwrap();
+        match ctx.encrypt_rtcp(&compound) {
+            Ok(p)  => p,
+            Err(e) => { debug!("[pli] encrypt failed user={}: {}", ep.user_id, e); return; }
+        }
+    };
+
+    if let Err(e) = socket.send_to(&encrypted, addr).await {
+        warn!("[pli] send failed user={} addr={}: {}", ep.user_id, addr, e);
+    } else {
+        debug!("[pli] keyframe request → user={} ssrcs={:?} reason={}", ep.user_id, ssrcs, reason);
+    }
+}
+
 // ----------------------------------------------------------------------------
 // [UDP 릴레이 서버]
 // ----------------------------------------------------------------------------
 
 pub async fn run_udp_relay(
-    peer_hub:     Arc<MediaPeerHub>,
-    channel_hub:  Arc<ChannelHub>,
-    cert:         Arc<ServerCert>,
+    state:        AppState,
     session_map:  Arc<DtlsSessionMap>,
-    udp_port:     u16,
     advertise_ip: Option<String>,
 ) {
+    let peer_hub = state.media_peer_hub;
+    let cert     = state.server_cert;
+    let udp_port = state.udp_port;
+
     // advertise_ip: SDP candidate에 광고할 IP
     // None이면 라우팅 테이블 기반 자동 감지
     let adv_ip = advertise_ip.unwrap_or_else(|| crate::protocol::sdp::detect_local_ip());
@@ -77,6 +157,7 @@ pub async fn run_udp_relay(
         Ok(s)  => { info!("[media] UDP relay on {}", addr); Arc::new(s) }
         Err(e) => { tracing::error!("[media] bind failed: {}", e); return; }
     };
+    let _ = UDP_SOCKET.set(Arc::clone(&socket));
 
     let mut buf = vec![0u8; UDP_RECV_BUF_SIZE];
 
@@ -116,7 +197,7 @@ pub async fn run_udp_relay(
                 ).await;
             }
             PacketKind::Srtp => {
-                handle_srtp(&socket, &packet, src_addr, &peer_hub, &channel_hub).await;
+                handle_srtp(&socket, &packet, src_addr, &peer_hub, &state.channel_hub, &state.user_hub, &state.trace_hub).await;
             }
             PacketKind::Unknown => {
                 trace!("[media] unknown packet type from {} byte0=0x{:02x}", src_addr, packet[0]);
@@ -252,7 +333,9 @@ async fn handle_srtp(
     packet:      &[u8],
     src_addr:    std::net::SocketAddr,
     peer_hub:    &MediaPeerHub,
-    channel_hub: &ChannelHub,
+    channel_hub: &Arc<ChannelHub>,
+    user_hub:    &Arc<UserHub>,
+    trace_hub:   &Arc<TraceHub>,
 ) {
     let b1 = packet.get(1).copied().unwrap_or(0);
     trace!("[srtp] enter addr={} len={} byte0=0x{:02x} byte1=0x{:02x}", src_addr, packet.len(), packet.first().unwrap_or(&0), b1);
@@ -277,7 +360,7 @@ async fn handle_srtp(
 
     // MutexGuard를 블록으로 감싸서 await 진입 전에 반드시 drop
     // (std::sync::MutexGuard는 Send가 아니므로 tokio::spawn 안에서 await 넘지불가)
-    enum DecryptResult { Rtcp, Rtp(Vec<u8>), Err }
+    enum DecryptResult { Rtcp(Vec<u8>), Mbcp(Vec<u8>), Rtp(Vec<u8>), Err }
 
     let result = {
         let mut ctx = ep.inbound_srtp.lock().unwrap();
@@ -286,7 +369,13 @@ async fn handle_srtp(
             return;
         }
         if is_rtcp {
+            let mut mbcp_app = None;
+            let mut rtcp     = None;
             match ctx.decrypt_rtcp(packet) {
+                // MBCP: compound 안의 APP "MCPT"만 잘라서 floor 경로로
+                Ok(plain) if mbcp::find_mcpt(&plain).is_some() => {
+                    mbcp_app = mbcp::find_mcpt(&plain).map(<[u8]>::to_vec);
+                }
                 Ok(plain) => {
                     // RTCP 헤더 파싱 (RFC 3550)
                     // byte[0]: V(2)|P(1)|RC(5)
@@ -322,10 +411,15 @@ async fn handle_srtp(
                     } else {
                         trace!("[srtcp] ok user={} (short packet)", ep.user_id);
                     }
+                    rtcp = Some(plain);
                 }
                 Err(e) => trace!("[srtcp] decrypt failed user={}: {}", ep.user_id, e),
             }
-            DecryptResult::Rtcp
+            match (mbcp_app, rtcp) {
+                (Some(app), _)   => DecryptResult::Mbcp(app),
+                (None, Some(p))  => DecryptResult::Rtcp(p),
+                (None, None)     => DecryptResult::Err,
+            }
         } else {
             match ctx.decrypt(packet) {
                 Ok(p)  => DecryptResult::Rtp(p),
@@ -341,13 +435,293 @@ async fn handle_srtp(
     };
 
     let plaintext = match result {
-        DecryptResult::Rtcp | DecryptResult::Err => return,
+        DecryptResult::Err => return,
+        DecryptResult::Rtcp(plain) => {
+            relay_rtcp(socket, &plain, &ep, peer_hub, channel_hub).await;
+            return;
+        }
+        DecryptResult::Mbcp(app) => {
+            mbcp::handle_mbcp(&ep, &app, user_hub, channel_hub, peer_hub, trace_hub).await;
+            return;
+        }
         DecryptResult::Rtp(p) => p,
     };
 
+    // MBCP 클라이언트는 FLOOR_PING을 보내지 않음 — holder의 미디어 수신을 생존 신호로 대체
+    if ep.is_mbcp() {
+        if let Some(ch) = channel_hub.get(&ep.channel_id) {
+            let mut floor = ch.floor.lock().unwrap();
+            if floor.floor_taken_by.as_deref() == Some(ep.user_id.as_str()) {
+                floor.on_ping();
+            }
+        }
+    }
+
+    // simulcast: rtp-stream-id 확장으로 처음 보는 레이어 SSRC 등록 → consumer 매핑에 포함
+    if ep.learn_layer(&plaintext) {
+        peer_hub.rebuild_relay_map(&ep.channel_id);
+    }
+
+    // VOX 채널: 음성 감지 → 자동 Floor Request / holder hang time 갱신 (릴레이 게이트보다 먼저 — 첫 음성 패킷도 릴레이)
+    vox::on_rtp(&ep, &plaintext, user_hub, channel_hub, peer_hub, trace_hub).await;
+    // 발언자 감지용 audio level 평활 (판정 / 브로드캐스트는 speaker::run_speaker_detector)
+    speaker::on_rtp(&ep, &plaintext);
+
     relay_to_channel(socket, &plaintext, &ep.user_id, &ep.ufrag, &ep.channel_id, peer_hub, channel_hub).await;
 }
 
+// ----------------------------------------------------------------------------
+// [RTCP 라우팅]
+//   SR              → 수신자에게 (sender info만, Conference는 consumer SSRC로 rewrite)
+//                     PTT는 릴레이 게이트와 같게 holder의 SR만 전달
+//   RR report block → 보고 대상 SSRC의 원 송신자에게 (consumer SSRC → 원본 SSRC)
+//   NACK / PLI / FIR → 대상 SSRC의 원 송신자에게 (동일 역매핑)
+//   송신자별로 RR(+feedback) compound 1개로 묶어 전송, SDES / BYE / APP는 전달하지 않음
+//   PTT 가상 talker 스트림: 가상 SSRC → 현재 talker 원본 SSRC, holder SR은 가상 SSRC / timestamp로
+//   simulcast consumer: consumer SSRC → 수신자가 지금 받는 레이어 SSRC, SR은 그 레이어 것만
+//                       RR fraction lost는 수신자별 자동 레이어 선택에도 반영
+// ----------------------------------------------------------------------------
+
+async fn relay_rtcp(
+    socket:      &UdpSocket,
+    plaintext:   &[u8],
+    ep:          &Arc<Endpoint>,
+    peer_hub:    &MediaPeerHub,
+    channel_hub: &ChannelHub,
+) {
+    let Some(ch) = channel_hub.get(&ep.channel_id) else { return };
+    let (forward_sr, virtual_talker) = if ch.is_ptt() {
+        let floor = ch.floor.lock().unwrap();
+        (floor.may_relay(&ep.user_id), floor.policy.virtual_talker)
+    } else {
+        (true, false)
+    };
+    let talker    = virtual_talker.then_some(&*ch);
+    let endpoints = peer_hub.get_channel_endpoints(&ep.channel_id);
+
+    // simulcast consumer 수신 손실 → 자동 레이어 조정 (전환은 릴레이 경로에서 keyframe 대기 + PLI)
+    if !ch.is_ptt() {
+        let now = current_timestamp();
+        for block in rtcp::split_compound(plaintext).into_iter().flat_map(rtcp::report_blocks) {
+            let (ssrc, lost) = (rtcp::block_source_ssrc(&block), rtcp::block_fraction_lost(&block));
+            if peer_hub.layer_feedback(&ep.user_id, ssrc, lost, now) {
+                debug!("[simulcast] user={} consumer={} auto layer changed (fraction_lost={})", ep.user_id, ssrc, lost);
+            }
+        }
+    }
+
+    // NACK → 재전송 버퍼에서 응답 (NACK 송신자에게 RTP), 남은 compound만 라우팅
+    let (plaintext, retransmits) = answer_nacks(plaintext, ep, &endpoints, peer_hub, talker);
+    let ep_addr = ep.get_address();
+    for pkt in retransmits {
+        let Some(addr) = ep_addr else { break };
+        let encrypted = {
+            let mut ctx = ep.outbound_srtp.lock().unwrap();
+            match ctx.encrypt(&pkt) {
+                Ok(p)  => p,
+                Err(e) => { trace!("[nack] encrypt failed user={}: {}", ep.user_id, e); continue; }
+            }
+        };
+        if let Err(e) = socket.send_to(&encrypted, addr).await {
+            warn!("[nack] send failed user={}: {}", ep.user_id, e);
+        }
+    }
+
+    for (target, pkt) in route_rtcp(&plaintext, ep, &endpoints, peer_hub, talker, forward_sr) {
+        let addr = match target.get_address() {
+            Some(a) => a,
+            None    => continue,
+        };
+        let encrypted = {
+            let mut ctx = target.outbound_srtp.lock().unwrap();
+            match ctx.encrypt_rtcp(&pkt) {
+                Ok(p)  => p,
+                Err(e) => { trace!("[rtcp] encrypt failed user={}: {}", target.user_id, e); continue; }
+            }
+        };
+        if let Err(e) = socket.send_to(&encrypted, addr).await {
+            warn!("[rtcp] send failed user={}: {}", target.user_id, e);
+        } else {
+            trace!("[rtcp] {} bytes {} → {}", pkt.len(), ep.user_id, target.user_id);
+        }
+    }
+}
+
+/// Generic NACK을 재전송 버퍼로 응답 → (나머지 compound, NACK 송신자에게 보낼 평문 RTP 목록)
+/// 버퍼에 없는 seq만 NACK으로 다시 조립해 compound에 남김 (전부 응답했으면 NACK 제거 — 원 송신자로 전달 안 함)
+/// 재전송 RTP는 NACK이 가리킨 SSRC(Conference는 consumer SSRC)로, RTX 스트림이 광고되었으면 RTX로
+/// 가상 talker / simulcast consumer SSRC는 버퍼가 rewrite된 seq 기준이라 버퍼 응답만 (원 송신자 seq와 달라 전달하지 않음)
+fn answer_nacks(
+    plaintext: &[u8],
+    ep:        &Endpoint,
+    endpoints: &[Arc<Endpoint>],
+    peer_hub:  &MediaPeerHub,
+    talker:    Option<&Channel>,
+) -> (Vec<u8>, Vec<Vec<u8>>) {
+    let now = current_timestamp();
+    let mut rest        = Vec::with_capacity(plaintext.len());
+    let mut retransmits = Vec::new();
+
+    for pkt in rtcp::split_compound(plaintext) {
+        let seqs     = rtcp::nack_seqs(pkt);
+        let resolved = rtcp::feedback_target_ssrc(pkt)
+            .and_then(|media| Some((media, resolve_media_source(media, ep, endpoints, peer_hub, talker)?.1)));
+        let (media, orig) = match resolved {
+            Some(r) if !seqs.is_empty() => r,
+            _ => { rest.extend_from_slice(pkt); continue; }
+        };
+        let rewritten  = talker.and_then(|ch| ch.talker_source(media)).is_some()
+            || peer_hub.is_layered(&ep.user_id, media);
+        let cache_ssrc = if rewritten { media } else { orig };
+
+        let mut missing = Vec::new();
+        for seq in &seqs {
+            match peer_hub.cached_rtp(cache_ssrc, *seq, now) {
+                Some(cached) => retransmits.push(retransmit_packet(cached, media, ep, peer_hub)),
+                None         => missing.push(*seq),
+            }
+        }
+        ep.nack_requested.fetch_add(seqs.len() as u64, Ordering::Relaxed);
+        ep.rtx_sent.fetch_add((seqs.len() - missing.len()) as u64, Ordering::Relaxed);
+        trace!("[nack] user={} ssrc={} requested={} cached={} missing={}",
+            ep.user_id, media, seqs.len(), seqs.len() - missing.len(), missing.len());
+
+        if !missing.is_empty() && !rewritten {
+            ep.nack_forwarded.fetch_add(missing.len() as u64, Ordering::Relaxed);
+            rest.extend(rtcp::build_nack(rtcp::sender_ssrc(pkt).unwrap_or(0), media, &missing));
+        }
+    }
+    (rest, retransmits)
+}
+
+/// 버퍼의 원본 RTP → 수신자용 재전송 패킷 (consumer SSRC rewrite, 가능하면 RTX)
+fn retransmit_packet(mut packet: Vec<u8>, media_ssrc: u32, ep: &Endpoint, peer_hub: &MediaPeerHub) -> Vec<u8> {
+    packet[8..12].copy_from_slice(&media_ssrc.to_be_bytes());
+    let rtx = rtp::payload_type(&packet)
+        .and_then(|pt| ep.rtx_pt(pt))
+        .and_then(|rtx_pt| {
+            let (rtx_ssrc, rtx_seq) = peer_hub.next_rtx(&ep.user_id, media_ssrc)?;
+            rtp::build_rtx(&packet, rtx_pt, rtx_ssrc, rtx_seq)
+        });
+    rtx.unwrap_or(packet)
+}
+
+/// 평문 compound RTCP → (대상 엔드포인트, 평문 RTCP) 목록 — 순수 함수 (테스트용 분리)
+fn route_rtcp(
+    plaintext:  &[u8],
+    ep:         &Arc<Endpoint>,
+    endpoints:  &[Arc<Endpoint>],
+    peer_hub:   &MediaPeerHub,
+    talker:     Option<&Channel>,
+    forward_sr: bool,
+) -> Vec<(Arc<Endpoint>, Vec<u8>)> {
+    let mut out: Vec<(Arc<Endpoint>, Vec<u8>)> = Vec::new();
+    // 원 송신자별 (엔드포인트, report block, feedback)
+    let mut to_senders: Vec<SenderRtcp> = Vec::new();
+    let mut reporter = 0;
+
+    for pkt in rtcp::split_compound(plaintext) {
+        let pt = rtcp::packet_type(pkt);
+        // 전달 RR의 보고자 SSRC — compound 첫 패킷(SR/RR, reduced-size면 feedback)의 송신자
+        if reporter == 0 {
+            reporter = rtcp::sender_ssrc(pkt).unwrap_or(0);
+        }
+
+        if pt == rtcp::PT_SR && forward_sr {
+            let sender_ssrc = rtcp::sender_ssrc(pkt).unwrap_or(0);
+            let targets     = peer_hub.get_relay_targets(sender_ssrc);
+            if let Some(ch) = talker {
+                // PTT 가상 talker: 현재 talker SR만 가상 SSRC / timestamp로 (RTP rewrite와 같은 offset)
+                let mapped = rtcp::sr_rtp_timestamp(pkt).and_then(|ts| ch.talker_sr(sender_ssrc, ts));
+                if let Some((ssrc, ts)) = mapped {
+                    for target in endpoints.iter().filter(|t| t.ufrag != ep.ufrag) {
+                        if let Some(mut sr) = rtcp::sr_sender_info(pkt, ssrc) {
+                            sr[16..20].copy_from_slice(&ts.to_be_bytes());
+                            out.push((Arc::clone(target), sr));
+                        }
+                    }
+                }
+            } else if targets.is_empty() {
+                // PTT / re-nego 전 Conference: 원본 SSRC 그대로 (RTP 브로드캐스트 릴레이와 동일)
+                for target in endpoints.iter().filter(|t| t.ufrag != ep.ufrag) {
+                    out.extend(rtcp::sr_sender_info(pkt, sender_ssrc).map(|sr| (Arc::clone(target), sr)));
+                }
+            } else {
+                for (receiver, consumer_ssrc) in targets {
+                    let target = endpoints.iter().find(|t| t.user_id == receiver && t.ufrag != ep.ufrag);
+                    let (Some(target), Some(mut sr)) = (target, rtcp::sr_sender_info(pkt, consumer_ssrc)) else { continue };
+                    // simulcast consumer: 지금 받는 레이어 SR만, consumer 스트림 timestamp로
+                    if peer_hub.is_layered(&receiver, consumer_ssrc) {
+                        let mapped = rtcp::sr_rtp_timestamp(pkt)
+                            .and_then(|ts| peer_hub.layer_sr_timestamp(&receiver, consumer_ssrc, sender_ssrc, ts));
+                        let Some(ts) = mapped else { continue };
+                        sr[16..20].copy_from_slice(&ts.to_be_bytes());
+                    }
+                    out.push((Arc::clone(target), sr));
+                }
+            }
+        }
+
+        for mut block in rtcp::report_blocks(pkt) {
+            if let Some((sender, ssrc)) = resolve_media_source(rtcp::block_source_ssrc(&block), ep, endpoints, peer_hub, talker) {
+                block[0..4].copy_from_slice(&ssrc.to_be_bytes());
+                sender_entry(&mut to_senders, sender).1.push(block);
+            }
+        }
+
+        if pt == rtcp::PT_RTPFB || pt == rtcp::PT_PSFB {
+            let resolved = rtcp::feedback_target_ssrc(pkt)
+                .and_then(|ssrc| resolve_media_source(ssrc, ep, endpoints, peer_hub, talker));
+            if let Some((sender, ssrc)) = resolved {
+                sender_entry(&mut to_senders, sender).2.push(rtcp::rewrite_feedback_target(pkt, ssrc));
+            }
+        }
+    }
+
+    for (sender, blocks, feedback) in to_senders {
+        let mut compound = rtcp::build_rr(reporter, &blocks);
+        for fb in feedback {
+            compound.extend_from_slice(&fb);
+        }
+        out.push((sender, compound));
+    }
+    out
+}
+
+/// 수신자가 보고한 SSRC → (원 송신자 엔드포인트, 원본 SSRC)
+/// Conference consumer SSRC면 역매핑 (simulcast면 수신자가 지금 받는 레이어), 가상 talker SSRC면 현재 talker 원본 SSRC,
+/// 아니면(PTT / rewrite 전) 채널 내 트랙 SSRC 그대로
+fn resolve_media_source(
+    ssrc:      u32,
+    receiver:  &Endpoint,
+    endpoints: &[Arc<Endpoint>],
+    peer_hub:  &MediaPeerHub,
+    talker:    Option<&Channel>,
+) -> Option<(Arc<Endpoint>, u32)> {
+    let ssrc = talker.and_then(|ch| ch.talker_source(ssrc)).unwrap_or(ssrc);
+    if let Some((sender, kind)) = peer_hub.resolve_consumer_ssrc(&receiver.user_id, ssrc) {
+        let ep   = endpoints.iter().find(|e| e.user_id == sender)?;
+        let orig = match peer_hub.layer_source(&receiver.user_id, ssrc) {
+            Some(layer) => layer,
+            None        => ep.tracks.read().unwrap().iter().find(|t| t.kind == kind)?.ssrc,
+        };
+        return Some((Arc::clone(ep), orig));
+    }
+    endpoints.iter()
+        .find(|e| e.ufrag != receiver.ufrag && e.tracks.read().unwrap().iter().any(|t| t.ssrc == ssrc))
+        .map(|e| (Arc::clone(e), ssrc))
+}
+
+type SenderRtcp = (Arc<Endpoint>, Vec<[u8; rtcp::REPORT_BLOCK_LEN]>, Vec<Vec<u8>>);
+
+fn sender_entry(list: &mut Vec<SenderRtcp>, sender: Arc<Endpoint>) -> &mut SenderRtcp {
+    let idx = match list.iter().position(|(ep, _, _)| ep.ufrag == sender.ufrag) {
+        Some(i) => i,
+        None    => { list.push((sender, Vec::new(), Vec::new())); list.len() - 1 }
+    };
+    &mut list[idx]
+}
+
 // ----------------------------------------------------------------------------
 // [릴레이] 같은 채널의 다른 엔드포인트에게 재암호화 후 전송
 // ----------------------------------------------------------------------------
@@ -363,20 +737,36 @@ async fn relay_to_channel(
 ) {
     // 모드별 릴레이 게이트
     //   PTT:        Floor holder만 릴레이 (Idle 또는 다른 holder면 drop)
+    //               Broadcast 발언 중에는 청취자 미디어를 항상 drop (단방향)
     //   Conference:  모든 발신자 통과 (Floor Control 미적용)
+    let mut rewritten: Option<Vec<u8>> = None;
     if let Some(ch) = channel_hub.get(channel_id) {
         if ch.is_ptt() {
-            let floor = ch.floor.lock().unwrap();
+            let mut floor = ch.floor.lock().unwrap();
+            let is_granted   = floor.may_relay(sender_user);
+            let virtual_talker = floor.policy.virtual_talker;
+            // holder 미디어 수신 기록 — media_inactive Revoke 판단 (run_floor_timer)
+            if is_granted {
+                floor.on_media(current_timestamp());
+            }
             let state        = &floor.state;
             let taken_by     = floor.floor_taken_by.as_deref().unwrap_or("none");
-            let is_granted   = *state == FloorControlState::Taken
-                && floor.floor_taken_by.as_deref() == Some(sender_user);
+            let broadcast    = floor.is_broadcast();
             trace!("[relay] floor check user={} state={:?} taken_by={} granted={}", sender_user, state, taken_by, is_granted);
             drop(floor);
             if !is_granted {
-                trace!("[relay] floor not granted for user={}, dropping", sender_user);
+                if broadcast {
+                    trace!("[relay] broadcast in progress, dropping listener media user={}", sender_user);
+                } else {
+                    trace!("[relay] floor not granted for user={}, dropping", sender_user);
+                }
                 return;
             }
+            // 가상 talker 스트림: holder SSRC / seq / timestamp → 채널 kind별 고정 SSRC로 연속 rewrite
+            if virtual_talker {
+                let kind = track_kind(peer_hub, sender_ufrag, plaintext);
+                rewritten = ch.rewrite_talker(kind, plaintext, current_timestamp());
+            }
         }
         // Conference 모드: floor check 없이 통과
     } else {
@@ -384,6 +774,18 @@ async fn relay_to_channel(
         return;
     }
 
+    // 릴레이되는 패킷만 재전송 버퍼에 보관 (원본 SSRC / seq 기준 — NACK 응답 시 수신자별 rewrite)
+    // 원본은 송신자에게 등록된 트랙 SSRC만, 가상 talker 스트림이면 rewrite된 패킷 기준 (수신자가 가상 SSRC / seq로 NACK)
+    match rewritten.as_deref() {
+        Some(p) => peer_hub.cache_rewritten_rtp(p, current_timestamp()),
+        None    => {
+            if let Some(sender) = peer_hub.get_by_ufrag(sender_ufrag) {
+                peer_hub.cache_rtp(&sender, plaintext, current_timestamp());
+            }
+        }
+    }
+    let plaintext = rewritten.as_deref().unwrap_or(plaintext);
+
     // Conference 모드: SSRC rewrite 경로
     // sender_ssrc를 기반으로 relay map에서 (receiver, consumer_ssrc) 매핑을 조회
     let is_conference = channel_hub.get(channel_id)
@@ -397,6 +799,13 @@ async fn relay_to_channel(
         let relay_targets = peer_hub.get_relay_targets(sender_ssrc);
 
         if !relay_targets.is_empty() {
+            // simulcast 레이어면 수신자별 선택 (목표 레이어 keyframe에서 전환, seq / timestamp 연속 rewrite)
+            let sender   = peer_hub.get_by_ufrag(sender_ufrag);
+            let layer    = sender.as_ref().and_then(|ep| ep.layer_of(sender_ssrc));
+            let keyframe = layer.and(sender.as_ref()).and_then(|ep| ep.is_keyframe(plaintext));
+            let now      = current_timestamp();
+            let mut wants_keyframe = false;
+
             // relay map이 있으면 SSRC rewrite 경로
             for (receiver_user_id, consumer_ssrc) in &relay_targets {
                 let target = match peer_hub.get_channel_endpoints(channel_id)
@@ -412,9 +821,20 @@ async fn relay_to_channel(
                     None    => { debug!("[relay-conf] user={} no addr", receiver_user_id); continue; }
                 };
 
-                // SSRC rewrite: RTP header offset 8~11
-                let mut rewritten = plaintext.to_vec();
-                rewritten[8..12].copy_from_slice(&consumer_ssrc.to_be_bytes());
+                let rewritten = match layer {
+                    Some(layer) => match peer_hub.forward_layer(receiver_user_id, *consumer_ssrc, layer, plaintext, keyframe, now) {
+                        // NACK은 consumer SSRC / rewrite된 seq로 오므로 rewrite된 패킷 기준으로 보관
+                        LayerForward::Send(p) => { peer_hub.cache_rewritten_rtp(&p, now); p }
+                        LayerForward::Drop    => continue,
+                        LayerForward::Waiting => { wants_keyframe = true; continue; }
+                    },
+                    None => {
+                        // SSRC rewrite: RTP header offset 8~11
+                        let mut rewritten = plaintext.to_vec();
+                        rewritten[8..12].copy_from_slice(&consumer_ssrc.to_be_bytes());
+                        rewritten
+                    }
+                };
 
                 let encrypted = {
                     let mut ctx = target.outbound_srtp.lock().unwrap();
@@ -431,6 +851,10 @@ async fn relay_to_channel(
                         encrypted.len(), receiver_user_id, sender_ssrc, consumer_ssrc);
                 }
             }
+            // 레이어 전환 대기 중인 수신자가 있으면 송신자에게 keyframe 요청 (KEYFRAME_REQUEST_MIN_MS 간격)
+            if let (true, Some(sender)) = (wants_keyframe, &sender) {
+                request_keyframe(sender, "simulcast layer switch").await;
+            }
             return;
         }
         // relay map이 아직 없으면 (아직 re-nego 전) fallthrough to broadcast
@@ -463,6 +887,15 @@ async fn relay_to_channel(
     }
 }
 
+/// 송신 엔드포인트에 등록된 트랙 종류 (미등록 SSRC는 audio — 하위 호환 단일 트랙)
+fn track_kind(peer_hub: &MediaPeerHub, sender_ufrag: &str, packet: &[u8]) -> TrackKind {
+    let (Some(ssrc), Some(ep)) = (rtp::ssrc(packet), peer_hub.get_by_ufrag(sender_ufrag)) else {
+        return TrackKind::Audio;
+    };
+    let tracks = ep.tracks.read().unwrap();
+    tracks.iter().find(|t| t.ssrc == ssrc).map_or(TrackKind::Audio, |t| t.kind.clone())
+}
+
 // ----------------------------------------------------------------------------
 // [STUN 유틸]
 // ----------------------------------------------------------------------------
@@ -596,6 +1029,169 @@ mod tests {
         assert!(matches!(classify(&[0xFF]), PacketKind::Srtp));
     }
 
+    #[test]
+    fn rtcp_conference_feedback_maps_back_to_sender() {
+        use crate::core::TrackKind;
+        use crate::media::rtcp::tests::sr;
+
+        let hub   = MediaPeerHub::new();
+        let alice = hub.insert("ua", "", "alice", "CH");
+        let bob   = hub.insert("ub", "", "bob", "CH");
+        alice.add_track(111, TrackKind::Video);
+        bob.add_track(222, TrackKind::Audio);
+        let consumer = hub.get_or_create_consumer_ssrc("bob", "alice", TrackKind::Video);
+        hub.rebuild_relay_map("CH");
+        let endpoints = hub.get_channel_endpoints("CH");
+
+        // bob: RR(block=consumer) + PLI(consumer) → alice에게 원본 SSRC로
+        let mut compound = rtcp::build_rr(222, &rtcp::report_blocks(&sr(0, &[consumer])));
+        compound.extend_from_slice(&rtcp::build_pli(222, consumer));
+        let routes = route_rtcp(&compound, &bob, &endpoints, &hub, None, true);
+        assert_eq!(routes.len(), 1);
+        assert_eq!(routes[0].0.user_id, "alice");
+        let pkts = rtcp::split_compound(&routes[0].1);
+        assert_eq!(rtcp::block_source_ssrc(&rtcp::report_blocks(pkts[0])[0]), 111);
+        assert_eq!(rtcp::feedback_target_ssrc(pkts[1]), Some(111));
+
+        // alice SR → bob에게 consumer SSRC로 (report block 제외)
+        let routes = route_rtcp(&sr(111, &[]), &alice, &endpoints, &hub, None, true);
+        assert_eq!(routes.len(), 1);
+        assert_eq!(routes[0].0.user_id, "bob");
+        assert_eq!(rtcp::sender_ssrc(&routes[0].1), Some(consumer));
+
+        // PTT 비 holder의 SR은 전달하지 않음
+        assert!(route_rtcp(&sr(111, &[]), &alice, &endpoints, &hub, None, false).is_empty());
+    }
+
+    #[test]
+    fn rtcp_ptt_feedback_uses_original_ssrc() {
+        use crate::core::TrackKind;
+
+        let hub   = MediaPeerHub::new();
+        let alice = hub.insert("ua", "", "alice", "CH");
+        let bob   = hub.insert("ub", "", "bob", "CH");
+        alice.add_track(111, TrackKind::Audio);
+        let endpoints = hub.get_channel_endpoints("CH");
+
+        let routes = route_rtcp(&rtcp::build_pli(1, 111), &bob, &endpoints, &hub, None, true);
+        assert_eq!(routes.len(), 1);
+        assert_eq!(routes[0].0.user_id, "alice");
+        // 모르는 SSRC는 버림
+        assert!(route_rtcp(&rtcp::build_pli(1, 999), &bob, &endpoints, &hub, None, true).is_empty());
+    }
+
+    #[test]
+    fn nack_answered_from_cache_and_misses_forwarded() {
+        use crate::core::TrackKind;
+
+        let hub   = MediaPeerHub::new();
+        let alice = hub.insert("ua", "", "alice", "CH");
+        let bob   = hub.insert("ub", "", "bob", "CH");
+        alice.add_track(111, TrackKind::Video);
+        bob.set_rtx_pts(vec![(96, 97)]);
+        let consumer = hub.get_or_create_consumer_ssrc("bob", "alice", TrackKind::Video);
+        let endpoints = hub.get_channel_endpoints("CH");
+
+        let now = current_timestamp();
+        for seq in [10u16, 11] {
+            let mut pkt = vec![0x80, 96];
+            pkt.extend_from_slice(&seq.to_be_bytes());
+            pkt.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0, 111, 0xAA, 0xBB]);
+            hub.cache_rtp(&alice, &pkt, now);
+        }
+
+        // RTX 스트림 미광고 → consumer SSRC로 원본 재전송, 12는 버퍼 미스 → NACK으로 남음
+        let (rest, rtx) = answer_nacks(&rtcp::build_nack(222, consumer, &[10, 12]), &bob, &endpoints, &hub, None);
+        assert_eq!(rtx.len(), 1);
+        assert_eq!((rtp::seq(&rtx[0]), rtp::ssrc(&rtx[0])), (Some(10), Some(consumer)));
+        assert_eq!(rtcp::nack_seqs(&rest), vec![12]);
+        let routes = route_rtcp(&rest, &bob, &endpoints, &hub, None, true);
+        assert_eq!(routes[0].0.user_id, "alice");
+        assert_eq!(rtcp::feedback_target_ssrc(rtcp::split_compound(&routes[0].1)[1]), Some(111));
+
+        // RTX 광고 후 → RTX PT / SSRC + OSN, 전부 응답했으면 NACK 제거
+        let rtx_ssrc = hub.get_or_create_rtx_ssrc("bob", consumer);
+        let (rest, rtx) = answer_nacks(&rtcp::build_nack(222, consumer, &[11]), &bob, &endpoints, &hub, None);
+        assert!(rest.is_empty());
+        assert_eq!((rtp::payload_type(&rtx[0]), rtp::ssrc(&rtx[0])), (Some(97), Some(rtx_ssrc)));
+        assert_eq!(&rtx[0][12..14], &11u16.to_be_bytes());
+
+        assert_eq!(bob.nack_requested.load(Ordering::Relaxed), 3);
+        assert_eq!(bob.rtx_sent.load(Ordering::Relaxed), 2);
+        assert_eq!(bob.nack_forwarded.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn simulcast_consumer_feedback_follows_current_layer() {
+        use crate::media::rtcp::tests::sr;
+
+        let hub   = MediaPeerHub::new();
+        let alice = hub.insert("ua", "", "alice", "CH");
+        let bob   = hub.insert("ub", "", "bob", "CH");
+        alice.set_simulcast(vec!["q".into(), "h".into()], Some(10));
+        assert!(alice.add_layer(111, "q"));
+        assert!(alice.add_layer(112, "h"));
+        let consumer = hub.get_or_create_consumer_ssrc("bob", "alice", TrackKind::Video);
+        hub.rebuild_relay_map("CH");
+        let endpoints = hub.get_channel_endpoints("CH");
+
+        // bob은 q 레이어 수신 중 (h는 아직 미수신)
+        let now = current_timestamp();
+        let pkt = vec![0x80, 96, 0, 10, 0, 0, 0, 0, 0, 0, 0, 111, 0xAA];
+        let LayerForward::Send(out) = hub.forward_layer("bob", consumer, (0, 2), &pkt, Some(true), now) else { panic!() };
+        hub.cache_rewritten_rtp(&out, now);
+
+        // PLI(consumer) → alice에게 지금 레이어 SSRC로
+        let routes = route_rtcp(&rtcp::build_pli(1, consumer), &bob, &endpoints, &hub, None, true);
+        assert_eq!(routes[0].0.user_id, "alice");
+        assert_eq!(rtcp::feedback_target_ssrc(rtcp::split_compound(&routes[0].1)[1]), Some(111));
+
+        // SR은 지금 레이어 것만 consumer SSRC로
+        assert!(route_rtcp(&sr(112, &[]), &alice, &endpoints, &hub, None, true).is_empty());
+        let routes = route_rtcp(&sr(111, &[]), &alice, &endpoints, &hub, None, true);
+        assert_eq!(rtcp::sender_ssrc(&routes[0].1), Some(consumer));
+
+        // NACK(consumer) → rewrite된 버퍼로 응답, 미스는 원 송신자로 전달하지 않음
+        let (rest, rtx) = answer_nacks(&rtcp::build_nack(222, consumer, &[10, 11]), &bob, &endpoints, &hub, None);
+        assert_eq!((rtp::seq(&rtx[0]), rtp::ssrc(&rtx[0])), (Some(10), Some(consumer)));
+        assert!(rest.is_empty());
+    }
+
+    #[test]
+    fn virtual_talker_feedback_maps_to_current_holder() {
+        use crate::core::ChannelMode;
+        use crate::media::rtcp::tests::sr;
+
+        let hub   = MediaPeerHub::new();
+        let alice = hub.insert("ua", "", "alice", "CH");
+        let bob   = hub.insert("ub", "", "bob", "CH");
+        alice.add_track(111, TrackKind::Audio);
+        let endpoints = hub.get_channel_endpoints("CH");
+        let ch        = Channel::new("CH".into(), "0001".into(), "ch".into(), ChannelMode::PTT, 10);
+        let vssrc     = ch.talker_ssrc(TrackKind::Audio);
+
+        let mut pkt = vec![0x80, 111, 0, 10, 0, 0, 0x03, 0xE8, 0, 0, 0, 111, 0xAA];
+        let out = ch.rewrite_talker(TrackKind::Audio, &pkt, current_timestamp()).unwrap();
+        hub.cache_rewritten_rtp(&out, current_timestamp());
+        pkt[8..12].copy_from_slice(&vssrc.to_be_bytes());
+
+        // PLI(가상 SSRC) → 현재 talker에게 원본 SSRC로
+        let routes = route_rtcp(&rtcp::build_pli(1, vssrc), &bob, &endpoints, &hub, Some(&ch), true);
+        assert_eq!(routes[0].0.user_id, "alice");
+        assert_eq!(rtcp::feedback_target_ssrc(rtcp::split_compound(&routes[0].1)[1]), Some(111));
+
+        // NACK(가상 SSRC) → 버퍼 응답, 미스는 원 송신자로 전달하지 않음
+        let (rest, rtx) = answer_nacks(&rtcp::build_nack(1, vssrc, &[10, 11]), &bob, &endpoints, &hub, Some(&ch));
+        assert!(rest.is_empty());
+        assert_eq!(rtx, vec![pkt]);
+
+        // holder SR → 가상 SSRC / timestamp
+        let routes = route_rtcp(&sr(111, &[]), &alice, &endpoints, &hub, Some(&ch), true);
+        assert_eq!(routes[0].0.user_id, "bob");
+        assert_eq!(rtcp::sender_ssrc(&routes[0].1), Some(vssrc));
+        assert_eq!(rtcp::sr_rtp_timestamp(&routes[0].1), rtcp::sr_rtp_timestamp(&sr(111, &[])));
+    }
+
     #[test]
     fn test_classify_unknown() {
         assert!(matches!(classify(&[0x50]), PacketKind::Unknown));
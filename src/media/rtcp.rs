@@ -0,0 +1,260 @@
+// author: kodeholic (powered by Claude)
+// RTCP 파싱 / 조립 유틸 — SRTCP 복호화 후 평문 compound 패킷에 사용
+//
+// 공통 헤더 (RFC 3550):
+//   byte[0]: V(2)|P(1)|RC 또는 FMT(5)
+//   byte[1]: PT — 200 SR / 201 RR / 202 SDES / 203 BYE / 204 APP / 205 RTPFB / 206 PSFB
+//   byte[2..3]: length (32bit word 수 - 1)
+//   byte[4..7]: 송신자 SSRC
+//
+// SR  : 헤더(8) + sender info(20: NTP 8 / RTP ts 4 / packet count 4 / octet count 4) + report block × RC
+// RR  : 헤더(8) + report block × RC
+//   report block(24B)의 첫 4B = 보고 대상(source) SSRC, 다음 1B = fraction lost (0~255 = 0~100%)
+// RTPFB / PSFB (RFC 4585): 헤더(8) + media source SSRC(4) + FCI
+//   RTPFB FMT 1 = Generic NACK, PSFB FMT 1 = PLI
+//     NACK FCI 항목(4B) = PID(2B, 유실 seq) | BLP(2B, PID+1~PID+16 유실 비트마스크)
+//   PSFB FMT 4 = FIR (RFC 5104) — media source SSRC는 0, 대상 SSRC는 FCI 항목(8B)의 첫 4B
+
+pub const PT_SR:    u8 = 200;
+pub const PT_RR:    u8 = 201;
+pub const PT_RTPFB: u8 = 205;
+pub const PT_PSFB:  u8 = 206;
+
+pub const FMT_NACK: u8 = 1;
+pub const FMT_PLI:  u8 = 1;
+pub const FMT_FIR:  u8 = 4;
+
+pub const REPORT_BLOCK_LEN: usize = 24;
+const SR_SENDER_INFO_END:   usize = 28;
+
+/// compound RTCP를 개별 패킷으로 분리 (길이 필드가 잘못된 지점에서 중단)
+pub fn split_compound(buf: &[u8]) -> Vec<&[u8]> {
+    let mut packets = Vec::new();
+    let mut offset  = 0;
+    while offset + 4 <= buf.len() {
+        if buf[offset] >> 6 != 2 { break; }
+        let len = (u16::from_be_bytes([buf[offset + 2], buf[offset + 3]]) as usize + 1) * 4;
+        match buf.get(offset..offset + len) {
+            Some(pkt) => packets.push(pkt),
+            None      => break,
+        }
+        offset += len;
+    }
+    packets
+}
+
+pub fn packet_type(pkt: &[u8]) -> u8 {
+    pkt.get(1).copied().unwrap_or(0)
+}
+
+/// RC (SR/RR) 또는 FMT (RTPFB/PSFB)
+pub fn count_or_fmt(pkt: &[u8]) -> u8 {
+    pkt.first().map(|b| b & 0x1F).unwrap_or(0)
+}
+
+pub fn sender_ssrc(pkt: &[u8]) -> Option<u32> {
+    read_u32(pkt, 4)
+}
+
+/// SR → report block을 뺀 sender info만의 SR (송신자 SSRC 교체)
+/// 수신자에게 전달하는 용도 — report block은 SR 송신자가 받은 스트림에 대한 것이므로 제외
+pub fn sr_sender_info(pkt: &[u8], ssrc: u32) -> Option<Vec<u8>> {
+    if packet_type(pkt) != PT_SR || pkt.len() < SR_SENDER_INFO_END {
+        return None;
+    }
+    let mut out = vec![0x80, PT_SR, 0, 6];
+    out.extend_from_slice(&ssrc.to_be_bytes());
+    out.extend_from_slice(&pkt[8..SR_SENDER_INFO_END]);
+    Some(out)
+}
+
+/// SR sender info의 RTP timestamp
+pub fn sr_rtp_timestamp(pkt: &[u8]) -> Option<u32> {
+    if packet_type(pkt) != PT_SR || pkt.len() < SR_SENDER_INFO_END {
+        return None;
+    }
+    Some(u32::from_be_bytes([pkt[16], pkt[17], pkt[18], pkt[19]]))
+}
+
+/// SR / RR의 report block 목록 (그 외 PT면 빈 Vec)
+pub fn report_blocks(pkt: &[u8]) -> Vec<[u8; REPORT_BLOCK_LEN]> {
+    let start = match packet_type(pkt) {
+        PT_SR => SR_SENDER_INFO_END,
+        PT_RR => 8,
+        _     => return Vec::new(),
+    };
+    (0..count_or_fmt(pkt) as usize)
+        .filter_map(|i| {
+            let at = start + i * REPORT_BLOCK_LEN;
+            pkt.get(at..at + REPORT_BLOCK_LEN)?.try_into().ok()
+        })
+        .collect()
+}
+
+/// report block 보고 대상 SSRC
+pub fn block_source_ssrc(block: &[u8; REPORT_BLOCK_LEN]) -> u32 {
+    u32::from_be_bytes([block[0], block[1], block[2], block[3]])
+}
+
+/// report block fraction lost (직전 보고 이후 손실 비율 × 256)
+pub fn block_fraction_lost(block: &[u8; REPORT_BLOCK_LEN]) -> u8 {
+    block[4]
+}
+
+/// report block들로 RR 조립 (최대 31개 — RC 5bit)
+pub fn build_rr(reporter_ssrc: u32, blocks: &[[u8; REPORT_BLOCK_LEN]]) -> Vec<u8> {
+    let blocks = &blocks[..blocks.len().min(31)];
+    let words  = 1 + blocks.len() * REPORT_BLOCK_LEN / 4;
+    let mut out = vec![0x80 | blocks.len() as u8, PT_RR];
+    out.extend_from_slice(&(words as u16).to_be_bytes());
+    out.extend_from_slice(&reporter_ssrc.to_be_bytes());
+    for b in blocks {
+        out.extend_from_slice(b);
+    }
+    out
+}
+
+/// feedback(RTPFB / PSFB)의 대상 미디어 SSRC — FIR은 첫 FCI 항목
+pub fn feedback_target_ssrc(pkt: &[u8]) -> Option<u32> {
+    match (packet_type(pkt), count_or_fmt(pkt)) {
+        (PT_PSFB, FMT_FIR)   => read_u32(pkt, 12),
+        (PT_RTPFB | PT_PSFB, _) => read_u32(pkt, 8),
+        _                    => None,
+    }
+}
+
+/// feedback 대상 SSRC 교체 — media source 필드 + FIR이면 모든 FCI 항목
+pub fn rewrite_feedback_target(pkt: &[u8], ssrc: u32) -> Vec<u8> {
+    let mut out = pkt.to_vec();
+    if packet_type(pkt) == PT_PSFB && count_or_fmt(pkt) == FMT_FIR {
+        let mut at = 12;
+        while at + 8 <= out.len() {
+            out[at..at + 4].copy_from_slice(&ssrc.to_be_bytes());
+            at += 8;
+        }
+    } else if out.len() >= 12 {
+        out[8..12].copy_from_slice(&ssrc.to_be_bytes());
+    }
+    out
+}
+
+/// PLI 조립 — FCI 없음 (keyframe 요청)
+pub fn build_pli(sender_ssrc: u32, media_ssrc: u32) -> Vec<u8> {
+    let mut out = vec![0x80 | FMT_PLI, PT_PSFB, 0, 2];
+    out.extend_from_slice(&sender_ssrc.to_be_bytes());
+    out.extend_from_slice(&media_ssrc.to_be_bytes());
+    out
+}
+
+/// Generic NACK이 요청한 seq 목록 (FCI 순서대로, NACK이 아니면 빈 Vec)
+pub fn nack_seqs(pkt: &[u8]) -> Vec<u16> {
+    if packet_type(pkt) != PT_RTPFB || count_or_fmt(pkt) != FMT_NACK {
+        return Vec::new();
+    }
+    let mut seqs = Vec::new();
+    for fci in pkt.get(12..).unwrap_or_default().chunks_exact(4) {
+        let pid = u16::from_be_bytes([fci[0], fci[1]]);
+        let blp = u16::from_be_bytes([fci[2], fci[3]]);
+        seqs.push(pid);
+        seqs.extend((0..16).filter(|i| blp & (1 << i) != 0).map(|i| pid.wrapping_add(i + 1)));
+    }
+    seqs
+}
+
+/// seq 목록으로 Generic NACK 조립 — 앞 항목 PID+1~16 범위면 BLP 비트로 합침
+pub fn build_nack(sender_ssrc: u32, media_ssrc: u32, seqs: &[u16]) -> Vec<u8> {
+    let mut fci: Vec<(u16, u16)> = Vec::new();
+    for &seq in seqs {
+        match fci.last_mut() {
+            Some((pid, blp)) if (1..=16).contains(&seq.wrapping_sub(*pid)) => {
+                *blp |= 1 << (seq.wrapping_sub(*pid) - 1);
+            }
+            _ => fci.push((seq, 0)),
+        }
+    }
+    let mut out = vec![0x80 | FMT_NACK, PT_RTPFB];
+    out.extend_from_slice(&(2 + fci.len() as u16).to_be_bytes());
+    out.extend_from_slice(&sender_ssrc.to_be_bytes());
+    out.extend_from_slice(&media_ssrc.to_be_bytes());
+    for (pid, blp) in fci {
+        out.extend_from_slice(&pid.to_be_bytes());
+        out.extend_from_slice(&blp.to_be_bytes());
+    }
+    out
+}
+
+fn read_u32(pkt: &[u8], at: usize) -> Option<u32> {
+    let b = pkt.get(at..at + 4)?;
+    Some(u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    pub(crate) fn sr(ssrc: u32, blocks: &[u32]) -> Vec<u8> {
+        let words = 6 + blocks.len() * 6;
+        let mut p = vec![0x80 | blocks.len() as u8, PT_SR, 0, words as u8];
+        p.extend_from_slice(&ssrc.to_be_bytes());
+        p.extend_from_slice(&[7; 20]);
+        for b in blocks {
+            p.extend_from_slice(&b.to_be_bytes());
+            p.extend_from_slice(&[1; 20]);
+        }
+        p
+    }
+
+    #[test]
+    fn split_sr_and_feedback_compound() {
+        let mut compound = sr(10, &[20, 30]);
+        compound.extend_from_slice(&build_pli(10, 40));
+        compound.extend_from_slice(&[0x80, PT_RR, 0, 9]); // length 초과 — 중단
+
+        let pkts = split_compound(&compound);
+        assert_eq!(pkts.len(), 2);
+        assert_eq!(packet_type(pkts[0]), PT_SR);
+        assert_eq!(sender_ssrc(pkts[0]), Some(10));
+        let blocks = report_blocks(pkts[0]);
+        assert_eq!(blocks.iter().map(block_source_ssrc).collect::<Vec<_>>(), vec![20, 30]);
+        assert_eq!(feedback_target_ssrc(pkts[1]), Some(40));
+    }
+
+    #[test]
+    fn sr_sender_info_strips_blocks_and_rewrites_ssrc() {
+        let out = sr_sender_info(&sr(10, &[20]), 99).unwrap();
+        assert_eq!(out.len(), 28);
+        assert_eq!(count_or_fmt(&out), 0);
+        assert_eq!(sender_ssrc(&out), Some(99));
+        assert_eq!(&out[8..28], &[7; 20]);
+        assert!(sr_sender_info(&build_pli(1, 2), 99).is_none());
+    }
+
+    #[test]
+    fn build_rr_and_rewrite_fir() {
+        let blocks = report_blocks(&sr(10, &[20, 30]));
+        let rr = build_rr(5, &blocks);
+        assert_eq!(split_compound(&rr).len(), 1);
+        assert_eq!(report_blocks(&rr), blocks);
+
+        // FIR: media source 0, FCI 대상 SSRC 교체
+        let mut fir = vec![0x80 | FMT_FIR, PT_PSFB, 0, 4, 0, 0, 0, 5, 0, 0, 0, 0];
+        fir.extend_from_slice(&[0, 0, 0, 40, 3, 0, 0, 0]);
+        assert_eq!(feedback_target_ssrc(&fir), Some(40));
+        let out = rewrite_feedback_target(&fir, 77);
+        assert_eq!(feedback_target_ssrc(&out), Some(77));
+        assert_eq!(&out[8..12], &[0; 4]);
+
+        assert_eq!(feedback_target_ssrc(&rewrite_feedback_target(&build_pli(1, 2), 3)), Some(3));
+    }
+
+    #[test]
+    fn nack_roundtrip_packs_blp() {
+        let seqs = vec![65535, 0, 2, 100];
+        let nack = build_nack(5, 40, &seqs);
+        assert_eq!(split_compound(&nack).len(), 1);
+        assert_eq!(feedback_target_ssrc(&nack), Some(40));
+        assert_eq!(nack.len(), 12 + 2 * 4); // seq wrap 포함 BLP 1개 + 100 별도
+        assert_eq!(nack_seqs(&nack), seqs);
+        assert!(nack_seqs(&build_pli(1, 2)).is_empty());
+    }
+}
@@ -0,0 +1,376 @@
+// author: kodeholic (powered by Claude)
+// RTP 헤더 파싱 유틸 — 릴레이 핫패스에서 복호화된 평문 RTP에 사용
+//
+// 헤더 확장 (RFC 8285):
+//   X bit(byte0 & 0x10)가 켜지면 고정 헤더(12B) + CSRC(4B × CC) 뒤에
+//   profile(2B) | length(2B, 32bit word 수) | 확장 항목들
+//   profile 0xBEDE → one-byte 헤더: ID(4bit) | L(4bit, 길이-1), ID 0 = padding, 15 = 중단
+//   profile 0x100X → two-byte 헤더: ID(1B) | 길이(1B)
+//
+// audio level (RFC 6464, urn:ietf:params:rtp-hdrext:ssrc-audio-level):
+//   데이터 1B = V(1bit, voice activity) | level(7bit, -dBov — 0이 최대, 127이 무음)
+//
+// RTX (RFC 4588):
+//   원본 헤더(확장 포함)에서 PT / seq / SSRC만 RTX 스트림 값으로 교체
+//   payload = OSN(원본 seq 2B) + 원본 payload
+//
+// 가상 talker 스트림 (PTT FloorPolicy.virtual_talker):
+//   kind별 고정 서버 SSRC 하나로 모든 talker의 RTP를 내보냄 — talker가 바뀌어도 수신 jitter buffer 유지
+//   source SSRC가 바뀐 첫 패킷에서 offset 재계산
+//     seq : 직전 출력 seq + 1
+//     ts  : 직전 출력 ts + 경과 시간 × clock rate (최소 1)
+//
+// keyframe 판별 (simulcast 레이어 전환 시점):
+//   VP8  (RFC 7741) — payload descriptor 뒤 VP8 헤더 P bit 0, 파티션 시작(S=1, PID=0) 패킷만
+//   VP9  (RFC 9628) — descriptor P bit(inter-picture) 0 + B bit(프레임 시작) 1
+//   H264 (RFC 6184) — IDR(5) / SPS(7) NAL, STAP-A 내부 NAL, FU-A 시작 조각
+
+/// RTP payload type (byte 1 하위 7bit)
+pub fn payload_type(packet: &[u8]) -> Option<u8> {
+    packet.get(1).map(|b| b & 0x7F)
+}
+
+/// RTP sequence number (byte 2~3)
+pub fn seq(packet: &[u8]) -> Option<u16> {
+    let b = packet.get(2..4)?;
+    Some(u16::from_be_bytes([b[0], b[1]]))
+}
+
+/// RTP timestamp (byte 4~7)
+pub fn timestamp(packet: &[u8]) -> Option<u32> {
+    let b = packet.get(4..8)?;
+    Some(u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+/// RTP SSRC (byte 8~11)
+pub fn ssrc(packet: &[u8]) -> Option<u32> {
+    let b = packet.get(8..12)?;
+    Some(u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+/// 헤더 확장에서 id 항목 데이터 조회 (확장 없음 / 해당 id 없음 / 손상된 패킷이면 None)
+pub fn header_extension(packet: &[u8], id: u8) -> Option<&[u8]> {
+    if packet.len() < 12 || packet[0] & 0x10 == 0 {
+        return None;
+    }
+    let offset  = 12 + (packet[0] & 0x0F) as usize * 4;
+    let header  = packet.get(offset..offset + 4)?;
+    let profile = u16::from_be_bytes([header[0], header[1]]);
+    let len     = u16::from_be_bytes([header[2], header[3]]) as usize * 4;
+    let body    = packet.get(offset + 4..offset + 4 + len)?;
+
+    let one_byte = profile == 0xBEDE;
+    if !one_byte && profile & 0xFFF0 != 0x1000 {
+        return None;
+    }
+
+    let mut i = 0;
+    while i < body.len() {
+        if body[i] == 0 { i += 1; continue; } // padding
+        let (eid, data_len, hdr_len) = if one_byte {
+            if body[i] >> 4 == 15 { break; }
+            (body[i] >> 4, (body[i] & 0x0F) as usize + 1, 1)
+        } else {
+            (body[i], *body.get(i + 1)? as usize, 2)
+        };
+        let data = body.get(i + hdr_len..i + hdr_len + data_len)?;
+        if eid == id {
+            return Some(data);
+        }
+        i += hdr_len + data_len;
+    }
+    None
+}
+
+/// RFC 6464 audio level (-dBov, 0~127) — 확장이 없으면 None
+pub fn audio_level(packet: &[u8], ext_id: u8) -> Option<u8> {
+    header_extension(packet, ext_id)?.first().map(|b| b & 0x7F)
+}
+
+/// payload 시작 위치 (고정 헤더 + CSRC + 헤더 확장)
+pub fn payload_offset(packet: &[u8]) -> Option<usize> {
+    let mut offset = 12 + (*packet.first()? & 0x0F) as usize * 4;
+    if packet[0] & 0x10 != 0 {
+        let header = packet.get(offset..offset + 4)?;
+        offset += 4 + u16::from_be_bytes([header[2], header[3]]) as usize * 4;
+    }
+    (offset <= packet.len()).then_some(offset)
+}
+
+/// 원본 RTP → RTX 패킷 (RFC 4588 — PT / seq / SSRC 교체 + payload 앞에 OSN)
+pub fn build_rtx(packet: &[u8], rtx_pt: u8, rtx_ssrc: u32, rtx_seq: u16) -> Option<Vec<u8>> {
+    let offset = payload_offset(packet)?;
+    let mut out = Vec::with_capacity(packet.len() + 2);
+    out.extend_from_slice(&packet[..offset]);
+    out[1] = (out[1] & 0x80) | (rtx_pt & 0x7F);
+    out[2..4].copy_from_slice(&rtx_seq.to_be_bytes());
+    out[8..12].copy_from_slice(&rtx_ssrc.to_be_bytes());
+    out.extend_from_slice(&packet[2..4]); // OSN
+    out.extend_from_slice(&packet[offset..]);
+    Some(out)
+}
+
+/// keyframe 시작 패킷인지 — codec은 a=rtpmap 인코딩 이름, 판별할 수 없는 코덱이면 None
+pub fn is_keyframe(packet: &[u8], codec: &str) -> Option<bool> {
+    let payload = packet.get(payload_offset(packet)?..)?;
+    let first   = *payload.first()?;
+    let key = if codec.eq_ignore_ascii_case("VP8") {
+        // X|R|N|S|R|PID(3) [I|L|T|K|RSV] [PictureID 1~2B] [TL0PICIDX] [TID|Y|KEYIDX]
+        let mut i = 1;
+        if first & 0x80 != 0 {
+            let ext = *payload.get(1)?;
+            i = 2;
+            if ext & 0x80 != 0 { i += if *payload.get(i)? & 0x80 != 0 { 2 } else { 1 }; }
+            if ext & 0x40 != 0 { i += 1; }
+            if ext & 0x30 != 0 { i += 1; }
+        }
+        first & 0x10 != 0 && first & 0x07 == 0 && *payload.get(i)? & 0x01 == 0
+    } else if codec.eq_ignore_ascii_case("VP9") {
+        // I|P|L|F|B|E|V|Z
+        first & 0x40 == 0 && first & 0x08 != 0
+    } else if codec.eq_ignore_ascii_case("H264") {
+        match first & 0x1F {
+            5 | 7 => true,
+            // STAP-A: [size 2B][NAL]...
+            24 => {
+                let mut i = 1;
+                let mut found = false;
+                while let Some(size) = payload.get(i..i + 2) {
+                    let size = u16::from_be_bytes([size[0], size[1]]) as usize;
+                    if matches!(payload.get(i + 2).map(|n| n & 0x1F), Some(5 | 7)) {
+                        found = true;
+                        break;
+                    }
+                    i += 2 + size;
+                }
+                found
+            }
+            // FU-A: 시작 조각(S bit)의 원래 NAL 종류
+            28 => payload.get(1).is_some_and(|h| h & 0x80 != 0 && h & 0x1F == 5),
+            _  => false,
+        }
+    } else {
+        return None;
+    };
+    Some(key)
+}
+
+// ----------------------------------------------------------------------------
+// [재전송 버퍼] 송신 SSRC별 최근 평문 RTP — seq % 용량 슬롯에 덮어쓰기
+// NACK 응답 시 seq가 일치하고 max_age 이내인 것만 반환 (오래된 패킷 재전송은 디코더에 무의미)
+// ----------------------------------------------------------------------------
+
+pub struct RtpRing {
+    slots:   Vec<Option<(u16, u64, Vec<u8>)>>, // (seq, 저장 시각, 평문 RTP)
+    last_at: u64,                              // 마지막 저장 시각 — 유휴 버퍼 정리 기준
+}
+
+impl RtpRing {
+    pub fn new(capacity: usize) -> Self {
+        Self { slots: vec![None; capacity.max(1)], last_at: 0 }
+    }
+
+    pub fn push(&mut self, packet: &[u8], now: u64) {
+        if let Some(seq) = seq(packet) {
+            let idx = seq as usize % self.slots.len();
+            self.slots[idx] = Some((seq, now, packet.to_vec()));
+            self.last_at = now;
+        }
+    }
+
+    pub fn last_at(&self) -> u64 {
+        self.last_at
+    }
+
+    pub fn get(&self, seq: u16, now: u64, max_age_ms: u64) -> Option<&[u8]> {
+        match &self.slots[seq as usize % self.slots.len()] {
+            Some((s, at, pkt)) if *s == seq && now.saturating_sub(*at) <= max_age_ms => Some(pkt),
+            _ => None,
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// [가상 talker 스트림] 채널 kind별 1개 — Channel.talker
+// ----------------------------------------------------------------------------
+
+pub struct TalkerStream {
+    pub ssrc:   u32,          // 서버 고정 SSRC (join SDP answer에 광고)
+    clock_rate: u32,          // timestamp 단위 (audio 48000 / video 90000)
+    source:     Option<u32>,  // 현재 출력 중인 talker의 원본 SSRC
+    seq_offset: u16,
+    ts_offset:  u32,
+    last_seq:   u16,          // 직전 출력 seq / ts / 시각 — 다음 talker offset 기준
+    last_ts:    u32,
+    last_at:    u64,
+}
+
+impl TalkerStream {
+    pub fn new(ssrc: u32, clock_rate: u32) -> Self {
+        Self { ssrc, clock_rate, source: None, seq_offset: 0, ts_offset: 0, last_seq: 0, last_ts: 0, last_at: 0 }
+    }
+
+    /// 현재 talker의 원본 SSRC (RTCP feedback 역매핑용)
+    pub fn source(&self) -> Option<u32> {
+        self.source
+    }
+
+    /// 원본 RTP → 가상 스트림 RTP (SSRC / seq / timestamp 교체)
+    pub fn rewrite(&mut self, packet: &[u8], now: u64) -> Option<Vec<u8>> {
+        let (src, in_seq, in_ts) = (ssrc(packet)?, seq(packet)?, timestamp(packet)?);
+        if self.source != Some(src) {
+            if self.last_at != 0 {
+                let elapsed = now.saturating_sub(self.last_at) * self.clock_rate as u64 / 1000;
+                let next_ts = self.last_ts.wrapping_add((elapsed as u32).max(1));
+                self.seq_offset = self.last_seq.wrapping_add(1).wrapping_sub(in_seq);
+                self.ts_offset  = next_ts.wrapping_sub(in_ts);
+            }
+            self.source = Some(src);
+        }
+
+        let out_seq = in_seq.wrapping_add(self.seq_offset);
+        let out_ts  = in_ts.wrapping_add(self.ts_offset);
+        // 순서 뒤바뀐 패킷은 기준값을 되돌리지 않음
+        if self.last_at == 0 || (out_seq.wrapping_sub(self.last_seq) as i16) > 0 {
+            self.last_seq = out_seq;
+            self.last_ts  = out_ts;
+        }
+        self.last_at = now;
+
+        let mut out = packet.to_vec();
+        out[2..4].copy_from_slice(&out_seq.to_be_bytes());
+        out[4..8].copy_from_slice(&out_ts.to_be_bytes());
+        out[8..12].copy_from_slice(&self.ssrc.to_be_bytes());
+        Some(out)
+    }
+
+    /// 현재 talker의 SR RTP timestamp → 가상 스트림 timestamp (talker가 아니면 None)
+    pub fn map_timestamp(&self, source: u32, ts: u32) -> Option<u32> {
+        (self.source == Some(source)).then(|| ts.wrapping_add(self.ts_offset))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// one-byte 확장(mid id=4 "0" + audio level id=1) 달린 RTP
+    fn rtp_with_level(level: u8) -> Vec<u8> {
+        let mut p = vec![0x90, 111, 0, 1, 0, 0, 0, 0, 0x11, 0x22, 0x33, 0x44];
+        p.extend_from_slice(&[0xBE, 0xDE, 0, 2]);
+        p.extend_from_slice(&[0x40, b'0', 0x10, 0x80 | level, 0, 0, 0, 0]);
+        p.extend_from_slice(&[0xAA; 20]); // payload
+        p
+    }
+
+    #[test]
+    fn parses_one_byte_audio_level() {
+        let p = rtp_with_level(30);
+        assert_eq!(ssrc(&p), Some(0x1122_3344));
+        assert_eq!(audio_level(&p, 1), Some(30));
+        assert_eq!(header_extension(&p, 4), Some(&b"0"[..]));
+        assert_eq!(audio_level(&p, 3), None);
+    }
+
+    #[test]
+    fn parses_two_byte_header_and_rejects_malformed() {
+        let mut p = vec![0x90, 111, 0, 1, 0, 0, 0, 0, 0, 0, 0, 1];
+        p.extend_from_slice(&[0x10, 0x00, 0, 1, 5, 1, 0x7F, 0]);
+        assert_eq!(audio_level(&p, 5), Some(127));
+
+        // X bit 없음
+        let mut no_ext = rtp_with_level(10);
+        no_ext[0] = 0x80;
+        assert_eq!(audio_level(&no_ext, 1), None);
+
+        // length가 패킷보다 김
+        let mut short = rtp_with_level(10);
+        short.truncate(16);
+        assert_eq!(audio_level(&short, 1), None);
+    }
+
+    #[test]
+    fn rtx_keeps_extension_and_prepends_osn() {
+        let mut p = rtp_with_level(30);
+        p[1] = 0x80 | 96; // marker + VP8
+        p[2..4].copy_from_slice(&1234u16.to_be_bytes());
+        assert_eq!(payload_offset(&p), Some(24));
+
+        let rtx = build_rtx(&p, 97, 0xAABB_CCDD, 7).unwrap();
+        assert_eq!(rtx[1], 0x80 | 97);
+        assert_eq!(seq(&rtx), Some(7));
+        assert_eq!(ssrc(&rtx), Some(0xAABB_CCDD));
+        assert_eq!(header_extension(&rtx, 4), Some(&b"0"[..]));
+        assert_eq!(&rtx[24..26], &1234u16.to_be_bytes());
+        assert_eq!(&rtx[26..], &p[24..]);
+    }
+
+    #[test]
+    fn talker_stream_continuous_across_sources() {
+        let pkt = |src: u32, seq: u16, ts: u32| {
+            let mut p = vec![0x80, 111];
+            p.extend_from_slice(&seq.to_be_bytes());
+            p.extend_from_slice(&ts.to_be_bytes());
+            p.extend_from_slice(&src.to_be_bytes());
+            p.extend_from_slice(&[0xAA; 4]);
+            p
+        };
+        let mut s = TalkerStream::new(0x5555, 48_000);
+        let a1 = s.rewrite(&pkt(1, 100, 1_000), 10_000).unwrap();
+        let a2 = s.rewrite(&pkt(1, 101, 1_960), 10_020).unwrap();
+        assert_eq!((ssrc(&a1), seq(&a1), timestamp(&a1)), (Some(0x5555), Some(100), Some(1_000)));
+        assert_eq!((seq(&a2), timestamp(&a2)), (Some(101), Some(1_960)));
+        assert_eq!(&a2[12..], &[0xAA; 4]);
+
+        // 500ms 뒤 다른 talker — seq +1, ts +500ms × 48kHz
+        let b1 = s.rewrite(&pkt(2, 9_000, 77), 10_520).unwrap();
+        let b2 = s.rewrite(&pkt(2, 9_001, 1_037), 10_540).unwrap();
+        assert_eq!((seq(&b1), timestamp(&b1)), (Some(102), Some(1_960 + 24_000)));
+        assert_eq!((seq(&b2), timestamp(&b2)), (Some(103), Some(1_960 + 24_000 + 960)));
+        assert_eq!(s.source(), Some(2));
+        assert_eq!(s.map_timestamp(2, 77), Some(1_960 + 24_000));
+        assert_eq!(s.map_timestamp(1, 77), None);
+
+        // 순서 뒤바뀐 이전 패킷은 기준값 유지
+        s.rewrite(&pkt(2, 9_000, 77), 10_550).unwrap();
+        let b3 = s.rewrite(&pkt(2, 9_002, 1_997), 10_560).unwrap();
+        assert_eq!(seq(&b3), Some(104));
+    }
+
+    #[test]
+    fn keyframe_detected_per_codec() {
+        let pkt = |payload: &[u8]| {
+            let mut p = vec![0x80, 96, 0, 1, 0, 0, 0, 0, 0, 0, 0, 1];
+            p.extend_from_slice(payload);
+            p
+        };
+        // VP8: X + I(15bit PictureID) + S, VP8 헤더 P bit
+        assert_eq!(is_keyframe(&pkt(&[0x90, 0x80, 0x81, 0x23, 0x10]), "VP8"), Some(true));
+        assert_eq!(is_keyframe(&pkt(&[0x90, 0x80, 0x81, 0x23, 0x11]), "VP8"), Some(false));
+        assert_eq!(is_keyframe(&pkt(&[0x80, 0x80, 0x81, 0x23, 0x10]), "VP8"), Some(false)); // 파티션 중간
+        // VP9: P=0, B=1
+        assert_eq!(is_keyframe(&pkt(&[0x08]), "VP9"), Some(true));
+        assert_eq!(is_keyframe(&pkt(&[0x48]), "VP9"), Some(false));
+        // H264: IDR / STAP-A(SPS) / FU-A 시작 조각 / 일반 P 슬라이스
+        assert_eq!(is_keyframe(&pkt(&[0x65, 0xAA]), "H264"), Some(true));
+        assert_eq!(is_keyframe(&pkt(&[0x78, 0, 2, 0x67, 0x42, 0, 1, 0x68]), "H264"), Some(true));
+        assert_eq!(is_keyframe(&pkt(&[0x7C, 0x85, 0xAA]), "H264"), Some(true));
+        assert_eq!(is_keyframe(&pkt(&[0x7C, 0x05, 0xAA]), "H264"), Some(false));
+        assert_eq!(is_keyframe(&pkt(&[0x41, 0xAA]), "H264"), Some(false));
+        assert_eq!(is_keyframe(&pkt(&[0x10]), "AV1"), None);
+    }
+
+    #[test]
+    fn ring_returns_only_matching_fresh_seq() {
+        let mut ring = RtpRing::new(4);
+        let mut p = rtp_with_level(0);
+        for s in [10u16, 11, 14] {
+            p[2..4].copy_from_slice(&s.to_be_bytes());
+            ring.push(&p, 1_000);
+        }
+        assert_eq!(ring.get(11, 1_500, 1_000).and_then(seq), Some(11));
+        assert!(ring.get(10, 1_500, 1_000).is_none()); // 14가 같은 슬롯 덮어씀
+        assert!(ring.get(12, 1_500, 1_000).is_none());
+        assert!(ring.get(11, 2_001, 1_000).is_none()); // max_age 초과
+    }
+}
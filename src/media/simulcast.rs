@@ -0,0 +1,201 @@
+// author: kodeholic (powered by Claude)
+// Simulcast 레이어 선택 — Conference video, (수신자, consumer SSRC)마다 1개 (MediaPeerHub.layer_selectors)
+//
+// 송신자는 a=simulcast / a=rid로 같은 영상을 여러 화질(레이어)로 보내고, 서버는 수신자별로 1개만 전달
+//   레이어 index = 송신자 offer의 a=simulcast 나열 순서 (저화질 → 고화질)
+//
+// 목표 레이어:
+//   preferred(클라이언트 SIMULCAST_LAYER 지정) 또는 auto(수신자 RR 손실 기반), 없으면 최상위
+//   수신이 끊긴 레이어(SIMULCAST_LAYER_TIMEOUT_MS)는 건너뜀 — 아래 레이어 우선, 없으면 위 레이어
+//
+// 전환:
+//   목표 레이어의 keyframe에서만 (중간 프레임부터 보내면 디코더가 참조 프레임 없이 깨짐)
+//   keyframe 전까지는 이전 레이어 유지 — 목표 레이어 패킷마다 Waiting(호출자가 PLI)
+//   출력은 TalkerStream으로 consumer SSRC / 연속 seq / timestamp rewrite (수신 jitter buffer 유지)
+
+use crate::config;
+use crate::media::rtp::TalkerStream;
+
+/// 레이어 패킷 1개 처리 결과
+#[derive(Debug, PartialEq)]
+pub enum LayerForward {
+    Send(Vec<u8>),  // 현재 레이어 — rewrite된 평문 RTP
+    Drop,           // 전달하지 않는 레이어
+    Waiting,        // 목표 레이어로 전환 대기 (keyframe 필요)
+}
+
+pub struct LayerSelector {
+    stream:     TalkerStream,
+    seen_at:    Vec<u64>,       // 레이어별 마지막 수신 시각 (0 = 미수신)
+    preferred:  Option<usize>,  // 클라이언트 지정 레이어 (None = auto)
+    auto:       usize,          // 손실 기반 레이어 (초기값 최상위)
+    current:    Option<usize>,  // 지금 전달 중인 레이어
+    changed_at: u64,            // 마지막 전환 / auto 변경 시각
+    good_since: u64,            // 손실 낮은 상태 시작 시각 (0 = 아님)
+}
+
+impl LayerSelector {
+    pub fn new(consumer_ssrc: u32) -> Self {
+        Self {
+            stream:     TalkerStream::new(consumer_ssrc, config::RTP_CLOCK_RATE_VIDEO),
+            seen_at:    Vec::new(),
+            preferred:  None,
+            auto:       usize::MAX,
+            current:    None,
+            changed_at: 0,
+            good_since: 0,
+        }
+    }
+
+    /// 레이어 패킷 1개 — layer/count는 송신자 rid 순서 기준, keyframe은 rtp::is_keyframe 결과
+    pub fn forward(&mut self, layer: usize, count: usize, packet: &[u8], keyframe: Option<bool>, now: u64) -> LayerForward {
+        if self.seen_at.len() < count {
+            self.seen_at.resize(count, 0);
+        }
+        if let Some(seen) = self.seen_at.get_mut(layer) {
+            *seen = now;
+        }
+
+        let target = self.target(now);
+        if self.current != Some(layer) {
+            if target != Some(layer) {
+                return LayerForward::Drop;
+            }
+            // keyframe이 아니면 대기 — 판별 불가 코덱(None)은 즉시 전환
+            if keyframe == Some(false) {
+                return LayerForward::Waiting;
+            }
+            self.current    = Some(layer);
+            self.changed_at = now;
+        }
+        self.stream.rewrite(packet, now).map_or(LayerForward::Drop, LayerForward::Send)
+    }
+
+    /// 클라이언트 지정 레이어 (None = auto) — 목표가 바뀌면 true
+    pub fn set_preferred(&mut self, layer: Option<usize>, now: u64) -> bool {
+        let before = self.target(now);
+        self.preferred  = layer;
+        self.good_since = 0;
+        self.target(now) != before
+    }
+
+    /// 수신자 RR fraction lost 반영 — 높으면 한 단계 낮추고, 낮은 상태가 이어지면 한 단계 올림 (auto 변경 시 true)
+    pub fn on_loss(&mut self, fraction_lost: u8, now: u64) -> bool {
+        let effective = self.target(now).unwrap_or(0);
+        if fraction_lost > config::SIMULCAST_LOSS_DOWN {
+            self.good_since = 0;
+            if effective == 0 || now.saturating_sub(self.changed_at) < config::SIMULCAST_DOWNGRADE_HOLD_MS {
+                return false;
+            }
+            self.auto       = effective - 1;
+            self.changed_at = now;
+            return true;
+        }
+        if fraction_lost >= config::SIMULCAST_LOSS_UP {
+            self.good_since = 0;
+            return false;
+        }
+        if self.good_since == 0 {
+            self.good_since = now;
+        }
+        if now.saturating_sub(self.good_since) < config::SIMULCAST_UPGRADE_MS || self.auto >= self.seen_at.len().saturating_sub(1) {
+            return false;
+        }
+        self.auto       = effective + 1;
+        self.good_since = now;
+        self.changed_at = now;
+        true
+    }
+
+    /// 지금 전달 중인 레이어의 원본 SSRC (RTCP feedback 역매핑용)
+    pub fn current_source(&self) -> Option<u32> {
+        self.current.and(self.stream.source())
+    }
+
+    /// 현재 레이어 SR timestamp → consumer 스트림 timestamp (다른 레이어면 None)
+    pub fn map_timestamp(&self, source: u32, ts: u32) -> Option<u32> {
+        self.stream.map_timestamp(source, ts)
+    }
+
+    /// 목표 레이어 — preferred/auto 이하에서 수신 중인 가장 높은 레이어, 없으면 그 위에서 가장 낮은 레이어
+    fn target(&self, now: u64) -> Option<usize> {
+        let want   = self.preferred.unwrap_or(self.auto).min(self.seen_at.len().checked_sub(1)?);
+        let active = |i: &usize| self.seen_at[*i] != 0 && now.saturating_sub(self.seen_at[*i]) <= config::SIMULCAST_LAYER_TIMEOUT_MS;
+        (0..=want).rev().find(active).or_else(|| (want + 1..self.seen_at.len()).find(active))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::media::rtp;
+
+    fn pkt(ssrc: u32, seq: u16, ts: u32) -> Vec<u8> {
+        let mut p = vec![0x80, 96];
+        p.extend_from_slice(&seq.to_be_bytes());
+        p.extend_from_slice(&ts.to_be_bytes());
+        p.extend_from_slice(&ssrc.to_be_bytes());
+        p.extend_from_slice(&[0xAA; 4]);
+        p
+    }
+
+    #[test]
+    fn switches_only_at_target_keyframe_with_continuous_seq() {
+        let mut sel = LayerSelector::new(0x7777);
+        // 저화질(0)만 수신 중 → 0 전달
+        let LayerForward::Send(a) = sel.forward(0, 3, &pkt(10, 100, 1_000), Some(true), 10_000) else { panic!() };
+        assert_eq!((rtp::ssrc(&a), rtp::seq(&a)), (Some(0x7777), Some(100)));
+
+        // 고화질(2) 수신 시작 → keyframe 전까지 Waiting, 0 계속 전달
+        assert_eq!(sel.forward(2, 3, &pkt(30, 5_000, 90_000), Some(false), 10_010), LayerForward::Waiting);
+        assert!(matches!(sel.forward(0, 3, &pkt(10, 101, 4_000), Some(false), 10_020), LayerForward::Send(_)));
+        assert_eq!(sel.forward(1, 3, &pkt(20, 7_000, 50_000), Some(true), 10_025), LayerForward::Drop);
+
+        // 2의 keyframe에서 전환 — seq 연속, 이후 0은 drop
+        let LayerForward::Send(b) = sel.forward(2, 3, &pkt(30, 5_001, 93_000), Some(true), 10_030) else { panic!() };
+        assert_eq!((rtp::ssrc(&b), rtp::seq(&b)), (Some(0x7777), Some(102)));
+        assert_eq!(sel.forward(0, 3, &pkt(10, 102, 7_000), Some(false), 10_040), LayerForward::Drop);
+        assert_eq!(sel.current_source(), Some(30));
+        assert!(sel.map_timestamp(30, 93_000).is_some());
+        assert!(sel.map_timestamp(10, 7_000).is_none());
+    }
+
+    #[test]
+    fn preference_and_loss_feedback_pick_target() {
+        let mut sel = LayerSelector::new(1);
+        // 상위 레이어부터 — 호출 간격이 timeout보다 길어도 먼저 갱신된 현재 레이어 유지
+        let feed = |sel: &mut LayerSelector, layers: usize, now: u64| {
+            for layer in (0..layers).rev() {
+                sel.forward(layer, 3, &pkt(layer as u32 + 10, 1, 0), Some(true), now);
+            }
+        };
+        let now = 10_000;
+        feed(&mut sel, 3, now);
+        assert_eq!(sel.target(now), Some(2));
+
+        // 지정 → auto 복귀
+        assert!(sel.set_preferred(Some(0), now));
+        assert_eq!(sel.target(now), Some(0));
+        assert!(sel.set_preferred(None, now));
+
+        // 손실 높음 → 한 단계씩 (전환 직후 hold)
+        let t = now + config::SIMULCAST_DOWNGRADE_HOLD_MS;
+        feed(&mut sel, 3, t);
+        assert!(sel.on_loss(80, t));
+        assert!(!sel.on_loss(80, t + 500));
+        assert_eq!(sel.target(t), Some(1));
+
+        // 손실 낮은 상태 유지 → 한 단계 올림
+        let t = t + 500;
+        assert!(!sel.on_loss(0, t));
+        let t = t + config::SIMULCAST_UPGRADE_MS;
+        feed(&mut sel, 3, t);
+        assert!(sel.on_loss(0, t));
+        assert_eq!(sel.target(t), Some(2));
+
+        // 최상위 레이어 수신이 끊기면 아래 레이어로
+        let later = t + config::SIMULCAST_LAYER_TIMEOUT_MS + 1;
+        feed(&mut sel, 2, later);
+        assert_eq!(sel.target(later), Some(1));
+    }
+}
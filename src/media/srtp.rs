@@ -67,6 +67,17 @@ impl SrtpContext {
         }
     }
 
+    /// plaintext RTCP 패킷 암호화 → SRTCP bytes (MBCP APP 송신용)
+    pub fn encrypt_rtcp(&mut self, packet: &[u8]) -> Result<Vec<u8>, SrtpError> {
+        match &mut self.inner {
+            None => Err(SrtpError::KeyNotInstalled),
+            Some(ctx) => ctx
+                .encrypt_rtcp(packet)
+                .map(|b: bytes::Bytes| b.to_vec())
+                .map_err(|e: webrtc_srtp::Error| SrtpError::EncryptFailed(e.to_string())),
+        }
+    }
+
     pub fn decrypt(&mut self, packet: &[u8]) -> Result<Vec<u8>, SrtpError> {
         match &mut self.inner {
             None => Err(SrtpError::KeyNotInstalled),
@@ -220,4 +231,23 @@ mod tests {
 
         assert_eq!(decrypted, plaintext);
     }
+
+    #[test]
+    fn encrypt_decrypt_rtcp_roundtrip() {
+        // MBCP APP 패킷 (헤더 12B, field 없음) — SRTCP encrypt 후 decrypt하면 원본 복원
+        let plaintext: Vec<u8> = vec![
+            0x85, 0xCC, 0x00, 0x02,   // V=2, subtype=5(Idle), PT=204, length=2
+            0x4D, 0x43, 0x50, 0x54,   // SSRC
+            b'M', b'C', b'P', b'T',   // name
+        ];
+
+        let mut enc_ctx = SrtpContext::new();
+        enc_ctx.install_key(&[0x01u8; 16], &[0x02u8; 14]);
+        let mut dec_ctx = SrtpContext::new();
+        dec_ctx.install_key(&[0x01u8; 16], &[0x02u8; 14]);
+
+        let encrypted = enc_ctx.encrypt_rtcp(&plaintext).expect("encrypt_rtcp failed");
+        assert_ne!(encrypted, plaintext);
+        assert_eq!(dec_ctx.decrypt_rtcp(&encrypted).expect("decrypt_rtcp failed"), plaintext);
+    }
 }
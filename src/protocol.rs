@@ -1,12 +1,17 @@
 // author: kodeholic (powered by Claude)
 
+pub mod call;
+pub mod emergency;
 pub mod floor;
+pub mod mbcp;
 pub mod message;
 pub mod opcode;
 pub mod protocol;
 pub mod sdp;
+pub mod speaker;
+pub mod vox;
 
-pub use protocol::{ws_handler, AppState};
+pub use protocol::{broadcast_message, is_reserved_author, ws_handler, AppState};
 
 // ----------------------------------------------------------------------------
 // 광고 IP 전역 저장소
@@ -0,0 +1,418 @@
+// author: kodeholic (powered by Claude)
+// 1:1 개별 호출 (MCPTT private call) 핸들러
+//
+// 시그널링 (UserHub 라우팅):
+//   caller CALL_INVITE  → ACK(call_id, sdp_answer) + CALL_RINGING   / callee CALL_INCOMING
+//   callee CALL_ACCEPT  → ACK(sdp_answer)                          / caller CALL_ACCEPTED
+//   callee CALL_REJECT · caller CALL_CANCEL · 양측 CALL_HANGUP → 상대에게 CALL_ENDED
+//   무응답 CALL_RING_TIMEOUT_MS 경과 / 당사자 WS 종료 → CALL_ENDED(timeout / disconnect)
+//
+// automatic commencement:
+//   INVITE 즉시 Active — caller는 RINGING 대신 CALL_ACCEPTED(automatic=true),
+//   callee는 CALL_INCOMING(automatic=true) 수신 후 CALL_ACCEPT로 미디어만 연결
+//
+// 미디어: call_id = channel_id인 숨은 2인 채널 (ChannelHub)
+//   full_duplex → Conference 채널 (Floor 미적용), ptt → PTT 채널 (FLOOR_* 그대로 사용)
+//   엔드포인트 / SDP answer / peer_added는 CHANNEL_JOIN과 같은 경로 (attach_endpoint)
+
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tracing::{info, trace};
+
+use crate::config;
+use crate::core::{Call, CallState, CallType, ChannelMode};
+use crate::error::LiveError;
+use crate::protocol::message::{
+    AckPayload, CallAcceptPayload, CallAcceptedPayload, CallAckData, CallEndedPayload,
+    CallIdPayload, CallIncomingPayload, CallInvitePayload, CallRingingPayload, GatewayPacket,
+};
+use crate::protocol::opcode::{client, server};
+use crate::protocol::protocol::{attach_endpoint, error_packet, peer_added_packet, AppState};
+use crate::trace::{TraceDir, TraceEvent};
+
+fn make_packet(op: u8, payload: impl serde::Serialize) -> String {
+    let packet = GatewayPacket::new(op, payload);
+    serde_json::to_string(&packet).unwrap_or_default()
+}
+
+async fn send(tx: &mpsc::Sender<String>, json: String) -> Result<(), LiveError> {
+    tx.send(json).await.map_err(|e| LiveError::InternalError(e.to_string()))
+}
+
+/// 특정 사용자에게 전송 (오프라인이면 무시)
+async fn send_to(state: &AppState, user_id: &str, json: String) {
+    if let Some(user) = state.user_hub.get(user_id) {
+        let _ = user.tx.send(json).await;
+    }
+}
+
+fn ack_packet(op: u8, data: impl serde::Serialize) -> String {
+    make_packet(server::ACK, AckPayload {
+        op,
+        data: serde_json::to_value(data).unwrap_or_default(),
+    })
+}
+
+fn channel_mode(call_type: CallType) -> ChannelMode {
+    match call_type {
+        CallType::FullDuplex => ChannelMode::Conference,
+        CallType::Ptt        => ChannelMode::PTT,
+    }
+}
+
+// ----------------------------------------------------------------------------
+// [op 핸들러들]
+// ----------------------------------------------------------------------------
+
+/// op: CALL_INVITE (50) — 개별 호출 발신
+pub async fn handle_call_invite(
+    tx:      &mpsc::Sender<String>,
+    user_id: &str,
+    state:   &AppState,
+    packet:  GatewayPacket,
+) -> Result<(), LiveError> {
+    let payload = parse_payload::<CallInvitePayload>(packet.d)?;
+    let callee  = payload.target_user_id.clone();
+    trace!("CALL_INVITE caller={} callee={} type={} automatic={}",
+        user_id, callee, payload.call_type.as_str(), payload.automatic);
+
+    if state.user_hub.get(&callee).is_none() {
+        return send(tx, error_packet(LiveError::CallUserOffline(callee))).await;
+    }
+    let call = match state.call_hub.invite(user_id, &callee, payload.call_type, payload.automatic) {
+        Ok(call) => call,
+        Err(e)   => return send(tx, error_packet(e)).await,
+    };
+
+    // 숨은 2인 미디어 채널 — 두 당사자를 미리 멤버로 등록 (정원 2, 제3자 JOIN 불가)
+    let channel = state.channel_hub.create(
+        &call.call_id, "0000", &format!("{} ↔ {}", user_id, callee), channel_mode(call.call_type), 2,
+    );
+    channel.add_member(user_id)?;
+    channel.add_member(&callee)?;
+
+    let sdp_answer = match payload.sdp_offer.is_some() || !payload.ufrag.is_empty() {
+        true  => attach_endpoint(state, user_id, &channel, payload.sdp_offer.as_deref(), &payload.ufrag, payload.ssrc, &payload.tracks).0,
+        false => None,
+    };
+
+    send(tx, ack_packet(client::CALL_INVITE, CallAckData {
+        call_id:    call.call_id.clone(),
+        channel_id: call.call_id.clone(),
+        call_type:  call.call_type,
+        state:      call.state.as_str().to_string(),
+        sdp_answer,
+    })).await?;
+
+    send_to(state, &callee, make_packet(server::CALL_INCOMING, CallIncomingPayload {
+        call_id:   call.call_id.clone(),
+        caller:    user_id.to_string(),
+        call_type: call.call_type,
+        automatic: call.automatic,
+    })).await;
+
+    if call.automatic {
+        send(tx, make_packet(server::CALL_ACCEPTED, CallAcceptedPayload {
+            call_id:   call.call_id.clone(),
+            callee:    callee.clone(),
+            automatic: true,
+        })).await?;
+    } else {
+        send(tx, make_packet(server::CALL_RINGING, CallRingingPayload {
+            call_id: call.call_id.clone(),
+            callee:  callee.clone(),
+        })).await?;
+        arm_ring_timeout(state.clone(), call.call_id.clone());
+    }
+
+    info!("Call invite: {} {} → {} type={} automatic={}",
+        call.call_id, user_id, callee, call.call_type.as_str(), call.automatic);
+    state.trace_hub.publish(TraceEvent::new(
+        TraceDir::Sys, Some(&call.call_id), Some(user_id),
+        server::CALL_INCOMING, "CALL_INVITE",
+        format!("caller={} callee={} type={} automatic={}", user_id, callee, call.call_type.as_str(), call.automatic),
+    ));
+    Ok(())
+}
+
+/// op: CALL_ACCEPT (51) — 피호출자 수락 + 미디어 연결
+/// automatic 호출은 이미 Active — 미디어 연결만 수행 (엔드포인트가 이미 있으면 CallInvalidState)
+pub async fn handle_call_accept(
+    tx:      &mpsc::Sender<String>,
+    user_id: &str,
+    state:   &AppState,
+    packet:  GatewayPacket,
+) -> Result<(), LiveError> {
+    let payload = parse_payload::<CallAcceptPayload>(packet.d)?;
+    let call_id = payload.call_id.clone();
+    trace!("CALL_ACCEPT user={} call={}", user_id, call_id);
+
+    let (call, channel) = match (
+        state.call_hub.get(&call_id).filter(|c| c.callee == user_id),
+        state.channel_hub.get(&call_id),
+    ) {
+        (Some(call), Some(channel)) => (call, channel),
+        _ => return send(tx, error_packet(LiveError::CallNotFound(call_id))).await,
+    };
+
+    let call = match call.state {
+        CallState::Ringing => match state.call_hub.accept(&call_id, user_id) {
+            Ok(call) => call,
+            Err(e)   => return send(tx, error_packet(e)).await,
+        },
+        CallState::Active if call.automatic
+            && !state.media_peer_hub.get_channel_endpoints(&call_id).iter().any(|ep| ep.user_id == user_id) => call,
+        CallState::Active => return send(tx, error_packet(LiveError::CallInvalidState(call_id))).await,
+    };
+
+    let ep = match payload.sdp_offer.is_some() || !payload.ufrag.is_empty() {
+        true  => Some(attach_endpoint(state, user_id, &channel, payload.sdp_offer.as_deref(), &payload.ufrag, payload.ssrc, &payload.tracks)),
+        false => None,
+    };
+
+    send(tx, ack_packet(client::CALL_ACCEPT, CallAckData {
+        call_id:    call.call_id.clone(),
+        channel_id: call.call_id.clone(),
+        call_type:  call.call_type,
+        state:      call.state.as_str().to_string(),
+        sdp_answer: ep.as_ref().and_then(|(answer, _)| answer.clone()),
+    })).await?;
+
+    if !call.automatic {
+        send_to(state, &call.caller, make_packet(server::CALL_ACCEPTED, CallAcceptedPayload {
+            call_id:   call.call_id.clone(),
+            callee:    user_id.to_string(),
+            automatic: false,
+        })).await;
+    }
+
+    // 양측 peer_added 교환 (re-negotiation 트리거 — CHANNEL_JOIN 5b/5c와 동일)
+    if let Some((_, ep)) = &ep {
+        send_to(state, &call.caller, peer_added_packet(&call.call_id, ep)).await;
+        for existing in state.media_peer_hub.get_channel_endpoints(&call.call_id) {
+            if existing.user_id == user_id { continue; }
+            send(tx, peer_added_packet(&call.call_id, &existing)).await?;
+        }
+    }
+
+    info!("Call accepted: {} by {}", call.call_id, user_id);
+    state.trace_hub.publish(TraceEvent::new(
+        TraceDir::Sys, Some(&call.call_id), Some(user_id),
+        server::CALL_ACCEPTED, "CALL_ACCEPT",
+        format!("caller={} callee={} media={}", call.caller, user_id, ep.is_some()),
+    ));
+    Ok(())
+}
+
+/// op: CALL_REJECT (52) — 피호출자 거절 (Ringing 중)
+pub async fn handle_call_reject(
+    tx:      &mpsc::Sender<String>,
+    user_id: &str,
+    state:   &AppState,
+    packet:  GatewayPacket,
+) -> Result<(), LiveError> {
+    end_by_party(tx, user_id, state, packet, client::CALL_REJECT).await
+}
+
+/// op: CALL_CANCEL (53) — 호출자 취소 (Ringing 중)
+pub async fn handle_call_cancel(
+    tx:      &mpsc::Sender<String>,
+    user_id: &str,
+    state:   &AppState,
+    packet:  GatewayPacket,
+) -> Result<(), LiveError> {
+    end_by_party(tx, user_id, state, packet, client::CALL_CANCEL).await
+}
+
+/// op: CALL_HANGUP (54) — 통화 종료 (양측, 상태 무관)
+pub async fn handle_call_hangup(
+    tx:      &mpsc::Sender<String>,
+    user_id: &str,
+    state:   &AppState,
+    packet:  GatewayPacket,
+) -> Result<(), LiveError> {
+    end_by_party(tx, user_id, state, packet, client::CALL_HANGUP).await
+}
+
+/// REJECT / CANCEL / HANGUP 공통 — 당사자 / 상태 검증 후 종료, 요청자에게 ACK
+///   REJECT: callee + Ringing / CANCEL: caller + Ringing / HANGUP: 양측, 상태 무관
+///   당사자가 아니면 호출 존재 여부를 노출하지 않도록 CallNotFound
+async fn end_by_party(
+    tx:      &mpsc::Sender<String>,
+    user_id: &str,
+    state:   &AppState,
+    packet:  GatewayPacket,
+    op:      u8,
+) -> Result<(), LiveError> {
+    let payload = parse_payload::<CallIdPayload>(packet.d)?;
+    let call_id = payload.call_id;
+
+    let Some(call) = state.call_hub.get(&call_id) else {
+        return send(tx, error_packet(LiveError::CallNotFound(call_id))).await;
+    };
+    let (reason, allowed, ringing_only) = match op {
+        client::CALL_REJECT => ("rejected",  call.callee == user_id, true),
+        client::CALL_CANCEL => ("cancelled", call.caller == user_id, true),
+        _                   => ("hangup",    call.is_party(user_id), false),
+    };
+    if !allowed {
+        return send(tx, error_packet(LiveError::CallNotFound(call_id))).await;
+    }
+    if ringing_only && call.state != CallState::Ringing {
+        return send(tx, error_packet(LiveError::CallInvalidState(call_id))).await;
+    }
+
+    if let Some(call) = state.call_hub.remove(&call_id) {
+        teardown(state, &call, reason, Some(user_id)).await;
+    }
+    send(tx, ack_packet(op, serde_json::json!({ "call_id": call_id }))).await
+}
+
+// ----------------------------------------------------------------------------
+// [종료 / 타임아웃]
+// ----------------------------------------------------------------------------
+
+/// 호출 종료 — 당사자 WS 종료 시 cleanup에서 호출 (CallHub에 없으면 무시)
+pub async fn end_call(state: &AppState, call_id: &str, reason: &str, by: Option<&str>) {
+    if let Some(call) = state.call_hub.remove(call_id) {
+        teardown(state, &call, reason, by).await;
+    }
+}
+
+/// 무응답 타임아웃 — Ringing 그대로면 종료 (수락 / 거절 / 취소가 먼저면 아무것도 안 함)
+fn arm_ring_timeout(state: AppState, call_id: String) {
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(config::CALL_RING_TIMEOUT_MS)).await;
+        if let Some(call) = state.call_hub.remove_if_ringing(&call_id) {
+            teardown(&state, &call, "timeout", None).await;
+        }
+    });
+}
+
+/// CallHub에서 이미 제거된 호출의 미디어 / 채널 정리 + 종료 요청자 외 당사자에게 CALL_ENDED
+async fn teardown(state: &AppState, call: &Call, reason: &str, by: Option<&str>) {
+    for ep in state.media_peer_hub.get_channel_endpoints(&call.call_id) {
+        state.media_peer_hub.remove(&ep.ufrag);
+    }
+    // CHANNEL_LEAVE와 동일 — 양측 consumer SSRC 정리 후 relay 맵 재구축
+    for party in [&call.caller, &call.callee] {
+        state.media_peer_hub.remove_consumer_ssrc_for_user(party);
+    }
+    state.media_peer_hub.rebuild_relay_map(&call.call_id);
+    if let Some(channel) = state.channel_hub.get(&call.call_id) {
+        state.media_peer_hub.remove_rtp_cache(&channel.talker_ssrcs());
+    }
+    state.channel_hub.remove(&call.call_id);
+
+    let ended = make_packet(server::CALL_ENDED, CallEndedPayload {
+        call_id: call.call_id.clone(),
+        reason:  reason.to_string(),
+        by:      by.map(str::to_string),
+    });
+    for party in [&call.caller, &call.callee] {
+        if Some(party.as_str()) == by { continue; }
+        send_to(state, party, ended.clone()).await;
+    }
+
+    info!("Call ended: {} reason={} by={}", call.call_id, reason, by.unwrap_or("-"));
+    state.trace_hub.publish(TraceEvent::new(
+        TraceDir::Sys, Some(&call.call_id), by,
+        server::CALL_ENDED, "CALL_ENDED",
+        format!("caller={} callee={} reason={}", call.caller, call.callee, reason),
+    ));
+}
+
+fn parse_payload<T: serde::de::DeserializeOwned>(
+    d: Option<serde_json::Value>,
+) -> Result<T, LiveError> {
+    let value = d.ok_or_else(|| LiveError::InvalidPayload("missing payload".to_string()))?;
+    serde_json::from_value(value).map_err(|e| LiveError::InvalidPayload(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::TrackKind;
+
+    fn pkt(op: u8, d: serde_json::Value) -> GatewayPacket {
+        GatewayPacket { op, d: Some(d) }
+    }
+
+    #[tokio::test]
+    async fn invite_accept_hangup_round_trip() {
+        let state = AppState::for_test();
+        let (tx_a, mut rx_a) = mpsc::channel(16);
+        let (tx_b, mut rx_b) = mpsc::channel(16);
+        state.user_hub.register("alice", tx_a.clone(), 100);
+        state.user_hub.register("bob", tx_b.clone(), 100);
+
+        // 오프라인 대상
+        handle_call_invite(&tx_a, "alice", &state, pkt(client::CALL_INVITE, serde_json::json!({ "target_user_id": "carol" }))).await.unwrap();
+        let offline = rx_a.recv().await.unwrap();
+        assert!(offline.contains("\"op\":201") && offline.contains("\"code\":5002"));
+        assert_eq!(state.call_hub.count(), 0);
+
+        handle_call_invite(&tx_a, "alice", &state, pkt(client::CALL_INVITE, serde_json::json!({ "target_user_id": "bob", "call_type": "ptt" }))).await.unwrap();
+        let ack = rx_a.recv().await.unwrap();
+        assert!(ack.contains("\"op\":200") && ack.contains("\"state\":\"ringing\""));
+        assert!(rx_a.recv().await.unwrap().contains("\"op\":130"));
+        assert!(rx_b.recv().await.unwrap().contains("\"op\":131"));
+
+        let call_id = state.call_hub.call_of("bob").unwrap().call_id;
+        let channel = state.channel_hub.get(&call_id).unwrap();
+        assert!(channel.is_call());
+        assert_eq!(channel.mode, ChannelMode::PTT);
+
+        // 호출자는 수락 / 거절 불가
+        handle_call_reject(&tx_a, "alice", &state, pkt(client::CALL_REJECT, serde_json::json!({ "call_id": call_id }))).await.unwrap();
+        let wrong = rx_a.recv().await.unwrap();
+        assert!(wrong.contains("\"op\":201") && wrong.contains("\"code\":5000"));
+
+        handle_call_accept(&tx_b, "bob", &state, pkt(client::CALL_ACCEPT, serde_json::json!({ "call_id": call_id }))).await.unwrap();
+        assert!(rx_b.recv().await.unwrap().contains("\"state\":\"active\""));
+        assert!(rx_a.recv().await.unwrap().contains("\"op\":132"));
+
+        // 통화 중 취소 불가 — HANGUP만
+        handle_call_cancel(&tx_a, "alice", &state, pkt(client::CALL_CANCEL, serde_json::json!({ "call_id": call_id }))).await.unwrap();
+        let cancel = rx_a.recv().await.unwrap();
+        assert!(cancel.contains("\"op\":201") && cancel.contains("\"code\":5003"));
+
+        // 통화 중 할당된 consumer SSRC는 종료 시 양측 모두 정리
+        let to_alice = state.media_peer_hub.get_or_create_consumer_ssrc("alice", "bob", TrackKind::Audio);
+        let to_bob   = state.media_peer_hub.get_or_create_consumer_ssrc("bob", "alice", TrackKind::Audio);
+
+        handle_call_hangup(&tx_b, "bob", &state, pkt(client::CALL_HANGUP, serde_json::json!({ "call_id": call_id }))).await.unwrap();
+        assert!(state.media_peer_hub.resolve_consumer_ssrc("alice", to_alice).is_none());
+        assert!(state.media_peer_hub.resolve_consumer_ssrc("bob", to_bob).is_none());
+        assert!(rx_b.recv().await.unwrap().contains("\"op\":200"));
+        let ended = rx_a.recv().await.unwrap();
+        assert!(ended.contains("\"op\":133") && ended.contains("\"reason\":\"hangup\""));
+        assert!(state.call_hub.get(&call_id).is_none());
+        assert!(state.channel_hub.get(&call_id).is_none());
+    }
+
+    #[tokio::test]
+    async fn automatic_commencement_skips_ringing() {
+        let state = AppState::for_test();
+        let (tx_a, mut rx_a) = mpsc::channel(16);
+        let (tx_b, mut rx_b) = mpsc::channel(16);
+        state.user_hub.register("alice", tx_a.clone(), 100);
+        state.user_hub.register("bob", tx_b.clone(), 100);
+
+        handle_call_invite(&tx_a, "alice", &state, pkt(client::CALL_INVITE, serde_json::json!({ "target_user_id": "bob", "automatic": true }))).await.unwrap();
+        assert!(rx_a.recv().await.unwrap().contains("\"state\":\"active\""));
+        let accepted = rx_a.recv().await.unwrap();
+        assert!(accepted.contains("\"op\":132") && accepted.contains("\"automatic\":true"));
+        let incoming = rx_b.recv().await.unwrap();
+        assert!(incoming.contains("\"op\":131") && incoming.contains("\"automatic\":true"));
+
+        let call_id = state.call_hub.call_of("alice").unwrap().call_id;
+        assert_eq!(state.channel_hub.get(&call_id).unwrap().mode, ChannelMode::Conference);
+
+        // 피호출자 연결 끊김 → 호출자에게 CALL_ENDED(disconnect)
+        end_call(&state, &call_id, "disconnect", Some("bob")).await;
+        let ended = rx_a.recv().await.unwrap();
+        assert!(ended.contains("\"op\":133") && ended.contains("\"reason\":\"disconnect\""));
+        assert_eq!(state.call_hub.count(), 0);
+    }
+}
@@ -0,0 +1,201 @@
+// author: kodeholic (powered by Claude)
+// 채널 긴급 상태 핸들러
+//
+// 발령:  EMERGENCY_ALERT(40) 또는 indicator=emergency FLOOR_REQUEST
+//        → 멤버 전원 EMERGENCY_RAISED(120) + Sys trace "EMERGENCY_ALERT" (webhook emergency)
+// 확인:  EMERGENCY_ACK(41) → 멤버 전원 EMERGENCY_ACKED(121)
+// 해제:  Dispatcher(Admin REST) → 멤버 전원 EMERGENCY_CLEARED(122)
+// 발령 중 입장한 멤버에게는 EMERGENCY_RAISED를 개별 전송
+
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tracing::{trace, warn};
+
+use crate::core::{Channel, ChannelHub, EmergencySource, EmergencyState, UserHub};
+use crate::error::LiveError;
+use crate::trace::{TraceDir, TraceEvent, TraceHub};
+use crate::webhook::WebhookEvent;
+use crate::protocol::message::{
+    AckPayload, EmergencyAckPayload, EmergencyAlertPayload, EmergencyClearedPayload,
+    EmergencyStatePayload, GatewayPacket,
+};
+use crate::protocol::opcode::{client, server};
+
+fn make_packet(op: u8, payload: impl serde::Serialize) -> String {
+    let packet = GatewayPacket::new(op, payload);
+    serde_json::to_string(&packet).unwrap_or_default()
+}
+
+async fn send(tx: &mpsc::Sender<String>, json: String) -> Result<(), LiveError> {
+    tx.send(json).await.map_err(|e| LiveError::InternalError(e.to_string()))
+}
+
+fn parse_payload<T: serde::de::DeserializeOwned>(
+    d: Option<serde_json::Value>,
+) -> Result<T, LiveError> {
+    let value = d.ok_or_else(|| LiveError::InvalidPayload("missing payload".to_string()))?;
+    serde_json::from_value(value).map_err(|e| LiveError::InvalidPayload(e.to_string()))
+}
+
+/// EMERGENCY_RAISED 패킷 — 발령 브로드캐스트 / 입장자 개별 전송 공용
+pub(crate) fn raised_packet(channel_id: &str, state: &EmergencyState) -> String {
+    make_packet(server::EMERGENCY_RAISED, EmergencyStatePayload {
+        channel_id: channel_id.to_string(),
+        state:      state.clone(),
+    })
+}
+
+/// 긴급 상태 발령 — 새로 발령된 경우에만 브로드캐스트 + trace, 발령 여부 반환
+pub async fn raise(
+    channel:   &Channel,
+    user_id:   &str,
+    source:    EmergencySource,
+    user_hub:  &Arc<UserHub>,
+    trace_hub: &Arc<TraceHub>,
+) -> bool {
+    let Some(state) = channel.raise_emergency(user_id, source) else {
+        return false;
+    };
+    let packet = raised_packet(&channel.channel_id, &state);
+    user_hub.broadcast_to(&channel.get_members(), &packet, None).await;
+
+    warn!("Emergency raised: channel={} user={} source={:?}", channel.channel_id, user_id, source);
+    trace_hub.publish(TraceEvent::new(
+        TraceDir::Sys, Some(&channel.channel_id), Some(user_id),
+        server::EMERGENCY_RAISED, "EMERGENCY_ALERT",
+        format!("user={} source={:?}", user_id, source),
+    ).with_webhook(WebhookEvent::Emergency));
+    true
+}
+
+/// 긴급 상태 해제 (Dispatcher) — 해제된 상태 반환, 발령 중이 아니면 None
+pub async fn clear(
+    channel:    &Channel,
+    cleared_by: &str,
+    user_hub:   &Arc<UserHub>,
+    trace_hub:  &Arc<TraceHub>,
+) -> Option<EmergencyState> {
+    let state = channel.clear_emergency()?;
+    let packet = make_packet(server::EMERGENCY_CLEARED, EmergencyClearedPayload {
+        channel_id: channel.channel_id.clone(),
+        cleared_by: cleared_by.to_string(),
+    });
+    user_hub.broadcast_to(&channel.get_members(), &packet, None).await;
+
+    warn!("Emergency cleared: channel={} by={} raised_by={}", channel.channel_id, cleared_by, state.raised_by);
+    trace_hub.publish(TraceEvent::new(
+        TraceDir::Sys, Some(&channel.channel_id), Some(&state.raised_by),
+        server::EMERGENCY_CLEARED, "EMERGENCY_CLEAR",
+        format!("by={} raised_by={} acked={}", cleared_by, state.raised_by, state.acked_by.len()),
+    ));
+    Some(state)
+}
+
+/// op: EMERGENCY_ALERT (40) — 채널 긴급 상태 발령
+pub async fn handle_emergency_alert(
+    tx:          &mpsc::Sender<String>,
+    user_id:     &str,
+    user_hub:    &Arc<UserHub>,
+    channel_hub: &Arc<ChannelHub>,
+    trace_hub:   &Arc<TraceHub>,
+    packet:      GatewayPacket,
+) -> Result<(), LiveError> {
+    let payload    = parse_payload::<EmergencyAlertPayload>(packet.d)?;
+    let channel_id = &payload.channel_id;
+    trace!("EMERGENCY_ALERT user={} channel={}", user_id, channel_id);
+
+    let channel = channel_hub.get(channel_id)
+        .ok_or_else(|| LiveError::ChannelNotFound(channel_id.clone()))?;
+    if !channel.get_members().contains(user_id) {
+        return Err(LiveError::NotInChannel(channel_id.clone()));
+    }
+
+    // 이미 발령 중이면 상태 유지 (raised=false)
+    let raised = raise(&channel, user_id, EmergencySource::Alert, user_hub, trace_hub).await;
+    send(tx, make_packet(server::ACK, AckPayload {
+        op:   client::EMERGENCY_ALERT,
+        data: serde_json::json!({ "channel_id": channel_id, "raised": raised }),
+    })).await
+}
+
+/// op: EMERGENCY_ACK (41) — 긴급 상태 확인
+pub async fn handle_emergency_ack(
+    tx:          &mpsc::Sender<String>,
+    user_id:     &str,
+    user_hub:    &Arc<UserHub>,
+    channel_hub: &Arc<ChannelHub>,
+    trace_hub:   &Arc<TraceHub>,
+    packet:      GatewayPacket,
+) -> Result<(), LiveError> {
+    let payload    = parse_payload::<EmergencyAckPayload>(packet.d)?;
+    let channel_id = &payload.channel_id;
+
+    let channel = channel_hub.get(channel_id)
+        .ok_or_else(|| LiveError::ChannelNotFound(channel_id.clone()))?;
+    let members = channel.get_members();
+    if !members.contains(user_id) {
+        return Err(LiveError::NotInChannel(channel_id.clone()));
+    }
+
+    let acked = channel.ack_emergency(user_id);
+    send(tx, make_packet(server::ACK, AckPayload {
+        op:   client::EMERGENCY_ACK,
+        data: serde_json::json!({ "channel_id": channel_id, "acked": acked.is_some() }),
+    })).await?;
+
+    if let Some(state) = acked {
+        trace!("Emergency ack: channel={} user={} total={}", channel_id, user_id, state.acked_by.len());
+        trace_hub.publish(TraceEvent::new(
+            TraceDir::Sys, Some(channel_id), Some(user_id),
+            server::EMERGENCY_ACKED, "EMERGENCY_ACK",
+            format!("user={} acked={}", user_id, state.acked_by.len()),
+        ));
+        let packet = make_packet(server::EMERGENCY_ACKED, EmergencyStatePayload {
+            channel_id: channel_id.clone(),
+            state,
+        });
+        user_hub.broadcast_to(&members, &packet, None).await;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ChannelMode;
+
+    #[tokio::test]
+    async fn alert_broadcasts_once_and_ack_updates_members() {
+        let user_hub    = Arc::new(UserHub::new());
+        let channel_hub = Arc::new(ChannelHub::new());
+        let trace_hub   = TraceHub::new();
+        let (tx_a, mut rx_a) = mpsc::channel(16);
+        let (tx_b, mut rx_b) = mpsc::channel(16);
+        user_hub.register("alice", tx_a.clone(), 100);
+        user_hub.register("bob", tx_b.clone(), 100);
+
+        let ch = channel_hub.create("CH_E", "0900", "em", ChannelMode::PTT, 10);
+        ch.add_member("alice").unwrap();
+        ch.add_member("bob").unwrap();
+
+        let alert = || GatewayPacket { op: client::EMERGENCY_ALERT, d: Some(serde_json::json!({ "channel_id": "CH_E" })) };
+        handle_emergency_alert(&tx_a, "alice", &user_hub, &channel_hub, &trace_hub, alert()).await.unwrap();
+        assert!(rx_b.recv().await.unwrap().contains("\"op\":120"));
+        assert!(rx_a.recv().await.unwrap().contains("\"op\":120"));
+        assert!(rx_a.recv().await.unwrap().contains("\"raised\":true"));
+
+        // 발령 중 재발령 — 브로드캐스트 없음
+        handle_emergency_alert(&tx_b, "bob", &user_hub, &channel_hub, &trace_hub, alert()).await.unwrap();
+        assert!(rx_b.recv().await.unwrap().contains("\"raised\":false"));
+
+        let ack = GatewayPacket { op: client::EMERGENCY_ACK, d: Some(serde_json::json!({ "channel_id": "CH_E" })) };
+        handle_emergency_ack(&tx_b, "bob", &user_hub, &channel_hub, &trace_hub, ack).await.unwrap();
+        assert!(rx_b.recv().await.unwrap().contains("\"acked\":true"));
+        let acked = rx_a.recv().await.unwrap();
+        assert!(acked.contains("\"op\":121") && acked.contains("\"acked_by\":[\"bob\"]"));
+
+        assert!(clear(&ch, "admin", &user_hub, &trace_hub).await.is_some());
+        assert!(rx_a.recv().await.unwrap().contains("\"op\":122"));
+        assert!(ch.emergency().is_none());
+    }
+}
@@ -6,7 +6,16 @@
 //   G: Floor Taken → Floor Release      → G: Floor Idle  (또는 다음 Queue Grant)
 //   G: Floor Taken → Floor Request(高)  → Preempt → G: Floor Taken
 //   G: Floor Taken → Floor Ping 무응답  → Revoke  → G: Floor Idle (또는 다음 Queue Grant)
+//   G: Floor Taken → holder RTP 무수신  → Revoke  → G: Floor Idle (또는 다음 Queue Grant)
 //   G: Floor Taken → 최대 발언 시간 초과 → Revoke  → G: Floor Idle (또는 다음 Queue Grant)
+//   G: Floor Taken → Floor Request(低)  → Queue 또는 Deny (채널 FloorPolicy에 따름)
+//
+// [타이머] run_floor_timer — 만료 시각 기준 (주기 sweep 아님)
+//   ping_timeout / max_duration → Revoke
+//   T101: FLOOR_GRANTED 후 holder 첫 FLOOR_PING 대기 — 만료 시 GRANTED 재전송
+//   T100: FLOOR_REVOKE 후 대상 FLOOR_RELEASE 대기 — 만료 시 REVOKE 재전송
+//   media_inactive: holder RTP 무수신 (relay_to_channel이 갱신) — 만료 시 Revoke
+//   VOX hang: VOX 채널 holder 무음 지속 — 만료 시 Release(vox_hang) (protocol/vox.rs)
 //
 // [Send 안전 원칙]
 //   std::sync::MutexGuard는 Send가 아니므로 .await 포인트를 넘길 수 없음.
@@ -15,27 +24,33 @@
 //   dispatch_packets()는 lock 해제 후 호출되는 async 함수
 
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::mpsc;
-use tracing::{trace, warn};
+use tracing::{info, trace, warn};
 
 use crate::config;
-use crate::core::{ChannelHub, FloorControl, FloorControlState, FloorIndicator, UserHub};
+use crate::core::{BurstEnd, ChannelHub, EmergencySource, FloorControl, FloorControlState, FloorDenyReason, FloorIndicator, MediaPeerHub, UserHub};
 use crate::error::LiveError;
 use crate::trace::{TraceDir, TraceEvent, TraceHub};
+use crate::webhook::WebhookEvent;
+use crate::utils::current_timestamp;
+use crate::protocol::mbcp;
 use crate::protocol::message::{
-    FloorGrantedPayload, FloorIdlePayload, FloorIndicatorDto,
-    FloorPingPayload, FloorPongPayload,
+    AckPayload, FloorDenyPayload, FloorGrantedPayload, FloorIdlePayload, FloorIndicatorDto,
+    FloorPingPayload, FloorPongPayload, FloorQueueCancelPayload,
     FloorQueuePosInfoPayload, FloorReleasePayload, FloorRequestPayload, FloorRevokePayload,
-    FloorTakenPayload, GatewayPacket,
+    FloorStatusPayload, FloorStatusQueueEntry, FloorSuspendedPayload, FloorStatusRequestPayload, FloorTakenPayload,
+    GatewayPacket,
 };
 #[allow(unused_imports)]
 use crate::protocol::opcode::{client, server};
+use crate::protocol::protocol::error_packet;
 
 // ----------------------------------------------------------------------------
 // [DTO 변환]
 // ----------------------------------------------------------------------------
 
-fn indicator_to_dto(ind: &FloorIndicator) -> FloorIndicatorDto {
+pub(crate) fn indicator_to_dto(ind: &FloorIndicator) -> FloorIndicatorDto {
     match ind {
         FloorIndicator::Normal        => FloorIndicatorDto::Normal,
         FloorIndicator::Broadcast     => FloorIndicatorDto::Broadcast,
@@ -66,6 +81,139 @@ async fn send(tx: &mpsc::Sender<String>, json: String) -> Result<(), LiveError>
     tx.send(json).await.map_err(|e| LiveError::InternalError(e.to_string()))
 }
 
+/// 요청자 응답 — WS(tx) + 이 채널에서 MBCP를 쓰면 SRTCP로도
+async fn reply(
+    tx:         &mpsc::Sender<String>,
+    peer_hub:   &MediaPeerHub,
+    channel_id: &str,
+    user_id:    &str,
+    json:       String,
+) -> Result<(), LiveError> {
+    mbcp::send_floor(peer_hub, channel_id, &json, |u| u == user_id).await;
+    send(tx, json).await
+}
+
+/// 대기 중인 전원에게 현재 순서 안내 (FLOOR_QUEUE_POS_INFO) — 대기열 변경 후 lock 보유 중 호출
+pub(crate) fn queue_pos_packets(
+    channel_id: &str,
+    floor:      &FloorControl,
+) -> Vec<(Option<String>, Option<String>, String)> {
+    let size = floor.queue.len();
+    floor.queue.iter().enumerate()
+        .map(|(i, e)| (Some(e.user_id.clone()), None, make_packet(server::FLOOR_QUEUE_POS_INFO, FloorQueuePosInfoPayload {
+            channel_id:     channel_id.to_string(),
+            queue_position: i + 1,
+            queue_size:     size,
+        })))
+        .collect()
+}
+
+/// 대기열에서 빠진 사용자에게 FLOOR_DENY (queue_expired / queue_cleared)
+pub(crate) fn queue_deny_packets(
+    channel_id: &str,
+    users:      &[String],
+    reason:     FloorDenyReason,
+) -> Vec<(Option<String>, Option<String>, String)> {
+    users.iter()
+        .map(|uid| (Some(uid.clone()), None, deny_packet(channel_id, reason)))
+        .collect()
+}
+
+/// FLOOR_DENY 패킷 — reason 문자열 + 4xxx 에러 코드
+pub(crate) fn deny_packet(channel_id: &str, reason: FloorDenyReason) -> String {
+    make_packet(server::FLOOR_DENY, FloorDenyPayload {
+        channel_id: channel_id.to_string(),
+        reason:     reason.as_str().to_string(),
+        code:       reason.to_error(channel_id).code(),
+    })
+}
+
+/// FLOOR_TAKEN 패킷 — 현재 holder / indicator (Idle이면 None)
+pub(crate) fn taken_packet(channel_id: &str, floor: &FloorControl) -> Option<String> {
+    let holder = floor.floor_taken_by.as_ref()?;
+    Some(make_packet(server::FLOOR_TAKEN, FloorTakenPayload {
+        channel_id: channel_id.to_string(),
+        user_id:    holder.clone(),
+        indicator:  indicator_to_dto(&floor.floor_indicator),
+    }))
+}
+
+/// grant() / resume() 직후 패킷 — holder에게 FLOOR_GRANTED, 나머지 멤버에게 FLOOR_TAKEN, 남은 대기자 순서 갱신
+/// duration은 남은 발언 시간 (신규 Grant = max_taken_ms, 재개 = 잔여, Broadcast / 무제한 = 0)
+pub(crate) fn grant_packets(
+    channel_id: &str,
+    floor:      &FloorControl,
+) -> Vec<(Option<String>, Option<String>, String)> {
+    let Some(holder) = floor.floor_taken_by.clone() else {
+        return vec![];
+    };
+    let mut packets = vec![(Some(holder.clone()), None, make_packet(server::FLOOR_GRANTED, FloorGrantedPayload {
+        channel_id: channel_id.to_string(),
+        user_id:    holder.clone(),
+        duration:   floor.remaining_ms(current_timestamp()).unwrap_or(0),
+    }))];
+    if let Some(taken) = taken_packet(channel_id, floor) {
+        packets.push((None, Some(holder), taken));
+    }
+    packets.extend(queue_pos_packets(channel_id, floor));
+    packets
+}
+
+/// suspended holder 재입장 시 발언 재개 — 재개 패킷 (GRANTED 잔여 duration + 나머지 멤버 FLOOR_TAKEN)
+/// 재개 대상이 아니면 빈 Vec (CHANNEL_JOIN에서 lock 보유 중 호출)
+pub(crate) fn resume_packets(
+    channel_id: &str,
+    user_id:    &str,
+    floor:      &mut FloorControl,
+) -> Vec<(Option<String>, Option<String>, String)> {
+    if !floor.resume(user_id) {
+        return vec![];
+    }
+    grant_packets(channel_id, floor)
+}
+
+/// FLOOR_STATUS 응답 패킷 — holder / indicator / 경과·잔여 시간 / 대기열
+pub(crate) fn status_packet(channel_id: &str, floor: &FloorControl, now: u64) -> String {
+    let taken = floor.state == FloorControlState::Taken;
+    let state = match (taken, floor.is_suspended()) {
+        (true, true)  => "suspended",
+        (true, false) => "taken",
+        _             => "idle",
+    };
+    make_packet(server::FLOOR_STATUS, FloorStatusPayload {
+        channel_id:   channel_id.to_string(),
+        state:        state.to_string(),
+        holder:       floor.floor_taken_by.clone(),
+        priority:     taken.then_some(floor.floor_priority),
+        indicator:    taken.then(|| indicator_to_dto(&floor.floor_indicator)),
+        elapsed_ms:   floor.elapsed_ms(now),
+        remaining_ms: floor.remaining_ms(now),
+        queue:        floor.queue.iter()
+            .map(|e| FloorStatusQueueEntry {
+                user_id:   e.user_id.clone(),
+                priority:  e.priority,
+                indicator: indicator_to_dto(&e.indicator),
+                wait_ms:   now.saturating_sub(e.queued_at),
+            })
+            .collect(),
+    })
+}
+
+/// 현재 talk-burst 종료 기록 — 채널 이력 + 이력 파일 (lock 보유 중, 상태 전이 직전 호출)
+pub(crate) fn end_burst(
+    channel_hub:  &ChannelHub,
+    channel_id:   &str,
+    floor:        &mut FloorControl,
+    end:          BurstEnd,
+    cause:        &str,
+    preempted_by: Option<&str>,
+) {
+    if let Some(burst) = floor.end_burst(end, cause, preempted_by) {
+        trace!("Talk burst: channel={} user={} {}ms cause={}", channel_id, burst.user_id, burst.duration_ms, cause);
+        channel_hub.record_burst(channel_id, &burst);
+    }
+}
+
 /// Revoke 후 상태 전이 결정 (순수 동기) — MutexGuard 보유 중에 호출
 ///
 /// 패턴:
@@ -80,21 +228,9 @@ fn decide_next(
     let mut packets: Vec<(Option<String>, Option<String>, String)> = Vec::new();
 
     if let Some(next) = floor.dequeue_next() {
+        // 대기 중이던 Broadcast 요청도 동일 — GRANTED duration 0(무제한), TAKEN indicator=broadcast
         floor.grant(next.user_id.clone(), next.priority, next.indicator.clone());
-
-        // 다음 holder에게만 FLOOR_GRANTED
-        packets.push((Some(next.user_id.clone()), None, make_packet(server::FLOOR_GRANTED, FloorGrantedPayload {
-            channel_id: channel_id.to_string(),
-            user_id:    next.user_id.clone(),
-            duration:   config::FLOOR_MAX_TAKEN_MS,
-        })));
-
-        // FLOOR_TAKEN: 나머지 멤버에게만 (holder 제외)
-        packets.push((None, Some(next.user_id.clone()), make_packet(server::FLOOR_TAKEN, FloorTakenPayload {
-            channel_id: channel_id.to_string(),
-            user_id:    next.user_id.clone(),
-            indicator:  indicator_to_dto(&next.indicator),
-        })));
+        packets.extend(grant_packets(channel_id, floor));
 
         trace!("Floor Queue → Grant: channel={} user={}", channel_id, next.user_id);
     } else {
@@ -116,25 +252,55 @@ fn decide_next(
 ///   target=None  : 전체 브로드캐스트
 ///   target=Some  : 특정 유저에게만
 ///   exclude=Some : 브로드캐스트 시 제외할 유저
-async fn dispatch_packets(
-    packets:  Vec<(Option<String>, Option<String>, String)>,
-    members:  &std::collections::HashSet<String>,
-    user_hub: &Arc<UserHub>,
+///   수신자가 이 채널에서 MBCP를 쓰면 같은 패킷을 SRTCP로도 전달 (mbcp::send_floor)
+pub(crate) async fn dispatch_packets(
+    packets:    Vec<(Option<String>, Option<String>, String)>,
+    channel_id: &str,
+    members:    &std::collections::HashSet<String>,
+    user_hub:   &Arc<UserHub>,
+    peer_hub:   &MediaPeerHub,
 ) {
     for (target, exclude, json) in packets {
         match target {
             Some(uid) => {
+                mbcp::send_floor(peer_hub, channel_id, &json, |u| u == uid).await;
                 if let Some(user) = user_hub.get(&uid) {
                     let _ = user.tx.send(json).await;
                 }
             }
             None => {
+                mbcp::send_floor(peer_hub, channel_id, &json, |u| members.contains(u) && exclude.as_deref() != Some(u)).await;
                 user_hub.broadcast_to(members, &json, exclude.as_deref()).await;
             }
         }
     }
 }
 
+/// decide_next 결과 trace — 다음 holder가 있으면 GRANTED(QUEUE) + TAKEN, 없으면 IDLE
+fn publish_next_holder(trace_hub: &TraceHub, channel_id: &str, next_holder: Option<&str>) {
+    match next_holder {
+        Some(next_uid) => {
+            trace_hub.publish(TraceEvent::new(
+                TraceDir::Out, Some(channel_id), Some(next_uid),
+                server::FLOOR_GRANTED, "FLOOR_GRANTED(QUEUE)",
+                format!("user={}", next_uid),
+            ).with_webhook(WebhookEvent::FloorGranted));
+            trace_hub.publish(TraceEvent::new(
+                TraceDir::Out, Some(channel_id), Some(next_uid),
+                server::FLOOR_TAKEN, "FLOOR_TAKEN",
+                format!("holder={}", next_uid),
+            ));
+        }
+        None => {
+            trace_hub.publish(TraceEvent::new(
+                TraceDir::Out, Some(channel_id), None,
+                server::FLOOR_IDLE, "FLOOR_IDLE",
+                format!("channel={}", channel_id),
+            ));
+        }
+    }
+}
+
 // ----------------------------------------------------------------------------
 // [op 핸들러들]
 // ----------------------------------------------------------------------------
@@ -145,6 +311,7 @@ pub async fn handle_floor_request(
     user_id:     &str,
     user_hub:    &Arc<UserHub>,
     channel_hub: &Arc<ChannelHub>,
+    peer_hub:    &MediaPeerHub,
     trace_hub:   &Arc<TraceHub>,
     packet:      GatewayPacket,
 ) -> Result<(), LiveError> {
@@ -153,14 +320,15 @@ pub async fn handle_floor_request(
 
     trace!("FLOOR_REQUEST user={} channel={}", user_id, channel_id);
 
-    let priority = payload.priority.unwrap_or_else(|| {
-        user_hub.get(user_id)
-            .map(|u| u.priority)
-            .unwrap_or(config::FLOOR_PRIORITY_DEFAULT)
-    });
+    // IDENTIFY 시 부여된 priority가 상한 — 요청 priority는 그 이하만 허용
+    let user_priority = user_hub.get(user_id)
+        .map(|u| u.priority)
+        .unwrap_or(config::FLOOR_PRIORITY_DEFAULT);
+    let priority = payload.priority.unwrap_or(user_priority);
     let indicator = payload.indicator.as_ref()
         .map(dto_to_indicator)
         .unwrap_or(FloorIndicator::Normal);
+    let is_emergency = indicator == FloorIndicator::Emergency;
 
     let channel = channel_hub.get(&channel_id)
         .ok_or_else(|| LiveError::ChannelNotFound(channel_id.clone()))?;
@@ -170,19 +338,34 @@ pub async fn handle_floor_request(
     enum Action {
         Granted  { granted_json: String, taken_json: String },
         Preempt  { revoke_json: String, granted_json: String, taken_json: String, old_holder: String },
-        Queued   { pos_json: String },
+        Queued   { pos_packets: Vec<(Option<String>, Option<String>, String)> },
+        Denied   { deny_json: String, reason: FloorDenyReason },
     }
 
+    let deny = |reason: FloorDenyReason| Action::Denied {
+        deny_json: deny_packet(&channel_id, reason),
+        reason,
+    };
+
     let action = {
         let mut floor = channel.floor.lock().unwrap();
         match floor.state {
+            // 요청 검증 — PTT 모드 / 채널 멤버 / priority 상한
+            _ if !channel.is_ptt()              => deny(FloorDenyReason::WrongMode),
+            _ if !members.contains(user_id)     => deny(FloorDenyReason::NotMember),
+            _ if priority > user_priority       => deny(FloorDenyReason::PriorityNotAllowed),
+            // Broadcast(일방 무제한)는 정책상 priority 이상 사용자만
+            _ if indicator == FloorIndicator::Broadcast
+                && user_priority < floor.policy.broadcast_min_priority => deny(FloorDenyReason::BroadcastNotAllowed),
+            // 발언 종료 직후 재요청 — Emergency는 cooldown 무시
+            _ if !is_emergency && floor.in_cooldown(user_id) => deny(FloorDenyReason::Cooldown),
             FloorControlState::Idle => {
                 floor.grant(user_id.to_string(), priority, indicator.clone());
                 Action::Granted {
                     granted_json: make_packet(server::FLOOR_GRANTED, FloorGrantedPayload {
                         channel_id: channel_id.clone(),
                         user_id:    user_id.to_string(),
-                        duration:   config::FLOOR_MAX_TAKEN_MS,
+                        duration:   floor.max_taken_ms(),
                     }),
                     taken_json: make_packet(server::FLOOR_TAKEN, FloorTakenPayload {
                         channel_id: channel_id.clone(),
@@ -191,6 +374,7 @@ pub async fn handle_floor_request(
                     }),
                 }
             }
+            // Broadcast 발언 중 — Emergency 외 선점 불가, 청취자 요청은 대기열 진입 또는 거부
             FloorControlState::Taken => {
                 if floor.can_preempt(priority, &indicator) {
                     let old_holder = floor.floor_taken_by.clone().unwrap_or_default();
@@ -198,13 +382,15 @@ pub async fn handle_floor_request(
                         channel_id: channel_id.clone(),
                         cause:      "preempted".to_string(),
                     });
+                    end_burst(channel_hub, &channel_id, &mut floor, BurstEnd::Revoke, "preempted", Some(user_id));
                     floor.grant(user_id.to_string(), priority, indicator.clone());
+                    floor.arm_t100(&old_holder, "preempted");
                     Action::Preempt {
                         revoke_json,
                         granted_json: make_packet(server::FLOOR_GRANTED, FloorGrantedPayload {
                             channel_id: channel_id.clone(),
                             user_id:    user_id.to_string(),
-                            duration:   config::FLOOR_MAX_TAKEN_MS,
+                            duration:   floor.max_taken_ms(),
                         }),
                         taken_json: make_packet(server::FLOOR_TAKEN, FloorTakenPayload {
                             channel_id: channel_id.clone(),
@@ -213,71 +399,104 @@ pub async fn handle_floor_request(
                         }),
                         old_holder,
                     }
+                } else if let Err(reason) = floor.check_enqueue(user_id) {
+                    match reason {
+                        FloorDenyReason::QueueDisabled if floor.is_broadcast() => deny(FloorDenyReason::BroadcastActive),
+                        _ => deny(reason),
+                    }
                 } else {
+                    // 신규 진입자 포함 대기자 전원 순서 갱신 (높은 priority 삽입 시 뒤로 밀림)
                     floor.enqueue(user_id.to_string(), priority, indicator);
-                    let pos  = floor.queue_position(user_id).unwrap_or(1);
-                    let size = floor.queue.len();
-                    Action::Queued {
-                        pos_json: make_packet(server::FLOOR_QUEUE_POS_INFO, FloorQueuePosInfoPayload {
-                            channel_id:     channel_id.clone(),
-                            queue_position: pos,
-                            queue_size:     size,
-                        }),
-                    }
+                    Action::Queued { pos_packets: queue_pos_packets(&channel_id, &floor) }
                 }
             }
         }
         // MutexGuard drop here
     };
 
+    // 긴급 요청은 처리 결과와 무관하게 별도 Sys trace
+    if is_emergency {
+        let result = match action {
+            Action::Granted { .. } => "granted",
+            Action::Preempt { .. } => "preempt",
+            Action::Queued { .. }  => "queued",
+            Action::Denied { .. }  => "denied",
+        };
+        warn!("Floor Emergency: channel={} user={} result={}", channel_id, user_id, result);
+        trace_hub.publish(TraceEvent::new(
+            TraceDir::Sys, Some(&channel_id), Some(user_id),
+            client::FLOOR_REQUEST, "FLOOR_EMERGENCY",
+            format!("user={} priority={} result={}", user_id, priority, result),
+        ));
+    }
+
+    // 검증을 통과한 긴급 요청 → 채널 긴급 상태 발령 (이미 발령 중이면 유지)
+    if is_emergency && !matches!(action, Action::Denied { .. }) {
+        super::emergency::raise(&channel, user_id, EmergencySource::FloorRequest, user_hub, trace_hub).await;
+    }
+
+    // Grant / Preempt / Queued → 만료 시각(T101, ping, max_duration, 대기열) 재계산
+    if !matches!(action, Action::Denied { .. }) {
+        channel_hub.wake_floor_timer();
+    }
+
     // lock 해제 후 await
     match action {
         Action::Granted { granted_json, taken_json } => {
-            send(tx, granted_json).await?;
-            user_hub.broadcast_to(&members, &taken_json, Some(user_id)).await;
+            reply(tx, peer_hub, &channel_id, user_id, granted_json).await?;
+            dispatch_packets(vec![(None, Some(user_id.to_string()), taken_json)], &channel_id, &members, user_hub, peer_hub).await;
             trace!("Floor Granted (Idle→Taken): channel={} user={}", channel_id, user_id);
             trace_hub.publish(TraceEvent::new(
                 TraceDir::Out, Some(&channel_id), Some(user_id),
                 server::FLOOR_GRANTED, "FLOOR_GRANTED",
                 format!("user={} priority={}", user_id, priority),
-            ));
+            ).with_webhook(WebhookEvent::FloorGranted));
             trace_hub.publish(TraceEvent::new(
                 TraceDir::Out, Some(&channel_id), Some(user_id),
                 server::FLOOR_TAKEN, "FLOOR_TAKEN",
                 format!("holder={}", user_id),
             ));
+            request_holder_keyframe(peer_hub, &channel_id, user_id).await;
         }
         Action::Preempt { revoke_json, granted_json, taken_json, old_holder } => {
-            if let Some(holder_user) = user_hub.get(&old_holder) {
-                let _ = holder_user.tx.send(revoke_json).await;
-            }
-            send(tx, granted_json).await?;
-            user_hub.broadcast_to(&members, &taken_json, Some(user_id)).await;
+            dispatch_packets(vec![(Some(old_holder.clone()), None, revoke_json)], &channel_id, &members, user_hub, peer_hub).await;
+            reply(tx, peer_hub, &channel_id, user_id, granted_json).await?;
+            dispatch_packets(vec![(None, Some(user_id.to_string()), taken_json)], &channel_id, &members, user_hub, peer_hub).await;
             warn!("Floor Preempted: channel={} old={} new={}", channel_id, old_holder, user_id);
             trace_hub.publish(TraceEvent::new(
                 TraceDir::Out, Some(&channel_id), Some(&old_holder),
                 server::FLOOR_REVOKE, "FLOOR_REVOKE(PREEMPT)",
                 format!("revoked={} by={}", old_holder, user_id),
-            ));
+            ).with_webhook(WebhookEvent::FloorRevoked));
             trace_hub.publish(TraceEvent::new(
                 TraceDir::Out, Some(&channel_id), Some(user_id),
                 server::FLOOR_GRANTED, "FLOOR_GRANTED(PREEMPT)",
                 format!("new={} old={} priority={}", user_id, old_holder, priority),
-            ));
+            ).with_webhook(WebhookEvent::FloorGranted));
             trace_hub.publish(TraceEvent::new(
                 TraceDir::Out, Some(&channel_id), Some(user_id),
                 server::FLOOR_TAKEN, "FLOOR_TAKEN",
                 format!("holder={}", user_id),
             ));
+            request_holder_keyframe(peer_hub, &channel_id, user_id).await;
         }
-        Action::Queued { pos_json } => {
-            send(tx, pos_json).await?;
+        Action::Queued { pos_packets } => {
+            dispatch_packets(pos_packets, &channel_id, &members, user_hub, peer_hub).await;
             trace_hub.publish(TraceEvent::new(
                 TraceDir::Out, Some(&channel_id), Some(user_id),
                 server::FLOOR_QUEUE_POS_INFO, "FLOOR_QUEUED",
                 format!("user={} priority={}", user_id, priority),
             ));
         }
+        Action::Denied { deny_json, reason } => {
+            reply(tx, peer_hub, &channel_id, user_id, deny_json).await?;
+            trace!("Floor Denied: channel={} user={} reason={}", channel_id, user_id, reason.as_str());
+            trace_hub.publish(TraceEvent::new(
+                TraceDir::Out, Some(&channel_id), Some(user_id),
+                server::FLOOR_DENY, "FLOOR_DENY",
+                format!("user={} reason={}", user_id, reason.as_str()),
+            ));
+        }
     }
 
     Ok(())
@@ -289,6 +508,7 @@ pub async fn handle_floor_release(
     user_id:     &str,
     user_hub:    &Arc<UserHub>,
     channel_hub: &Arc<ChannelHub>,
+    peer_hub:    &MediaPeerHub,
     trace_hub:   &Arc<TraceHub>,
     packet:      GatewayPacket,
 ) -> Result<(), LiveError> {
@@ -301,20 +521,37 @@ pub async fn handle_floor_release(
         .ok_or_else(|| LiveError::ChannelNotFound(channel_id.clone()))?;
     let members = channel.get_members();
 
-    let (packets, next_holder) = {
+    // Err = non-holder 처리 결과 (대기열 순서 갱신 패킷)
+    let released = {
         let mut floor = channel.floor.lock().unwrap();
         if floor.floor_taken_by.as_deref() != Some(user_id) {
-            // non-holder → 큐에서 제거만 하고 종료 (상태 전이 없음)
-            floor.remove_from_queue(user_id);
-            trace!("FLOOR_RELEASE queue-remove: user={} channel={}", user_id, channel_id);
-            return Ok(());
+            // non-holder → Revoke 확인(T100 해제) + 큐에서 제거만 하고 종료 (상태 전이 없음)
+            if floor.ack_revoke(user_id) {
+                trace!("FLOOR_RELEASE revoke-ack: user={} channel={}", user_id, channel_id);
+            }
+            if floor.remove_from_queue(user_id) {
+                trace!("FLOOR_RELEASE queue-remove: user={} channel={}", user_id, channel_id);
+                Err(queue_pos_packets(channel_id, &floor))
+            } else {
+                Err(vec![])
+            }
+        } else {
+            end_burst(channel_hub, channel_id, &mut floor, BurstEnd::Release, "release", None);
+            let pkts = decide_next(channel_id, &mut floor, &members);
+            let next = floor.floor_taken_by.clone(); // Grant된 다음 holder (없으면 None = Idle)
+            Ok((pkts, next))
         }
-        let pkts = decide_next(channel_id, &mut floor, &members);
-        let next = floor.floor_taken_by.clone(); // Grant된 다음 holder (없으면 None = Idle)
-        (pkts, next)
         // MutexGuard drop here
     };
 
+    let (packets, next_holder) = match released {
+        Ok(r) => r,
+        Err(pos_packets) => {
+            dispatch_packets(pos_packets, channel_id, &members, user_hub, peer_hub).await;
+            return Ok(());
+        }
+    };
+
     // Release trace
     trace_hub.publish(TraceEvent::new(
         TraceDir::Out, Some(channel_id), Some(user_id),
@@ -323,26 +560,15 @@ pub async fn handle_floor_release(
     ));
 
     // 다음 holder가 있으면 GRANTED + TAKEN trace
-    if let Some(ref next_uid) = next_holder {
-        trace_hub.publish(TraceEvent::new(
-            TraceDir::Out, Some(channel_id), Some(next_uid),
-            server::FLOOR_GRANTED, "FLOOR_GRANTED(QUEUE)",
-            format!("user={}", next_uid),
-        ));
-        trace_hub.publish(TraceEvent::new(
-            TraceDir::Out, Some(channel_id), Some(next_uid),
-            server::FLOOR_TAKEN, "FLOOR_TAKEN",
-            format!("holder={}", next_uid),
-        ));
-    } else {
-        trace_hub.publish(TraceEvent::new(
-            TraceDir::Out, Some(channel_id), None,
-            server::FLOOR_IDLE, "FLOOR_IDLE",
-            format!("channel={}", channel_id),
-        ));
+    publish_next_holder(trace_hub, channel_id, next_holder.as_deref());
+    if next_holder.is_some() {
+        channel_hub.wake_floor_timer();
     }
 
-    dispatch_packets(packets, &members, user_hub).await;
+    dispatch_packets(packets, channel_id, &members, user_hub, peer_hub).await;
+    if let Some(next) = next_holder.as_deref() {
+        request_holder_keyframe(peer_hub, channel_id, next).await;
+    }
     Ok(())
 }
 
@@ -376,16 +602,113 @@ pub async fn handle_floor_ping(
     })).await
 }
 
+/// op: FLOOR_QUEUE_CANCEL (33) — 대기열 이탈 (발언 요청 취소)
+/// ACK(cancelled) 응답 + 남은 대기자 순서 갱신
+pub async fn handle_floor_queue_cancel(
+    tx:          &mpsc::Sender<String>,
+    user_id:     &str,
+    user_hub:    &Arc<UserHub>,
+    channel_hub: &Arc<ChannelHub>,
+    peer_hub:    &MediaPeerHub,
+    trace_hub:   &Arc<TraceHub>,
+    packet:      GatewayPacket,
+) -> Result<(), LiveError> {
+    let payload    = parse_payload::<FloorQueueCancelPayload>(packet.d)?;
+    let channel_id = &payload.channel_id;
+
+    let channel = channel_hub.get(channel_id)
+        .ok_or_else(|| LiveError::ChannelNotFound(channel_id.clone()))?;
+    let members = channel.get_members();
+
+    let (cancelled, pos_packets) = {
+        let mut floor = channel.floor.lock().unwrap();
+        let cancelled = floor.remove_from_queue(user_id);
+        let packets   = if cancelled { queue_pos_packets(channel_id, &floor) } else { vec![] };
+        (cancelled, packets)
+        // MutexGuard drop
+    };
+
+    send(tx, make_packet(server::ACK, AckPayload {
+        op:   client::FLOOR_QUEUE_CANCEL,
+        data: serde_json::json!({ "channel_id": channel_id, "cancelled": cancelled }),
+    })).await?;
+
+    if cancelled {
+        trace!("Floor Queue Cancel: channel={} user={}", channel_id, user_id);
+        trace_hub.publish(TraceEvent::new(
+            TraceDir::Sys, Some(channel_id), Some(user_id),
+            client::FLOOR_QUEUE_CANCEL, "FLOOR_QUEUE_CANCEL",
+            format!("user={} remaining={}", user_id, pos_packets.len()),
+        ));
+        dispatch_packets(pos_packets, channel_id, &members, user_hub, peer_hub).await;
+    }
+    Ok(())
+}
+
+/// op: FLOOR_STATUS (34) — 현재 Floor 상태 조회, 요청자에게만 FLOOR_STATUS(118) 응답
+pub async fn handle_floor_status(
+    tx:          &mpsc::Sender<String>,
+    user_id:     &str,
+    channel_hub: &Arc<ChannelHub>,
+    peer_hub:    &MediaPeerHub,
+    packet:      GatewayPacket,
+) -> Result<(), LiveError> {
+    let payload    = parse_payload::<FloorStatusRequestPayload>(packet.d)?;
+    let channel_id = &payload.channel_id;
+
+    let channel = channel_hub.get(channel_id)
+        .ok_or_else(|| LiveError::ChannelNotFound(channel_id.clone()))?;
+    if !channel.get_members().contains(user_id) {
+        return send(tx, error_packet(LiveError::NotInChannel(channel_id.clone()))).await;
+    }
+
+    let json = {
+        let floor = channel.floor.lock().unwrap();
+        status_packet(channel_id, &floor, current_timestamp())
+        // MutexGuard drop
+    };
+    trace!("FLOOR_STATUS user={} channel={}", user_id, channel_id);
+    reply(tx, peer_hub, channel_id, user_id, json).await
+}
+
 // ----------------------------------------------------------------------------
-// [Floor 타임아웃 체크] — zombie reaper에서 주기적으로 호출
-// run_floor_ping_task 대체 — 별도 태스크 없이 reaper와 통합
+// [Floor 타이머] — 만료 시각 기반 단일 태스크
+//   채널별 next_deadline() 중 최소값까지 sleep → 만료 처리 → 재계산
+//   Grant / 정책 변경 / T100 시작 등 만료 시각이 앞당겨지면 ChannelHub::wake_floor_timer()
+//   누락 대비 최대 대기 REAPER_INTERVAL_MS (기존 reaper 주기와 동일한 상한)
 // ----------------------------------------------------------------------------
 
-/// 모든 채널의 Floor 상태를 순회하며 타임아웃/max_duration Revoke 처리
+pub async fn run_floor_timer(
+    user_hub:    Arc<UserHub>,
+    channel_hub: Arc<ChannelHub>,
+    peer_hub:    Arc<MediaPeerHub>,
+    trace_hub:   Arc<TraceHub>,
+) {
+    info!("[floor-timer] Started (T101={}ms, T100={}ms, retransmit={})",
+        config::FLOOR_T101_MS, config::FLOOR_T100_MS, config::FLOOR_RETRANSMIT_MAX);
+
+    loop {
+        let now     = current_timestamp();
+        let wait_ms = channel_hub.next_floor_deadline()
+            .map(|d| d.saturating_sub(now))
+            .unwrap_or(config::REAPER_INTERVAL_MS)
+            .min(config::REAPER_INTERVAL_MS);
+
+        tokio::select! {
+            _ = tokio::time::sleep(Duration::from_millis(wait_ms)) => {}
+            _ = channel_hub.floor_timer.notified() => {}
+        }
+
+        check_floor_timeouts(&user_hub, &channel_hub, &peer_hub, Some(&trace_hub)).await;
+    }
+}
+
+/// 모든 채널의 Floor 상태를 순회하며 타임아웃/max_duration Revoke + 대기열 만료 + T101/T100 재전송 처리
 /// trace_hub: Some이면 Revoke 이벤트 publish, None이면 생략
 pub async fn check_floor_timeouts(
     user_hub:    &Arc<UserHub>,
     channel_hub: &Arc<ChannelHub>,
+    peer_hub:    &MediaPeerHub,
     trace_hub:   Option<&Arc<TraceHub>>,
 ) {
     let channel_ids: Vec<String> = {
@@ -398,59 +721,139 @@ pub async fn check_floor_timeouts(
             None     => continue,
         };
 
-        enum Action {
-            Skip,
-            Revoke {
-                cause:       String,
-                holder:      String,
-                revoke_json: String,
-                packets:     Vec<(Option<String>, Option<String>, String)>,
-                members:     std::collections::HashSet<String>,
-            },
+        struct Revoke {
+            cause:       String,
+            holder:      String,
+            next_holder: Option<String>,
+            revoke_json: String,
+            packets:     Vec<(Option<String>, Option<String>, String)>,
         }
 
-        let action = {
+        let members = channel.get_members();
+        let (revoke, vox_release, expired, queue_packets, retransmits) = {
             let mut floor = channel.floor.lock().unwrap();
 
-            if floor.state != FloorControlState::Taken {
-                Action::Skip
+            let cause = if floor.state != FloorControlState::Taken {
+                None
+            } else if floor.is_suspend_expired(current_timestamp()) {
+                Some("disconnect") // suspended 유예 만료 — 재입장 없음 (max_duration보다 우선, hog_cooldown 미적용)
             } else if floor.is_max_taken_exceeded() {
-                let holder      = floor.floor_taken_by.clone().unwrap_or_default();
-                let revoke_json = make_packet(server::FLOOR_REVOKE, FloorRevokePayload {
-                    channel_id: channel_id.clone(),
-                    cause:      "max_duration".to_string(),
-                });
-                let members = channel.get_members();
-                let packets = decide_next(&channel_id, &mut floor, &members);
-                Action::Revoke { cause: "max_duration".to_string(), holder, revoke_json, packets, members }
+                Some("max_duration")
             } else if floor.is_ping_timeout() {
+                Some("ping_timeout")
+            } else if floor.is_media_inactive(current_timestamp()) {
+                Some("media_inactive") // FLOOR_PING / WS는 살아 있으나 RTP 없음
+            } else {
+                None
+            };
+
+            let revoke = cause.map(|cause| {
                 let holder      = floor.floor_taken_by.clone().unwrap_or_default();
                 let revoke_json = make_packet(server::FLOOR_REVOKE, FloorRevokePayload {
                     channel_id: channel_id.clone(),
-                    cause:      "ping_timeout".to_string(),
+                    cause:      cause.to_string(),
                 });
-                let members = channel.get_members();
+                end_burst(channel_hub, &channel_id, &mut floor, BurstEnd::Revoke, cause, None);
+                let packets     = decide_next(&channel_id, &mut floor, &members);
+                floor.arm_t100(&holder, cause);
+                Revoke { cause: cause.to_string(), holder, next_holder: floor.floor_taken_by.clone(), revoke_json, packets }
+            });
+
+            // VOX hang time 만료 → holder 자발적 Release와 동일 처리 (Revoke 아님 — T100 / hog_cooldown 없음)
+            let vox_release = if revoke.is_none() && floor.is_vox_hang_expired(current_timestamp()) {
+                let holder  = floor.floor_taken_by.clone().unwrap_or_default();
+                end_burst(channel_hub, &channel_id, &mut floor, BurstEnd::Release, "vox_hang", None);
                 let packets = decide_next(&channel_id, &mut floor, &members);
-                Action::Revoke { cause: "ping_timeout".to_string(), holder, revoke_json, packets, members }
+                Some((holder, floor.floor_taken_by.clone(), packets))
             } else {
-                Action::Skip
+                None
+            };
+
+            // 대기열 만료 → FLOOR_DENY(queue_expired) + 남은 대기자 순서 갱신
+            let expired = floor.expire_queue(current_timestamp());
+            let mut queue_packets = queue_deny_packets(&channel_id, &expired, FloorDenyReason::QueueExpired);
+            if !expired.is_empty() {
+                queue_packets.extend(queue_pos_packets(&channel_id, &floor));
             }
+
+            // T101 / T100 재전송 패킷 (target, op, json, trace 이름, summary)
+            let expiry = floor.expire_timers(current_timestamp());
+            let mut retransmits: Vec<(String, u8, String, &'static str, String)> = Vec::new();
+            if let Some(holder) = expiry.t101 {
+                let elapsed = floor.floor_taken_at.map(|t| current_timestamp().saturating_sub(t)).unwrap_or(0);
+                let max     = floor.max_taken_ms();
+                retransmits.push((holder.clone(), server::FLOOR_GRANTED, make_packet(server::FLOOR_GRANTED, FloorGrantedPayload {
+                    channel_id: channel_id.clone(),
+                    user_id:    holder.clone(),
+                    duration:   if max == 0 { 0 } else { max.saturating_sub(elapsed) },
+                }), "FLOOR_GRANTED(T101)", format!("user={} retransmit", holder)));
+            }
+            for (uid, cause) in expiry.t100 {
+                retransmits.push((uid.clone(), server::FLOOR_REVOKE, make_packet(server::FLOOR_REVOKE, FloorRevokePayload {
+                    channel_id: channel_id.clone(),
+                    cause:      cause.clone(),
+                }), "FLOOR_REVOKE(T100)", format!("user={} cause={} retransmit", uid, cause)));
+            }
+
+            (revoke, vox_release, expired, queue_packets, retransmits)
             // MutexGuard drop
         };
 
-        if let Action::Revoke { cause, holder, revoke_json, packets, members } = action {
+        if let Some(Revoke { cause, holder, next_holder, revoke_json, packets }) = revoke {
             warn!("Floor Revoke ({}): channel={} user={}", cause, channel_id, holder);
-            if let Some(user) = user_hub.get(&holder) {
-                let _ = user.tx.send(revoke_json).await;
-            }
+            dispatch_packets(vec![(Some(holder.clone()), None, revoke_json)], &channel_id, &members, user_hub, peer_hub).await;
             if let Some(th) = trace_hub {
                 th.publish(TraceEvent::new(
                     TraceDir::Sys, Some(&channel_id), Some(&holder),
                     server::FLOOR_REVOKE, "FLOOR_REVOKE",
                     format!("cause={} user={}", cause, holder),
+                ).with_webhook(WebhookEvent::FloorRevoked));
+                publish_next_holder(th, &channel_id, next_holder.as_deref());
+            }
+            dispatch_packets(packets, &channel_id, &members, user_hub, peer_hub).await;
+            if let Some(next) = next_holder.as_deref() {
+                request_holder_keyframe(peer_hub, &channel_id, next).await;
+            }
+        }
+
+        if let Some((holder, next_holder, packets)) = vox_release {
+            info!("Floor VOX hang release: channel={} user={}", channel_id, holder);
+            if let Some(th) = trace_hub {
+                th.publish(TraceEvent::new(
+                    TraceDir::Sys, Some(&channel_id), Some(&holder),
+                    server::FLOOR_IDLE, "FLOOR_RELEASE",
+                    format!("released_by={} cause=vox_hang", holder),
+                ));
+                publish_next_holder(th, &channel_id, next_holder.as_deref());
+            }
+            dispatch_packets(packets, &channel_id, &members, user_hub, peer_hub).await;
+            if let Some(next) = next_holder.as_deref() {
+                request_holder_keyframe(peer_hub, &channel_id, next).await;
+            }
+        }
+
+        for uid in &expired {
+            info!("Floor Queue Expired: channel={} user={}", channel_id, uid);
+            if let Some(th) = trace_hub {
+                th.publish(TraceEvent::new(
+                    TraceDir::Out, Some(&channel_id), Some(uid),
+                    server::FLOOR_DENY, "FLOOR_DENY",
+                    format!("user={} reason={}", uid, FloorDenyReason::QueueExpired.as_str()),
                 ));
             }
-            dispatch_packets(packets, &members, user_hub).await;
+        }
+        dispatch_packets(queue_packets, &channel_id, &members, user_hub, peer_hub).await;
+
+        for (uid, op, json, op_name, summary) in retransmits {
+            trace!("Floor retransmit {}: channel={} user={}", op_name, channel_id, uid);
+            dispatch_packets(vec![(Some(uid.clone()), None, json)], &channel_id, &members, user_hub, peer_hub).await;
+            if let Some(th) = trace_hub {
+                th.publish(TraceEvent::new(TraceDir::Out, Some(&channel_id), Some(&uid), op, op_name, summary));
+            }
+            // GRANTED 재전송 — 첫 keyframe 요청이 유실됐을 수 있으므로 한 번 더
+            if op == server::FLOOR_GRANTED {
+                request_holder_keyframe(peer_hub, &channel_id, &uid).await;
+            }
         }
     }
 }
@@ -459,12 +862,17 @@ pub async fn check_floor_timeouts(
 // [WS cleanup 연동]
 // ----------------------------------------------------------------------------
 
-/// WS 연결 종료 시 해당 user의 Floor 상태 정리
+/// WS 연결 종료 / CHANNEL_LEAVE 시 해당 user의 Floor 상태 정리
+/// cause: "disconnect" | "leave" (FLOOR_REVOKE trace 표기)
+/// disconnect + suspend_grace_ms > 0 이면 holder는 즉시 Revoke 대신 suspended (유예 만료 시 타이머가 Revoke)
 pub async fn on_user_disconnect(
     user_id:     &str,
     channel_id:  &str,
+    cause:       &str,
     user_hub:    &Arc<UserHub>,
     channel_hub: &Arc<ChannelHub>,
+    peer_hub:    &MediaPeerHub,
+    trace_hub:   &Arc<TraceHub>,
 ) {
     let channel = match channel_hub.get(channel_id) {
         Some(ch) => ch,
@@ -472,19 +880,82 @@ pub async fn on_user_disconnect(
     };
     let members = channel.get_members();
 
-    let packets = {
+    // was_holder=true 일 때만 next_holder 의미 있음
+    let mut suspended = None;
+    let (packets, was_holder, next_holder) = {
         let mut floor = channel.floor.lock().unwrap();
-        floor.remove_from_queue(user_id);
-        if floor.floor_taken_by.as_deref() == Some(user_id) {
-            warn!("Floor Disconnect Revoke: channel={} user={}", channel_id, user_id);
-            decide_next(channel_id, &mut floor, &members)
+        let dequeued = floor.remove_from_queue(user_id);
+        floor.ack_revoke(user_id); // 연결 종료 — REVOKE 재전송 불필요
+        let is_holder = floor.floor_taken_by.as_deref() == Some(user_id);
+        if is_holder && cause != "leave" && floor.suspend(current_timestamp()) {
+            // 연결 끊김 — 유예 동안 Floor 유지, 나머지 멤버에게 FLOOR_SUSPENDED
+            let grace_ms = floor.policy.suspend_grace_ms;
+            suspended = Some(grace_ms);
+            let pkt = make_packet(server::FLOOR_SUSPENDED, FloorSuspendedPayload {
+                channel_id: channel_id.to_string(),
+                user_id:    user_id.to_string(),
+                grace_ms,
+            });
+            let mut pkts = vec![(None, Some(user_id.to_string()), pkt)];
+            if dequeued {
+                pkts.extend(queue_pos_packets(channel_id, &floor));
+            }
+            (pkts, false, None)
+        } else if is_holder {
+            warn!("Floor Disconnect Revoke: channel={} user={} cause={}", channel_id, user_id, cause);
+            let end = if cause == "leave" { BurstEnd::Release } else { BurstEnd::Revoke };
+            end_burst(channel_hub, channel_id, &mut floor, end, cause, None);
+            let pkts = decide_next(channel_id, &mut floor, &members);
+            (pkts, true, floor.floor_taken_by.clone())
+        } else if dequeued {
+            (queue_pos_packets(channel_id, &floor), false, None)
         } else {
-            vec![]
+            (vec![], false, None)
         }
         // MutexGuard drop here
     };
 
-    dispatch_packets(packets, &members, user_hub).await;
+    if let Some(grace_ms) = suspended {
+        warn!("Floor Suspended: channel={} user={} grace={}ms", channel_id, user_id, grace_ms);
+        trace_hub.publish(TraceEvent::new(
+            TraceDir::Sys, Some(channel_id), Some(user_id),
+            server::FLOOR_SUSPENDED, "FLOOR_SUSPENDED",
+            format!("user={} grace={}ms", user_id, grace_ms),
+        ));
+        channel_hub.wake_floor_timer();
+    }
+
+    if was_holder {
+        trace_hub.publish(TraceEvent::new(
+            TraceDir::Sys, Some(channel_id), Some(user_id),
+            server::FLOOR_REVOKE, "FLOOR_REVOKE",
+            format!("cause={} user={}", cause, user_id),
+        ).with_webhook(WebhookEvent::FloorRevoked));
+        publish_next_holder(trace_hub, channel_id, next_holder.as_deref());
+        if next_holder.is_some() {
+            channel_hub.wake_floor_timer();
+        }
+    }
+
+    dispatch_packets(packets, channel_id, &members, user_hub, peer_hub).await;
+    if let Some(next) = next_holder.as_deref() {
+        request_holder_keyframe(peer_hub, channel_id, next).await;
+    }
+}
+
+// ----------------------------------------------------------------------------
+// [keyframe 요청] Grant 직후 호출 — 요청 / 대기열 / 선점 / 재개 / Admin / VOX / MBCP 공통
+//   PTT 게이트가 열리기 전 holder 영상은 drop되었으므로 수신자는 다음 자연 keyframe까지 깨진 화면 / 정지
+//   → holder video 트랙에 PLI로 즉시 keyframe 유도 (T101 GRANTED 재전송 시에도 한 번 더)
+// ----------------------------------------------------------------------------
+
+pub(crate) async fn request_holder_keyframe(peer_hub: &MediaPeerHub, channel_id: &str, holder: &str) {
+    let ep = peer_hub.get_channel_endpoints(channel_id)
+        .into_iter()
+        .find(|ep| ep.user_id == holder);
+    if let Some(ep) = ep {
+        crate::media::net::request_keyframe(&ep, "floor_granted").await;
+    }
 }
 
 // ----------------------------------------------------------------------------
@@ -497,3 +968,395 @@ fn parse_payload<T: serde::de::DeserializeOwned>(
     let value = d.ok_or_else(|| LiveError::InvalidPayload("missing payload".to_string()))?;
     serde_json::from_value(value).map_err(|e| LiveError::InvalidPayload(e.to_string()))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Channel, ChannelMode, Endpoint, FloorPolicy, TrackKind};
+
+    #[test]
+    fn queue_pos_packets_target_every_waiter() {
+        let mut f = FloorControl::new();
+        f.enqueue("low".into(), 50, FloorIndicator::Normal);
+        f.enqueue("high".into(), 200, FloorIndicator::Normal);
+
+        let packets = queue_pos_packets("CH_1", &f);
+        assert_eq!(packets.len(), 2);
+        assert_eq!(packets[0].0.as_deref(), Some("high"));
+        assert!(packets[0].2.contains("\"queue_position\":1"));
+        assert_eq!(packets[1].0.as_deref(), Some("low"));
+        assert!(packets[1].2.contains("\"queue_position\":2"));
+    }
+
+    /// 공통 fixture — hub 3종 + PTT 채널 하나 (members 가입, 연결은 connect()로 별도)
+    struct Fixture {
+        user_hub:    Arc<UserHub>,
+        channel_hub: Arc<ChannelHub>,
+        peer_hub:    Arc<MediaPeerHub>,
+        trace_hub:   Arc<TraceHub>,
+        channel:     Arc<Channel>,
+    }
+
+    fn fixture(channel_id: &str, members: &[&str]) -> Fixture {
+        let channel_hub = Arc::new(ChannelHub::new());
+        let channel     = channel_hub.create(channel_id, "0900", "test", ChannelMode::PTT, 10);
+        for uid in members {
+            channel.add_member(uid).unwrap();
+        }
+        Fixture {
+            user_hub:  Arc::new(UserHub::new()),
+            peer_hub:  Arc::new(MediaPeerHub::new()),
+            trace_hub: TraceHub::new(),
+            channel_hub,
+            channel,
+        }
+    }
+
+    impl Fixture {
+        /// user 등록 — tx는 핸들러 응답 경로, rx로 수신 패킷 확인
+        fn connect(&self, user_id: &str, priority: u8) -> (mpsc::Sender<String>, mpsc::Receiver<String>) {
+            let (tx, rx) = mpsc::channel(16);
+            self.user_hub.register(user_id, tx.clone(), priority);
+            (tx, rx)
+        }
+
+        /// fixture 채널 대상 패킷 (추가 필드는 extra에)
+        fn packet(&self, op: u8, extra: serde_json::Value) -> GatewayPacket {
+            let mut d = serde_json::json!({ "channel_id": self.channel.channel_id });
+            if let (Some(d), Some(extra)) = (d.as_object_mut(), extra.as_object()) {
+                d.extend(extra.clone());
+            }
+            GatewayPacket { op, d: Some(d) }
+        }
+
+        async fn request(&self, tx: &mpsc::Sender<String>, user_id: &str, extra: serde_json::Value) {
+            handle_floor_request(tx, user_id, &self.user_hub, &self.channel_hub, &self.peer_hub, &self.trace_hub, self.packet(client::FLOOR_REQUEST, extra)).await.unwrap();
+        }
+
+        async fn release(&self, tx: &mpsc::Sender<String>, user_id: &str) {
+            handle_floor_release(tx, user_id, &self.user_hub, &self.channel_hub, &self.peer_hub, &self.trace_hub, self.packet(client::FLOOR_RELEASE, serde_json::json!({}))).await.unwrap();
+        }
+
+        async fn disconnect(&self, user_id: &str) {
+            on_user_disconnect(user_id, &self.channel.channel_id, "disconnect", &self.user_hub, &self.channel_hub, &self.peer_hub, &self.trace_hub).await;
+        }
+
+        async fn check_timeouts(&self) {
+            check_floor_timeouts(&self.user_hub, &self.channel_hub, &self.peer_hub, Some(&self.trace_hub)).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn floor_timer_revokes_on_deadline() {
+        let f = fixture("CH_T", &["alice"]);
+        let (_tx, mut rx) = f.connect("alice", 100);
+        // validate() 하한 우회 — 테스트용 짧은 타임아웃
+        f.channel.set_floor_policy(FloorPolicy { ping_timeout_ms: 150, ..Default::default() }).unwrap();
+
+        tokio::spawn(run_floor_timer(Arc::clone(&f.user_hub), Arc::clone(&f.channel_hub), Arc::clone(&f.peer_hub), Arc::clone(&f.trace_hub)));
+        f.channel.floor.lock().unwrap().grant("alice".into(), 100, FloorIndicator::Normal);
+        f.channel_hub.wake_floor_timer();
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(f.channel.floor.lock().unwrap().state, FloorControlState::Taken);
+
+        // 150ms 만료 직후 Revoke — reaper 주기(10초)와 무관
+        let msg = tokio::time::timeout(Duration::from_millis(500), rx.recv()).await.unwrap().unwrap();
+        assert!(msg.contains("ping_timeout"));
+        assert_eq!(f.channel.floor.lock().unwrap().state, FloorControlState::Idle);
+        assert_eq!(f.channel.floor.lock().unwrap().t100.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn retransmits_do_not_fire_webhooks() {
+        use crate::webhook::webhook_event;
+
+        let f = fixture("CH_W", &["alice"]);
+        let _alice = f.connect("alice", 100);
+        {
+            let mut floor = f.channel.floor.lock().unwrap();
+            floor.grant("alice".into(), 100, FloorIndicator::Normal);
+            floor.arm_t100("bob", "preempted");
+            floor.t101.as_mut().unwrap().deadline = 0;
+            floor.t100[0].deadline = 0;
+        }
+
+        let mut sub = f.trace_hub.subscribe();
+        f.check_timeouts().await;
+
+        let mut names = Vec::new();
+        while let Ok(ev) = sub.try_recv() {
+            assert_eq!(webhook_event(&ev), None, "{}", ev.op_name);
+            names.push(ev.op_name);
+        }
+        assert_eq!(names, vec!["FLOOR_GRANTED(T101)", "FLOOR_REVOKE(T100)"]);
+    }
+
+    #[tokio::test]
+    async fn floor_request_validation_denies_with_code() {
+        let f = fixture("CH_P", &[]);
+        let (tx, mut rx) = f.connect("alice", 100);
+        f.channel_hub.create("CH_C", "0901", "conf", ChannelMode::Conference, 10).add_member("alice").unwrap();
+
+        // 미참여 채널
+        f.request(&tx, "alice", serde_json::json!({})).await;
+        let msg = rx.recv().await.unwrap();
+        assert!(msg.contains("not_member") && msg.contains("4000"));
+
+        // Conference 채널
+        let conf = GatewayPacket { op: client::FLOOR_REQUEST, d: Some(serde_json::json!({ "channel_id": "CH_C" })) };
+        handle_floor_request(&tx, "alice", &f.user_hub, &f.channel_hub, &f.peer_hub, &f.trace_hub, conf).await.unwrap();
+        let msg = rx.recv().await.unwrap();
+        assert!(msg.contains("wrong_mode") && msg.contains("4001"));
+
+        // IDENTIFY priority(100) 초과
+        f.channel.add_member("alice").unwrap();
+        f.request(&tx, "alice", serde_json::json!({ "priority": 200 })).await;
+        let msg = rx.recv().await.unwrap();
+        assert!(msg.contains("priority_not_allowed") && msg.contains("4002"));
+        assert_eq!(f.channel.floor.lock().unwrap().state, FloorControlState::Idle);
+    }
+
+    #[tokio::test]
+    async fn preempt_and_release_recorded_in_history() {
+        let f = fixture("CH_H", &["alice", "bob"]);
+        let (tx_a, _rx_a) = f.connect("alice", 100);
+        let (tx_b, _rx_b) = f.connect("bob", 200);
+
+        f.request(&tx_a, "alice", serde_json::json!({})).await;
+        f.request(&tx_b, "bob", serde_json::json!({})).await;
+        f.release(&tx_b, "bob").await;
+
+        let floor = f.channel.floor.lock().unwrap();
+        assert_eq!(floor.history.len(), 2);
+        assert_eq!(floor.history[0].user_id, "alice");
+        assert_eq!(floor.history[0].cause, "preempted");
+        assert_eq!(floor.history[0].preempted_by.as_deref(), Some("bob"));
+        assert_eq!(floor.history[1].user_id, "bob");
+        assert_eq!(floor.history[1].end, BurstEnd::Release);
+        assert_eq!(floor.history[1].priority, 200);
+    }
+
+    #[tokio::test]
+    async fn grant_requests_keyframe_from_new_holder() {
+        let f = fixture("CH_K", &["alice", "bob"]);
+        let (tx_a, _rx_a) = f.connect("alice", 100);
+        let (tx_b, _rx_b) = f.connect("bob", 100);
+        let ep_a = f.peer_hub.insert("ua", "", "alice", "CH_K");
+        let ep_b = f.peer_hub.insert("ub", "", "bob", "CH_K");
+        ep_a.add_track(1, TrackKind::Video);
+        ep_b.add_track(2, TrackKind::Video);
+
+        let requested = |ep: &Endpoint| ep.keyframe_req_at.load(std::sync::atomic::Ordering::Relaxed) != 0;
+
+        // 직접 Grant → alice PLI, 대기 중인 bob은 요청 없음
+        f.request(&tx_a, "alice", serde_json::json!({})).await;
+        f.request(&tx_b, "bob", serde_json::json!({})).await;
+        assert!(requested(&ep_a));
+        assert!(!requested(&ep_b));
+
+        // Release → 대기열 bob Grant → bob PLI
+        f.release(&tx_a, "alice").await;
+        assert!(requested(&ep_b));
+    }
+
+    #[tokio::test]
+    async fn broadcast_listeners_queue_or_deny_and_emergency_preempts() {
+        let f = fixture("CH_B", &["caller", "bob"]);
+        let (tx_a, mut rx_a) = f.connect("caller", config::FLOOR_BROADCAST_MIN_PRIORITY);
+        let (tx_b, mut rx_b) = f.connect("bob", 255);
+
+        f.request(&tx_a, "caller", serde_json::json!({ "indicator": "broadcast" })).await;
+        assert!(rx_a.recv().await.unwrap().contains("\"duration\":0"));
+        assert!(rx_b.recv().await.unwrap().contains("\"indicator\":\"broadcast\""));
+
+        // 높은 priority 청취자 — 선점 대신 대기열
+        f.request(&tx_b, "bob", serde_json::json!({ "indicator": "imminent_peril" })).await;
+        assert!(rx_b.recv().await.unwrap().contains("\"queue_position\":1"));
+        assert_eq!(f.channel.floor.lock().unwrap().floor_taken_by.as_deref(), Some("caller"));
+
+        // 대기열 비활성 — broadcast_active 거부
+        f.channel.floor.lock().unwrap().queue.clear();
+        f.channel.set_floor_policy(FloorPolicy { queue_enabled: false, ..Default::default() }).unwrap();
+        f.request(&tx_b, "bob", serde_json::json!({ "indicator": "normal" })).await;
+        let msg = rx_b.recv().await.unwrap();
+        assert!(msg.contains("broadcast_active") && msg.contains("4008"));
+
+        // Emergency만 선점
+        f.request(&tx_b, "bob", serde_json::json!({ "indicator": "emergency" })).await;
+        assert!(rx_a.recv().await.unwrap().contains("\"op\":120")); // EMERGENCY_RAISED
+        assert!(rx_a.recv().await.unwrap().contains("preempted"));
+        assert_eq!(f.channel.floor.lock().unwrap().floor_taken_by.as_deref(), Some("bob"));
+    }
+
+    #[tokio::test]
+    async fn broadcast_denied_below_min_priority() {
+        let f = fixture("CH_BP", &["alice"]);
+        let (tx_a, mut rx_a) = f.connect("alice", config::FLOOR_PRIORITY_DEFAULT);
+
+        // 기본 정책 — 일반 사용자의 Broadcast 요청 거부, 발언권 변화 없음
+        f.request(&tx_a, "alice", serde_json::json!({ "indicator": "broadcast" })).await;
+        let msg = rx_a.recv().await.unwrap();
+        assert!(msg.contains("broadcast_not_allowed") && msg.contains("4009"));
+        assert!(f.channel.floor.lock().unwrap().floor_taken_by.is_none());
+
+        // 일반 요청은 그대로 허용
+        f.request(&tx_a, "alice", serde_json::json!({ "indicator": "normal" })).await;
+        assert!(rx_a.recv().await.unwrap().contains(&format!("\"op\":{}", server::FLOOR_GRANTED)));
+        f.release(&tx_a, "alice").await;
+        while rx_a.try_recv().is_ok() {}
+
+        // 정책으로 하한을 낮추면 허용
+        f.channel.set_floor_policy(FloorPolicy {
+            broadcast_min_priority: config::FLOOR_PRIORITY_DEFAULT, ..Default::default()
+        }).unwrap();
+        f.request(&tx_a, "alice", serde_json::json!({ "indicator": "broadcast" })).await;
+        assert!(rx_a.recv().await.unwrap().contains("\"duration\":0"));
+        assert!(f.channel.floor.lock().unwrap().is_broadcast());
+    }
+
+    #[tokio::test]
+    async fn holder_disconnect_suspends_then_resumes_or_revokes() {
+        let f = fixture("CH_R", &["bob"]);
+        let (_tx_b, mut rx_b) = f.connect("bob", 100);
+        f.channel.floor.lock().unwrap().grant("alice".into(), 100, FloorIndicator::Normal);
+
+        // 연결 끊김 — Revoke 대신 FLOOR_SUSPENDED
+        f.disconnect("alice").await;
+        let msg = rx_b.recv().await.unwrap();
+        assert!(msg.contains(&format!("\"op\":{}", server::FLOOR_SUSPENDED)) && msg.contains("\"grace_ms\":5000"));
+        assert!(f.channel.floor.lock().unwrap().is_suspended());
+
+        // 재입장 — 재개 패킷 (alice GRANTED, 나머지 FLOOR_TAKEN)
+        let pkts = resume_packets("CH_R", "alice", &mut f.channel.floor.lock().unwrap());
+        assert_eq!(pkts[0].0.as_deref(), Some("alice"));
+        assert!(pkts[1].2.contains("\"op\":112"));
+        assert!(!f.channel.floor.lock().unwrap().is_suspended());
+
+        // 다시 끊김 + 유예 만료 → Revoke(disconnect) 후 Idle — 유예 중 max_duration을 넘겼어도 원인은 disconnect
+        f.disconnect("alice").await;
+        rx_b.recv().await.unwrap();
+        {
+            let mut floor = f.channel.floor.lock().unwrap();
+            floor.suspended_at   = Some(current_timestamp() - 5_000);
+            floor.floor_taken_at = Some(current_timestamp() - config::FLOOR_MAX_TAKEN_MS - 1);
+        }
+        f.check_timeouts().await;
+        assert!(rx_b.recv().await.unwrap().contains("\"op\":113"));
+        let floor = f.channel.floor.lock().unwrap();
+        assert_eq!(floor.state, FloorControlState::Idle);
+        assert_eq!(floor.history.back().unwrap().cause, "disconnect");
+        assert!(!floor.in_cooldown("alice"));
+    }
+
+    #[tokio::test]
+    async fn holder_without_media_is_revoked() {
+        let f = fixture("CH_M", &["alice"]);
+        let (_tx_a, mut rx_a) = f.connect("alice", 100);
+
+        // FLOOR_PING은 계속 오지만 RTP 없음
+        {
+            let mut floor = f.channel.floor.lock().unwrap();
+            floor.grant("alice".into(), 100, FloorIndicator::Normal);
+            floor.on_ping();
+            floor.last_media_at = current_timestamp() - config::FLOOR_MEDIA_TIMEOUT_MS;
+        }
+        f.check_timeouts().await;
+        let revoke = rx_a.recv().await.unwrap();
+        assert!(revoke.contains(&format!("\"op\":{}", server::FLOOR_REVOKE)) && revoke.contains("media_inactive"));
+        let floor = f.channel.floor.lock().unwrap();
+        assert_eq!(floor.state, FloorControlState::Idle);
+        assert_eq!(floor.history.back().unwrap().cause, "media_inactive");
+    }
+
+    #[tokio::test]
+    async fn vox_hang_releases_to_next_in_queue() {
+        let f = fixture("CH_X", &["alice", "bob"]);
+        let (_tx_b, mut rx_b) = f.connect("bob", 100);
+        {
+            let mut floor = f.channel.floor.lock().unwrap();
+            floor.policy = FloorPolicy { vox: true, ..Default::default() };
+            floor.grant("alice".into(), 100, FloorIndicator::Normal);
+            floor.enqueue("bob".into(), 100, FloorIndicator::Normal);
+        }
+
+        // hang time 이내 → 유지
+        f.check_timeouts().await;
+        assert_eq!(f.channel.floor.lock().unwrap().floor_taken_by.as_deref(), Some("alice"));
+
+        // 무음 hang time 경과 → Release(vox_hang) → bob Grant
+        f.channel.floor.lock().unwrap().last_voice_at = current_timestamp() - config::FLOOR_VOX_HANG_MS;
+        f.check_timeouts().await;
+        assert!(rx_b.recv().await.unwrap().contains(&format!("\"op\":{}", server::FLOOR_GRANTED)));
+        let floor = f.channel.floor.lock().unwrap();
+        assert_eq!(floor.floor_taken_by.as_deref(), Some("bob"));
+        let burst = floor.history.back().unwrap();
+        assert_eq!((burst.end, burst.cause.as_str()), (BurstEnd::Release, "vox_hang"));
+    }
+
+    #[tokio::test]
+    async fn fair_rerank_sends_positions_to_displaced_waiters() {
+        let f = fixture("CH_RR", &["alice", "bob", "carol"]);
+        let (_tx_a, _rx_a)   = f.connect("alice", 100);
+        let (tx_b, mut rx_b) = f.connect("bob", 100);
+        let (tx_c, mut rx_c) = f.connect("carol", 100);
+        f.channel.set_floor_policy(FloorPolicy { round_robin: true, ..Default::default() }).unwrap();
+        {
+            let mut floor = f.channel.floor.lock().unwrap();
+            floor.grant("bob".into(), 100, FloorIndicator::Normal);
+            floor.end_burst(BurstEnd::Release, "release", None);
+            floor.grant("alice".into(), 100, FloorIndicator::Normal);
+        }
+
+        f.request(&tx_b, "bob", serde_json::json!({})).await;
+        assert!(rx_b.recv().await.unwrap().contains("\"queue_position\":1"));
+
+        // 발언 이력 없는 carol이 앞으로 — 밀려난 bob에게도 새 순서 안내
+        f.request(&tx_c, "carol", serde_json::json!({})).await;
+        assert!(rx_c.recv().await.unwrap().contains("\"queue_position\":1"));
+        let moved = rx_b.recv().await.unwrap();
+        assert!(moved.contains(&format!("\"op\":{}", server::FLOOR_QUEUE_POS_INFO)) && moved.contains("\"queue_position\":2"));
+    }
+
+    #[tokio::test]
+    async fn floor_status_reports_holder_timing_and_queue() {
+        let f = fixture("CH_S", &["alice"]);
+        let (tx, mut rx) = mpsc::channel(16);
+
+        let status = || f.packet(client::FLOOR_STATUS, serde_json::json!({}));
+        handle_floor_status(&tx, "alice", &f.channel_hub, &f.peer_hub, status()).await.unwrap();
+        let idle: serde_json::Value = serde_json::from_str(&rx.recv().await.unwrap()).unwrap();
+        assert_eq!(idle["op"], server::FLOOR_STATUS);
+        assert_eq!(idle["d"]["state"], "idle");
+        assert!(idle["d"]["holder"].is_null() && idle["d"]["remaining_ms"].is_null());
+
+        {
+            let mut floor = f.channel.floor.lock().unwrap();
+            floor.grant("bob".into(), 255, FloorIndicator::Emergency);
+            floor.enqueue("carol".into(), 100, FloorIndicator::Normal);
+            // 입장 시 FLOOR_TAKEN — 실제 indicator
+            assert!(taken_packet("CH_S", &floor).unwrap().contains("\"indicator\":\"emergency\""));
+        }
+        handle_floor_status(&tx, "alice", &f.channel_hub, &f.peer_hub, status()).await.unwrap();
+        let taken: serde_json::Value = serde_json::from_str(&rx.recv().await.unwrap()).unwrap();
+        let d = &taken["d"];
+        assert_eq!(d["state"], "taken");
+        assert_eq!(d["holder"], "bob");
+        assert_eq!(d["indicator"], "emergency");
+        assert!(d["elapsed_ms"].as_u64().is_some());
+        assert!(d["remaining_ms"].as_u64().unwrap() <= config::FLOOR_MAX_TAKEN_MS);
+        assert_eq!(d["queue"][0]["user_id"], "carol");
+    }
+
+    #[tokio::test]
+    async fn floor_status_rejects_non_member() {
+        let f = fixture("CH_S", &["alice"]);
+        let (tx, mut rx) = f.connect("mallory", 100);
+
+        let status = f.packet(client::FLOOR_STATUS, serde_json::json!({}));
+        handle_floor_status(&tx, "mallory", &f.channel_hub, &f.peer_hub, status).await.unwrap();
+        let err: serde_json::Value = serde_json::from_str(&rx.recv().await.unwrap()).unwrap();
+        assert_eq!(err["op"], server::ERROR);
+        assert_eq!(err["d"]["code"], 2004);
+    }
+}
@@ -0,0 +1,748 @@
+// author: kodeholic (powered by Claude)
+// MBCP (TS 24.380) 바이너리 Floor Control 코덱 + JSON 게이트웨이 변환
+//
+// 전송: SRTCP APP 패킷 (RFC 3550 §6.7, PT=204, name="MCPT")
+//   byte[0]:     V(2)=2 | P(1) | subtype(5)  — subtype = ack 요청(1bit) | 메시지 타입(4bit)
+//   byte[1]:     PT = 204
+//   byte[2..3]:  length (32bit words - 1)
+//   byte[4..7]:  SSRC
+//   byte[8..11]: name "MCPT"
+//   byte[12..]:  field 목록 — id(1) | len(1) | value(len) | 32bit 정렬 padding
+//
+// JSON 클라이언트와 MBCP 클라이언트는 같은 FloorControl 상태머신을 공유:
+//   수신: MBCP → GatewayPacket(FLOOR_REQUEST 등) 변환 → 기존 floor 핸들러 호출
+//   송신: floor 핸들러 송신 지점(floor::dispatch_packets)에서 send_floor() → MBCP 변환 → SRTCP 전송
+//         (해당 엔드포인트에서 MBCP를 한 번이라도 수신한 경우만)
+
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tracing::{debug, trace, warn};
+
+use crate::config;
+use crate::core::{ChannelHub, Endpoint, MediaPeerHub, RateDecision, UserHub};
+use crate::protocol::floor;
+use crate::protocol::message::{FloorIndicatorDto, GatewayPacket};
+use crate::protocol::opcode::{client, server};
+use crate::protocol::protocol::rate_class;
+use crate::trace::{TraceDir, TraceEvent, TraceHub};
+use crate::utils::current_timestamp;
+
+/// RTCP APP packet type
+const RTCP_PT_APP: u8 = 204;
+
+/// MBCP APP name (TS 24.380 §8.2.2)
+const MCPT_NAME: &[u8; 4] = b"MCPT";
+
+/// subtype 최상위 bit — 수신 측 Floor Ack 요청
+const ACK_REQUESTED_BIT: u8 = 0x10;
+
+/// Queue Info position: 대기열에 없음 (TS 24.380 §8.2.3.5)
+pub const QUEUE_POSITION_NOT_QUEUED: u8 = 255;
+
+// ----------------------------------------------------------------------------
+// [메시지 타입] TS 24.380 Table 8.2.2-1 (floor control server 관점)
+// ----------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MbcpType {
+    Request,         // 0  C→S
+    Granted,         // 1  S→C
+    Taken,           // 2  S→C
+    Deny,            // 3  S→C
+    Release,         // 4  C→S
+    Idle,            // 5  S→C
+    Revoke,          // 6  S→C
+    QueuePosRequest, // 8  C→S
+    QueuePosInfo,    // 9  S→C
+    Ack,             // 10 양방향
+}
+
+impl MbcpType {
+    pub fn code(self) -> u8 {
+        match self {
+            MbcpType::Request         => 0,
+            MbcpType::Granted         => 1,
+            MbcpType::Taken           => 2,
+            MbcpType::Deny            => 3,
+            MbcpType::Release         => 4,
+            MbcpType::Idle            => 5,
+            MbcpType::Revoke          => 6,
+            MbcpType::QueuePosRequest => 8,
+            MbcpType::QueuePosInfo    => 9,
+            MbcpType::Ack             => 10,
+        }
+    }
+
+    pub fn from_code(code: u8) -> Option<Self> {
+        match code {
+            0  => Some(MbcpType::Request),
+            1  => Some(MbcpType::Granted),
+            2  => Some(MbcpType::Taken),
+            3  => Some(MbcpType::Deny),
+            4  => Some(MbcpType::Release),
+            5  => Some(MbcpType::Idle),
+            6  => Some(MbcpType::Revoke),
+            8  => Some(MbcpType::QueuePosRequest),
+            9  => Some(MbcpType::QueuePosInfo),
+            10 => Some(MbcpType::Ack),
+            _  => None,
+        }
+    }
+
+    /// trace op_name 용
+    pub fn as_str(self) -> &'static str {
+        match self {
+            MbcpType::Request         => "MBCP_FLOOR_REQUEST",
+            MbcpType::Granted         => "MBCP_FLOOR_GRANTED",
+            MbcpType::Taken           => "MBCP_FLOOR_TAKEN",
+            MbcpType::Deny            => "MBCP_FLOOR_DENY",
+            MbcpType::Release         => "MBCP_FLOOR_RELEASE",
+            MbcpType::Idle            => "MBCP_FLOOR_IDLE",
+            MbcpType::Revoke          => "MBCP_FLOOR_REVOKE",
+            MbcpType::QueuePosRequest => "MBCP_QUEUE_POS_REQUEST",
+            MbcpType::QueuePosInfo    => "MBCP_QUEUE_POS_INFO",
+            MbcpType::Ack             => "MBCP_FLOOR_ACK",
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// [field] TS 24.380 Table 8.2.3.1-2 — 지원하는 field만 파싱, 나머지는 skip
+// ----------------------------------------------------------------------------
+
+mod field {
+    pub const PRIORITY:       u8 = 0;
+    pub const DURATION:       u8 = 1;
+    pub const REJECT_CAUSE:   u8 = 2;
+    pub const QUEUE_INFO:     u8 = 3;
+    pub const GRANTED_PARTY:  u8 = 4;
+    pub const PERMISSION:     u8 = 5;
+    pub const USER_ID:        u8 = 6;
+    pub const QUEUE_SIZE:     u8 = 7;
+    pub const MESSAGE_TYPE:   u8 = 12;
+    pub const INDICATOR:      u8 = 13;
+}
+
+/// Floor Indicator bit (TS 24.380 §8.2.3.15)
+mod indicator_bit {
+    pub const NORMAL:         u16 = 0x8000;
+    pub const BROADCAST:      u16 = 0x4000;
+    pub const EMERGENCY:      u16 = 0x1000;
+    pub const IMMINENT_PERIL: u16 = 0x0800;
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MbcpFields {
+    pub priority:      Option<u8>,
+    pub duration_secs: Option<u16>,
+    pub reject_cause:  Option<(u16, String)>, // (cause code, reason phrase)
+    pub queue_info:    Option<(u8, u8)>,      // (position, priority)
+    pub granted_party: Option<String>,
+    pub permission:    Option<bool>,          // Permission to Request the Floor
+    pub user_id:       Option<String>,
+    pub queue_size:    Option<u16>,
+    pub message_type:  Option<u8>,            // Floor Ack 대상 메시지 타입
+    pub indicator:     Option<u16>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MbcpMessage {
+    pub kind:          MbcpType,
+    pub ack_requested: bool,
+    pub ssrc:          u32,
+    pub fields:        MbcpFields,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum MbcpError {
+    TooShort,
+    NotMcpt,
+    UnknownType(u8),
+    Malformed(&'static str),
+}
+
+impl std::fmt::Display for MbcpError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MbcpError::TooShort       => write!(f, "packet too short"),
+            MbcpError::NotMcpt        => write!(f, "not an MCPT APP packet"),
+            MbcpError::UnknownType(t) => write!(f, "unknown message type {}", t),
+            MbcpError::Malformed(m)   => write!(f, "malformed: {}", m),
+        }
+    }
+}
+
+impl std::error::Error for MbcpError {}
+
+impl MbcpMessage {
+    pub fn new(kind: MbcpType) -> Self {
+        Self {
+            kind,
+            ack_requested: false,
+            ssrc:          config::MBCP_SERVER_SSRC,
+            fields:        MbcpFields::default(),
+        }
+    }
+
+    /// APP 패킷 직렬화 (평문 RTCP — SRTCP 암호화는 호출자 몫)
+    pub fn encode(&self) -> Vec<u8> {
+        let mut body = Vec::with_capacity(32);
+        let f = &self.fields;
+
+        if let Some(p) = f.priority {
+            put_field(&mut body, field::PRIORITY, &[p, 0]);
+        }
+        if let Some(d) = f.duration_secs {
+            put_field(&mut body, field::DURATION, &d.to_be_bytes());
+        }
+        if let Some((code, phrase)) = &f.reject_cause {
+            let mut v = code.to_be_bytes().to_vec();
+            v.extend_from_slice(clamp_utf8(phrase, FIELD_VALUE_MAX - 2).as_bytes());
+            put_field(&mut body, field::REJECT_CAUSE, &v);
+        }
+        if let Some((pos, prio)) = f.queue_info {
+            put_field(&mut body, field::QUEUE_INFO, &[pos, prio]);
+        }
+        if let Some(party) = &f.granted_party {
+            put_field(&mut body, field::GRANTED_PARTY, clamp_utf8(party, FIELD_VALUE_MAX).as_bytes());
+        }
+        if let Some(perm) = f.permission {
+            put_field(&mut body, field::PERMISSION, &[0, perm as u8]);
+        }
+        if let Some(uid) = &f.user_id {
+            put_field(&mut body, field::USER_ID, clamp_utf8(uid, FIELD_VALUE_MAX).as_bytes());
+        }
+        if let Some(size) = f.queue_size {
+            put_field(&mut body, field::QUEUE_SIZE, &size.to_be_bytes());
+        }
+        if let Some(t) = f.message_type {
+            put_field(&mut body, field::MESSAGE_TYPE, &[t, 0]);
+        }
+        if let Some(bits) = f.indicator {
+            put_field(&mut body, field::INDICATOR, &bits.to_be_bytes());
+        }
+
+        let subtype = self.kind.code() | if self.ack_requested { ACK_REQUESTED_BIT } else { 0 };
+        let words   = (12 + body.len()) / 4 - 1;
+
+        let mut pkt = Vec::with_capacity(12 + body.len());
+        pkt.push(0x80 | subtype);
+        pkt.push(RTCP_PT_APP);
+        pkt.extend_from_slice(&(words as u16).to_be_bytes());
+        pkt.extend_from_slice(&self.ssrc.to_be_bytes());
+        pkt.extend_from_slice(MCPT_NAME);
+        pkt.extend_from_slice(&body);
+        pkt
+    }
+
+    /// 단일 APP 패킷 파싱 (compound에서 잘라낸 것 — find_mcpt 참조)
+    pub fn decode(pkt: &[u8]) -> Result<Self, MbcpError> {
+        if pkt.len() < 12 { return Err(MbcpError::TooShort); }
+        if pkt[1] != RTCP_PT_APP || &pkt[8..12] != MCPT_NAME {
+            return Err(MbcpError::NotMcpt);
+        }
+
+        let subtype = pkt[0] & 0x1F;
+        let kind    = MbcpType::from_code(subtype & 0x0F)
+            .ok_or(MbcpError::UnknownType(subtype & 0x0F))?;
+        let ssrc    = u32::from_be_bytes([pkt[4], pkt[5], pkt[6], pkt[7]]);
+        let end     = ((u16::from_be_bytes([pkt[2], pkt[3]]) as usize) + 1) * 4;
+        if end > pkt.len() { return Err(MbcpError::Malformed("length exceeds packet")); }
+
+        let mut f      = MbcpFields::default();
+        let mut offset = 12usize;
+        while offset + 2 <= end {
+            let id  = pkt[offset];
+            let len = pkt[offset + 1] as usize;
+            let v_start = offset + 2;
+            if v_start + len > end { return Err(MbcpError::Malformed("field exceeds packet")); }
+            let v = &pkt[v_start..v_start + len];
+
+            match id {
+                field::PRIORITY      => f.priority      = Some(*v.first().ok_or(MbcpError::Malformed("priority"))?),
+                field::DURATION      => f.duration_secs = Some(be_u16(v).ok_or(MbcpError::Malformed("duration"))?),
+                field::REJECT_CAUSE  => {
+                    let code = be_u16(v).ok_or(MbcpError::Malformed("reject cause"))?;
+                    f.reject_cause = Some((code, String::from_utf8_lossy(&v[2..]).into_owned()));
+                }
+                field::QUEUE_INFO    => {
+                    if v.len() < 2 { return Err(MbcpError::Malformed("queue info")); }
+                    f.queue_info = Some((v[0], v[1]));
+                }
+                field::GRANTED_PARTY => f.granted_party = Some(String::from_utf8_lossy(v).into_owned()),
+                field::PERMISSION    => f.permission    = Some(be_u16(v).ok_or(MbcpError::Malformed("permission"))? != 0),
+                field::USER_ID       => f.user_id       = Some(String::from_utf8_lossy(v).into_owned()),
+                field::QUEUE_SIZE    => f.queue_size    = Some(be_u16(v).ok_or(MbcpError::Malformed("queue size"))?),
+                field::MESSAGE_TYPE  => f.message_type  = Some(*v.first().ok_or(MbcpError::Malformed("message type"))? & 0x0F),
+                field::INDICATOR     => f.indicator     = Some(be_u16(v).ok_or(MbcpError::Malformed("indicator"))?),
+                _ => trace!("[mbcp] skip unknown field id={} len={}", id, len),
+            }
+
+            // id + len + value → 32bit 정렬
+            offset += (2 + len + 3) & !3;
+        }
+
+        Ok(Self { kind, ack_requested: subtype & ACK_REQUESTED_BIT != 0, ssrc, fields: f })
+    }
+}
+
+/// field value 최대 길이 — len 1byte (TS 24.380 §8.2.3.1)
+const FIELD_VALUE_MAX: usize = u8::MAX as usize;
+
+/// 문자열 field를 FIELD_VALUE_MAX 이하로 — UTF-8 문자 경계에서 자름
+fn clamp_utf8(s: &str, max: usize) -> &str {
+    &s[..s.floor_char_boundary(max)]
+}
+
+fn put_field(buf: &mut Vec<u8>, id: u8, value: &[u8]) {
+    let value = &value[..value.len().min(FIELD_VALUE_MAX)];
+    buf.push(id);
+    buf.push(value.len() as u8);
+    buf.extend_from_slice(value);
+    while !buf.len().is_multiple_of(4) { buf.push(0); }
+}
+
+fn be_u16(v: &[u8]) -> Option<u16> {
+    (v.len() >= 2).then(|| u16::from_be_bytes([v[0], v[1]]))
+}
+
+/// compound RTCP 평문에서 APP "MCPT" 패킷 위치 탐색
+/// SRTCP 하나에 RR + APP 처럼 여러 패킷이 묶여 올 수 있음 (RFC 3550 §6.1)
+pub fn find_mcpt(plain: &[u8]) -> Option<&[u8]> {
+    let mut offset = 0usize;
+    while offset + 4 <= plain.len() {
+        let len = ((u16::from_be_bytes([plain[offset + 2], plain[offset + 3]]) as usize) + 1) * 4;
+        let end = offset + len;
+        if end > plain.len() { return None; }
+        let pkt = &plain[offset..end];
+        if pkt.len() >= 12 && pkt[1] == RTCP_PT_APP && &pkt[8..12] == MCPT_NAME {
+            return Some(pkt);
+        }
+        offset = end;
+    }
+    None
+}
+
+// ----------------------------------------------------------------------------
+// [Floor Indicator / Reject Cause 변환]
+// ----------------------------------------------------------------------------
+
+pub fn indicator_bits(ind: &FloorIndicatorDto) -> u16 {
+    match ind {
+        FloorIndicatorDto::Normal        => indicator_bit::NORMAL,
+        FloorIndicatorDto::Broadcast     => indicator_bit::BROADCAST,
+        FloorIndicatorDto::ImminentPeril => indicator_bit::IMMINENT_PERIL,
+        FloorIndicatorDto::Emergency     => indicator_bit::EMERGENCY,
+    }
+}
+
+/// 여러 bit가 켜져 있으면 높은 쪽 우선 (Emergency > ImminentPeril > Broadcast > Normal)
+pub fn indicator_from_bits(bits: u16) -> FloorIndicatorDto {
+    if bits & indicator_bit::EMERGENCY != 0 {
+        FloorIndicatorDto::Emergency
+    } else if bits & indicator_bit::IMMINENT_PERIL != 0 {
+        FloorIndicatorDto::ImminentPeril
+    } else if bits & indicator_bit::BROADCAST != 0 {
+        FloorIndicatorDto::Broadcast
+    } else {
+        FloorIndicatorDto::Normal
+    }
+}
+
+/// FLOOR_DENY reason → Floor Deny reject cause (TS 24.380 §8.2.6.2)
+fn deny_cause(reason: &str) -> u16 {
+    match reason {
+        "queue_disabled" | "queue_full"              => 1,  // Another MCPTT client has permission
+        "cooldown" | "rate_limited"                  => 4,  // Retry-after timer has not expired
+        "broadcast_active" | "broadcast_not_allowed" => 5,  // Receive only
+        _                                            => 255, // Other reason
+    }
+}
+
+/// FLOOR_REVOKE cause → Floor Revoke reject cause (TS 24.380 §8.2.6.2)
+fn revoke_cause(cause: &str) -> u16 {
+    match cause {
+        "max_duration"             => 2,  // Media burst too long
+        "preempted" | "admin_grant" => 4, // Media burst pre-empted
+        _                          => 255, // Other reason
+    }
+}
+
+// ----------------------------------------------------------------------------
+// [게이트웨이 변환]
+// ----------------------------------------------------------------------------
+
+/// MBCP(C→S) → 기존 floor 핸들러가 받는 GatewayPacket
+/// Floor Release는 holder가 아니고 대기 중이면 대기열 이탈(QUEUE_CANCEL)로 해석
+/// Floor Ack는 GRANTED에 대한 것만 FLOOR_PING(T101 확인)으로 매핑, 나머지는 None
+pub fn to_gateway(msg: &MbcpMessage, channel_id: &str, queued: bool) -> Option<GatewayPacket> {
+    let (op, d) = match msg.kind {
+        MbcpType::Request => (client::FLOOR_REQUEST, serde_json::json!({
+            "channel_id": channel_id,
+            "priority":   msg.fields.priority,
+            "indicator":  msg.fields.indicator.map(indicator_from_bits),
+        })),
+        MbcpType::Release if queued => (client::FLOOR_QUEUE_CANCEL, serde_json::json!({ "channel_id": channel_id })),
+        MbcpType::Release           => (client::FLOOR_RELEASE,      serde_json::json!({ "channel_id": channel_id })),
+        MbcpType::QueuePosRequest   => (client::FLOOR_STATUS,       serde_json::json!({ "channel_id": channel_id })),
+        MbcpType::Ack if msg.fields.message_type == Some(MbcpType::Granted.code()) =>
+            (client::FLOOR_PING, serde_json::json!({ "channel_id": channel_id })),
+        _ => return None,
+    };
+    Some(GatewayPacket { op, d: Some(d) })
+}
+
+/// floor JSON(S→C) → MBCP. Floor 이외 op 또는 MBCP 대응이 없는 op(PONG/SUSPENDED 등)는 None
+/// user_id: 수신자 — FLOOR_STATUS 응답에서 본인 대기 순서를 찾는 데 사용
+pub fn from_gateway(json: &str, user_id: &str) -> Option<MbcpMessage> {
+    let packet: GatewayPacket = serde_json::from_str(json).ok()?;
+    let d = packet.d.unwrap_or_default();
+    let str_of = |key: &str| d.get(key).and_then(|v| v.as_str()).unwrap_or("").to_string();
+    let u64_of = |key: &str| d.get(key).and_then(|v| v.as_u64()).unwrap_or(0);
+
+    let mut msg = match packet.op {
+        server::FLOOR_GRANTED => {
+            // holder가 Floor Ack로 확인 → FLOOR_PING과 동일하게 T101 해제
+            let mut m = MbcpMessage::new(MbcpType::Granted);
+            m.ack_requested        = true;
+            m.fields.duration_secs = Some(u64_of("duration").div_ceil(1000).min(u16::MAX as u64) as u16);
+            m
+        }
+        server::FLOOR_DENY => {
+            let reason = str_of("reason");
+            let mut m  = MbcpMessage::new(MbcpType::Deny);
+            m.fields.reject_cause = Some((deny_cause(&reason), reason));
+            m
+        }
+        server::FLOOR_TAKEN => {
+            let ind = d.get("indicator")
+                .and_then(|v| serde_json::from_value::<FloorIndicatorDto>(v.clone()).ok())
+                .unwrap_or(FloorIndicatorDto::Normal);
+            let mut m = MbcpMessage::new(MbcpType::Taken);
+            m.fields.granted_party = Some(str_of("user_id"));
+            m.fields.permission    = Some(ind != FloorIndicatorDto::Broadcast);
+            m.fields.indicator     = Some(indicator_bits(&ind));
+            m
+        }
+        server::FLOOR_IDLE => MbcpMessage::new(MbcpType::Idle),
+        server::FLOOR_REVOKE => {
+            let cause = str_of("cause");
+            let mut m = MbcpMessage::new(MbcpType::Revoke);
+            m.fields.reject_cause = Some((revoke_cause(&cause), cause));
+            m
+        }
+        server::FLOOR_QUEUE_POS_INFO => {
+            let mut m = MbcpMessage::new(MbcpType::QueuePosInfo);
+            m.fields.queue_info = Some((u64_of("queue_position").min(254) as u8, 0));
+            m.fields.queue_size = Some(u64_of("queue_size").min(u16::MAX as u64) as u16);
+            m
+        }
+        server::FLOOR_STATUS => {
+            // Queue Position Request 응답 — 본인 대기 항목만 발췌
+            let queue = d.get("queue").and_then(|v| v.as_array()).cloned().unwrap_or_default();
+            let mine  = queue.iter().enumerate()
+                .find(|(_, e)| e.get("user_id").and_then(|v| v.as_str()) == Some(user_id));
+            let info  = match mine {
+                Some((i, e)) => ((i + 1).min(254) as u8, e.get("priority").and_then(|v| v.as_u64()).unwrap_or(0) as u8),
+                None         => (QUEUE_POSITION_NOT_QUEUED, 0),
+            };
+            let mut m = MbcpMessage::new(MbcpType::QueuePosInfo);
+            m.fields.queue_info = Some(info);
+            m.fields.queue_size = Some(queue.len().min(u16::MAX as usize) as u16);
+            m
+        }
+        _ => return None,
+    };
+    msg.fields.user_id = Some(user_id.to_string());
+    Some(msg)
+}
+
+// ----------------------------------------------------------------------------
+// [송신] floor 핸들러 송신 지점(floor::dispatch_packets / 요청자 응답)에서 호출
+//   채널의 MBCP 엔드포인트 중 to(user_id)인 수신자에게 같은 floor 이벤트를 SRTCP로도 전달
+//   JSON은 그대로 WS로 나감 — 두 경로 중 클라이언트가 쓰는 쪽을 소비
+//   MBCP 엔드포인트가 없으면(대부분) 변환 없이 종료
+// ----------------------------------------------------------------------------
+
+pub async fn send_floor(peer_hub: &MediaPeerHub, channel_id: &str, json: &str, to: impl Fn(&str) -> bool) {
+    for ep in peer_hub.get_channel_endpoints(channel_id) {
+        if !ep.is_mbcp() || !to(&ep.user_id) {
+            continue;
+        }
+        if let Some(msg) = from_gateway(json, &ep.user_id) {
+            crate::media::net::send_mbcp(&ep, &msg).await;
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// [수신 처리] media/net.rs SRTCP 경로에서 호출
+//   엔드포인트를 MBCP 사용으로 표시 → GatewayPacket 변환 → 기존 floor 핸들러
+//   핸들러 응답은 사용자 WS 채널 + send_floor()로 MBCP 응답
+// ----------------------------------------------------------------------------
+
+pub async fn handle_mbcp(
+    ep:          &Arc<Endpoint>,
+    pkt:         &[u8],
+    user_hub:    &Arc<UserHub>,
+    channel_hub: &Arc<ChannelHub>,
+    peer_hub:    &MediaPeerHub,
+    trace_hub:   &Arc<TraceHub>,
+) {
+    let msg = match MbcpMessage::decode(pkt) {
+        Ok(m)  => m,
+        Err(e) => { debug!("[mbcp] decode failed user={}: {}", ep.user_id, e); return; }
+    };
+
+    if !ep.mark_mbcp() {
+        debug!("[mbcp] endpoint switched to MBCP user={} channel={}", ep.user_id, ep.channel_id);
+    }
+    trace!("[mbcp] {} user={} channel={} fields={:?}", msg.kind.as_str(), ep.user_id, ep.channel_id, msg.fields);
+
+    trace_hub.publish(TraceEvent::new(
+        TraceDir::In, Some(&ep.channel_id), Some(&ep.user_id),
+        msg.kind.code(), msg.kind.as_str(),
+        format!("user={} ssrc={}", ep.user_id, msg.ssrc),
+    ));
+
+    // 상대가 Ack를 요청한 메시지는 처리 전 즉시 Floor Ack
+    if msg.ack_requested {
+        let mut ack = MbcpMessage::new(MbcpType::Ack);
+        ack.fields.message_type = Some(msg.kind.code());
+        crate::media::net::send_mbcp(ep, &ack).await;
+    }
+
+    let queued = channel_hub.get(&ep.channel_id)
+        .map(|ch| {
+            let floor = ch.floor.lock().unwrap();
+            floor.floor_taken_by.as_deref() != Some(ep.user_id.as_str())
+                && floor.queue_position(&ep.user_id).is_some()
+        })
+        .unwrap_or(false);
+
+    let packet = match to_gateway(&msg, &ep.channel_id, queued) {
+        Some(p) => p,
+        None    => { trace!("[mbcp] no floor action for {} user={}", msg.kind.as_str(), ep.user_id); return; }
+    };
+
+    // 핸들러 응답 경로 = 사용자 WS 송신 채널 (MBCP 변환은 floor 핸들러의 send_floor가 담당)
+    let Some(user) = user_hub.get(&ep.user_id) else {
+        debug!("[mbcp] user not online user={}", ep.user_id);
+        return;
+    };
+    let tx: mpsc::Sender<String> = user.tx.clone();
+    let user_id = ep.user_id.as_str();
+
+    // WS 경로와 같은 opcode 계열 버킷 차감 — 초과 시 폐기, Floor Request에는 Floor Deny(rate_limited)
+    if let Some(class) = rate_class(packet.op) {
+        let decision = user.rate_limiter.lock().unwrap().check(class, current_timestamp());
+        if decision != RateDecision::Allow {
+            trace_hub.publish(TraceEvent::new(
+                TraceDir::Sys, Some(&ep.channel_id), Some(user_id),
+                packet.op, "RATE_LIMITED",
+                format!("class={} decision={:?} via=mbcp", class.as_str(), decision),
+            ));
+            debug!("[mbcp] rate limited user={} class={} decision={:?}", user_id, class.as_str(), decision);
+            if msg.kind == MbcpType::Request {
+                send_deny(ep, deny_cause("rate_limited"), "rate_limited".to_string()).await;
+            }
+            return;
+        }
+    }
+
+    let result = match packet.op {
+        client::FLOOR_REQUEST      => floor::handle_floor_request(&tx, user_id, user_hub, channel_hub, peer_hub, trace_hub, packet).await,
+        client::FLOOR_RELEASE      => floor::handle_floor_release(&tx, user_id, user_hub, channel_hub, peer_hub, trace_hub, packet).await,
+        client::FLOOR_QUEUE_CANCEL => floor::handle_floor_queue_cancel(&tx, user_id, user_hub, channel_hub, peer_hub, trace_hub, packet).await,
+        client::FLOOR_STATUS       => floor::handle_floor_status(&tx, user_id, channel_hub, peer_hub, packet).await,
+        client::FLOOR_PING         => floor::handle_floor_ping(&tx, user_id, channel_hub, packet).await,
+        _                          => Ok(()),
+    };
+
+    // 핸들러 에러(채널 미참여 등)는 MBCP Floor Deny로 응답
+    if let Err(e) = result {
+        warn!("[mbcp] {} failed user={}: {}", msg.kind.as_str(), user_id, e);
+        if msg.kind == MbcpType::Request {
+            send_deny(ep, 255, e.to_string()).await;
+        }
+    }
+}
+
+/// 요청자에게 Floor Deny (floor 핸들러를 거치지 않은 거부)
+async fn send_deny(ep: &Endpoint, cause: u16, phrase: String) {
+    let mut deny = MbcpMessage::new(MbcpType::Deny);
+    deny.fields.reject_cause = Some((cause, phrase));
+    deny.fields.user_id      = Some(ep.user_id.clone());
+    crate::media::net::send_mbcp(ep, &deny).await;
+}
+
+// ----------------------------------------------------------------------------
+// [테스트]
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn request_roundtrip_with_padding() {
+        let mut msg = MbcpMessage::new(MbcpType::Request);
+        msg.ssrc            = 0x1122_3344;
+        msg.ack_requested   = true;
+        msg.fields.priority  = Some(200);
+        msg.fields.user_id   = Some("alice".into());   // 5B → padding 필요
+        msg.fields.indicator = Some(indicator_bits(&FloorIndicatorDto::Emergency));
+
+        let pkt = msg.encode();
+        assert_eq!(pkt.len() % 4, 0);
+        assert_eq!(pkt[0], 0x80 | 0x10);              // V=2, ack 요청, type 0
+        assert_eq!(pkt[1], 204);
+        assert_eq!(&pkt[8..12], b"MCPT");
+        assert_eq!(MbcpMessage::decode(&pkt).unwrap(), msg);
+    }
+
+    #[test]
+    fn long_fields_clamped_on_utf8_boundary() {
+        let long_id = format!("a{}", "가".repeat(100)); // 301B
+        let mut msg = MbcpMessage::new(MbcpType::Taken);
+        msg.fields.granted_party = Some(long_id.clone());
+        msg.fields.user_id       = Some(long_id.clone());
+        msg.fields.reject_cause  = Some((4, long_id.clone()));
+        msg.fields.indicator     = Some(indicator_bits(&FloorIndicatorDto::Normal));
+
+        // 255B 상한 → 3B 문자 경계(253B)에서 잘림, 뒤 field도 정상 파싱
+        let f = MbcpMessage::decode(&msg.encode()).unwrap().fields;
+        assert_eq!(f.user_id.as_deref(), Some(&long_id[..253]));
+        assert_eq!(f.granted_party.as_deref(), Some(&long_id[..253]));
+        assert_eq!(f.reject_cause, Some((4, long_id[..253].to_string()))); // cause 2B + phrase 253B
+        assert_eq!(f.indicator, Some(0x8000));
+    }
+
+    #[test]
+    fn server_messages_roundtrip() {
+        let mut deny = MbcpMessage::new(MbcpType::Deny);
+        deny.fields.reject_cause = Some((5, "broadcast_active".into()));
+        assert_eq!(MbcpMessage::decode(&deny.encode()).unwrap(), deny);
+
+        let mut info = MbcpMessage::new(MbcpType::QueuePosInfo);
+        info.fields.queue_info = Some((2, 100));
+        info.fields.queue_size = Some(3);
+        assert_eq!(MbcpMessage::decode(&info.encode()).unwrap(), info);
+
+        let idle = MbcpMessage::new(MbcpType::Idle);
+        assert_eq!(idle.encode().len(), 12);
+        assert_eq!(MbcpMessage::decode(&idle.encode()).unwrap(), idle);
+    }
+
+    #[test]
+    fn decode_rejects_bad_packets() {
+        assert_eq!(MbcpMessage::decode(&[0x80, 204, 0, 1]), Err(MbcpError::TooShort));
+
+        let mut pkt = MbcpMessage::new(MbcpType::Release).encode();
+        pkt[8..12].copy_from_slice(b"ABCD");
+        assert_eq!(MbcpMessage::decode(&pkt), Err(MbcpError::NotMcpt));
+
+        let mut pkt = MbcpMessage::new(MbcpType::Release).encode();
+        pkt[0] = 0x80 | 7;
+        assert_eq!(MbcpMessage::decode(&pkt), Err(MbcpError::UnknownType(7)));
+
+        let mut pkt = MbcpMessage::new(MbcpType::Release).encode();
+        pkt[3] = 9; // length가 실제보다 김
+        assert!(matches!(MbcpMessage::decode(&pkt), Err(MbcpError::Malformed(_))));
+    }
+
+    #[test]
+    fn find_mcpt_in_compound() {
+        // RR(빈 report, 8B) + APP MCPT
+        let mut compound = vec![0x80, 201, 0x00, 0x01, 0, 0, 0, 1];
+        let app = MbcpMessage::new(MbcpType::Release).encode();
+        compound.extend_from_slice(&app);
+
+        assert_eq!(find_mcpt(&compound), Some(app.as_slice()));
+        assert_eq!(find_mcpt(&compound[..8]), None);
+    }
+
+    #[test]
+    fn indicator_bits_prefer_highest() {
+        assert_eq!(indicator_from_bits(0x8000 | 0x1000), FloorIndicatorDto::Emergency);
+        assert_eq!(indicator_from_bits(0x8000 | 0x0800), FloorIndicatorDto::ImminentPeril);
+        assert_eq!(indicator_from_bits(0x4000), FloorIndicatorDto::Broadcast);
+        assert_eq!(indicator_from_bits(0), FloorIndicatorDto::Normal);
+    }
+
+    #[test]
+    fn to_gateway_maps_client_messages() {
+        let mut req = MbcpMessage::new(MbcpType::Request);
+        req.fields.priority  = Some(50);
+        req.fields.indicator = Some(0x1000);
+        let p = to_gateway(&req, "ch", false).unwrap();
+        assert_eq!(p.op, client::FLOOR_REQUEST);
+        let d = p.d.unwrap();
+        assert_eq!(d["priority"], 50);
+        assert_eq!(d["indicator"], "emergency");
+
+        let rel = MbcpMessage::new(MbcpType::Release);
+        assert_eq!(to_gateway(&rel, "ch", false).unwrap().op, client::FLOOR_RELEASE);
+        assert_eq!(to_gateway(&rel, "ch", true).unwrap().op, client::FLOOR_QUEUE_CANCEL);
+
+        let mut ack = MbcpMessage::new(MbcpType::Ack);
+        ack.fields.message_type = Some(MbcpType::Granted.code());
+        assert_eq!(to_gateway(&ack, "ch", false).unwrap().op, client::FLOOR_PING);
+        ack.fields.message_type = Some(MbcpType::Taken.code());
+        assert!(to_gateway(&ack, "ch", false).is_none());
+    }
+
+    #[test]
+    fn from_gateway_maps_floor_json() {
+        let granted = r#"{"op":110,"d":{"channel_id":"ch","user_id":"alice","duration":30000}}"#;
+        let m = from_gateway(granted, "alice").unwrap();
+        assert_eq!(m.kind, MbcpType::Granted);
+        assert!(m.ack_requested);
+        assert_eq!(m.fields.duration_secs, Some(30));
+
+        let taken = r#"{"op":112,"d":{"channel_id":"ch","user_id":"alice","indicator":"broadcast"}}"#;
+        let m = from_gateway(taken, "bob").unwrap();
+        assert_eq!(m.fields.granted_party.as_deref(), Some("alice"));
+        assert_eq!(m.fields.permission, Some(false));
+        assert_eq!(m.fields.indicator, Some(0x4000));
+
+        let revoke = r#"{"op":114,"d":{"channel_id":"ch","cause":"preempted"}}"#;
+        assert_eq!(from_gateway(revoke, "alice").unwrap().fields.reject_cause, Some((4, "preempted".into())));
+
+        let status = r#"{"op":118,"d":{"channel_id":"ch","queue":[{"user_id":"carol","priority":10},{"user_id":"bob","priority":90}]}}"#;
+        let m = from_gateway(status, "bob").unwrap();
+        assert_eq!(m.kind, MbcpType::QueuePosInfo);
+        assert_eq!(m.fields.queue_info, Some((2, 90)));
+        assert_eq!(m.fields.queue_size, Some(2));
+        assert_eq!(from_gateway(status, "dave").unwrap().fields.queue_info, Some((QUEUE_POSITION_NOT_QUEUED, 0)));
+
+        assert!(from_gateway(r#"{"op":116,"d":{"channel_id":"ch"}}"#, "alice").is_none());
+        assert!(from_gateway(r#"{"op":101,"d":{"channel_id":"ch"}}"#, "alice").is_none());
+    }
+
+    #[tokio::test]
+    async fn mbcp_requests_charge_floor_rate_limit() {
+        use crate::core::{BucketSpec, ChannelMode, RateLimitConfig};
+
+        let cfg = RateLimitConfig { floor: BucketSpec { per_sec: 0, burst: 1 }, ..RateLimitConfig::default() };
+        let user_hub    = Arc::new(UserHub::with_rate_limit(cfg));
+        let channel_hub = Arc::new(ChannelHub::new());
+        let peer_hub    = MediaPeerHub::new();
+        let trace_hub   = TraceHub::new();
+        let channel     = channel_hub.create("CH_M", "0900", "mbcp", ChannelMode::PTT, 10);
+        channel.add_member("alice").unwrap();
+        let (tx, _rx) = mpsc::channel(16);
+        user_hub.register("alice", tx, 100);
+        let ep = peer_hub.insert("ua", "p", "alice", "CH_M");
+
+        let request = MbcpMessage::new(MbcpType::Request).encode();
+        handle_mbcp(&ep, &request, &user_hub, &channel_hub, &peer_hub, &trace_hub).await;
+        assert_eq!(channel.floor.lock().unwrap().floor_taken_by.as_deref(), Some("alice"));
+
+        // floor 버킷 소진 → Release도 처리되지 않음 (JSON FLOOR_RELEASE와 같은 버킷)
+        let release = MbcpMessage::new(MbcpType::Release).encode();
+        handle_mbcp(&ep, &release, &user_hub, &channel_hub, &peer_hub, &trace_hub).await;
+        assert_eq!(channel.floor.lock().unwrap().floor_taken_by.as_deref(), Some("alice"));
+        assert_eq!(user_hub.get("alice").unwrap().rate_limiter.lock().unwrap().total_violations(), 1);
+    }
+}
@@ -2,6 +2,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::core::{CallType, EmergencyState, FloorPolicy};
+
 // ----------------------------------------------------------------------------
 // [공통] Gateway 패킷 봉투 (Envelope)
 // ----------------------------------------------------------------------------
@@ -21,8 +23,10 @@ pub struct GatewayPacket {
 #[derive(Deserialize, Debug)]
 pub struct IdentifyPayload {
     pub user_id:  String,
+    #[serde(default)]
     pub token:    String,
-    pub priority: Option<u8>,  // Floor Control 우선순위 (없으면 FLOOR_PRIORITY_DEFAULT)
+    pub priority: Option<u8>,      // Floor Control 우선순위 (없으면 FLOOR_PRIORITY_DEFAULT)
+    pub api_key:  Option<String>,  // 서비스 계정 키 (있으면 token 대신 검증, user_id = 계정 id)
 }
 
 /// op: CHANNEL_CREATE (10)
@@ -32,6 +36,7 @@ pub struct ChannelCreatePayload {
     pub freq:         String,   // 주파수번호 4자리
     pub channel_name: String,
     pub mode:         Option<String>,  // "ptt" | "conference" (없으면 기본 ptt)
+    pub floor_policy: Option<FloorPolicy>,  // 없으면 config 기본값 (필드 부분 지정 가능)
 }
 
 /// op: CHANNEL_JOIN (11)
@@ -168,6 +173,8 @@ pub struct ChannelInfoData {
 pub struct MemberInfo {
     pub user_id: String,
     pub ssrc:    u32,
+    #[serde(default)]
+    pub service: bool,  // 서비스 계정(봇/연동) 여부
 }
 
 // ----------------------------------------------------------------------------
@@ -221,6 +228,18 @@ pub struct FloorPingPayload {
     pub channel_id: String,
 }
 
+/// op: FLOOR_QUEUE_CANCEL (33) — C→S, 대기열 이탈
+#[derive(Deserialize, Debug)]
+pub struct FloorQueueCancelPayload {
+    pub channel_id: String,
+}
+
+/// op: FLOOR_STATUS (34) — C→S, 현재 Floor 상태 조회
+#[derive(Deserialize, Debug)]
+pub struct FloorStatusRequestPayload {
+    pub channel_id: String,
+}
+
 /// op: FLOOR_GRANTED (110) — S→C, 발언권 허가
 #[derive(Serialize, Debug)]
 pub struct FloorGrantedPayload {
@@ -234,6 +253,7 @@ pub struct FloorGrantedPayload {
 pub struct FloorDenyPayload {
     pub channel_id: String,
     pub reason:     String,
+    pub code:       u16,    // 4xxx LiveError 코드
 }
 
 /// op: FLOOR_TAKEN (112) — S→C 브로드캐스트, 누군가 발언 중
@@ -271,6 +291,151 @@ pub struct FloorPongPayload {
     pub channel_id: String,
 }
 
+/// op: FLOOR_SUSPENDED (119) — S→C 브로드캐스트, holder 연결 끊김 (유예 중 Floor 유지)
+#[derive(Serialize, Debug)]
+pub struct FloorSuspendedPayload {
+    pub channel_id: String,
+    pub user_id:    String,
+    pub grace_ms:   u64,   // 이 시간 내 재입장하지 않으면 FLOOR_IDLE / 다음 Grant
+}
+
+/// op: FLOOR_STATUS (118) — S→C, FLOOR_STATUS 요청 응답
+#[derive(Serialize, Debug)]
+pub struct FloorStatusPayload {
+    pub channel_id:   String,
+    pub state:        String,                     // "idle" | "taken" | "suspended"
+    pub holder:       Option<String>,
+    pub priority:     Option<u8>,
+    pub indicator:    Option<FloorIndicatorDto>,
+    pub elapsed_ms:   Option<u64>,
+    pub remaining_ms: Option<u64>,                // max_duration까지 (무제한이면 null)
+    pub queue:        Vec<FloorStatusQueueEntry>, // 대기 순서대로
+}
+
+#[derive(Serialize, Debug)]
+pub struct FloorStatusQueueEntry {
+    pub user_id:   String,
+    pub priority:  u8,
+    pub indicator: FloorIndicatorDto,
+    pub wait_ms:   u64,
+}
+
+// ----------------------------------------------------------------------------
+// [Emergency] 채널 긴급 상태
+// ----------------------------------------------------------------------------
+
+/// op: EMERGENCY_ALERT (40) — C→S, 긴급 상태 발령
+#[derive(Deserialize, Debug)]
+pub struct EmergencyAlertPayload {
+    pub channel_id: String,
+}
+
+/// op: EMERGENCY_ACK (41) — C→S, 긴급 상태 확인
+#[derive(Deserialize, Debug)]
+pub struct EmergencyAckPayload {
+    pub channel_id: String,
+}
+
+/// op: EMERGENCY_RAISED (120) / EMERGENCY_ACKED (121) — S→C 브로드캐스트, 현재 긴급 상태
+#[derive(Serialize, Debug)]
+pub struct EmergencyStatePayload {
+    pub channel_id: String,
+    #[serde(flatten)]
+    pub state:      EmergencyState,
+}
+
+/// op: EMERGENCY_CLEARED (122) — S→C 브로드캐스트, Dispatcher 해제
+#[derive(Serialize, Debug)]
+pub struct EmergencyClearedPayload {
+    pub channel_id: String,
+    pub cleared_by: String,
+}
+
+// ----------------------------------------------------------------------------
+// [1:1 개별 호출] MCPTT private call
+// ----------------------------------------------------------------------------
+
+/// op: CALL_INVITE (50) — C→S, 개별 호출 발신
+/// 미디어 필드(ssrc / ufrag / sdp_offer / tracks)는 CHANNEL_JOIN과 동일 의미
+#[derive(Deserialize, Debug)]
+pub struct CallInvitePayload {
+    pub target_user_id: String,
+    #[serde(default)]
+    pub call_type:      CallType,
+    /// automatic commencement — 피호출자 응답 없이 즉시 연결
+    #[serde(default)]
+    pub automatic:      bool,
+    #[serde(default)]
+    pub ssrc:           u32,
+    #[serde(default)]
+    pub ufrag:          String,
+    pub sdp_offer:      Option<String>,
+    #[serde(default)]
+    pub tracks:         Vec<TrackInfo>,
+}
+
+/// op: CALL_ACCEPT (51) — C→S, 피호출자 수락 (+ 미디어 연결)
+#[derive(Deserialize, Debug)]
+pub struct CallAcceptPayload {
+    pub call_id:   String,
+    #[serde(default)]
+    pub ssrc:      u32,
+    #[serde(default)]
+    pub ufrag:     String,
+    pub sdp_offer: Option<String>,
+    #[serde(default)]
+    pub tracks:    Vec<TrackInfo>,
+}
+
+/// op: CALL_REJECT (52) / CALL_CANCEL (53) / CALL_HANGUP (54) — C→S
+#[derive(Deserialize, Debug)]
+pub struct CallIdPayload {
+    pub call_id: String,
+}
+
+/// op: ACK > CALL_INVITE / CALL_ACCEPT 성공 시 data
+/// channel_id = call_id (FLOOR_* / RENEGOTIATE에 그대로 사용)
+#[derive(Serialize, Debug)]
+pub struct CallAckData {
+    pub call_id:    String,
+    pub channel_id: String,
+    pub call_type:  CallType,
+    pub state:      String,   // "ringing" | "active"
+    pub sdp_answer: Option<String>,
+}
+
+/// op: CALL_RINGING (130) — S→C, 호출자에게
+#[derive(Serialize, Debug)]
+pub struct CallRingingPayload {
+    pub call_id: String,
+    pub callee:  String,
+}
+
+/// op: CALL_INCOMING (131) — S→C, 피호출자에게
+#[derive(Serialize, Debug)]
+pub struct CallIncomingPayload {
+    pub call_id:   String,
+    pub caller:    String,
+    pub call_type: CallType,
+    pub automatic: bool,
+}
+
+/// op: CALL_ACCEPTED (132) — S→C, 호출자에게
+#[derive(Serialize, Debug)]
+pub struct CallAcceptedPayload {
+    pub call_id:   String,
+    pub callee:    String,
+    pub automatic: bool,
+}
+
+/// op: CALL_ENDED (133) — S→C, 종료 요청자를 제외한 당사자에게
+#[derive(Serialize, Debug)]
+pub struct CallEndedPayload {
+    pub call_id: String,
+    pub reason:  String,          // "rejected" | "cancelled" | "hangup" | "timeout" | "disconnect"
+    pub by:      Option<String>,  // 종료한 당사자 (timeout이면 None)
+}
+
 // ----------------------------------------------------------------------------
 // [Renegotiation] Unified Plan SDP 재협상
 // ----------------------------------------------------------------------------
@@ -293,6 +458,15 @@ pub struct MidMapEntry {
     pub kind:    String,     // "audio" | "video"
 }
 
+/// op: SIMULCAST_LAYER (18) — C→S, Conference simulcast 수신 레이어 지정
+#[derive(Deserialize, Debug)]
+pub struct SimulcastLayerPayload {
+    pub channel_id: String,
+    pub user_id:    String,          // 영상 송신자
+    #[serde(default)]
+    pub layer:      Option<String>,  // 송신자 rid — 없거나 "auto"면 수신 품질(RR 손실) 기반 자동 선택
+}
+
 /// op: RENEGOTIATE_ACK (117) — S→C, SDP re-answer 응답
 #[derive(Serialize, Debug)]
 pub struct RenegotiateAckPayload {
@@ -307,8 +481,10 @@ pub struct RenegotiateAckPayload {
 /// 트랙 단위 미디어 정보
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct TrackInfo {
-    pub kind: String,   // "audio" | "video"
+    pub kind: String,          // "audio" | "video"
     pub ssrc: u32,
+    #[serde(default)]
+    pub rid:  Option<String>,  // simulcast 레이어 rid (offer의 a=rid) — 없으면 단일 트랙
 }
 
 /// peer 단위 미디어 정보
@@ -32,9 +32,34 @@ pub mod client {
     pub const FLOOR_RELEASE: u8 = 31;
     /// Floor Ping — 클라이언트가 holder 생존 신호 전송 (GRANTED 후 2초 주기)
     pub const FLOOR_PING:    u8 = 32;
+    /// 대기열에서 스스로 빠짐 (발언 요청 취소)
+    pub const FLOOR_QUEUE_CANCEL: u8 = 33;
+    /// 현재 Floor 상태 조회 (holder / 경과·잔여 시간 / 대기열)
+    pub const FLOOR_STATUS:       u8 = 34;
+
+    // --- Emergency ---
+    /// 채널 긴급 상태 발령 (Dispatcher 해제 전까지 유지)
+    pub const EMERGENCY_ALERT: u8 = 40;
+    /// 긴급 상태 확인
+    pub const EMERGENCY_ACK:   u8 = 41;
+
+    // --- 1:1 개별 호출 (MCPTT private call) ---
+    /// 개별 호출 발신 (target_user_id, call_type, automatic, SDP offer)
+    pub const CALL_INVITE:     u8 = 50;
+    /// 피호출자 수락 (SDP offer) — automatic 호출이면 미디어 연결용
+    pub const CALL_ACCEPT:     u8 = 51;
+    /// 피호출자 거절 (Ringing 중)
+    pub const CALL_REJECT:     u8 = 52;
+    /// 호출자 취소 (Ringing 중)
+    pub const CALL_CANCEL:     u8 = 53;
+    /// 통화 종료 (양측 모두)
+    pub const CALL_HANGUP:     u8 = 54;
 
     /// SDP re-offer 전송 (Unified Plan 재협상)
     pub const RENEGOTIATE:   u8 = 17;
+
+    /// Conference simulcast 수신 레이어 지정 (rid 또는 "auto")
+    pub const SIMULCAST_LAYER: u8 = 18;
 }
 
 /// Server → Client opcodes
@@ -67,10 +92,32 @@ pub mod server {
     pub const FLOOR_IDLE:           u8 = 113;
     /// 발언권 강제 회수 (Preemption 또는 타임아웃)
     pub const FLOOR_REVOKE:         u8 = 114;
-    /// 대기열 등록 확인 — Floor Deny 대신 대기열 진입 시 사용
+    /// 대기열 순서 안내 — 진입 시 + 대기열 변경 시마다 대기 중인 전원에게
     pub const FLOOR_QUEUE_POS_INFO: u8 = 115;
     /// 서버 → holder Floor Ping 응답 (Pong)
     pub const FLOOR_PONG:           u8 = 116;
+    /// FLOOR_STATUS 응답 — 요청자에게만
+    pub const FLOOR_STATUS:         u8 = 118;
+    /// holder 연결 끊김 — 유예 동안 Floor 유지 (재입장 시 FLOOR_TAKEN으로 재개 안내)
+    pub const FLOOR_SUSPENDED:      u8 = 119;
+
+    // --- Emergency ---
+    /// 긴급 상태 발령 브로드캐스트 (발령 시 + 발령 중 채널 입장 시)
+    pub const EMERGENCY_RAISED:     u8 = 120;
+    /// 멤버 확인 브로드캐스트 (acked_by 갱신)
+    pub const EMERGENCY_ACKED:      u8 = 121;
+    /// 긴급 상태 해제 브로드캐스트
+    pub const EMERGENCY_CLEARED:    u8 = 122;
+
+    // --- 1:1 개별 호출 ---
+    /// 호출자에게 — 피호출자 단말 울림 중
+    pub const CALL_RINGING:         u8 = 130;
+    /// 피호출자에게 — 호출 도착 (automatic이면 즉시 CALL_ACCEPT로 미디어 연결)
+    pub const CALL_INCOMING:        u8 = 131;
+    /// 호출자에게 — 피호출자 수락 (automatic이면 INVITE 직후)
+    pub const CALL_ACCEPTED:        u8 = 132;
+    /// 통화 종료 (rejected / cancelled / hangup / timeout / disconnect)
+    pub const CALL_ENDED:           u8 = 133;
 
     /// SDP re-answer 응답 (Unified Plan 재협상)
     pub const RENEGOTIATE_ACK:      u8 = 117;
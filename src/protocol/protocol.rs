@@ -5,26 +5,32 @@ use axum::{
     response::Response,
 };
 use futures_util::{sink::SinkExt, stream::StreamExt};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use tokio::sync::mpsc;
 use tracing::{error, trace, warn};
 
 use crate::config;
-use crate::core::{ChannelHub, ChannelMode, MediaPeerHub, UserHub};
+use crate::core::{
+    ApiScope, CallHub, ChannelHub, ChannelMode, MediaPeerHub, RateClass, RateDecision, ServiceAccountHub, UserHub,
+};
 use crate::error::LiveError;
+use crate::filter::{FilterChain, FilterOutcome, MessageContext};
 use crate::trace::{TraceDir, TraceEvent, TraceHub};
 use crate::protocol::{
-    floor,
+    call, emergency, floor,
     message::{
         AckPayload, ChannelCreatePayload, ChannelDeletePayload, ChannelEventPayload,
         ChannelInfoData, ChannelJoinAckData, ChannelJoinPayload, ChannelLeavePayload,
         ChannelSummary, ChannelUpdatePayload, ErrorPayload, GatewayPacket, HelloPayload,
         IdentifyPayload, MemberInfo, MessageCreatePayload, MessageEventPayload,
-        PeerMediaInfo, ReadyPayload, RenegotiatePayload, RenegotiateAckPayload, TrackInfo,
+        PeerMediaInfo, ReadyPayload, RenegotiatePayload, RenegotiateAckPayload,
+        SimulcastLayerPayload, TrackInfo,
     },
     opcode::{client, server},
 };
 use crate::utils::current_timestamp;
+use crate::webhook::WebhookEvent;
 
 // ----------------------------------------------------------------------------
 // [공유 상태]
@@ -32,12 +38,33 @@ use crate::utils::current_timestamp;
 
 #[derive(Clone)]
 pub struct AppState {
-    pub user_hub:       Arc<UserHub>,
-    pub channel_hub:    Arc<ChannelHub>,
-    pub media_peer_hub: Arc<MediaPeerHub>,
-    pub server_cert:    Arc<crate::media::ServerCert>,
-    pub trace_hub:      Arc<TraceHub>,
-    pub udp_port:       u16,  // SDP answer candidate 포트
+    pub user_hub:         Arc<UserHub>,
+    pub channel_hub:      Arc<ChannelHub>,
+    pub media_peer_hub:   Arc<MediaPeerHub>,
+    pub call_hub:         Arc<CallHub>,
+    pub server_cert:      Arc<crate::media::ServerCert>,
+    pub trace_hub:        Arc<TraceHub>,
+    pub message_filter:   Arc<FilterChain>,
+    pub service_accounts: Arc<ServiceAccountHub>,
+    pub udp_port:         u16,  // SDP answer candidate 포트
+}
+
+#[cfg(test)]
+impl AppState {
+    /// 핸들러 단위 테스트 공용 — 빈 Hub + 임시 인증서
+    pub(crate) fn for_test() -> Self {
+        Self {
+            user_hub:         Arc::new(UserHub::new()),
+            channel_hub:      Arc::new(ChannelHub::new()),
+            media_peer_hub:   Arc::new(MediaPeerHub::new()),
+            call_hub:         Arc::new(CallHub::new()),
+            server_cert:      Arc::new(crate::media::ServerCert::generate().unwrap()),
+            trace_hub:        TraceHub::new(),
+            message_filter:   Arc::new(FilterChain::new()),
+            service_accounts: Arc::new(ServiceAccountHub::new()),
+            udp_port:         0,
+        }
+    }
 }
 
 // ----------------------------------------------------------------------------
@@ -129,6 +156,48 @@ async fn handle_socket(socket: WebSocket, state: AppState) {
             }
         }
 
+        // 사용자별 opcode 계열 rate limit — 초과 시 요청 폐기, 위반 누적 시 강제 종료
+        if let (Some(class), Some(user_id)) = (rate_class(packet.op), session.user_id.as_deref()) {
+            let decision = state.user_hub.get(user_id)
+                .map(|u| u.rate_limiter.lock().unwrap().check(class, current_timestamp()))
+                .unwrap_or(RateDecision::Allow);
+            if decision != RateDecision::Allow {
+                state.trace_hub.publish(TraceEvent::new(
+                    TraceDir::Sys,
+                    session.current_channel.as_deref(),
+                    Some(user_id),
+                    packet.op,
+                    "RATE_LIMITED",
+                    format!("class={} decision={:?}", class.as_str(), decision),
+                ));
+                let _ = broadcast_tx.send(error_packet(LiveError::RateLimited(class.as_str().to_string()))).await;
+                if decision == RateDecision::Disconnect {
+                    warn!("rate limit 위반 누적 — 연결 종료 user={}", user_id);
+                    break;
+                }
+                continue;
+            }
+        }
+
+        // 서비스 계정 — 삭제(키 폐기)된 계정은 즉시 종료, scope 밖 opcode는 거부
+        let service = session.user_id.as_deref()
+            .and_then(|uid| state.user_hub.get(uid))
+            .and_then(|u| u.service.clone());
+        if let Some(sa) = service {
+            let revoked = !state.service_accounts.get(&sa.id).is_some_and(|cur| Arc::ptr_eq(&cur, &sa));
+            if revoked {
+                warn!("서비스 계정 폐기됨 — 연결 종료 user={}", sa.id);
+                let _ = broadcast_tx.send(error_packet(LiveError::InvalidToken)).await;
+                break;
+            }
+            if let Some(scope) = required_scope(packet.op) {
+                if !sa.has_scope(scope) {
+                    let _ = broadcast_tx.send(error_packet(LiveError::ScopeDenied(scope.as_str().to_string()))).await;
+                    continue;
+                }
+            }
+        }
+
         // trace: C→S 수신 이벤트 publish
         let trace_channel = session.current_channel.as_deref();
         let trace_user    = session.user_id.as_deref();
@@ -145,10 +214,20 @@ async fn handle_socket(socket: WebSocket, state: AppState) {
             client::CHANNEL_LIST   => handle_channel_list(&broadcast_tx, &state).await,
             client::CHANNEL_INFO   => handle_channel_info(&broadcast_tx, &state, packet).await,
             client::MESSAGE_CREATE => handle_message_create(&broadcast_tx, &session, &state, packet).await,
-            client::FLOOR_REQUEST  => floor::handle_floor_request(&broadcast_tx, session.user_id.as_deref().unwrap(), &state.user_hub, &state.channel_hub, &state.trace_hub, packet).await,
-            client::FLOOR_RELEASE  => floor::handle_floor_release(&broadcast_tx, session.user_id.as_deref().unwrap(), &state.user_hub, &state.channel_hub, &state.trace_hub, packet).await,
+            client::FLOOR_REQUEST  => floor::handle_floor_request(&broadcast_tx, session.user_id.as_deref().unwrap(), &state.user_hub, &state.channel_hub, &state.media_peer_hub, &state.trace_hub, packet).await,
+            client::FLOOR_RELEASE  => floor::handle_floor_release(&broadcast_tx, session.user_id.as_deref().unwrap(), &state.user_hub, &state.channel_hub, &state.media_peer_hub, &state.trace_hub, packet).await,
             client::FLOOR_PING     => floor::handle_floor_ping(&broadcast_tx, session.user_id.as_deref().unwrap(), &state.channel_hub, packet).await,
+            client::FLOOR_QUEUE_CANCEL => floor::handle_floor_queue_cancel(&broadcast_tx, session.user_id.as_deref().unwrap(), &state.user_hub, &state.channel_hub, &state.media_peer_hub, &state.trace_hub, packet).await,
+            client::FLOOR_STATUS   => floor::handle_floor_status(&broadcast_tx, session.user_id.as_deref().unwrap(), &state.channel_hub, &state.media_peer_hub, packet).await,
+            client::EMERGENCY_ALERT => emergency::handle_emergency_alert(&broadcast_tx, session.user_id.as_deref().unwrap(), &state.user_hub, &state.channel_hub, &state.trace_hub, packet).await,
+            client::EMERGENCY_ACK  => emergency::handle_emergency_ack(&broadcast_tx, session.user_id.as_deref().unwrap(), &state.user_hub, &state.channel_hub, &state.trace_hub, packet).await,
             client::RENEGOTIATE    => handle_renegotiate(&broadcast_tx, &session, &state, packet).await,
+            client::SIMULCAST_LAYER => handle_simulcast_layer(&broadcast_tx, &session, &state, packet).await,
+            client::CALL_INVITE    => call::handle_call_invite(&broadcast_tx, session.user_id.as_deref().unwrap(), &state, packet).await,
+            client::CALL_ACCEPT    => call::handle_call_accept(&broadcast_tx, session.user_id.as_deref().unwrap(), &state, packet).await,
+            client::CALL_REJECT    => call::handle_call_reject(&broadcast_tx, session.user_id.as_deref().unwrap(), &state, packet).await,
+            client::CALL_CANCEL    => call::handle_call_cancel(&broadcast_tx, session.user_id.as_deref().unwrap(), &state, packet).await,
+            client::CALL_HANGUP    => call::handle_call_hangup(&broadcast_tx, session.user_id.as_deref().unwrap(), &state, packet).await,
             unknown => {
                 warn!("알 수 없는 opcode: {}", unknown);
                 send(&broadcast_tx, error_packet(LiveError::InvalidOpcode(unknown))).await
@@ -182,16 +261,33 @@ async fn handle_identify(
     let payload = parse_payload::<IdentifyPayload>(packet.d)?;
     trace!("IDENTIFY - user_id: {}", payload.user_id);
 
-    // Secret Key 검증 (환경변수 LIVECHAT_SECRET 우선, 없으면 DEFAULT_SECRET_KEY)
-    let expected = std::env::var("LIVECHAT_SECRET")
-        .unwrap_or_else(|_| config::DEFAULT_SECRET_KEY.to_string());
-    if payload.token != expected {
-        warn!("IDENTIFY 토큰 불일치 - user_id: {}", payload.user_id);
-        return send(tx, error_packet(LiveError::InvalidToken)).await;
-    }
-
     let priority = payload.priority.unwrap_or(config::FLOOR_PRIORITY_DEFAULT);
-    state.user_hub.register(&payload.user_id, tx.clone(), priority);
+
+    if let Some(ref key) = payload.api_key {
+        // 서비스 계정 — 키 검증 후 user_id는 계정 id로 고정
+        let account = match state.service_accounts.authenticate(key) {
+            Some(a) if a.id == payload.user_id => a,
+            _ => {
+                warn!("IDENTIFY api_key 불일치 - user_id: {}", payload.user_id);
+                return send(tx, error_packet(LiveError::InvalidToken)).await;
+            }
+        };
+        state.user_hub.register_service(account, tx.clone(), priority);
+    } else {
+        // Secret Key 검증 (환경변수 LIVECHAT_SECRET 우선, 없으면 DEFAULT_SECRET_KEY)
+        let expected = std::env::var("LIVECHAT_SECRET")
+            .unwrap_or_else(|_| config::DEFAULT_SECRET_KEY.to_string());
+        if payload.token != expected {
+            warn!("IDENTIFY 토큰 불일치 - user_id: {}", payload.user_id);
+            return send(tx, error_packet(LiveError::InvalidToken)).await;
+        }
+        // 공용 secret으로 서비스 계정 id / REST 주입 author(system, bot:*) 사칭 방지
+        if state.service_accounts.contains(&payload.user_id) || is_reserved_author(&payload.user_id) {
+            warn!("IDENTIFY 예약 id를 token으로 사용 - user_id: {}", payload.user_id);
+            return send(tx, error_packet(LiveError::InvalidToken)).await;
+        }
+        state.user_hub.register(&payload.user_id, tx.clone(), priority);
+    }
     session.user_id = Some(payload.user_id.clone());
 
     send(tx, make_packet(server::READY, ReadyPayload {
@@ -211,13 +307,34 @@ async fn handle_channel_create(
         .unwrap_or_default();
     trace!("CHANNEL_CREATE - channel_id: {} mode: {}", payload.channel_id, mode);
 
-    state.channel_hub.create(
+    if let Some(Err(e)) = payload.floor_policy.as_ref().map(|p| p.validate()) {
+        return send(tx, error_packet(LiveError::InvalidPayload(e))).await;
+    }
+
+    // 1:1 호출 채널 id 대역은 CALL_* 전용 (기존 호출 채널 / 선점 생성 모두 차단)
+    if payload.channel_id.starts_with(config::CALL_ID_PREFIX) {
+        return send(tx, error_packet(LiveError::ChannelAccessDenied(payload.channel_id))).await;
+    }
+
+    let (channel, created) = state.channel_hub.create_if_absent(
         &payload.channel_id,
         &payload.freq,
         &payload.channel_name,
         mode,
         config::MAX_PEERS_PER_CHANNEL,
     );
+    // floor_policy는 생성 시에만 — 기존 채널 정책 변경은 Admin REST (PUT /admin/channels/{id}/floor-policy)
+    if let Some(policy) = payload.floor_policy {
+        if !created {
+            return send(tx, error_packet(LiveError::InvalidPayload(format!(
+                "floor_policy can only be set on a new channel: {}", payload.channel_id
+            )))).await;
+        }
+        if let Err(e) = channel.set_floor_policy(policy) {
+            return send(tx, error_packet(LiveError::InvalidPayload(e))).await;
+        }
+        state.channel_hub.wake_floor_timer();
+    }
 
     send(tx, make_packet(server::ACK, AckPayload {
         op:   client::CHANNEL_CREATE,
@@ -226,6 +343,7 @@ async fn handle_channel_create(
             "freq":         payload.freq,
             "channel_name": payload.channel_name,
             "mode":         mode.to_string(),
+            "floor_policy": channel.floor_policy(),
         }),
     })).await
 }
@@ -240,52 +358,22 @@ async fn handle_channel_join(
     let user_id = session.user_id.as_ref().unwrap().clone();
     trace!("CHANNEL_JOIN - user:{} channel:{}", user_id, payload.channel_id);
 
-    // 1. 채널 정원 체크 + 멤버 등록
+    // 1. 채널 정원 체크 + 멤버 등록 (1:1 호출 채널은 CALL_INVITE / CALL_ACCEPT로만 참여)
     let channel = state.channel_hub.get(&payload.channel_id)
         .ok_or_else(|| LiveError::ChannelNotFound(payload.channel_id.clone()))?;
+    if channel.is_call() {
+        return send(tx, error_packet(LiveError::ChannelAccessDenied(payload.channel_id))).await;
+    }
     channel.add_member(&user_id)?;
 
-    // 3. SDP answer 생성 (offer가 있을 때만)
-    // server_ufrag: 서버가 생성한 ICE ufrag → MediaPeerHub 등록 키
-    // STUN USERNAME = "server_ufrag:client_ufrag" 구조이므로 서버 ufrag로 조회해야 함
-    let is_conference = channel.mode == ChannelMode::Conference;
-    let (sdp_answer, ep_ufrag, ep_pwd) = match payload.sdp_offer.as_deref() {
-        Some(offer) => {
-            // offer의 a=setup 값 확인용 로그 (DTLS 역할 디버그)
-            let offer_setup = offer.lines()
-                .find(|l| l.starts_with("a=setup:"))
-                .unwrap_or("a=setup:(없음)");
-            trace!("[sdp] offer a=setup: {} conference={}", offer_setup, is_conference);
-            // Conference: sendrecv→recvonly 강제 (BUNDLE PT 충돌 방지)
-            let (sdp, server_ufrag, server_pwd) = crate::protocol::sdp::build_sdp_answer_with_ice(
-                offer, &state.server_cert.fingerprint, state.udp_port,
-                None, None, is_conference,
-            );
-            trace!("[sdp] answer built ufrag={} (offer_setup={})", server_ufrag, offer_setup);
-            (Some(sdp), server_ufrag, server_pwd)
-        }
-        None => (None, payload.ufrag.clone(), String::new()),
-    };
-
-    // 2. Endpoint 등록 (server_ufrag 주키, ice_pwd 포함)
-    let ep = state.media_peer_hub.insert(&ep_ufrag, &ep_pwd, &user_id, &payload.channel_id);
-    // tracks 배열이 있으면 전부 등록, 없으면 ssrc로 audio 1개 (하위 호환)
-    if payload.tracks.is_empty() {
-        ep.add_track(payload.ssrc, crate::core::TrackKind::Audio);
-    } else {
-        for t in &payload.tracks {
-            let kind = match t.kind.as_str() {
-                "video" => crate::core::TrackKind::Video,
-                _       => crate::core::TrackKind::Audio,
-            };
-            ep.add_track(t.ssrc, kind);
-            trace!("[join] registered track kind={} ssrc={} for user={}", t.kind, t.ssrc, user_id);
-        }
-    }
+    // 2~3. SDP answer 생성 + Endpoint 등록
+    let (sdp_answer, ep) = attach_endpoint(
+        state, &user_id, &channel, payload.sdp_offer.as_deref(), &payload.ufrag, payload.ssrc, &payload.tracks,
+    );
 
     session.current_channel = Some(payload.channel_id.clone());
     session.current_ssrc    = Some(payload.ssrc);
-    session.current_ufrag   = Some(ep_ufrag);
+    session.current_ufrag   = Some(ep.ufrag.clone());
 
     // 4. 본인에게 ACK (SDP answer + 현재 채널 멤버 목록 포함)
     let active_members = collect_members(&payload.channel_id, state);
@@ -308,30 +396,13 @@ async fn handle_channel_join(
         channel_id: payload.channel_id.clone(),
         data:       serde_json::to_value(MemberInfo {
             user_id: user_id.clone(), ssrc: payload.ssrc,
+            service: state.user_hub.is_service(&user_id),
         }).unwrap_or_default(),
     });
     state.user_hub.broadcast_to(&members, &join_event, Some(&user_id)).await;
 
     // 5b. peer_added 이벤트 (re-negotiation 트리거 — 미디어 트랙 정보 포함)
-    let tracks: Vec<TrackInfo> = ep.tracks.read().unwrap()
-        .iter()
-        .map(|t| TrackInfo {
-            kind: match t.kind {
-                crate::core::TrackKind::Audio => "audio".to_string(),
-                crate::core::TrackKind::Video => "video".to_string(),
-                crate::core::TrackKind::Data  => "data".to_string(),
-            },
-            ssrc: t.ssrc,
-        })
-        .collect();
-    let peer_added_event = make_packet(server::CHANNEL_EVENT, ChannelEventPayload {
-        event:      "peer_added".to_string(),
-        channel_id: payload.channel_id.clone(),
-        data:       serde_json::to_value(PeerMediaInfo {
-            user_id: user_id.clone(),
-            tracks,
-        }).unwrap_or_default(),
-    });
+    let peer_added_event = peer_added_packet(&payload.channel_id, &ep);
     state.user_hub.broadcast_to(&members, &peer_added_event, Some(&user_id)).await;
 
     // 5c. 기존 멤버들의 peer_added를 신규 입장자에게 개별 전송
@@ -339,51 +410,40 @@ async fn handle_channel_join(
     let existing_endpoints = state.media_peer_hub.get_channel_endpoints(&payload.channel_id);
     for existing_ep in &existing_endpoints {
         if existing_ep.user_id == user_id { continue; }  // 본인 제외
-        let existing_tracks: Vec<TrackInfo> = existing_ep.tracks.read().unwrap()
-            .iter()
-            .map(|t| TrackInfo {
-                kind: match t.kind {
-                    crate::core::TrackKind::Audio => "audio".to_string(),
-                    crate::core::TrackKind::Video => "video".to_string(),
-                    crate::core::TrackKind::Data  => "data".to_string(),
-                },
-                ssrc: t.ssrc,
-            })
-            .collect();
-        let existing_peer_event = make_packet(server::CHANNEL_EVENT, ChannelEventPayload {
-            event:      "peer_added".to_string(),
-            channel_id: payload.channel_id.clone(),
-            data:       serde_json::to_value(PeerMediaInfo {
-                user_id: existing_ep.user_id.clone(),
-                tracks:  existing_tracks,
-            }).unwrap_or_default(),
-        });
-        let _ = tx.send(existing_peer_event).await;
+        let _ = tx.send(peer_added_packet(&payload.channel_id, existing_ep)).await;
         trace!("[join] sent existing peer_added to {} for peer {}", user_id, existing_ep.user_id);
     }
 
     // 6. Floor Taken 상태라면 신규 입장자에게 FLOOR_TAKEN 전송
     //    MutexGuard가 await를 걸치면 Send 불만족 → 동기 블록에서 패킷 문자열만 추출,
     //    Guard는 블록 끝에서 drop되고 await는 그 다음에 실행됨
-    let floor_taken_packet: Option<String> = {
-        use crate::core::FloorControlState;
-        use crate::protocol::message::{FloorTakenPayload, FloorIndicatorDto};
-        let floor = channel.floor.lock().unwrap();
-        if floor.state == FloorControlState::Taken {
-            floor.floor_taken_by.as_ref().map(|holder| {
-                make_packet(server::FLOOR_TAKEN, FloorTakenPayload {
-                    channel_id: payload.channel_id.clone(),
-                    user_id:    holder.clone(),
-                    indicator:  FloorIndicatorDto::Normal,
-                })
-            })
-        } else {
-            None
-        }
+    //    indicator는 현재 발언의 실제 값 (Emergency 등)
+    //    suspended holder 본인의 재입장이면 발언 재개 (GRANTED + 나머지 멤버 FLOOR_TAKEN)
+    let (floor_taken_packet, resume_packets) = {
+        let mut floor = channel.floor.lock().unwrap();
+        let resume = floor::resume_packets(&payload.channel_id, &user_id, &mut floor);
+        let taken  = if resume.is_empty() { floor::taken_packet(&payload.channel_id, &floor) } else { None };
+        (taken, resume)
     }; // ← MutexGuard 여기서 drop
     if let Some(pkt) = floor_taken_packet {
         let _ = tx.send(pkt).await;
     }
+    if !resume_packets.is_empty() {
+        warn!("Floor Resumed: channel={} user={}", payload.channel_id, user_id);
+        state.channel_hub.wake_floor_timer();
+        floor::dispatch_packets(resume_packets, &payload.channel_id, &channel.get_members(), &state.user_hub, &state.media_peer_hub).await;
+        floor::request_holder_keyframe(&state.media_peer_hub, &payload.channel_id, &user_id).await;
+        state.trace_hub.publish(TraceEvent::new(
+            TraceDir::Sys, Some(&payload.channel_id), Some(&user_id),
+            server::FLOOR_GRANTED, "FLOOR_RESUMED",
+            format!("user={}", user_id),
+        ));
+    }
+
+    // 7. 긴급 상태 발령 중이면 신규 입장자에게 EMERGENCY_RAISED 전송
+    if let Some(em) = channel.emergency() {
+        let _ = tx.send(emergency::raised_packet(&payload.channel_id, &em)).await;
+    }
 
     state.trace_hub.publish(TraceEvent::new(
         TraceDir::Sys,
@@ -392,11 +452,118 @@ async fn handle_channel_join(
         server::CHANNEL_EVENT,
         "CHANNEL_JOIN",
         format!("user={} ssrc={}", user_id, payload.ssrc),
-    ));
+    ).with_webhook(WebhookEvent::Join));
 
     Ok(())
 }
 
+/// SDP answer 생성 + Endpoint 등록 — CHANNEL_JOIN / CALL_INVITE / CALL_ACCEPT 공용
+/// server_ufrag: 서버가 생성한 ICE ufrag → MediaPeerHub 등록 키
+/// STUN USERNAME = "server_ufrag:client_ufrag" 구조이므로 서버 ufrag로 조회해야 함
+/// offer가 없으면 클라이언트 ufrag로 등록하고 answer는 None
+pub(crate) fn attach_endpoint(
+    state:     &AppState,
+    user_id:   &str,
+    channel:   &crate::core::Channel,
+    sdp_offer: Option<&str>,
+    ufrag:     &str,
+    ssrc:      u32,
+    tracks:    &[TrackInfo],
+) -> (Option<String>, Arc<crate::core::Endpoint>) {
+    let is_conference = channel.mode == ChannelMode::Conference;
+    let (sdp_answer, ep_ufrag, ep_pwd) = match sdp_offer {
+        Some(offer) => {
+            // offer의 a=setup 값 확인용 로그 (DTLS 역할 디버그)
+            let offer_setup = offer.lines()
+                .find(|l| l.starts_with("a=setup:"))
+                .unwrap_or("a=setup:(없음)");
+            trace!("[sdp] offer a=setup: {} conference={}", offer_setup, is_conference);
+            // Conference: sendrecv→recvonly 강제 (BUNDLE PT 충돌 방지)
+            let (sdp, server_ufrag, server_pwd) = crate::protocol::sdp::build_sdp_answer_with_ice(
+                offer, &state.server_cert.fingerprint, state.udp_port,
+                None, None, is_conference,
+            );
+            trace!("[sdp] answer built ufrag={} (offer_setup={})", server_ufrag, offer_setup);
+            // PTT 가상 talker: 채널 고정 SSRC를 join 시 한 번만 광고 (발언자 교체에 re-nego 불필요)
+            let sdp = if channel.is_ptt() && channel.floor_policy().virtual_talker {
+                let talkers: Vec<crate::protocol::sdp::SsrcMapping> = crate::protocol::sdp::media_mids(&sdp)
+                    .into_iter()
+                    .filter_map(|(media, mid)| {
+                        let kind = match media.as_str() {
+                            "audio" => crate::core::TrackKind::Audio,
+                            "video" => crate::core::TrackKind::Video,
+                            _       => return None,
+                        };
+                        Some(crate::protocol::sdp::SsrcMapping { mid, ssrc: channel.talker_ssrc(kind), rtx_ssrc: None })
+                    })
+                    .collect();
+                crate::protocol::sdp::insert_ssrc_lines(sdp, &talkers)
+            } else {
+                sdp
+            };
+            (Some(sdp), server_ufrag, server_pwd)
+        }
+        None => (None, ufrag.to_string(), String::new()),
+    };
+
+    // Endpoint 등록 (server_ufrag 주키, ice_pwd 포함)
+    let ep = state.media_peer_hub.insert(&ep_ufrag, &ep_pwd, user_id, &channel.channel_id);
+    if let Some(id) = sdp_offer.and_then(crate::protocol::sdp::audio_level_ext_id) {
+        ep.set_audio_level_ext(id);
+    }
+    if let Some(offer) = sdp_offer {
+        ep.set_rtx_pts(crate::protocol::sdp::rtx_payload_types(offer));
+        ep.set_video_codecs(crate::protocol::sdp::video_codecs(offer));
+        ep.set_simulcast(crate::protocol::sdp::simulcast_rids(offer), crate::protocol::sdp::rid_ext_id(offer));
+    }
+    // tracks 배열이 있으면 전부 등록, 없으면 ssrc로 audio 1개 (하위 호환)
+    // rid가 있는 video는 simulcast 레이어 (클라이언트가 레이어 SSRC를 모르면 RTP rid 확장으로 학습)
+    if tracks.is_empty() {
+        ep.add_track(ssrc, crate::core::TrackKind::Audio);
+    } else {
+        for t in tracks {
+            let kind = match t.kind.as_str() {
+                "video" => crate::core::TrackKind::Video,
+                _       => crate::core::TrackKind::Audio,
+            };
+            if kind == crate::core::TrackKind::Video && t.rid.as_deref().is_some_and(|rid| ep.add_layer(t.ssrc, rid)) {
+                trace!("[join] registered layer rid={:?} ssrc={} for user={}", t.rid, t.ssrc, user_id);
+                continue;
+            }
+            ep.add_track(t.ssrc, kind);
+            trace!("[join] registered track kind={} ssrc={} for user={}", t.kind, t.ssrc, user_id);
+        }
+    }
+    (sdp_answer, ep)
+}
+
+/// peer_added 채널 이벤트 (re-negotiation 트리거 — 엔드포인트 트랙 정보 포함)
+/// simulcast 레이어는 첫 레이어 1개만 — 수신자는 레이어와 무관하게 video consumer SSRC 하나로 받음
+pub(crate) fn peer_added_packet(channel_id: &str, ep: &crate::core::Endpoint) -> String {
+    let mut layer_listed = false;
+    let tracks: Vec<TrackInfo> = ep.tracks.read().unwrap()
+        .iter()
+        .filter(|t| t.rid.is_none() || !std::mem::replace(&mut layer_listed, true))
+        .map(|t| TrackInfo {
+            kind: match t.kind {
+                crate::core::TrackKind::Audio => "audio".to_string(),
+                crate::core::TrackKind::Video => "video".to_string(),
+                crate::core::TrackKind::Data  => "data".to_string(),
+            },
+            ssrc: t.ssrc,
+            rid:  t.rid.clone(),
+        })
+        .collect();
+    make_packet(server::CHANNEL_EVENT, ChannelEventPayload {
+        event:      "peer_added".to_string(),
+        channel_id: channel_id.to_string(),
+        data:       serde_json::to_value(PeerMediaInfo {
+            user_id: ep.user_id.clone(),
+            tracks,
+        }).unwrap_or_default(),
+    })
+}
+
 async fn handle_channel_leave(
     tx:      &mpsc::Sender<String>,
     session: &mut Session,
@@ -424,6 +591,7 @@ async fn handle_channel_leave(
             channel_id: payload.channel_id.clone(),
             data:       serde_json::to_value(MemberInfo {
                 user_id: user_id.clone(), ssrc,
+                service: state.user_hub.is_service(&user_id),
             }).unwrap_or_default(),
         });
         state.user_hub.broadcast_to(&members, &leave_event, Some(&user_id)).await;
@@ -437,6 +605,9 @@ async fn handle_channel_leave(
         state.user_hub.broadcast_to(&members, &peer_removed_event, Some(&user_id)).await;
 
         channel.remove_member(&user_id);
+
+        // 1c. Floor 정리 (holder면 Revoke → 다음 대기자, 대기 중이면 대기열 제거)
+        floor::on_user_disconnect(&user_id, &payload.channel_id, "leave", &state.user_hub, &state.channel_hub, &state.media_peer_hub, &state.trace_hub).await;
     }
 
     // 2. Endpoint 제거 + consumer SSRC 정리
@@ -448,6 +619,15 @@ async fn handle_channel_leave(
     session.current_ssrc    = None;
     session.current_ufrag   = None;
 
+    state.trace_hub.publish(TraceEvent::new(
+        TraceDir::Sys,
+        Some(&payload.channel_id),
+        Some(&user_id),
+        server::CHANNEL_EVENT,
+        "CHANNEL_LEAVE",
+        format!("user={} ssrc={} cause=leave", user_id, ssrc),
+    ).with_webhook(WebhookEvent::Leave));
+
     send(tx, make_packet(server::ACK, AckPayload {
         op:   client::CHANNEL_LEAVE,
         data: serde_json::json!({ "channel_id": payload.channel_id }),
@@ -464,6 +644,9 @@ async fn handle_channel_update(
 
     let channel = state.channel_hub.get(&payload.channel_id)
         .ok_or_else(|| LiveError::ChannelNotFound(payload.channel_id.clone()))?;
+    if channel.is_call() {
+        return send(tx, error_packet(LiveError::ChannelAccessDenied(payload.channel_id))).await;
+    }
 
     // TODO: Channel에 name 필드 추가 후 실제 업데이트
 
@@ -492,7 +675,12 @@ async fn handle_channel_delete(
     let payload = parse_payload::<ChannelDeletePayload>(packet.d)?;
     trace!("CHANNEL_DELETE - channel:{}", payload.channel_id);
 
-    if let Some(channel) = state.channel_hub.get(&payload.channel_id) {
+    // 1:1 호출 채널은 CallHub 수명을 따름 (CALL_HANGUP / 종료 시 제거)
+    let channel = state.channel_hub.get(&payload.channel_id);
+    if let Some(ref channel) = channel {
+        if channel.is_call() {
+            return send(tx, error_packet(LiveError::ChannelAccessDenied(payload.channel_id))).await;
+        }
         let members    = channel.get_members();
         let event_json = make_packet(server::CHANNEL_EVENT, ChannelEventPayload {
             event:      "delete".to_string(),
@@ -505,6 +693,10 @@ async fn handle_channel_delete(
     if !state.channel_hub.remove(&payload.channel_id) {
         return send(tx, error_packet(LiveError::ChannelNotFound(payload.channel_id))).await;
     }
+    // 가상 talker 스트림 재전송 버퍼는 엔드포인트 소유가 아님 — 채널과 함께 정리
+    if let Some(channel) = channel {
+        state.media_peer_hub.remove_rtp_cache(&channel.talker_ssrcs());
+    }
 
     send(tx, make_packet(server::ACK, AckPayload {
         op:   client::CHANNEL_DELETE,
@@ -521,6 +713,7 @@ async fn handle_channel_list(
     let list: Vec<ChannelSummary> = {
         let channels = state.channel_hub.channels.read().unwrap();
         let mut list: Vec<ChannelSummary> = channels.values()
+            .filter(|ch| !ch.is_call())
             .map(|ch| ChannelSummary {
                 channel_id:   ch.channel_id.clone(),
                 freq:         ch.freq.clone(),
@@ -556,6 +749,9 @@ async fn handle_channel_info(
 
     let channel = state.channel_hub.get(channel_id)
         .ok_or_else(|| LiveError::ChannelNotFound(channel_id.to_string()))?;
+    if channel.is_call() {
+        return send(tx, error_packet(LiveError::ChannelAccessDenied(channel_id.to_string()))).await;
+    }
 
     let peers = collect_members(channel_id, state);
 
@@ -597,25 +793,104 @@ async fn handle_message_create(
     let channel = state.channel_hub.get(&payload.channel_id)
         .ok_or_else(|| LiveError::ChannelNotFound(payload.channel_id.clone()))?;
 
-    let members    = channel.get_members();
-    let event_json = make_packet(server::MESSAGE_EVENT, MessageEventPayload {
-        message_id: format!("msg_{}_{}", user_id, current_timestamp()),
-        channel_id: payload.channel_id,
-        author_id:  user_id,
-        content:    payload.content,
-        timestamp:  current_timestamp(),
-    });
+    // 콘텐츠 정책 필터 (pass / rewrite / reject) — 판정 결과는 trace로 남김
+    let mut content = payload.content;
+    if !state.message_filter.is_empty() {
+        let priority = state.user_hub.get(&user_id)
+            .map(|u| u.priority)
+            .unwrap_or(config::FLOOR_PRIORITY_DEFAULT);
+        let ctx = MessageContext {
+            channel_id: &payload.channel_id,
+            author_id:  &user_id,
+            priority,
+        };
+        let verdict = state.message_filter.apply(&ctx, &content);
+        let reason  = match &verdict.outcome {
+            FilterOutcome::Pass                  => String::new(),
+            FilterOutcome::Rewrite { reason, .. }
+            | FilterOutcome::Reject { reason }   => reason.clone(),
+        };
+        state.trace_hub.publish(TraceEvent::new(
+            TraceDir::Sys,
+            Some(&payload.channel_id),
+            Some(&user_id),
+            client::MESSAGE_CREATE,
+            "MESSAGE_FILTER",
+            format!("outcome={} filter={} reason={}",
+                verdict.outcome.as_str(),
+                verdict.filter.as_deref().unwrap_or("-"),
+                if reason.is_empty() { "-" } else { &reason }),
+        ));
+        match verdict.outcome {
+            FilterOutcome::Pass                       => {}
+            FilterOutcome::Rewrite { content: c, .. } => content = c,
+            FilterOutcome::Reject { reason }          => {
+                return send(tx, error_packet(LiveError::MessageRejected(reason))).await;
+            }
+        }
+    }
 
     // 발신자 포함 전원에게 브로드캐스트
-    state.user_hub.broadcast_to(&members, &event_json, None).await;
+    let members = channel.get_members();
+    broadcast_message(
+        &state.user_hub, &state.trace_hub, &payload.channel_id, &members, &user_id, content, "ws",
+    ).await;
 
     Ok(())
 }
 
+static MESSAGE_SEQ: AtomicU64 = AtomicU64::new(1);
+
+/// MESSAGE_EVENT 브로드캐스트 + trace publish — WS MESSAGE_CREATE / Admin REST 공용
+/// source: trace data 표기용 ("ws" | "admin")
+/// 반환: message_id (msg_{timestamp}_{seq} — 같은 ms / 같은 author라도 유일)
+pub async fn broadcast_message(
+    user_hub:   &UserHub,
+    trace_hub:  &TraceHub,
+    channel_id: &str,
+    members:    &std::collections::HashSet<String>,
+    author_id:  &str,
+    content:    String,
+    source:     &str,
+) -> String {
+    let timestamp  = current_timestamp();
+    let message_id = format!("msg_{}_{}", timestamp, MESSAGE_SEQ.fetch_add(1, Ordering::Relaxed));
+
+    // webhook 등 외부 전달용 — 본문은 data에 실음
+    trace_hub.publish(TraceEvent::new(
+        TraceDir::Sys,
+        Some(channel_id),
+        Some(author_id),
+        server::MESSAGE_EVENT,
+        "MESSAGE_EVENT",
+        format!("message_id={} len={} source={}", message_id, content.len(), source),
+    ).with_data(serde_json::json!({
+        "message_id": message_id,
+        "content":    content,
+        "source":     source,
+    })).with_webhook(WebhookEvent::Message));
+
+    let event_json = make_packet(server::MESSAGE_EVENT, MessageEventPayload {
+        message_id: message_id.clone(),
+        channel_id: channel_id.to_string(),
+        author_id:  author_id.to_string(),
+        content,
+        timestamp,
+    });
+    user_hub.broadcast_to(members, &event_json, None).await;
+
+    message_id
+}
+
 // ----------------------------------------------------------------------------
 // [내부 유틸]
 // ----------------------------------------------------------------------------
 
+/// REST 키 없는 메시지 주입용 author — SYSTEM_AUTHOR_ID 또는 BOT_AUTHOR_PREFIX + 이름
+pub fn is_reserved_author(author_id: &str) -> bool {
+    author_id == config::SYSTEM_AUTHOR_ID || author_id.starts_with(config::BOT_AUTHOR_PREFIX)
+}
+
 fn parse_payload<T: serde::de::DeserializeOwned>(
     d: Option<serde_json::Value>,
 ) -> Result<T, LiveError> {
@@ -632,7 +907,7 @@ fn make_no_data(op: u8) -> String {
     serde_json::to_string(&GatewayPacket::no_data(op)).unwrap_or_default()
 }
 
-fn error_packet(err: LiveError) -> String {
+pub(crate) fn error_packet(err: LiveError) -> String {
     make_packet(server::ERROR, ErrorPayload {
         code:   err.code(),
         reason: err.to_string(),
@@ -654,7 +929,11 @@ fn collect_members(channel_id: &str, state: &AppState) -> Vec<MemberInfo> {
                 .first()
                 .map(|t| t.ssrc)
                 .unwrap_or(0);
-            MemberInfo { user_id: ep.user_id.clone(), ssrc }
+            MemberInfo {
+                user_id: ep.user_id.clone(),
+                ssrc,
+                service: state.user_hub.is_service(&ep.user_id),
+            }
         })
         .collect()
 }
@@ -681,12 +960,18 @@ async fn handle_renegotiate(
 
     // mid_map → SsrcMapping 변환
     // 각 mid에 대응하는 peer의 SSRC를 MediaPeerHub에서 조회
-    use crate::protocol::sdp::{build_sdp_answer_for_renego, SsrcMapping};
+    use crate::protocol::sdp::{
+        build_sdp_answer_for_renego, rid_ext_id, rtx_payload_types, simulcast_rids, video_codecs, SsrcMapping,
+    };
 
     // 기존 Endpoint의 ICE credential 조회 (re-negotiation에서는 ICE restart 방지)
+    // 1:1 호출 채널은 session.current_ufrag와 무관 — 채널 엔드포인트에서 먼저 찾는다
     let current_ufrag = session.current_ufrag.as_deref().unwrap_or("");
     trace!("[renego] looking up endpoint: current_ufrag='{}'", current_ufrag);
-    let existing_ep = state.media_peer_hub.get_by_ufrag(current_ufrag);
+    let existing_ep = state.media_peer_hub.get_channel_endpoints(&payload.channel_id)
+        .into_iter()
+        .find(|ep| ep.user_id == user_id)
+        .or_else(|| state.media_peer_hub.get_by_ufrag(current_ufrag));
     let (existing_ufrag, existing_pwd) = match &existing_ep {
         Some(ep) => {
             trace!("[renego] found endpoint: ufrag='{}' ice_pwd='{}'", ep.ufrag, ep.ice_pwd);
@@ -698,8 +983,17 @@ async fn handle_renegotiate(
         }
     };
 
+    // RTX 협상 여부 — 서버 NACK 재전송을 RTX로 보낼 때 receiver 기준 PT
+    let rtx_pts = rtx_payload_types(&payload.sdp_offer);
+    if let Some(ep) = &existing_ep {
+        ep.set_rtx_pts(rtx_pts.clone());
+        ep.set_video_codecs(video_codecs(&payload.sdp_offer));
+        ep.set_simulcast(simulcast_rids(&payload.sdp_offer), rid_ext_id(&payload.sdp_offer));
+    }
+
     // Conference: consumer SSRC를 서버가 생성하여 SDP에 삽입
     // relay 시 원본 SSRC → consumer SSRC로 rewrite됨
+    // video는 RTX가 협상되었으면 RTX SSRC도 할당해 FID 그룹으로 광고
     let ssrc_map: Vec<SsrcMapping> = payload.mid_map.iter().filter_map(|entry| {
         let target_kind = match entry.kind.as_str() {
             "audio" => crate::core::TrackKind::Audio,
@@ -707,14 +1001,17 @@ async fn handle_renegotiate(
             _       => return None,
         };
         // 서버가 consumer SSRC를 할당 (receiver=현재유저, sender=entry.user_id)
+        let is_video      = target_kind == crate::core::TrackKind::Video;
         let consumer_ssrc = state.media_peer_hub.get_or_create_consumer_ssrc(
             &user_id,
             &entry.user_id,
             target_kind,
         );
-        trace!("[renego] mid={} -> receiver={} sender={} kind={} consumer_ssrc={}",
-            entry.mid, user_id, entry.user_id, entry.kind, consumer_ssrc);
-        Some(SsrcMapping { mid: entry.mid.clone(), ssrc: consumer_ssrc })
+        let rtx_ssrc = (is_video && !rtx_pts.is_empty())
+            .then(|| state.media_peer_hub.get_or_create_rtx_ssrc(&user_id, consumer_ssrc));
+        trace!("[renego] mid={} -> receiver={} sender={} kind={} consumer_ssrc={} rtx_ssrc={:?}",
+            entry.mid, user_id, entry.user_id, entry.kind, consumer_ssrc, rtx_ssrc);
+        Some(SsrcMapping { mid: entry.mid.clone(), ssrc: consumer_ssrc, rtx_ssrc })
     }).collect();
 
     let sdp_answer = build_sdp_answer_for_renego(
@@ -729,12 +1026,74 @@ async fn handle_renegotiate(
     // relay map 재구축 (consumer SSRC 변경 반영)
     state.media_peer_hub.rebuild_relay_map(&payload.channel_id);
 
+    // 새로 수신하게 된 video 송신자에게 keyframe 요청 — 다음 자연 keyframe까지 검은 화면 방지
+    // (입장 시 구성원 전원 re-nego 연쇄는 송신자별 KEYFRAME_REQUEST_MIN_MS로 묶임)
+    let video_senders: std::collections::HashSet<&str> = payload.mid_map.iter()
+        .filter(|e| e.kind == "video" && e.user_id != user_id)
+        .map(|e| e.user_id.as_str())
+        .collect();
+    for ep in state.media_peer_hub.get_channel_endpoints(&payload.channel_id) {
+        if video_senders.contains(ep.user_id.as_str()) {
+            crate::media::net::request_keyframe(&ep, "renegotiate").await;
+        }
+    }
+
     send(tx, make_packet(server::RENEGOTIATE_ACK, RenegotiateAckPayload {
         channel_id:  payload.channel_id,
         sdp_answer,
     })).await
 }
 
+// ----------------------------------------------------------------------------
+// [SIMULCAST_LAYER] Conference simulcast 수신 레이어 지정
+// 레이어 전환은 릴레이 경로에서 목표 레이어 keyframe을 기다려 수행 — 여기서는 목표만 바꾸고 PLI
+// ----------------------------------------------------------------------------
+
+async fn handle_simulcast_layer(
+    tx:      &mpsc::Sender<String>,
+    session: &Session,
+    state:   &AppState,
+    packet:  GatewayPacket,
+) -> Result<(), LiveError> {
+    let payload = parse_payload::<SimulcastLayerPayload>(packet.d)?;
+    let user_id = session.user_id.as_ref().unwrap().clone();
+    trace!("SIMULCAST_LAYER - user:{} channel:{} sender:{} layer:{:?}",
+        user_id, payload.channel_id, payload.user_id, payload.layer);
+
+    let channel = state.channel_hub.get(&payload.channel_id)
+        .ok_or_else(|| LiveError::ChannelNotFound(payload.channel_id.clone()))?;
+    if !channel.get_members().contains(&user_id) {
+        return Err(LiveError::NotInChannel(payload.channel_id.clone()));
+    }
+    let sender = state.media_peer_hub.get_channel_endpoints(&payload.channel_id)
+        .into_iter()
+        .find(|ep| ep.user_id == payload.user_id)
+        .ok_or_else(|| LiveError::InvalidPayload(format!("no media from user {}", payload.user_id)))?;
+
+    // rid → 레이어 index (송신자 a=simulcast 순서)
+    let layer = match payload.layer.as_deref() {
+        None | Some("auto") => None,
+        Some(rid) => {
+            let index = sender.simulcast.read().unwrap().iter().position(|r| r == rid);
+            Some(index.ok_or_else(|| LiveError::InvalidPayload(format!("unknown simulcast layer {}", rid)))?)
+        }
+    };
+
+    let changed = state.media_peer_hub.set_layer_preference(&user_id, &payload.user_id, layer, current_timestamp());
+    if changed {
+        crate::media::net::request_keyframe(&sender, "simulcast layer").await;
+    }
+
+    send(tx, make_packet(server::ACK, AckPayload {
+        op:   client::SIMULCAST_LAYER,
+        data: serde_json::json!({
+            "channel_id": payload.channel_id,
+            "user_id":    payload.user_id,
+            "layer":      payload.layer.unwrap_or_else(|| "auto".to_string()),
+        }),
+    })).await
+}
+
 // SDP answer 생성은 protocol/sdp.rs 로 분리됨
 // handle_channel_join에서 build_sdp_answer_with_ice 직접 호출 (conference_mode 파라미터 전달)
 
@@ -776,11 +1135,60 @@ fn op_meta_in(op: u8, user_id: Option<&str>) -> (&'static str, String) {
         client::FLOOR_REQUEST  => ("FLOOR_REQUEST",  format!("user={}", uid)),
         client::FLOOR_RELEASE  => ("FLOOR_RELEASE",  format!("user={}", uid)),
         client::FLOOR_PING     => ("FLOOR_PING",     format!("user={}", uid)),
+        client::FLOOR_QUEUE_CANCEL => ("FLOOR_QUEUE_CANCEL", format!("user={}", uid)),
+        client::FLOOR_STATUS   => ("FLOOR_STATUS",   format!("user={}", uid)),
+        client::EMERGENCY_ALERT => ("EMERGENCY_ALERT", format!("user={}", uid)),
+        client::EMERGENCY_ACK  => ("EMERGENCY_ACK",  format!("user={}", uid)),
         client::RENEGOTIATE    => ("RENEGOTIATE",    format!("user={}", uid)),
+        client::SIMULCAST_LAYER => ("SIMULCAST_LAYER", format!("user={}", uid)),
+        client::CALL_INVITE    => ("CALL_INVITE",    format!("user={}", uid)),
+        client::CALL_ACCEPT    => ("CALL_ACCEPT",    format!("user={}", uid)),
+        client::CALL_REJECT    => ("CALL_REJECT",    format!("user={}", uid)),
+        client::CALL_CANCEL    => ("CALL_CANCEL",    format!("user={}", uid)),
+        client::CALL_HANGUP    => ("CALL_HANGUP",    format!("user={}", uid)),
         _                      => ("UNKNOWN",         format!("op={} user={}", op, uid)),
     }
 }
 
+/// C→S opcode → rate limit 계열 (IDENTIFY/HEARTBEAT는 제한 없음)
+/// 알 수 없는 opcode도 조회 계열로 묶어 플러딩 방어 — MBCP 경로(mbcp::handle_mbcp)도 변환된 opcode로 같은 버킷 차감
+pub(crate) fn rate_class(op: u8) -> Option<RateClass> {
+    match op {
+        client::IDENTIFY | client::HEARTBEAT => None,
+        client::MESSAGE_CREATE               => Some(RateClass::Message),
+        client::FLOOR_REQUEST
+        | client::FLOOR_RELEASE
+        | client::FLOOR_PING
+        | client::FLOOR_QUEUE_CANCEL
+        | client::EMERGENCY_ALERT
+        | client::EMERGENCY_ACK              => Some(RateClass::Floor),
+        client::CHANNEL_CREATE
+        | client::CHANNEL_JOIN
+        | client::CHANNEL_LEAVE
+        | client::CHANNEL_UPDATE
+        | client::CHANNEL_DELETE
+        | client::RENEGOTIATE
+        | client::SIMULCAST_LAYER
+        | client::CALL_INVITE
+        | client::CALL_ACCEPT
+        | client::CALL_REJECT
+        | client::CALL_CANCEL
+        | client::CALL_HANGUP                => Some(RateClass::Channel),
+        _                                    => Some(RateClass::Query),
+    }
+}
+
+/// 서비스 계정에 필요한 scope (None이면 제한 없음 — 일반 사용자는 항상 허용)
+fn required_scope(op: u8) -> Option<ApiScope> {
+    match op {
+        client::MESSAGE_CREATE => Some(ApiScope::PostMessage),
+        client::CHANNEL_CREATE
+        | client::CHANNEL_UPDATE
+        | client::CHANNEL_DELETE => Some(ApiScope::ManageChannels),
+        _                        => None,
+    }
+}
+
 /// WS 종료 시 클린업
 async fn cleanup(session: &mut Session, state: &AppState) {
     let user_id = match session.user_id.take() {
@@ -804,6 +1212,7 @@ async fn cleanup(session: &mut Session, state: &AppState) {
                 channel_id: channel_id.clone(),
                 data:       serde_json::to_value(MemberInfo {
                     user_id: user_id.clone(), ssrc,
+                    service: state.user_hub.is_service(&user_id),
                 }).unwrap_or_default(),
             });
             state.user_hub.broadcast_to(&members, &leave_event, Some(&user_id)).await;
@@ -819,6 +1228,15 @@ async fn cleanup(session: &mut Session, state: &AppState) {
             channel.remove_member(&user_id);
         }
 
+        state.trace_hub.publish(TraceEvent::new(
+            TraceDir::Sys,
+            Some(&channel_id),
+            Some(&user_id),
+            server::CHANNEL_EVENT,
+            "CHANNEL_LEAVE",
+            format!("user={} ssrc={} cause=disconnect", user_id, ssrc),
+        ).with_webhook(WebhookEvent::Leave));
+
         state.media_peer_hub.remove(&ufrag);
 
         // Conference consumer SSRC 정리 + relay map 재구축
@@ -826,8 +1244,102 @@ async fn cleanup(session: &mut Session, state: &AppState) {
         state.media_peer_hub.rebuild_relay_map(&channel_id);
 
         // Floor Control 정리 (holder면 Revoke, 대기열이면 제거)
-        floor::on_user_disconnect(&user_id, &channel_id, &state.user_hub, &state.channel_hub).await;
+        floor::on_user_disconnect(&user_id, &channel_id, "disconnect", &state.user_hub, &state.channel_hub, &state.media_peer_hub, &state.trace_hub).await;
+    }
+
+    // 1:1 호출 당사자면 종료 (상대에게 CALL_ENDED(disconnect))
+    if let Some(c) = state.call_hub.call_of(&user_id) {
+        call::end_call(state, &c.call_id, "disconnect", Some(&user_id)).await;
     }
 
     state.user_hub.unregister(&user_id);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::FloorPolicy;
+
+    fn create_pkt(channel_id: &str, policy: serde_json::Value) -> GatewayPacket {
+        GatewayPacket { op: client::CHANNEL_CREATE, d: Some(serde_json::json!({
+            "channel_id": channel_id, "freq": "0900", "channel_name": "ops", "floor_policy": policy,
+        })) }
+    }
+
+    #[tokio::test]
+    async fn message_ids_unique_per_message() {
+        let state   = AppState::for_test();
+        let members = std::collections::HashSet::new();
+        let a = broadcast_message(&state.user_hub, &state.trace_hub, "CH_1", &members, "alice", "hi".into(), "ws").await;
+        let b = broadcast_message(&state.user_hub, &state.trace_hub, "CH_1", &members, "alice", "hi".into(), "ws").await;
+        assert_ne!(a, b);
+        assert!(!a.contains("alice"));
+    }
+
+    #[test]
+    fn reserved_authors() {
+        assert!(is_reserved_author(config::SYSTEM_AUTHOR_ID));
+        assert!(is_reserved_author("bot:cad"));
+        assert!(!is_reserved_author("alice"));
+        assert!(!is_reserved_author("robot:x"));
+    }
+
+    #[tokio::test]
+    async fn channel_create_policy_only_for_new_channel() {
+        let state   = AppState::for_test();
+        let (tx, mut rx) = mpsc::channel(16);
+        state.channel_hub.create("CH_P", "0001", "preset", ChannelMode::PTT, 10);
+
+        // 기존 채널 정책 덮어쓰기 불가
+        handle_channel_create(&tx, &state, create_pkt("CH_P", serde_json::json!({ "queue_enabled": false }))).await.unwrap();
+        let err = rx.recv().await.unwrap();
+        assert!(err.contains("\"op\":201") && err.contains("\"code\":1004"));
+        assert_eq!(state.channel_hub.get("CH_P").unwrap().floor_policy(), FloorPolicy::default());
+
+        // 새 채널은 적용
+        handle_channel_create(&tx, &state, create_pkt("CH_N", serde_json::json!({ "queue_enabled": false }))).await.unwrap();
+        assert!(rx.recv().await.unwrap().contains("\"op\":200"));
+        assert!(!state.channel_hub.get("CH_N").unwrap().floor_policy().queue_enabled);
+
+        // 1:1 호출 채널 id 대역은 CHANNEL_CREATE 대상 아님
+        let call_id = format!("{}000001", config::CALL_ID_PREFIX);
+        handle_channel_create(&tx, &state, create_pkt(&call_id, serde_json::Value::Null)).await.unwrap();
+        let err = rx.recv().await.unwrap();
+        assert!(err.contains("\"op\":201") && err.contains("\"code\":2002"));
+        assert!(state.channel_hub.get(&call_id).is_none());
+    }
+
+    #[tokio::test]
+    async fn channel_create_invalid_policy_replies_error() {
+        let state = AppState::for_test();
+        let (tx, mut rx) = mpsc::channel(16);
+
+        handle_channel_create(&tx, &state, create_pkt("CH_V", serde_json::json!({ "ping_timeout_ms": 1 }))).await.unwrap();
+        let err = rx.recv().await.unwrap();
+        assert!(err.contains("\"op\":201") && err.contains("\"code\":1004") && err.contains("ping_timeout_ms"));
+        assert!(state.channel_hub.get("CH_V").is_none());
+    }
+
+    #[tokio::test]
+    async fn call_channel_hidden_from_channel_ops() {
+        let state   = AppState::for_test();
+        let (tx, mut rx) = mpsc::channel(16);
+        let call_id = format!("{}000001", config::CALL_ID_PREFIX);
+        state.channel_hub.create(&call_id, "0000", "alice ↔ bob", ChannelMode::PTT, 2);
+        let pkt = |op| GatewayPacket { op, d: Some(serde_json::json!({ "channel_id": call_id, "channel_name": "x", "ssrc": 0, "ufrag": "" })) };
+
+        let mut session = Session::new();
+        session.user_id = Some("carol".to_string());
+        handle_channel_join(&tx, &mut session, &state, pkt(client::CHANNEL_JOIN)).await.unwrap();
+        handle_channel_info(&tx, &state, pkt(client::CHANNEL_INFO)).await.unwrap();
+        handle_channel_update(&tx, &state, pkt(client::CHANNEL_UPDATE)).await.unwrap();
+        handle_channel_delete(&tx, &state, pkt(client::CHANNEL_DELETE)).await.unwrap();
+        for _ in 0..4 {
+            let err = rx.recv().await.unwrap();
+            assert!(err.contains("\"op\":201") && err.contains("\"code\":2002"));
+        }
+        assert!(session.current_channel.is_none());
+        assert!(state.channel_hub.get(&call_id).unwrap().get_members().is_empty());
+        assert!(state.channel_hub.get(&call_id).is_some());
+    }
+}
@@ -14,6 +14,10 @@
 //   - offer의 direction을 읽어서 answer에 적절히 반전
 //     sendrecv → sendrecv, recvonly → sendonly, inactive → inactive
 //   - a=extmap (MID header extension 등) 보존 — BUNDLE demux용
+//
+// Simulcast (a=simulcast / a=rid, RFC 8853 / 8851):
+//   - answer에는 방향만 반전해 미러링 (offer send → answer recv)
+//   - 송신 rid 나열 순서를 레이어 순서(저화질 → 고화질)로 사용 — 클라이언트 sendEncodings도 같은 순서
 
 /// SDP answer 조립 후 (sdp_string, server_ufrag, server_pwd) 반환
 /// server_ufrag: MediaPeerHub 등록 키
@@ -122,6 +126,10 @@ pub fn build_sdp_answer_with_ice(
         if line.starts_with("a=mid:") {
             sec.mid = line["a=mid:".len()..].trim().to_string();
         }
+        if line.starts_with("a=rid:") || line.starts_with("a=simulcast:") {
+            sec.codec_lines.push(flip_rid_direction(line));
+            continue;
+        }
         sec.codec_lines.push(line.to_string());
     }
     if let Some(sec) = current.take() {
@@ -276,8 +284,9 @@ pub fn build_sdp_answer_with_ice(
 
 /// SSRC 매핑 정보: mid → (user_id, kind, ssrc)
 pub struct SsrcMapping {
-    pub mid:     String,
-    pub ssrc:    u32,
+    pub mid:      String,
+    pub ssrc:     u32,
+    pub rtx_ssrc: Option<u32>,  // 서버 NACK 재전송용 RTX 스트림 — a=ssrc-group:FID로 광고
 }
 
 /// re-negotiation용 SDP answer 생성
@@ -293,7 +302,7 @@ pub fn build_sdp_answer_for_renego(
 ) -> String {
     // 기존 ICE credential을 직접 주입해서 answer 생성 — replace 해킹 없이 깨끗하게
     // re-negotiation은 항상 Conference 모드에서만 발생 (sendrecv→recvonly 강제)
-    let (mut sdp, _ufrag, _pwd) = build_sdp_answer_with_ice(
+    let (sdp, _ufrag, _pwd) = build_sdp_answer_with_ice(
         offer,
         fingerprint,
         udp_port,
@@ -302,18 +311,22 @@ pub fn build_sdp_answer_for_renego(
         true,  // conference_mode
     );
     tracing::trace!("[sdp-renego] answer built with existing_ufrag='{}'", existing_ufrag);
+    insert_ssrc_lines(sdp, ssrc_map)
+}
 
-    // sendonly m-line에 a=ssrc: 라인 삽입
-    // 전략: a=end-of-candidates 라인 직전에 mid에 해당하는 ssrc 라인 삽입
-    // 이를 위해 완성된 SDP를 섹션별로 다시 파싱해서 조작
+/// 완성된 answer의 mid 섹션에 서버 SSRC `a=ssrc:` 라인 삽입
+/// (re-nego sendonly m-line / PTT 가상 talker 스트림 광고 공용)
+/// 전략: a=end-of-candidates 라인 직전에 mid에 해당하는 ssrc 라인 삽입
+/// 이를 위해 완성된 SDP를 섹션별로 다시 파싱해서 조작
+pub fn insert_ssrc_lines(mut sdp: String, ssrc_map: &[SsrcMapping]) -> String {
     if ssrc_map.is_empty() {
         return sdp;
     }
 
-    // 빠른 조회용 mid → ssrc HashMap
-    let ssrc_lookup: std::collections::HashMap<&str, u32> = ssrc_map
+    // 빠른 조회용 mid → SsrcMapping HashMap
+    let ssrc_lookup: std::collections::HashMap<&str, &SsrcMapping> = ssrc_map
         .iter()
-        .map(|m| (m.mid.as_str(), m.ssrc))
+        .map(|m| (m.mid.as_str(), m))
         .collect();
 
     // SDP를 라인별로 재조립하면서 a=mid:N 뒤에 a=ssrc: 삽입
@@ -336,10 +349,16 @@ pub fn build_sdp_answer_for_renego(
         // a=end-of-candidates 직전에 ssrc 삽입 (섹션 마지막 속성)
         if line.starts_with("a=end-of-candidates") && !ssrc_inserted {
             if let Some(mid) = current_mid {
-                if let Some(&ssrc) = ssrc_lookup.get(mid) {
-                    result.push_str(&format!("a=ssrc:{} cname:mini-livechat\r\n", ssrc));
+                if let Some(m) = ssrc_lookup.get(mid) {
+                    if let Some(rtx) = m.rtx_ssrc {
+                        result.push_str(&format!("a=ssrc-group:FID {} {}\r\n", m.ssrc, rtx));
+                    }
+                    result.push_str(&format!("a=ssrc:{} cname:mini-livechat\r\n", m.ssrc));
+                    if let Some(rtx) = m.rtx_ssrc {
+                        result.push_str(&format!("a=ssrc:{} cname:mini-livechat\r\n", rtx));
+                    }
                     ssrc_inserted = true;
-                    tracing::trace!("[sdp] inserted a=ssrc:{} for mid={}", ssrc, mid);
+                    tracing::trace!("[sdp] inserted a=ssrc:{} rtx={:?} for mid={}", m.ssrc, m.rtx_ssrc, mid);
                 }
             }
         }
@@ -352,6 +371,115 @@ pub fn build_sdp_answer_for_renego(
     sdp
 }
 
+/// SDP의 미디어 섹션 목록 (media 종류, mid) — `m=audio …` + `a=mid:0` → ("audio", "0")
+pub fn media_mids(sdp: &str) -> Vec<(String, String)> {
+    let mut out   = Vec::new();
+    let mut media = None;
+    for line in sdp.lines().map(str::trim) {
+        if let Some(m) = line.strip_prefix("m=") {
+            media = m.split_whitespace().next().map(str::to_string);
+        } else if let Some(mid) = line.strip_prefix("a=mid:") {
+            out.extend(media.take().map(|kind| (kind, mid.to_string())));
+        }
+    }
+    out
+}
+
+/// offer의 audio level 헤더 확장 id (RFC 6464 — `a=extmap:N urn:ietf:params:rtp-hdrext:ssrc-audio-level`)
+/// answer는 extmap을 그대로 미러링하므로 offer의 id가 곧 수신 RTP의 id (VOX 음성 감지용)
+pub fn audio_level_ext_id(offer: &str) -> Option<u8> {
+    offer.lines()
+        .filter_map(|l| l.trim().strip_prefix("a=extmap:"))
+        .find(|l| l.contains("urn:ietf:params:rtp-hdrext:ssrc-audio-level"))
+        .and_then(|l| l.split(|c: char| c == '/' || c.is_whitespace()).next())
+        .and_then(|id| id.parse().ok())
+}
+
+/// offer의 RTX 협상 (원본 PT, RTX PT) 목록 — `a=fmtp:97 apt=96` → (96, 97)
+/// answer는 코덱 라인을 미러링하므로 offer의 PT가 곧 서버가 보낼 RTX PT (NACK 재전송용)
+pub fn rtx_payload_types(offer: &str) -> Vec<(u8, u8)> {
+    let mut pts: Vec<(u8, u8)> = Vec::new();
+    for line in offer.lines().filter_map(|l| l.trim().strip_prefix("a=fmtp:")) {
+        let Some((rtx, params)) = line.split_once(' ') else { continue };
+        let apt = params.split(';').find_map(|p| p.trim().strip_prefix("apt="));
+        if let (Ok(rtx), Some(Ok(apt))) = (rtx.parse::<u8>(), apt.map(str::parse::<u8>)) {
+            if !pts.contains(&(apt, rtx)) {
+                pts.push((apt, rtx));
+            }
+        }
+    }
+    pts
+}
+
+/// offer의 simulcast 송신 rid 목록 — `a=simulcast:send q;h;f` → [q, h, f] (simulcast가 아니면 빈 Vec)
+/// `~`(일시 정지) 표시는 떼고, `,` 대안 목록은 첫 rid만 사용
+pub fn simulcast_rids(offer: &str) -> Vec<String> {
+    let Some(value) = offer.lines().find_map(|l| l.trim().strip_prefix("a=simulcast:")) else {
+        return Vec::new();
+    };
+    let mut parts = value.split_whitespace();
+    while let Some(dir) = parts.next() {
+        let list = parts.next().unwrap_or("");
+        if dir == "send" {
+            return list.split(';')
+                .filter_map(|alt| alt.split(',').next())
+                .map(|rid| rid.trim_start_matches('~').to_string())
+                .filter(|rid| !rid.is_empty())
+                .collect();
+        }
+    }
+    Vec::new()
+}
+
+/// offer의 rtp-stream-id 헤더 확장 id (`urn:ietf:params:rtp-hdrext:sdes:rtp-stream-id`) — 레이어 SSRC 학습용
+pub fn rid_ext_id(offer: &str) -> Option<u8> {
+    offer.lines()
+        .filter_map(|l| l.trim().strip_prefix("a=extmap:"))
+        .find(|l| l.split_whitespace().nth(1) == Some("urn:ietf:params:rtp-hdrext:sdes:rtp-stream-id"))
+        .and_then(|l| l.split(|c: char| c == '/' || c.is_whitespace()).next())
+        .and_then(|id| id.parse().ok())
+}
+
+/// offer의 video 코덱 (PT, 인코딩 이름) — `a=rtpmap:96 VP8/90000` → (96, "VP8")
+pub fn video_codecs(offer: &str) -> Vec<(u8, String)> {
+    let mut codecs: Vec<(u8, String)> = Vec::new();
+    let mut in_video = false;
+    for line in offer.lines().map(str::trim) {
+        if let Some(m) = line.strip_prefix("m=") {
+            in_video = m.starts_with("video");
+            continue;
+        }
+        let Some(map) = line.strip_prefix("a=rtpmap:").filter(|_| in_video) else { continue };
+        let Some((pt, enc)) = map.split_once(' ') else { continue };
+        let (Ok(pt), Some(name)) = (pt.parse::<u8>(), enc.split('/').next()) else { continue };
+        if !codecs.iter().any(|(p, _)| *p == pt) {
+            codecs.push((pt, name.to_string()));
+        }
+    }
+    codecs
+}
+
+/// a=rid / a=simulcast 라인의 방향 반전 (send ↔ recv)
+///   `a=rid:h send max-width=1280` → `a=rid:h recv max-width=1280`
+///   `a=simulcast:send q;h;f`      → `a=simulcast:recv q;h;f`
+fn flip_rid_direction(line: &str) -> String {
+    fn flip(t: &str) -> &str {
+        match t {
+            "send" => "recv",
+            "recv" => "send",
+            other  => other,
+        }
+    }
+    // a=rid:<id> <방향> ... / a=simulcast:<방향> <목록> [<방향> <목록>]
+    let (prefix, value) = line.split_once(':').unwrap_or((line, ""));
+    let is_rid = prefix == "a=rid";
+    let parts: Vec<&str> = value.split(' ')
+        .enumerate()
+        .map(|(i, t)| if (is_rid && i == 1) || (!is_rid && i % 2 == 0) { flip(t) } else { t })
+        .collect();
+    format!("{}:{}", prefix, parts.join(" "))
+}
+
 /// 라우팅 테이블 기반 로컬 IP 자동 감지
 /// UDP 소켓으로 8.8.8.8:80 connect (실제 패킷 없음) → local_addr() 조회
 /// 멀티홈 환경에서도 외부 통신에 실제로 쓰이는 인터페이스 IP가 정확히 반환됨
@@ -676,24 +804,39 @@ mod tests {
     fn renego_ssrc_inserted_for_sendonly() {
         let offer = make_renego_offer();
         let ssrc_map = vec![
-            SsrcMapping { mid: "2".to_string(), ssrc: 111222 },
-            SsrcMapping { mid: "3".to_string(), ssrc: 333444 },
+            SsrcMapping { mid: "2".to_string(), ssrc: 111222, rtx_ssrc: None },
+            SsrcMapping { mid: "3".to_string(), ssrc: 333444, rtx_ssrc: Some(555666) },
         ];
         let sdp = build_sdp_answer_for_renego(&offer, "sha-256 FF:00", 40000, "testufrag", "testpwd", &ssrc_map);
         assert!(sdp.contains("a=ssrc:111222 cname:mini-livechat"), "audio ssrc should be inserted");
         assert!(sdp.contains("a=ssrc:333444 cname:mini-livechat"), "video ssrc should be inserted");
+        assert!(sdp.contains("a=ssrc-group:FID 333444 555666\r\n"), "video rtx should be grouped");
+        assert!(sdp.contains("a=ssrc:555666 cname:mini-livechat"));
+        assert_eq!(sdp.matches("a=ssrc-group:").count(), 1);
     }
 
     #[test]
     fn renego_ssrc_not_inserted_for_sendrecv() {
         let offer = make_renego_offer();
         let ssrc_map = vec![
-            SsrcMapping { mid: "0".to_string(), ssrc: 999999 },
+            SsrcMapping { mid: "0".to_string(), ssrc: 999999, rtx_ssrc: None },
         ];
         let sdp = build_sdp_answer_for_renego(&offer, "sha-256 FF:00", 40000, "testufrag", "testpwd", &ssrc_map);
         assert!(sdp.contains("a=ssrc:999999"));
     }
 
+    #[test]
+    fn media_mids_lists_sections_and_talker_ssrc_inserted() {
+        let (sdp, _, _) = build_sdp_answer(&make_bundle_offer(), "sha-256 FF:00", 40000);
+        let mids = media_mids(&sdp);
+        assert_eq!(mids, vec![("audio".to_string(), "0".to_string()), ("video".to_string(), "1".to_string())]);
+
+        let sdp = insert_ssrc_lines(sdp, &[SsrcMapping { mid: "1".to_string(), ssrc: 4242, rtx_ssrc: None }]);
+        let video = &sdp[sdp.find("m=video").unwrap()..];
+        assert!(video.contains("a=ssrc:4242 cname:mini-livechat\r\na=end-of-candidates"));
+        assert_eq!(sdp.matches("a=ssrc:").count(), 1);
+    }
+
     #[test]
     fn renego_empty_ssrc_map_no_change() {
         let offer = make_renego_offer();
@@ -806,6 +949,41 @@ mod tests {
             .to_string()
     }
 
+    #[test]
+    fn simulcast_offer_parsed_and_answer_flips_direction() {
+        let offer = make_renego_offer_full_codecs().replacen(
+            "a=extmap:4 urn:ietf:params:rtp-hdrext:sdes:mid\r\nm=audio",
+            "a=extmap:4 urn:ietf:params:rtp-hdrext:sdes:mid\r\n\
+             a=extmap:10 urn:ietf:params:rtp-hdrext:sdes:rtp-stream-id\r\n\
+             a=extmap:11 urn:ietf:params:rtp-hdrext:sdes:repaired-rtp-stream-id\r\n\
+             a=rid:q send max-width=320\r\n\
+             a=rid:h send\r\n\
+             a=rid:f send\r\n\
+             a=simulcast:send q;h,h2;~f\r\n\
+             m=audio",
+            1,
+        );
+        assert_eq!(simulcast_rids(&offer), vec!["q", "h", "f"]);
+        assert_eq!(rid_ext_id(&offer), Some(10));
+        assert_eq!(video_codecs(&offer), vec![
+            (96, "VP8".to_string()), (97, "rtx".to_string()), (102, "H264".to_string()), (103, "rtx".to_string()),
+        ]);
+        assert!(simulcast_rids(&make_audio_offer("u")).is_empty());
+        assert_eq!(rid_ext_id(&make_audio_offer("u")), None);
+
+        let (sdp, _, _) = build_sdp_answer(&offer, "sha-256 FF:00", 40000);
+        assert!(sdp.contains("a=rid:q recv max-width=320\r\n"));
+        assert!(sdp.contains("a=simulcast:recv q;h,h2;~f\r\n"));
+        assert!(!sdp.contains(" send"));
+    }
+
+    #[test]
+    fn rtx_payload_types_from_apt() {
+        let pts = rtx_payload_types(&make_renego_offer_full_codecs());
+        assert_eq!(pts, vec![(96, 97), (102, 103)]);
+        assert!(rtx_payload_types(&make_audio_offer("u")).is_empty());
+    }
+
     #[test]
     fn sendonly_audio_filters_to_opus_only() {
         let offer = make_renego_offer_full_codecs();
@@ -887,6 +1065,18 @@ mod tests {
         assert_eq!(msid_count, 1, "only sendonly section should have msid");
     }
 
+    // ----- audio_level_ext_id -----
+
+    #[test]
+    fn audio_level_ext_id_from_offer() {
+        let offer = "m=audio 9 UDP/TLS/RTP/SAVPF 111\r\n\
+                     a=extmap:1 urn:ietf:params:rtp-hdrext:ssrc-audio-level\r\n\
+                     a=extmap:4 urn:ietf:params:rtp-hdrext:sdes:mid\r\n";
+        assert_eq!(audio_level_ext_id(offer), Some(1));
+        assert_eq!(audio_level_ext_id("a=extmap:3/sendrecv urn:ietf:params:rtp-hdrext:ssrc-audio-level vad=on"), Some(3));
+        assert_eq!(audio_level_ext_id("a=extmap:4 urn:ietf:params:rtp-hdrext:sdes:mid"), None);
+    }
+
     // ----- detect_local_ip -----
 
     #[test]
@@ -0,0 +1,206 @@
+// author: kodeholic (powered by Claude)
+// 발언자 감지 (Active Speaker) — Conference UI의 "누가 말하고 있는지" 표시용
+//
+// 수신 audio RTP의 RFC 6464 audio level(-dBov)을 엔드포인트별로 평활 (media/net.rs에서 복호화 후 on_rtp 호출)
+//   확장이 협상되지 않은 엔드포인트는 측정하지 않음 (VOX와 달리 packet flow로 대체 불가 — 레벨 비교가 목적)
+//
+// run_speaker_detector가 speaker_interval_ms 주기로 Conference 채널마다 CHANNEL_EVENT 브로드캐스트:
+//   speaker_changed — dominant speaker(가장 큰 발언자)가 바뀌었을 때
+//                     현재 발언자가 말하는 동안은 SPEAKER_SWITCH_MARGIN_DB 넘게 큰 발언자만 교체 (겹침 시 깜빡임 방지)
+//                     모두 멈추면 마지막 발언자 유지 (UI 강조 유지용)
+//   audio_levels    — 발언 중인 멤버 레벨 스냅샷 (큰 순서) — 발언자가 있는 동안 매 주기, 모두 멈추면 빈 목록 1회
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, trace};
+
+use crate::config;
+use crate::core::{ChannelHub, Endpoint, MediaPeerHub, TrackKind, UserHub};
+use crate::media::rtp;
+use crate::protocol::message::{ChannelEventPayload, GatewayPacket};
+use crate::protocol::opcode::server;
+use crate::utils::current_timestamp;
+
+/// 채널별 판정 상태 (감지 태스크 로컬)
+#[derive(Default)]
+struct SpeakerState {
+    dominant: Option<String>,  // 마지막으로 알린 dominant speaker
+    active:   bool,            // 직전 스냅샷에 발언자가 있었는지 (빈 목록 1회 전송용)
+}
+
+/// 복호화된 RTP 1개 처리 — audio level 확장이 없거나 video 트랙이면 아무것도 하지 않음
+pub fn on_rtp(ep: &Endpoint, packet: &[u8]) {
+    let Some(id)   = ep.audio_level_ext() else { return };
+    let Some(ssrc) = rtp::ssrc(packet) else { return };
+    // 등록된 video 트랙은 제외 (미등록 SSRC는 audio로 간주 — 하위 호환 단일 트랙)
+    if ep.tracks.read().unwrap().iter().any(|t| t.ssrc == ssrc && t.kind != TrackKind::Audio) {
+        return;
+    }
+    if let Some(level) = rtp::audio_level(packet, id) {
+        ep.update_audio_level(level, current_timestamp());
+    }
+}
+
+/// 발언자 감지 태스크 — interval_ms 주기로 Conference 채널 판정 (0이면 비활성)
+pub async fn run_speaker_detector(
+    user_hub:    Arc<UserHub>,
+    channel_hub: Arc<ChannelHub>,
+    peer_hub:    Arc<MediaPeerHub>,
+    interval_ms: u64,
+) {
+    if interval_ms == 0 {
+        info!("[speaker] disabled");
+        return;
+    }
+    info!("[speaker] Started (interval={}ms, threshold=-{}dBov)", interval_ms, config::SPEAKER_THRESHOLD_DBOV);
+
+    let mut states: HashMap<String, SpeakerState> = HashMap::new();
+    let mut timer = tokio::time::interval(Duration::from_millis(interval_ms));
+    loop {
+        timer.tick().await;
+        let now = current_timestamp();
+
+        let channels: Vec<_> = channel_hub.channels.read().unwrap()
+            .values()
+            .filter(|ch| !ch.is_ptt())
+            .cloned()
+            .collect();
+        states.retain(|id, _| channels.iter().any(|ch| &ch.channel_id == id));
+
+        for ch in channels {
+            let levels = voiced_levels(&peer_hub.get_channel_endpoints(&ch.channel_id), now);
+            let state  = states.entry(ch.channel_id.clone()).or_default();
+            let packets = evaluate(&ch.channel_id, &levels, state);
+            if packets.is_empty() {
+                continue;
+            }
+            let members = ch.get_members();
+            for json in packets {
+                user_hub.broadcast_to(&members, &json, None).await;
+            }
+        }
+    }
+}
+
+/// 발언 중인 엔드포인트 (user_id, 평활 level) — 큰 소리(작은 -dBov) 순
+fn voiced_levels(endpoints: &[Arc<Endpoint>], now: u64) -> Vec<(String, u8)> {
+    let mut levels: Vec<(String, u8)> = endpoints.iter()
+        .filter_map(|ep| Some((ep.user_id.clone(), ep.audio_level(now)?)))
+        .filter(|(_, level)| *level <= config::SPEAKER_THRESHOLD_DBOV)
+        .collect();
+    levels.sort_by_key(|(_, level)| *level);
+    levels
+}
+
+/// 채널 1개 판정 — 보낼 CHANNEL_EVENT 패킷 목록 (state 갱신)
+fn evaluate(channel_id: &str, levels: &[(String, u8)], state: &mut SpeakerState) -> Vec<String> {
+    let mut out = Vec::new();
+
+    if let Some((loudest, loudest_level)) = levels.first() {
+        // 현재 발언자가 아직 말하고 있고 가장 큰 발언자와 차이가 margin 이내면 유지
+        let keep = state.dominant.as_ref()
+            .and_then(|d| levels.iter().find(|(u, _)| u == d))
+            .is_some_and(|(_, level)| *level <= loudest_level.saturating_add(config::SPEAKER_SWITCH_MARGIN_DB));
+        if !keep && state.dominant.as_ref() != Some(loudest) {
+            trace!("[speaker] channel={} dominant {:?} → {} (-{}dBov)", channel_id, state.dominant, loudest, loudest_level);
+            state.dominant = Some(loudest.clone());
+            out.push(channel_event(channel_id, "speaker_changed", serde_json::json!({
+                "user_id": loudest,
+                "level":   loudest_level,
+            })));
+        }
+    }
+
+    if !levels.is_empty() || state.active {
+        let snapshot: Vec<serde_json::Value> = levels.iter()
+            .map(|(user_id, level)| serde_json::json!({ "user_id": user_id, "level": level }))
+            .collect();
+        out.push(channel_event(channel_id, "audio_levels", serde_json::json!({ "levels": snapshot })));
+    }
+    state.active = !levels.is_empty();
+    out
+}
+
+fn channel_event(channel_id: &str, event: &str, data: serde_json::Value) -> String {
+    let packet = GatewayPacket::new(server::CHANNEL_EVENT, ChannelEventPayload {
+        event:      event.to_string(),
+        channel_id: channel_id.to_string(),
+        data,
+    });
+    serde_json::to_string(&packet).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn events(packets: &[String]) -> Vec<(String, serde_json::Value)> {
+        packets.iter()
+            .map(|p| {
+                let v: serde_json::Value = serde_json::from_str(p).unwrap();
+                (v["d"]["event"].as_str().unwrap().to_string(), v["d"]["data"].clone())
+            })
+            .collect()
+    }
+
+    fn lv(list: &[(&str, u8)]) -> Vec<(String, u8)> {
+        list.iter().map(|(u, l)| (u.to_string(), *l)).collect()
+    }
+
+    #[test]
+    fn dominant_speaker_with_margin_and_snapshots() {
+        let mut state = SpeakerState::default();
+
+        // alice 발언 → speaker_changed + 스냅샷
+        let ev = events(&evaluate("CH", &lv(&[("alice", 30)]), &mut state));
+        assert_eq!(ev.len(), 2);
+        assert_eq!((ev[0].0.as_str(), ev[0].1["user_id"].as_str()), ("speaker_changed", Some("alice")));
+        assert_eq!(ev[1].1["levels"][0]["level"], 30);
+
+        // bob이 조금 더 큼 (margin 이내) → 유지, 스냅샷만
+        let ev = events(&evaluate("CH", &lv(&[("bob", 26), ("alice", 30)]), &mut state));
+        assert_eq!(ev.len(), 1);
+        assert_eq!(ev[0].0, "audio_levels");
+
+        // bob이 margin 넘게 큼 → 교체
+        let ev = events(&evaluate("CH", &lv(&[("bob", 20), ("alice", 30)]), &mut state));
+        assert_eq!(ev[0].1["user_id"], "bob");
+
+        // 모두 멈춤 → 빈 스냅샷 1회, dominant 유지
+        let ev = events(&evaluate("CH", &[], &mut state));
+        assert_eq!(ev.len(), 1);
+        assert_eq!(ev[0].1["levels"].as_array().map(Vec::len), Some(0));
+        assert!(evaluate("CH", &[], &mut state).is_empty());
+        assert_eq!(state.dominant.as_deref(), Some("bob"));
+    }
+
+    #[test]
+    fn levels_from_endpoints_filtered_and_sorted() {
+        let hub = MediaPeerHub::new();
+        let a = hub.insert("ua", "", "alice", "CH");
+        let b = hub.insert("ub", "", "bob", "CH");
+        let c = hub.insert("uc", "", "carol", "CH");
+        hub.insert("ud", "", "dave", "CH"); // audio level 미협상
+        a.set_audio_level_ext(1);
+
+        // one-byte 확장 id=1 audio level
+        let rtp = |level: u8| {
+            let mut p = vec![0x90, 111, 0, 1, 0, 0, 0, 0, 0, 0, 0, 1];
+            p.extend_from_slice(&[0xBE, 0xDE, 0, 1, 0x10, level, 0, 0, 0xAA]);
+            p
+        };
+        // 평활 수렴까지 여러 패킷
+        let now = current_timestamp();
+        for _ in 0..20 {
+            on_rtp(&a, &rtp(40));
+            on_rtp(&b, &rtp(10)); // 확장 미협상 → 무시
+            b.update_audio_level(20, now);
+            c.update_audio_level(90, now); // 기준 초과 → 무음
+        }
+        assert!(b.audio_level(now).unwrap() <= 21);
+
+        let levels = voiced_levels(&hub.get_channel_endpoints("CH"), now);
+        assert_eq!(levels.iter().map(|(u, _)| u.as_str()).collect::<Vec<_>>(), vec!["bob", "alice"]);
+    }
+}
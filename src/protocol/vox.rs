@@ -0,0 +1,146 @@
+// author: kodeholic (powered by Claude)
+// VOX (음성 감지) Floor — PTT를 누를 수 없는 핸즈프리 단말용
+//
+// FloorPolicy.vox 채널에서 멤버의 수신 audio RTP를 음성 감지에 사용 (media/net.rs에서 복호화 후 호출):
+//   RFC 6464 audio level 확장이 협상되었으면 level <= vox_threshold 일 때 음성
+//   확장이 없으면 audio 패킷 수신 자체를 음성으로 간주 (packet flow — 무음 시 DTX로 송신이 멈추는 단말 전제)
+//
+//   음성 + holder          → on_voice() — hang time 재시작 (음성이 FLOOR_PING을 겸함)
+//   음성 + 대기 중          → 무시 (대기열 순서대로 Grant)
+//   음성 + holder / 대기 아님 → FLOOR_REQUEST와 같은 핸들러 (priority / 대기열 / 선점 / cooldown 규칙 그대로)
+//                              VOX_REQUEST_RETRY_MS 안에는 재요청하지 않음 (Deny 폭주 방지)
+//   holder 무음이 vox_hang_ms 지속 → run_floor_timer가 Release(vox_hang)
+
+use std::sync::Arc;
+use tracing::{debug, trace};
+
+use crate::core::{ChannelHub, Endpoint, MediaPeerHub, TrackKind, UserHub};
+use crate::media::rtp;
+use crate::protocol::floor;
+use crate::protocol::message::GatewayPacket;
+use crate::protocol::opcode::client;
+use crate::trace::{TraceDir, TraceEvent, TraceHub};
+use crate::utils::current_timestamp;
+
+/// 복호화된 RTP 1개 처리 — VOX 채널이 아니거나 음성이 아니면 아무것도 하지 않음
+pub async fn on_rtp(
+    ep:          &Arc<Endpoint>,
+    packet:      &[u8],
+    user_hub:    &Arc<UserHub>,
+    channel_hub: &Arc<ChannelHub>,
+    peer_hub:    &MediaPeerHub,
+    trace_hub:   &Arc<TraceHub>,
+) {
+    let channel = match channel_hub.get(&ep.channel_id) {
+        Some(ch) if ch.is_ptt() => ch,
+        _                       => return,
+    };
+    // 등록된 video 트랙은 제외 (미등록 SSRC는 audio로 간주 — 하위 호환 단일 트랙)
+    let Some(ssrc) = rtp::ssrc(packet) else { return };
+    if ep.tracks.read().unwrap().iter().any(|t| t.ssrc == ssrc && t.kind != TrackKind::Audio) {
+        return;
+    }
+
+    let should_request = {
+        let mut floor = channel.floor.lock().unwrap();
+        if !floor.policy.vox {
+            return;
+        }
+        let voiced = match ep.audio_level_ext() {
+            Some(id) => rtp::audio_level(packet, id).is_none_or(|level| level <= floor.policy.vox_threshold),
+            None     => true,
+        };
+        if !voiced {
+            return;
+        }
+        if floor.floor_taken_by.as_deref() == Some(ep.user_id.as_str()) {
+            floor.on_voice();
+            false
+        } else {
+            floor.queue_position(&ep.user_id).is_none()
+        }
+        // MutexGuard drop here
+    };
+
+    if !should_request || !ep.try_vox_request(current_timestamp()) {
+        return;
+    }
+
+    let tx = match user_hub.get(&ep.user_id) {
+        Some(u) => u.tx.clone(),
+        None    => { debug!("[vox] user not online user={}", ep.user_id); return; }
+    };
+    trace!("[vox] voice detected → FLOOR_REQUEST user={} channel={}", ep.user_id, ep.channel_id);
+    trace_hub.publish(TraceEvent::new(
+        TraceDir::In, Some(&ep.channel_id), Some(&ep.user_id),
+        client::FLOOR_REQUEST, "FLOOR_REQUEST(VOX)",
+        format!("user={} ssrc={}", ep.user_id, ssrc),
+    ));
+
+    let packet = GatewayPacket {
+        op: client::FLOOR_REQUEST,
+        d:  Some(serde_json::json!({ "channel_id": ep.channel_id })),
+    };
+    if let Err(e) = floor::handle_floor_request(&tx, &ep.user_id, user_hub, channel_hub, peer_hub, trace_hub, packet).await {
+        debug!("[vox] FLOOR_REQUEST failed user={}: {}", ep.user_id, e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::mpsc;
+    use crate::core::{ChannelMode, FloorPolicy};
+
+    fn rtp(ssrc: u32, level: u8) -> Vec<u8> {
+        let mut p = vec![0x90, 111, 0, 1, 0, 0, 0, 0];
+        p.extend_from_slice(&ssrc.to_be_bytes());
+        p.extend_from_slice(&[0xBE, 0xDE, 0, 1, 0x10, level, 0, 0]);
+        p.extend_from_slice(&[0xAA; 20]);
+        p
+    }
+
+    #[tokio::test]
+    async fn voice_requests_floor_and_silence_does_not() {
+        let user_hub    = Arc::new(UserHub::new());
+        let channel_hub = Arc::new(ChannelHub::new());
+        let peer_hub    = Arc::new(MediaPeerHub::new());
+        let trace_hub   = TraceHub::new();
+        let (tx_a, mut rx_a) = mpsc::channel(16);
+        let (tx_b, _rx_b)    = mpsc::channel(16);
+        user_hub.register("alice", tx_a, 100);
+        user_hub.register("bob", tx_b, 100);
+
+        let ch = channel_hub.create("CH_V", "0700", "vox", ChannelMode::PTT, 10);
+        ch.floor.lock().unwrap().policy = FloorPolicy { vox: true, ..Default::default() };
+        ch.add_member("alice").unwrap();
+        ch.add_member("bob").unwrap();
+
+        let ep_a = peer_hub.insert("ua", "", "alice", "CH_V");
+        let ep_b = peer_hub.insert("ub", "", "bob", "CH_V");
+        ep_a.set_audio_level_ext(1);
+        ep_b.set_audio_level_ext(1);
+        ep_a.add_track(1, TrackKind::Audio);
+        ep_a.add_track(2, TrackKind::Video);
+
+        // 무음(-90dBov) / video 트랙 → 요청 없음
+        on_rtp(&ep_a, &rtp(1, 90), &user_hub, &channel_hub, &peer_hub, &trace_hub).await;
+        on_rtp(&ep_a, &rtp(2, 10), &user_hub, &channel_hub, &peer_hub, &trace_hub).await;
+        assert!(ch.floor.lock().unwrap().floor_taken_by.is_none());
+
+        // 음성(-20dBov) → Grant
+        on_rtp(&ep_a, &rtp(1, 20), &user_hub, &channel_hub, &peer_hub, &trace_hub).await;
+        assert_eq!(ch.floor.lock().unwrap().floor_taken_by.as_deref(), Some("alice"));
+        assert!(rx_a.recv().await.unwrap().contains("\"op\":110"));
+
+        // 동일 priority 상대 음성 → 대기열, 재요청 간격 안의 음성은 무시
+        on_rtp(&ep_b, &rtp(9, 20), &user_hub, &channel_hub, &peer_hub, &trace_hub).await;
+        assert_eq!(ch.floor.lock().unwrap().queue_position("bob"), Some(1));
+        assert!(!ep_b.try_vox_request(current_timestamp()));
+
+        // holder 음성 → hang time 재시작
+        ch.floor.lock().unwrap().last_voice_at = 0;
+        on_rtp(&ep_a, &rtp(1, 20), &user_hub, &channel_hub, &peer_hub, &trace_hub).await;
+        assert!(ch.floor.lock().unwrap().last_voice_at > 0);
+    }
+}
@@ -5,22 +5,23 @@
 //   1. 좀비 User (WS 하트비트 없음) 제거 + 소속 채널 멤버에서 제외
 //   2. 좀비 Endpoint (UDP 패킷 없음) 제거
 //   3. 단절된 DTLS 핸드셰이크 세션 제거 (tx 닫힌 세션 정리)
-//   4. Floor 타임아웃 체크 (ping_timeout / max_duration Revoke)
+//   4. 유휴 재전송 버퍼 제거 (NACK_CACHE_MAX_AGE_MS 동안 저장 없음 — 소유 엔드포인트 없이 남은 SSRC 포함)
+//
+// Floor 타임아웃(ping_timeout / max_duration)은 protocol::floor::run_floor_timer가 만료 시각 기준으로 처리
 
 use std::sync::Arc;
-use tracing::info;
+use tracing::{debug, info};
 
 use crate::config;
 use crate::core::{ChannelHub, MediaPeerHub, UserHub};
 use crate::media::DtlsSessionMap;
-use crate::trace::TraceHub;
+use crate::utils::current_timestamp;
 
 pub async fn run_zombie_reaper(
     user_hub:     Arc<UserHub>,
     channel_hub:  Arc<ChannelHub>,
     media_hub:    Arc<MediaPeerHub>,
     session_map:  Arc<DtlsSessionMap>,
-    trace_hub:    Arc<TraceHub>,
 ) {
     let interval  = tokio::time::Duration::from_millis(config::REAPER_INTERVAL_MS);
     let mut timer = tokio::time::interval(interval);
@@ -62,8 +63,11 @@ pub async fn run_zombie_reaper(
             info!("[zombie-reaper] dtls session stale addr={}", addr);
         }
 
-        // 4. Floor 타임아웃 체크 (ping_timeout / max_duration Revoke)
-        crate::protocol::floor::check_floor_timeouts(&user_hub, &channel_hub, Some(&trace_hub)).await;
+        // 4. 유휴 재전송 버퍼 정리
+        let pruned = media_hub.prune_rtp_cache(current_timestamp());
+        if pruned > 0 {
+            debug!("[zombie-reaper] {} idle rtp cache stream(s) pruned", pruned);
+        }
 
         let total = dead_users.len() + dead_peers.len() + stale.len();
         if total > 0 {
@@ -7,7 +7,8 @@
 //       └── TraceHub::publish(event)
 //               └── broadcast::Sender  (채널 구독자 수에 무관하게 O(1) publish)
 //                       ├── SSE subscriber (lctrace 프로세스 1)
-//                       └── SSE subscriber (lctrace 프로세스 2)
+//                       ├── SSE subscriber (lctrace 프로세스 2)
+//                       └── WebhookHub (외부 백엔드 HTTP POST 전달)
 //
 // Vue EventBus 패턴과 동일:
 //   emit(event)  ≈ publish()
@@ -20,6 +21,7 @@ use std::sync::Arc;
 use tokio::sync::broadcast;
 use serde::Serialize;
 use crate::utils::current_timestamp;
+use crate::webhook::WebhookEvent;
 
 /// 브로드캐스트 채널 버퍼 크기
 /// 구독자가 느릴 때 최대 보유 이벤트 수 — 초과 시 오래된 이벤트 drop
@@ -58,6 +60,12 @@ pub struct TraceEvent {
     pub op_name:    String,
     /// 이벤트 요약 메시지
     pub summary:    String,
+    /// 구조화 부가 데이터 (webhook 전달용, 예: 메시지 본문) — 없으면 직렬화 생략
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data:       Option<serde_json::Value>,
+    /// 외부 webhook 이벤트 — 발생 지점에서 명시 (재전송 / 조회 trace는 None)
+    #[serde(skip)]
+    pub webhook:    Option<WebhookEvent>,
 }
 
 impl TraceEvent {
@@ -77,8 +85,22 @@ impl TraceEvent {
             op,
             op_name:    op_name.to_string(),
             summary:    summary.into(),
+            data:       None,
+            webhook:    None,
         }
     }
+
+    /// 구조화 부가 데이터 첨부
+    pub fn with_data(mut self, data: serde_json::Value) -> Self {
+        self.data = Some(data);
+        self
+    }
+
+    /// webhook 전달 대상 표시
+    pub fn with_webhook(mut self, event: WebhookEvent) -> Self {
+        self.webhook = Some(event);
+        self
+    }
 }
 
 // ----------------------------------------------------------------------------
@@ -159,5 +181,15 @@ mod tests {
         let json = serde_json::to_string(&event).unwrap();
         assert!(json.contains("\"dir\":\"sys\""));
         assert!(json.contains("\"op\":10"));
+        assert!(!json.contains("\"data\""));
+    }
+
+    #[test]
+    fn trace_event_with_data_serializes() {
+        let event = TraceEvent::new(
+            TraceDir::Sys, Some("CH"), Some("u1"), 101, "MESSAGE_EVENT", "msg",
+        ).with_data(serde_json::json!({ "content": "hi" }));
+        let json = serde_json::to_string(&event).unwrap();
+        assert!(json.contains("\"data\":{\"content\":\"hi\"}"));
     }
 }
@@ -0,0 +1,440 @@
+// author: kodeholic (powered by Claude)
+//
+// WebhookHub — 서버 이벤트를 외부 백엔드로 HTTP POST 전달
+//
+// 구조:
+//   TraceHub (broadcast)
+//       └── run_webhook_dispatcher  (TraceHub 구독자 1개)
+//               ├── 발생 지점에서 with_webhook()으로 표시된 이벤트만 선별 → JSON 직렬화 1회
+//               └── 대상 URL별 bounded mpsc 에 try_send (꽉 차면 drop + 카운트)
+//                       └── run_delivery_worker (URL당 1개)
+//                               POST + HMAC 서명, 실패 시 지수 백오프 재시도
+//
+// 시그널링 핸들러는 TraceHub::publish 만 호출하므로 느린 endpoint가
+// WS 처리 경로를 막지 않는다. 느린 URL 하나가 다른 URL 전달을 막지도 않는다.
+//
+// 서명 (LIVECHAT_WEBHOOK_SECRET 설정 시):
+//   X-Livechat-Timestamp: <unix millis>
+//   X-Livechat-Signature: sha256=<hex(HMAC-SHA256(secret, "{timestamp}.{body}"))>
+//   수신 측은 같은 방식으로 계산해 비교하고, timestamp로 재전송 공격을 거른다.
+
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use sha2::Sha256;
+use tokio::sync::{broadcast, mpsc};
+use tracing::{info, warn};
+
+use crate::config;
+use crate::trace::{TraceEvent, TraceHub};
+use crate::utils::current_timestamp;
+
+// ----------------------------------------------------------------------------
+// [WebhookEvent] 외부로 전달하는 이벤트 종류
+// ----------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebhookEvent {
+    Join,
+    Leave,
+    FloorGranted,
+    FloorRevoked,
+    Message,
+    Emergency,
+}
+
+impl WebhookEvent {
+    pub const ALL: [WebhookEvent; 6] = [
+        WebhookEvent::Join,
+        WebhookEvent::Leave,
+        WebhookEvent::FloorGranted,
+        WebhookEvent::FloorRevoked,
+        WebhookEvent::Message,
+        WebhookEvent::Emergency,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            WebhookEvent::Join         => "join",
+            WebhookEvent::Leave        => "leave",
+            WebhookEvent::FloorGranted => "floor_granted",
+            WebhookEvent::FloorRevoked => "floor_revoked",
+            WebhookEvent::Message      => "message",
+            WebhookEvent::Emergency    => "emergency",
+        }
+    }
+}
+
+impl FromStr for WebhookEvent {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_lowercase();
+        WebhookEvent::ALL.iter()
+            .copied()
+            .find(|e| e.as_str() == s)
+            .ok_or_else(|| format!("'{}' — join | leave | floor_granted | floor_revoked | message | emergency 중 하나", s))
+    }
+}
+
+/// TraceEvent → WebhookEvent — 발생 지점에서 TraceEvent::with_webhook()으로 표시한 이벤트만
+/// (op_name 문자열로 추정하지 않음 — T101 / T100 재전송 등 같은 이름 계열 trace 중복 방지)
+pub fn webhook_event(ev: &TraceEvent) -> Option<WebhookEvent> {
+    ev.webhook
+}
+
+// ----------------------------------------------------------------------------
+// [WebhookConfig] 기본값은 config.rs 상수, 대상/필터는 CLI, 서명 키는 환경변수
+// ----------------------------------------------------------------------------
+
+#[derive(Debug, Clone)]
+pub struct WebhookConfig {
+    pub urls:        Vec<String>,
+    pub secret:      Option<String>,      // None이면 서명 헤더 생략
+    pub events:      Vec<WebhookEvent>,   // 비어 있으면 전체 이벤트
+    pub queue_size:  usize,
+    pub max_retries: u32,
+    pub timeout_ms:  u64,
+}
+
+impl WebhookConfig {
+    pub fn is_enabled(&self) -> bool {
+        !self.urls.is_empty()
+    }
+
+    pub fn accepts(&self, event: WebhookEvent) -> bool {
+        self.events.is_empty() || self.events.contains(&event)
+    }
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            urls:        Vec::new(),
+            secret:      None,
+            events:      Vec::new(),
+            queue_size:  config::WEBHOOK_QUEUE_SIZE,
+            max_retries: config::WEBHOOK_MAX_RETRIES,
+            timeout_ms:  config::WEBHOOK_TIMEOUT_MS,
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// [WebhookPayload] POST body
+// ----------------------------------------------------------------------------
+
+#[derive(Debug, Serialize)]
+pub struct WebhookPayload<'a> {
+    pub id:         String,              // 수신 측 중복 제거용 (재시도 시 동일)
+    pub event:      &'static str,
+    pub ts:         u64,
+    pub channel_id: Option<&'a str>,
+    pub user_id:    Option<&'a str>,
+    pub op_name:    &'a str,
+    pub summary:    &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data:       Option<&'a serde_json::Value>,
+}
+
+static EVENT_SEQ: AtomicU64 = AtomicU64::new(1);
+
+impl<'a> WebhookPayload<'a> {
+    pub fn from_trace(event: WebhookEvent, ev: &'a TraceEvent) -> Self {
+        let seq = EVENT_SEQ.fetch_add(1, Ordering::Relaxed);
+        Self {
+            id:         format!("evt_{}_{}", ev.ts, seq),
+            event:      event.as_str(),
+            ts:         ev.ts,
+            channel_id: ev.channel_id.as_deref(),
+            user_id:    ev.user_id.as_deref(),
+            op_name:    &ev.op_name,
+            summary:    &ev.summary,
+            data:       ev.data.as_ref(),
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// [서명 / 백오프 유틸]
+// ----------------------------------------------------------------------------
+
+/// HMAC-SHA256 hex (소문자)
+pub fn sign(secret: &[u8], message: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret)
+        .expect("HMAC accepts keys of any length");
+    mac.update(message);
+    mac.finalize().into_bytes().iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// X-Livechat-Signature 헤더 값 — "{timestamp}.{body}" 서명
+pub fn signature_header(secret: &str, timestamp: u64, body: &str) -> String {
+    let message = format!("{}.{}", timestamp, body);
+    format!("sha256={}", sign(secret.as_bytes(), message.as_bytes()))
+}
+
+/// attempt번째 재시도 전 대기 시간 (0부터) — base * 2^attempt, 상한 WEBHOOK_RETRY_MAX_MS
+pub fn backoff_ms(attempt: u32) -> u64 {
+    config::WEBHOOK_RETRY_BASE_MS
+        .saturating_mul(1u64 << attempt.min(20))
+        .min(config::WEBHOOK_RETRY_MAX_MS)
+}
+
+/// 재시도 대상 HTTP 상태 — 5xx / 408 / 429
+fn is_retryable(status: reqwest::StatusCode) -> bool {
+    status.is_server_error()
+        || status == reqwest::StatusCode::REQUEST_TIMEOUT
+        || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+}
+
+// ----------------------------------------------------------------------------
+// [Dispatcher] TraceHub 구독 → URL별 큐 분배
+// ----------------------------------------------------------------------------
+
+/// 직렬화된 전달 단위 (URL별 큐가 Arc로 공유)
+struct Delivery {
+    event: WebhookEvent,
+    body:  String,
+}
+
+struct WebhookTarget {
+    url:     String,
+    tx:      mpsc::Sender<Arc<Delivery>>,
+    dropped: u64,
+}
+
+pub async fn run_webhook_dispatcher(trace_hub: Arc<TraceHub>, cfg: WebhookConfig) {
+    let client = match reqwest::Client::builder()
+        .timeout(std::time::Duration::from_millis(cfg.timeout_ms))
+        .build()
+    {
+        Ok(c)  => c,
+        Err(e) => {
+            warn!("[webhook] Failed to build HTTP client: {}", e);
+            return;
+        }
+    };
+
+    let cfg = Arc::new(cfg);
+    let mut targets: Vec<WebhookTarget> = cfg.urls.iter()
+        .map(|url| {
+            let (tx, rx) = mpsc::channel(cfg.queue_size);
+            tokio::spawn(run_delivery_worker(client.clone(), url.clone(), Arc::clone(&cfg), rx));
+            WebhookTarget { url: url.clone(), tx, dropped: 0 }
+        })
+        .collect();
+
+    info!("[webhook] Started: {} target(s), signed={}, events={}",
+        targets.len(),
+        cfg.secret.is_some(),
+        if cfg.events.is_empty() {
+            "all".to_string()
+        } else {
+            cfg.events.iter().map(|e| e.as_str()).collect::<Vec<_>>().join(",")
+        });
+
+    let mut rx = trace_hub.subscribe();
+    loop {
+        let ev = match rx.recv().await {
+            Ok(ev) => ev,
+            Err(broadcast::error::RecvError::Lagged(n)) => {
+                warn!("[webhook] trace lagged — {} event(s) skipped", n);
+                continue;
+            }
+            Err(broadcast::error::RecvError::Closed) => break,
+        };
+
+        let event = match webhook_event(&ev) {
+            Some(e) if cfg.accepts(e) => e,
+            _                         => continue,
+        };
+        let body = match serde_json::to_string(&WebhookPayload::from_trace(event, &ev)) {
+            Ok(b)  => b,
+            Err(e) => {
+                warn!("[webhook] serialize failed: {}", e);
+                continue;
+            }
+        };
+        let delivery = Arc::new(Delivery { event, body });
+
+        for target in &mut targets {
+            if let Err(mpsc::error::TrySendError::Full(_)) = target.tx.try_send(Arc::clone(&delivery)) {
+                target.dropped += 1;
+                warn!("[webhook] queue full, event={} dropped (url={} total_dropped={})",
+                    event.as_str(), target.url, target.dropped);
+            }
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// [Delivery worker] URL당 1개 — 순서 보장, 실패 시 백오프 재시도
+// ----------------------------------------------------------------------------
+
+async fn run_delivery_worker(
+    client: reqwest::Client,
+    url:    String,
+    cfg:    Arc<WebhookConfig>,
+    mut rx: mpsc::Receiver<Arc<Delivery>>,
+) {
+    while let Some(delivery) = rx.recv().await {
+        let mut attempt = 0;
+        loop {
+            match post_once(&client, &url, cfg.secret.as_deref(), &delivery).await {
+                Ok(()) => break,
+                Err((retryable, reason)) => {
+                    if !retryable || attempt >= cfg.max_retries {
+                        warn!("[webhook] delivery failed, giving up: url={} event={} attempts={} reason={}",
+                            url, delivery.event.as_str(), attempt + 1, reason);
+                        break;
+                    }
+                    let wait = backoff_ms(attempt);
+                    warn!("[webhook] delivery failed, retry in {}ms: url={} event={} reason={}",
+                        wait, url, delivery.event.as_str(), reason);
+                    tokio::time::sleep(std::time::Duration::from_millis(wait)).await;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+}
+
+/// POST 1회 — Err((재시도 가능 여부, 사유))
+async fn post_once(
+    client:   &reqwest::Client,
+    url:      &str,
+    secret:   Option<&str>,
+    delivery: &Delivery,
+) -> Result<(), (bool, String)> {
+    let timestamp = current_timestamp();
+    let mut req = client.post(url)
+        .header("Content-Type", "application/json")
+        .header("X-Livechat-Event", delivery.event.as_str())
+        .header("X-Livechat-Timestamp", timestamp.to_string());
+    if let Some(secret) = secret {
+        req = req.header("X-Livechat-Signature", signature_header(secret, timestamp, &delivery.body));
+    }
+
+    match req.body(delivery.body.clone()).send().await {
+        Ok(resp) if resp.status().is_success() => Ok(()),
+        Ok(resp) => Err((is_retryable(resp.status()), format!("status {}", resp.status()))),
+        Err(e)   => Err((true, e.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::opcode::server;
+    use crate::trace::TraceDir;
+
+    fn ev(dir: TraceDir, op_name: &str) -> TraceEvent {
+        TraceEvent::new(dir, Some("CH_0001"), Some("alice"), 0, op_name, "test")
+    }
+
+    #[test]
+    fn only_tagged_trace_events_map() {
+        let granted = ev(TraceDir::Out, "FLOOR_GRANTED").with_webhook(WebhookEvent::FloorGranted);
+        assert_eq!(webhook_event(&granted), Some(WebhookEvent::FloorGranted));
+        assert_eq!(webhook_event(&ev(TraceDir::Sys, "CHANNEL_JOIN").with_webhook(WebhookEvent::Join)), Some(WebhookEvent::Join));
+        // 이름이 같아도 표시 없는 trace(재전송, C→S 수신)는 제외
+        assert_eq!(webhook_event(&ev(TraceDir::Out, "FLOOR_GRANTED(T101)")), None);
+        assert_eq!(webhook_event(&ev(TraceDir::Out, "FLOOR_REVOKE(T100)")),  None);
+        assert_eq!(webhook_event(&ev(TraceDir::In,  "CHANNEL_JOIN")),        None);
+    }
+
+    #[test]
+    fn hmac_sha256_rfc4231_vector() {
+        // RFC 4231 Test Case 2
+        assert_eq!(
+            sign(b"Jefe", b"what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+        );
+    }
+
+    #[test]
+    fn signature_covers_timestamp() {
+        let a = signature_header("k", 1000, "{}");
+        let b = signature_header("k", 1001, "{}");
+        assert!(a.starts_with("sha256="));
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn backoff_grows_and_caps() {
+        assert_eq!(backoff_ms(0), config::WEBHOOK_RETRY_BASE_MS);
+        assert_eq!(backoff_ms(1), config::WEBHOOK_RETRY_BASE_MS * 2);
+        assert_eq!(backoff_ms(30), config::WEBHOOK_RETRY_MAX_MS);
+    }
+
+    #[test]
+    fn event_filter_and_parse() {
+        let cfg = WebhookConfig {
+            events: vec!["message".parse().unwrap(), "EMERGENCY".parse().unwrap()],
+            ..Default::default()
+        };
+        assert!(cfg.accepts(WebhookEvent::Message));
+        assert!(!cfg.accepts(WebhookEvent::Join));
+        assert!(WebhookConfig::default().accepts(WebhookEvent::Join));
+        assert!("typing".parse::<WebhookEvent>().is_err());
+    }
+
+    #[test]
+    fn payload_carries_trace_data() {
+        let trace = TraceEvent::new(
+            TraceDir::Sys, Some("CH_0001"), Some("alice"), server::MESSAGE_EVENT, "MESSAGE_EVENT", "len=2",
+        ).with_data(serde_json::json!({ "content": "hi" }));
+        let json = serde_json::to_value(WebhookPayload::from_trace(WebhookEvent::Message, &trace)).unwrap();
+        assert_eq!(json["event"], "message");
+        assert_eq!(json["channel_id"], "CH_0001");
+        assert_eq!(json["data"]["content"], "hi");
+        assert!(json["id"].as_str().unwrap().starts_with("evt_"));
+    }
+
+    #[tokio::test]
+    async fn delivers_signed_post_with_retry() {
+        use axum::{http::{HeaderMap, StatusCode}, routing::post, Router};
+        use std::sync::atomic::AtomicUsize;
+
+        // 첫 요청은 503, 두 번째부터 200 — 재시도 후 서명 검증
+        let hits = Arc::new(AtomicUsize::new(0));
+        let (got_tx, mut got_rx) = mpsc::channel::<(HeaderMap, String)>(4);
+        let app = {
+            let hits = Arc::clone(&hits);
+            Router::new().route("/hook", post(move |headers: HeaderMap, body: String| {
+                let hits   = Arc::clone(&hits);
+                let got_tx = got_tx.clone();
+                async move {
+                    if hits.fetch_add(1, Ordering::SeqCst) == 0 {
+                        return StatusCode::SERVICE_UNAVAILABLE;
+                    }
+                    let _ = got_tx.send((headers, body)).await;
+                    StatusCode::OK
+                }
+            }))
+        };
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr     = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let hub = TraceHub::new();
+        tokio::spawn(run_webhook_dispatcher(Arc::clone(&hub), WebhookConfig {
+            urls:   vec![format!("http://{}/hook", addr)],
+            secret: Some("s3cret".to_string()),
+            ..Default::default()
+        }));
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await; // 구독 대기
+
+        hub.publish(ev(TraceDir::Sys, "CHANNEL_JOIN").with_webhook(WebhookEvent::Join));
+
+        let (headers, body) = tokio::time::timeout(std::time::Duration::from_secs(5), got_rx.recv())
+            .await.unwrap().unwrap();
+        let ts: u64 = headers["x-livechat-timestamp"].to_str().unwrap().parse().unwrap();
+        assert_eq!(headers["x-livechat-event"], "join");
+        assert_eq!(headers["x-livechat-signature"].to_str().unwrap(), signature_header("s3cret", ts, &body));
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+    }
+}